@@ -8,6 +8,36 @@ pub trait CanonProvider {
     fn canon(&self, v: Value) -> Value;
 }
 
+/// NFC-normalize every string value and object key in `v`.
+///
+/// Applied at the VM's canon boundary (`JsonNormalize`) before the
+/// `CanonProvider` runs, so rc_cids are always computed over NFC text no
+/// matter which provider is plugged in or how the client encoded its input.
+/// Fails with the offending key when two keys of one object collide after
+/// normalization.
+pub fn nfc_normalize(v: Value) -> Result<Value, String> {
+    Ok(match v {
+        Value::String(s) => Value::String(s.nfc().collect()),
+        Value::Array(arr) => Value::Array(
+            arr.into_iter()
+                .map(nfc_normalize)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(map) => {
+            let mut out = Map::new();
+            for (k, val) in map {
+                let key: String = k.nfc().collect();
+                if out.contains_key(&key) {
+                    return Err(key);
+                }
+                out.insert(key, nfc_normalize(val)?);
+            }
+            Value::Object(out)
+        }
+        other => other,
+    })
+}
+
 /// Implementação ingênua para desenvolvimento: ordena chaves recursivamente.
 /// DEPRECATED: Use `RhoCanon` for production. `NaiveCanon` does NOT enforce ρ rules.
 pub struct NaiveCanon;
@@ -194,6 +224,14 @@ mod tests {
         assert_eq!(keys, vec!["a", "b"]);
     }
 
+    #[test]
+    fn nfc_normalize_strings_and_keys() {
+        let out = nfc_normalize(json!({"Cafe\u{0301}": ["e\u{0301}", 1]})).unwrap();
+        assert_eq!(out, json!({"Caf\u{00e9}": ["\u{00e9}", 1]}));
+        let err = nfc_normalize(json!({"e\u{0301}": 1, "\u{00e9}": 2})).unwrap_err();
+        assert_eq!(err, "\u{00e9}");
+    }
+
     // ── RhoCanon tests ──
 
     #[test]
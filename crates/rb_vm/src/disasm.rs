@@ -24,6 +24,19 @@ pub fn disassemble(bytecode: &[u8]) -> Result<String, DecodeError> {
     Ok(out)
 }
 
+/// Count TLV instructions in a bytecode buffer (each is 3-byte header + payload).
+/// Walks headers only, so a truncated tail still counts as one instruction.
+pub fn count_tlv_instrs(bytecode: &[u8]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i + 2 < bytecode.len() {
+        let len = u16::from_be_bytes([bytecode[i + 1], bytecode[i + 2]]) as usize;
+        i += 3 + len;
+        count += 1;
+    }
+    count
+}
+
 fn format_instr(offset: usize, op: Opcode, payload: &[u8]) -> String {
     let name = format!("{:?}", op);
     let detail = format_payload(op, payload);
@@ -129,6 +142,14 @@ mod tests {
         buf
     }
 
+    #[test]
+    fn count_tlv_instrs_matches_decoded_stream() {
+        let mut bc = encode_instr(Opcode::ConstI64, &7i64.to_be_bytes());
+        bc.extend(encode_instr(Opcode::EmitRc, &[]));
+        assert_eq!(count_tlv_instrs(&bc), decode_stream(&bc).unwrap().len());
+        assert_eq!(count_tlv_instrs(&[]), 0);
+    }
+
     #[test]
     fn disasm_empty() {
         let out = disassemble(&[]).unwrap();
@@ -1,29 +1,75 @@
-use crate::canon::CanonProvider;
+use crate::canon::{nfc_normalize, CanonProvider};
 use crate::{
     opcode::Opcode,
-    tlv::Instr,
+    tlv::{decode_stream, DecodeError, Instr},
     types::{Cid, RcPayload, Value},
 };
 use base64::Engine;
 use serde_json::json;
 use ubl_unc1 as unc1;
+use unicode_normalization::UnicodeNormalization;
 
 pub type Fuel = u64;
 
-#[derive(Debug, thiserror::Error)]
-pub enum ExecError {
+/// Default cap on operand stack depth (see [`Vm::with_max_stack_depth`]).
+pub const DEFAULT_MAX_STACK_DEPTH: usize = 1024;
+
+/// Structured VM failure. Each variant has a stable machine code
+/// ([`VmError::code`]) so callers can map precisely instead of parsing text.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VmError {
     #[error("fuel exhausted")]
     FuelExhausted,
+    #[error("memory limit exceeded: stack depth above {0}")]
+    MemoryLimit(usize),
+    #[error("invalid opcode {0:#x}")]
+    InvalidOpcode(u8),
+    #[error("truncated bytecode")]
+    Truncated,
     #[error("stack underflow for {0:?}")]
     StackUnderflow(Opcode),
     #[error("type mismatch for {0:?}")]
     TypeMismatch(Opcode),
     #[error("invalid payload for {0:?}")]
     InvalidPayload(Opcode),
+    #[error("non-finite number for {0:?}")]
+    NonFinite(Opcode),
+    #[error("cas miss: {0}")]
+    CasMiss(String),
     #[error("deny: {0}")]
     Deny(String),
 }
 
+/// Former name of [`VmError`], kept for existing callers.
+pub type ExecError = VmError;
+
+impl VmError {
+    /// Stable machine code, e.g. `FUEL_EXHAUSTED`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            VmError::FuelExhausted => "FUEL_EXHAUSTED",
+            VmError::MemoryLimit(_) => "MEMORY_LIMIT",
+            VmError::InvalidOpcode(_) => "INVALID_OPCODE",
+            VmError::Truncated => "TRUNCATED",
+            VmError::StackUnderflow(_) => "STACK_UNDERFLOW",
+            VmError::TypeMismatch(_) => "TYPE_MISMATCH",
+            VmError::InvalidPayload(_) => "INVALID_PAYLOAD",
+            VmError::NonFinite(_) => "NON_FINITE",
+            VmError::CasMiss(_) => "CAS_MISS",
+            VmError::Deny(_) => "DENY",
+        }
+    }
+}
+
+impl From<DecodeError> for VmError {
+    fn from(e: DecodeError) -> Self {
+        match e {
+            DecodeError::Truncated => VmError::Truncated,
+            DecodeError::UnknownOpcode(op) => VmError::InvalidOpcode(op),
+        }
+    }
+}
+
 pub trait CasProvider {
     fn put(&mut self, bytes: &[u8]) -> Cid;
     fn get(&self, cid: &Cid) -> Option<Vec<u8>>;
@@ -64,6 +110,7 @@ pub struct Vm<'a, C: CasProvider, S: SignProvider, K: CanonProvider> {
     trace: Vec<TraceStep>,
     /// Body size in bytes — exposed to bytecode via PushBodySize (0x25).
     body_size: usize,
+    max_stack_depth: usize,
 }
 
 #[derive(Debug)]
@@ -91,6 +138,7 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
             proofs: Vec::new(),
             trace: Vec::new(),
             body_size: 0,
+            max_stack_depth: DEFAULT_MAX_STACK_DEPTH,
         }
     }
 
@@ -101,6 +149,12 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
         self
     }
 
+    /// Cap the operand stack depth; exceeding it fails with `MemoryLimit`.
+    pub fn with_max_stack_depth(mut self, depth: usize) -> Self {
+        self.max_stack_depth = depth;
+        self
+    }
+
     fn charge(&mut self, units: Fuel) -> Result<(), ExecError> {
         let next = self.fuel_used.saturating_add(units);
         if next > self.cfg.fuel_limit {
@@ -134,6 +188,12 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
         }
     }
 
+    /// Decode a TLV stream and run it; decode failures surface as `VmError`.
+    pub fn run_bytes(&mut self, bytecode: &[u8]) -> Result<VmOutcome, VmError> {
+        let code = decode_stream(bytecode)?;
+        self.run(&code)
+    }
+
     pub fn run(&mut self, code: &[Instr<'_>]) -> Result<VmOutcome, ExecError> {
         use Value::*;
         for ins in code {
@@ -223,7 +283,7 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                     let bytes = self
                         .cas
                         .get(&cid)
-                        .ok_or(ExecError::Deny("cas_get_not_found".into()))?;
+                        .ok_or_else(|| ExecError::CasMiss(cid.0.clone()))?;
                     self.push(Bytes(bytes));
                 }
                 Opcode::CasPut => {
@@ -242,6 +302,11 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                     };
                     let v: serde_json::Value = serde_json::from_slice(&bytes)
                         .map_err(|_| ExecError::Deny("json_parse_error".into()))?;
+                    // Strings enter the VM as NFC whatever the provider does,
+                    // so NFC/NFD spellings of the same text share one rc_cid.
+                    let v = nfc_normalize(v).map_err(|key| {
+                        ExecError::Deny(format!("json_duplicate_key_after_nfc: {}", key))
+                    })?;
                     // Canon real plugável; aqui usamos o provider
                     let v = self.canon.canon(v);
                     self.push(Value::Json(v));
@@ -257,12 +322,13 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                 Opcode::JsonGetKey => {
                     let key = std::str::from_utf8(ins.payload)
                         .map_err(|_| ExecError::InvalidPayload(Opcode::JsonGetKey))?;
+                    let key: String = key.nfc().collect();
                     let v = match self.pop()? {
                         Value::Json(v) => v,
                         _ => return Err(ExecError::TypeMismatch(Opcode::JsonGetKey)),
                     };
                     let extracted = v
-                        .get(key)
+                        .get(&key)
                         .ok_or(ExecError::Deny("json_key_missing_or_not_i64".into()))?;
                     if let Some(n) = extracted.as_i64() {
                         self.push(Value::I64(n));
@@ -360,6 +426,9 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                     if bits_i64 < 0 {
                         return Err(ExecError::InvalidPayload(Opcode::NumFromF64Bits));
                     }
+                    if !f64::from_bits(bits_i64 as u64).is_finite() {
+                        return Err(ExecError::NonFinite(Opcode::NumFromF64Bits));
+                    }
                     let num = unc1::from_f64_bits(bits_i64 as u64)
                         .map_err(|e| ExecError::Deny(format!("num_from_f64_bits: {}", e)))?;
                     self.push(Value::Num(num));
@@ -445,11 +514,12 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                 Opcode::JsonGetKeyBytes => {
                     let key = std::str::from_utf8(ins.payload)
                         .map_err(|_| ExecError::InvalidPayload(Opcode::JsonGetKeyBytes))?;
+                    let key: String = key.nfc().collect();
                     let v = match self.pop()? {
                         Value::Json(v) => v,
                         _ => return Err(ExecError::TypeMismatch(Opcode::JsonGetKeyBytes)),
                     };
-                    let s = v.get(key).and_then(|val| val.as_str()).ok_or_else(|| {
+                    let s = v.get(&key).and_then(|val| val.as_str()).ok_or_else(|| {
                         ExecError::Deny(format!("json_key_missing_or_not_string: {}", key))
                     })?;
                     self.push(Value::Bytes(s.as_bytes().to_vec()));
@@ -459,11 +529,12 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                 Opcode::JsonHasKey => {
                     let key = std::str::from_utf8(ins.payload)
                         .map_err(|_| ExecError::InvalidPayload(Opcode::JsonHasKey))?;
+                    let key: String = key.nfc().collect();
                     let v = match self.pop()? {
                         Value::Json(v) => v,
                         _ => return Err(ExecError::TypeMismatch(Opcode::JsonHasKey)),
                     };
-                    let exists = v.get(key).map(|val| !val.is_null()).unwrap_or(false);
+                    let exists = v.get(&key).map(|val| !val.is_null()).unwrap_or(false);
                     self.push(Value::Bool(exists));
                 }
 
@@ -643,6 +714,9 @@ impl<'a, C: CasProvider, S: SignProvider, K: CanonProvider> Vm<'a, C, S, K> {
                     });
                 }
             }
+            if self.stack.len() > self.max_stack_depth {
+                return Err(ExecError::MemoryLimit(self.max_stack_depth));
+            }
             if self.cfg.trace {
                 self.trace.push(TraceStep {
                     step: self.steps,
@@ -15,7 +15,10 @@ pub mod tlv;
 pub mod types;
 
 pub use canon::RhoCanon;
-pub use disasm::disassemble;
-pub use exec::{CasProvider, ExecError, Fuel, SignProvider, TraceStep, Vm, VmConfig, VmOutcome};
+pub use disasm::{count_tlv_instrs, disassemble};
+pub use exec::{
+    CasProvider, ExecError, Fuel, SignProvider, TraceStep, Vm, VmConfig, VmError, VmOutcome,
+    DEFAULT_MAX_STACK_DEPTH,
+};
 pub use opcode::Opcode;
 pub use types::{Cid, RcPayload, Value};
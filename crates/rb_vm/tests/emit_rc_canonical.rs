@@ -154,3 +154,47 @@ fn vm_emitrc_sig_persisted_and_linked() {
     assert_eq!(rc_payload_cid.0, rc_cid.0);
     assert!(rc_sig.starts_with("ed25519:"));
 }
+
+fn emit_rc_cid_for(json: &str) -> String {
+    let signer = CaptureSigner {
+        last_signed: Arc::new(Mutex::new(None)),
+    };
+    let cas = CaptureCas::new(Arc::new(Mutex::new(None)));
+
+    let mut code = Vec::new();
+    code.extend(tlv_const_bytes(json.as_bytes()));
+    code.extend(tlv_json_normalize());
+    code.extend(tlv_set_rc_body());
+    code.extend(tlv_emit_rc());
+
+    let instructions = tlv::decode_stream(&code).expect("valid TLV");
+    let mut vm = Vm::new(
+        VmConfig {
+            fuel_limit: 1_000,
+            ghost: false,
+            trace: false,
+        },
+        cas,
+        &signer,
+        NaiveCanon,
+        vec![],
+    );
+    vm.run(&instructions)
+        .expect("vm run ok")
+        .rc_cid
+        .expect("EmitRc returns CID")
+        .0
+}
+
+#[test]
+fn vm_emitrc_cid_is_identical_for_nfc_and_nfd_input() {
+    // "Café" — precomposed U+00E9 vs. "e" + combining acute U+0301,
+    // in both a value and a key. NaiveCanon does no normalization itself,
+    // so this pins the VM's own NFC boundary.
+    let nfc = "{\"name\":\"Caf\u{00e9}\",\"caf\u{00e9}\":1}";
+    let nfd = "{\"name\":\"Cafe\u{0301}\",\"cafe\u{0301}\":1}";
+    assert_ne!(nfc.as_bytes(), nfd.as_bytes());
+
+    let cid = emit_rc_cid_for(nfc);
+    assert_eq!(cid, emit_rc_cid_for(nfd));
+}
@@ -0,0 +1,127 @@
+use rb_vm::canon::NaiveCanon;
+use rb_vm::exec::{CasProvider, SignProvider};
+use rb_vm::{Cid, Vm, VmConfig, VmError, VmOutcome};
+use std::collections::HashMap;
+
+#[derive(Default)]
+struct MemCas {
+    store: HashMap<String, Vec<u8>>,
+}
+
+impl CasProvider for MemCas {
+    fn put(&mut self, bytes: &[u8]) -> Cid {
+        let cid = format!("b3:{}", hex::encode(blake3::hash(bytes).as_bytes()));
+        self.store.insert(cid.clone(), bytes.to_vec());
+        Cid(cid)
+    }
+
+    fn get(&self, cid: &Cid) -> Option<Vec<u8>> {
+        self.store.get(&cid.0).cloned()
+    }
+}
+
+struct FixedSigner;
+
+impl SignProvider for FixedSigner {
+    fn sign_jws(&self, _payload_nrf_bytes: &[u8]) -> Vec<u8> {
+        vec![7u8; 64]
+    }
+
+    fn kid(&self) -> String {
+        "did:test#k1".to_string()
+    }
+}
+
+fn tlv_instr(op: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = vec![op];
+    out.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    out.extend_from_slice(payload);
+    out
+}
+
+fn const_i64(v: i64) -> Vec<u8> {
+    tlv_instr(0x01, &v.to_be_bytes())
+}
+
+fn run_with(
+    code: &[u8],
+    fuel_limit: u64,
+    max_stack_depth: usize,
+    inputs: Vec<Cid>,
+) -> Result<VmOutcome, VmError> {
+    let signer = FixedSigner;
+    let mut vm = Vm::new(
+        VmConfig {
+            fuel_limit,
+            ghost: false,
+            trace: false,
+        },
+        MemCas::default(),
+        &signer,
+        NaiveCanon,
+        inputs,
+    )
+    .with_max_stack_depth(max_stack_depth);
+    vm.run_bytes(code)
+}
+
+fn run(code: &[u8]) -> Result<VmOutcome, VmError> {
+    run_with(code, 10_000, rb_vm::DEFAULT_MAX_STACK_DEPTH, vec![])
+}
+
+#[test]
+fn fuel_exhausted_is_structured() {
+    let mut code = Vec::new();
+    for _ in 0..5 {
+        code.extend(const_i64(1));
+        code.extend(tlv_instr(0x11, &[]));
+    }
+    let err = run_with(&code, 3, rb_vm::DEFAULT_MAX_STACK_DEPTH, vec![]).unwrap_err();
+    assert_eq!(err, VmError::FuelExhausted);
+    assert_eq!(err.code(), "FUEL_EXHAUSTED");
+    assert_eq!(err.to_string(), "fuel exhausted");
+}
+
+#[test]
+fn memory_limit_is_structured() {
+    let mut code = Vec::new();
+    for _ in 0..5 {
+        code.extend(const_i64(1));
+    }
+    let err = run_with(&code, 10_000, 4, vec![]).unwrap_err();
+    assert_eq!(err, VmError::MemoryLimit(4));
+    assert_eq!(err.code(), "MEMORY_LIMIT");
+}
+
+#[test]
+fn invalid_opcode_is_structured() {
+    let err = run(&tlv_instr(0xFF, &[])).unwrap_err();
+    assert_eq!(err, VmError::InvalidOpcode(0xFF));
+    assert_eq!(err.code(), "INVALID_OPCODE");
+}
+
+#[test]
+fn stack_underflow_is_structured() {
+    let err = run(&tlv_instr(0x11, &[])).unwrap_err();
+    assert!(matches!(err, VmError::StackUnderflow(_)));
+    assert_eq!(err.code(), "STACK_UNDERFLOW");
+}
+
+#[test]
+fn non_finite_is_structured() {
+    let mut code = const_i64(f64::INFINITY.to_bits() as i64);
+    code.extend(tlv_instr(0x18, &[]));
+    let err = run(&code).unwrap_err();
+    assert_eq!(err, VmError::NonFinite(rb_vm::Opcode::NumFromF64Bits));
+    assert_eq!(err.code(), "NON_FINITE");
+}
+
+#[test]
+fn cas_miss_is_structured() {
+    let mut code = tlv_instr(0x12, &0u16.to_be_bytes());
+    code.extend(tlv_instr(0x0C, &[]));
+    let missing = Cid("b3:missing".to_string());
+    let err = run_with(&code, 10_000, rb_vm::DEFAULT_MAX_STACK_DEPTH, vec![missing]).unwrap_err();
+    assert_eq!(err, VmError::CasMiss("b3:missing".to_string()));
+    assert_eq!(err.code(), "CAS_MISS");
+}
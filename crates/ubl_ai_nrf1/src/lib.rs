@@ -4,6 +4,7 @@
 pub mod chip_format;
 pub mod envelope;
 pub mod nrf;
+pub mod number_policy;
 
 // Re-export key types and functions
 pub use chip_format::{
@@ -12,5 +13,6 @@ pub use chip_format::{
 pub use envelope::{EnvelopeError, UblEnvelope};
 pub use nrf::{
     compute_cid, normalize_as_set, normalize_for_input, normalize_timestamp, to_nrf1_bytes,
-    CompileError,
+    to_nrf1_bytes_with_policy, CompileError,
 };
+pub use number_policy::CanonNumberPolicy;
@@ -1,4 +1,5 @@
 // NRF-1.1 canonical encoding and CID
+use crate::number_policy::CanonNumberPolicy;
 use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use serde_json::Value;
@@ -189,8 +190,13 @@ fn decode_varint32<R: Read>(r: &mut R) -> Result<u32> {
     Ok(result)
 }
 
+/// Convert JSON to an NRF value under the `UBL_CANON_NUMBER_POLICY` policy.
 pub fn json_to_nrf(value: &Value) -> Result<NrfValue> {
-    json_to_nrf_with_path(value, "body")
+    json_to_nrf_with_policy(value, CanonNumberPolicy::from_env())
+}
+
+pub fn json_to_nrf_with_policy(value: &Value, policy: CanonNumberPolicy) -> Result<NrfValue> {
+    json_to_nrf_with_path(value, "body", policy)
 }
 
 fn path_for_key(path: &str, key: &str) -> String {
@@ -208,17 +214,11 @@ fn path_for_index(path: &str, idx: usize) -> String {
     format!("{}[{}]", path, idx)
 }
 
-fn json_to_nrf_with_path(value: &Value, path: &str) -> Result<NrfValue> {
+fn json_to_nrf_with_path(value: &Value, path: &str, policy: CanonNumberPolicy) -> Result<NrfValue> {
     match value {
         Value::Null => Ok(NrfValue::Null),
         Value::Bool(b) => Ok(NrfValue::Bool(*b)),
-        Value::Number(n) => {
-            if n.is_i64() {
-                Ok(NrfValue::Int(n.as_i64().unwrap()))
-            } else {
-                bail!("ρ violation at {}: Number must be i64", path);
-            }
-        }
+        Value::Number(n) => policy.number_to_nrf(n, path),
         Value::String(s) => {
             if s.chars().any(|c| c == '\u{feff}') {
                 bail!("ρ violation at {}: BOMPresent", path);
@@ -234,7 +234,11 @@ fn json_to_nrf_with_path(value: &Value, path: &str) -> Result<NrfValue> {
         Value::Array(arr) => {
             let mut items = Vec::with_capacity(arr.len());
             for (idx, v) in arr.iter().enumerate() {
-                items.push(json_to_nrf_with_path(v, &path_for_index(path, idx))?);
+                items.push(json_to_nrf_with_path(
+                    v,
+                    &path_for_index(path, idx),
+                    policy,
+                )?);
             }
             Ok(NrfValue::Array(items))
         }
@@ -255,7 +259,7 @@ fn json_to_nrf_with_path(value: &Value, path: &str) -> Result<NrfValue> {
                 if v.is_null() {
                     continue;
                 }
-                let nrf_val = json_to_nrf_with_path(v, &key_path)?;
+                let nrf_val = json_to_nrf_with_path(v, &key_path, policy)?;
                 if bt.insert(k.clone(), nrf_val).is_some() {
                     bail!("ρ violation at {}: DuplicateKey({})", path, k);
                 }
@@ -397,9 +401,10 @@ fn normalize_as_set_with_path(
     items: &[serde_json::Value],
     path: &str,
 ) -> Result<Vec<serde_json::Value>> {
+    let policy = CanonNumberPolicy::from_env();
     let mut pairs: Vec<(Vec<u8>, serde_json::Value)> = Vec::with_capacity(items.len());
     for (idx, item) in items.iter().enumerate() {
-        let nrf = json_to_nrf_with_path(item, &path_for_index(path, idx))?;
+        let nrf = json_to_nrf_with_path(item, &path_for_index(path, idx), policy)?;
         let bytes = encode_to_vec(&nrf)?;
         pairs.push((bytes, item.clone()));
     }
@@ -425,7 +430,15 @@ pub enum CompileError {
 
 /// Convert JSON to NRF-1 bytes
 pub fn to_nrf1_bytes(json: &serde_json::Value) -> Result<Vec<u8>, CompileError> {
-    let nrf_value = json_to_nrf(json)?;
+    to_nrf1_bytes_with_policy(json, CanonNumberPolicy::from_env())
+}
+
+/// Convert JSON to NRF-1 bytes under an explicit number policy.
+pub fn to_nrf1_bytes_with_policy(
+    json: &serde_json::Value,
+    policy: CanonNumberPolicy,
+) -> Result<Vec<u8>, CompileError> {
+    let nrf_value = json_to_nrf_with_policy(json, policy)?;
     let bytes = encode_to_vec(&nrf_value)?;
     Ok(bytes)
 }
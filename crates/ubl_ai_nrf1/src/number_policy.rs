@@ -0,0 +1,335 @@
+//! Canonical number policy — how JSON numbers outside `i64` reach NRF-1.
+//!
+//! Selected with `UBL_CANON_NUMBER_POLICY` (default `strict_i64`):
+//!
+//! - `strict_i64`: only `i64` literals are canonical; anything else is a ρ
+//!   violation. This is the historical behavior and every existing CID.
+//! - `big_decimal_string`: integral values in `i64` range encode as `Int`;
+//!   every other number encodes as its exact decimal string, so `1.5` and
+//!   `"1.5"` share a CID.
+//! - `json_number`: integral values in `i64` range encode as `Int`; larger
+//!   integers encode as UNC-1 `int/1` and fractions as `dec/1`, matching the
+//!   CID of the equivalent `@num` atom.
+//!
+//! The integer `-0` is `0` in every mode; outside `strict_i64`, so is `-0.0`.
+//! In every mode `i64` literals keep their strict-mode bytes, so switching
+//! policy never changes an existing CID.
+//!
+//! Digits come from the literal text (the workspace builds `serde_json` with
+//! `arbitrary_precision`), never from an `f64`, so two numbers share bytes
+//! only when they are numerically equal. A literal whose exponent would
+//! expand past [`MAX_CANONICAL_DIGITS`] is rejected.
+
+use crate::nrf::NrfValue;
+use anyhow::{anyhow, bail, Result};
+use serde_json::Number;
+use std::collections::BTreeMap;
+
+pub const CANON_NUMBER_POLICY_ENV: &str = "UBL_CANON_NUMBER_POLICY";
+
+/// Longest canonical decimal a number may expand to (`1e5000` is refused).
+pub const MAX_CANONICAL_DIGITS: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CanonNumberPolicy {
+    #[default]
+    StrictI64,
+    BigDecimalString,
+    JsonNumber,
+}
+
+impl CanonNumberPolicy {
+    pub fn from_env() -> Self {
+        std::env::var(CANON_NUMBER_POLICY_ENV)
+            .ok()
+            .and_then(|v| Self::parse(&v))
+            .unwrap_or_default()
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "strict_i64" => Some(Self::StrictI64),
+            "big_decimal_string" => Some(Self::BigDecimalString),
+            "json_number" => Some(Self::JsonNumber),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::StrictI64 => "strict_i64",
+            Self::BigDecimalString => "big_decimal_string",
+            Self::JsonNumber => "json_number",
+        }
+    }
+
+    /// Encode one JSON number under this policy.
+    pub fn number_to_nrf(self, n: &Number, path: &str) -> Result<NrfValue> {
+        if let Some(i) = n.as_i64() {
+            return Ok(NrfValue::Int(i));
+        }
+        if self == Self::StrictI64 {
+            bail!("ρ violation at {}: Number must be i64", path);
+        }
+        let decimal = canonical_decimal(n)
+            .ok_or_else(|| anyhow!("ρ violation at {}: Number is not representable", path))?;
+        if let Ok(i) = decimal.parse::<i64>() {
+            return Ok(NrfValue::Int(i));
+        }
+        Ok(match self {
+            Self::StrictI64 => unreachable!(),
+            Self::BigDecimalString => NrfValue::String(decimal),
+            Self::JsonNumber => unc1_atom(&decimal),
+        })
+    }
+}
+
+/// An integral number that fits `i64` (`1e10`, `-0.0`, `2^53` as a float).
+pub fn integral_i64(n: &Number) -> Option<i64> {
+    canonical_decimal(n)?.parse().ok()
+}
+
+/// Exact decimal digits of a number: no exponent, no leading integer or
+/// trailing fraction zeros, and `-0` rendered as `0`. Read from the literal
+/// text; `None` when it is not a JSON number or expands past
+/// [`MAX_CANONICAL_DIGITS`].
+pub fn canonical_decimal(n: &Number) -> Option<String> {
+    let text = n.to_string();
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.as_str()),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(at) => (&unsigned[..at], unsigned[at + 1..].parse::<i64>().ok()?),
+        None => (unsigned, 0),
+    };
+    let (whole, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if whole.is_empty()
+        || !whole
+            .bytes()
+            .chain(frac.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    // digits × 10^scale, with no leading or trailing zeros in `digits`.
+    let digits = format!("{}{}", whole, frac);
+    let digits = digits.trim_start_matches('0');
+    let trimmed = digits.trim_end_matches('0');
+    if trimmed.is_empty() {
+        return Some("0".to_string());
+    }
+    let scale = exponent
+        .checked_sub(frac.len() as i64)?
+        .checked_add((digits.len() - trimmed.len()) as i64)?;
+    let sign = if negative { "-" } else { "" };
+
+    let width = trimmed.len() as i64 + scale.abs();
+    if width > MAX_CANONICAL_DIGITS as i64 {
+        return None;
+    }
+    Some(if scale >= 0 {
+        format!("{}{}{}", sign, trimmed, "0".repeat(scale as usize))
+    } else {
+        let point = trimmed.len() as i64 + scale;
+        if point > 0 {
+            let (int, frac) = trimmed.split_at(point as usize);
+            format!("{}{}.{}", sign, int, frac)
+        } else {
+            format!(
+                "{}0.{}{}",
+                sign,
+                "0".repeat(point.unsigned_abs() as usize),
+                trimmed
+            )
+        }
+    })
+}
+
+/// UNC-1 atom for a canonical decimal string, as an NRF map.
+fn unc1_atom(decimal: &str) -> NrfValue {
+    let mut map = BTreeMap::new();
+    match decimal.split_once('.') {
+        None => {
+            map.insert("@num".to_string(), NrfValue::String("int/1".into()));
+            map.insert("v".to_string(), NrfValue::String(decimal.to_string()));
+        }
+        Some((whole, frac)) => {
+            let (sign, whole) = match whole.strip_prefix('-') {
+                Some(rest) => ("-", rest),
+                None => ("", whole),
+            };
+            let digits = format!("{}{}", whole, frac);
+            let digits = digits.trim_start_matches('0');
+            map.insert("@num".to_string(), NrfValue::String("dec/1".into()));
+            map.insert(
+                "m".to_string(),
+                NrfValue::String(format!("{}{}", sign, digits)),
+            );
+            map.insert("s".to_string(), NrfValue::Int(frac.len() as i64));
+        }
+    }
+    NrfValue::Map(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nrf::{json_to_nrf_with_policy, to_nrf1_bytes_with_policy};
+    use serde_json::{json, Value};
+
+    const ALL: [CanonNumberPolicy; 3] = [
+        CanonNumberPolicy::StrictI64,
+        CanonNumberPolicy::BigDecimalString,
+        CanonNumberPolicy::JsonNumber,
+    ];
+
+    fn parse(raw: &str) -> Value {
+        serde_json::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn policy_names_round_trip() {
+        for policy in ALL {
+            assert_eq!(CanonNumberPolicy::parse(policy.as_str()), Some(policy));
+        }
+        assert_eq!(CanonNumberPolicy::parse("f64"), None);
+        assert_eq!(CanonNumberPolicy::default(), CanonNumberPolicy::StrictI64);
+    }
+
+    #[test]
+    fn two_pow_53_is_int_in_every_mode() {
+        let literal = parse("9007199254740992");
+        let float = parse("9007199254740992.0");
+        for policy in ALL {
+            assert_eq!(
+                json_to_nrf_with_policy(&literal, policy).unwrap(),
+                NrfValue::Int(9_007_199_254_740_992)
+            );
+        }
+        assert!(json_to_nrf_with_policy(&float, CanonNumberPolicy::StrictI64).is_err());
+        for policy in [
+            CanonNumberPolicy::BigDecimalString,
+            CanonNumberPolicy::JsonNumber,
+        ] {
+            assert_eq!(
+                to_nrf1_bytes_with_policy(&float, policy).unwrap(),
+                to_nrf1_bytes_with_policy(&literal, policy).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn negative_zero_is_zero_outside_strict() {
+        // serde_json reads the integer `-0` as `0`, an i64 in every mode.
+        assert_eq!(
+            json_to_nrf_with_policy(&parse("-0"), CanonNumberPolicy::StrictI64).unwrap(),
+            NrfValue::Int(0)
+        );
+        for raw in ["-0.0", "-0e3"] {
+            let neg_zero = parse(raw);
+            let err = json_to_nrf_with_policy(&neg_zero, CanonNumberPolicy::StrictI64)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("Number must be i64"));
+            for policy in [
+                CanonNumberPolicy::BigDecimalString,
+                CanonNumberPolicy::JsonNumber,
+            ] {
+                assert_eq!(
+                    json_to_nrf_with_policy(&neg_zero, policy).unwrap(),
+                    NrfValue::Int(0)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn one_e10_matches_its_integer_spelling() {
+        let exp = parse("1e10");
+        let int = parse("10000000000");
+        assert!(json_to_nrf_with_policy(&exp, CanonNumberPolicy::StrictI64).is_err());
+        for policy in [
+            CanonNumberPolicy::BigDecimalString,
+            CanonNumberPolicy::JsonNumber,
+        ] {
+            assert_eq!(
+                to_nrf1_bytes_with_policy(&exp, policy).unwrap(),
+                to_nrf1_bytes_with_policy(&int, policy).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn digits_come_from_the_literal_not_an_f64() {
+        for policy in [
+            CanonNumberPolicy::BigDecimalString,
+            CanonNumberPolicy::JsonNumber,
+        ] {
+            for (a, b) in [
+                ("12345678901234567890123", "12345678901234567890124"),
+                ("0.10000000000000000001", "0.1"),
+                ("9007199254740993.0", "9007199254740992.0"),
+            ] {
+                assert_ne!(
+                    to_nrf1_bytes_with_policy(&parse(a), policy).unwrap(),
+                    to_nrf1_bytes_with_policy(&parse(b), policy).unwrap(),
+                    "{} vs {}",
+                    a,
+                    b
+                );
+            }
+        }
+        let exact = |raw: &str| match parse(raw) {
+            Value::Number(n) => canonical_decimal(&n),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            exact("12345678901234567890123").unwrap(),
+            "12345678901234567890123"
+        );
+        assert_eq!(exact("-0.000120e2").unwrap(), "-0.012");
+        assert_eq!(exact("1.5E+3").unwrap(), "1500");
+        assert_eq!(exact("1e-3").unwrap(), "0.001");
+        assert_eq!(exact("0.0e7").unwrap(), "0");
+        assert!(exact("1e5000").is_none());
+        assert!(json_to_nrf_with_policy(&parse("1e5000"), CanonNumberPolicy::JsonNumber).is_err());
+    }
+
+    #[test]
+    fn values_beyond_i64_follow_the_policy() {
+        let big = parse("9223372036854775808");
+        let frac = parse("-12.50");
+        assert!(json_to_nrf_with_policy(&big, CanonNumberPolicy::StrictI64).is_err());
+        assert!(json_to_nrf_with_policy(&frac, CanonNumberPolicy::StrictI64).is_err());
+
+        assert_eq!(
+            json_to_nrf_with_policy(&big, CanonNumberPolicy::BigDecimalString).unwrap(),
+            NrfValue::String("9223372036854775808".into())
+        );
+        assert_eq!(
+            json_to_nrf_with_policy(&frac, CanonNumberPolicy::BigDecimalString).unwrap(),
+            NrfValue::String("-12.5".into())
+        );
+
+        let policy = CanonNumberPolicy::JsonNumber;
+        assert_eq!(
+            to_nrf1_bytes_with_policy(&big, policy).unwrap(),
+            to_nrf1_bytes_with_policy(
+                &json!({"@num": "int/1", "v": "9223372036854775808"}),
+                policy
+            )
+            .unwrap()
+        );
+        let dec = ubl_unc1::Num::Dec {
+            m: "-125".into(),
+            s: 1,
+            u: None,
+        };
+        assert_eq!(
+            to_nrf1_bytes_with_policy(&frac, policy).unwrap(),
+            to_nrf1_bytes_with_policy(&serde_json::to_value(dec).unwrap(), policy).unwrap()
+        );
+    }
+}
@@ -16,6 +16,8 @@ pub mod domains {
     pub const RB_VM: &str = "ubl-rb-vm/v1";
     pub const CAPABILITY: &str = "ubl-capability/v1";
     pub const RUNTIME_ATTESTATION: &str = "ubl/runtime-attestation/v1";
+    pub const RECEIPT_BUNDLE: &str = "ubl/receipt-bundle/v1";
+    pub const EVENT: &str = "ubl/event/v1";
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
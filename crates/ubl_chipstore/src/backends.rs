@@ -1,5 +1,6 @@
 //! Storage backends for ChipStore
 
+use crate::indexing::IndexMaps;
 use crate::{ChipQuery, ChipStoreBackend, ChipStoreError, QueryResult, StoredChip};
 use async_trait::async_trait;
 use serde_json;
@@ -276,6 +277,36 @@ impl ChipStoreBackend for FsBackend {
     async fn scan_all(&self) -> Result<Vec<StoredChip>, ChipStoreError> {
         self.iter_all_chips()
     }
+
+    async fn for_each_entry(
+        &self,
+        visit: &mut (dyn FnMut(String, Result<StoredChip, ChipStoreError>) + Send),
+    ) -> Result<(), ChipStoreError> {
+        let entries = std::fs::read_dir(self.chips_dir())
+            .map_err(|e| ChipStoreError::Backend(format!("FsBackend read_dir: {}", e)))?;
+        for entry in entries {
+            let entry = entry
+                .map_err(|e| ChipStoreError::Backend(format!("FsBackend dir entry: {}", e)))?;
+            let path = entry.path();
+            if path.extension().and_then(|x| x.to_str()) != Some("json") {
+                continue;
+            }
+            // File names are `sanitize_cid(cid)`: undo the single `:` → `_`.
+            let key = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .replacen('_', ":", 1);
+            let chip = std::fs::read(&path)
+                .map_err(|e| ChipStoreError::Backend(format!("FsBackend read: {}", e)))
+                .and_then(|bytes| {
+                    serde_json::from_slice(&bytes)
+                        .map_err(|e| ChipStoreError::Serialization(e.to_string()))
+                });
+            visit(key, chip);
+        }
+        Ok(())
+    }
 }
 
 /// S3-compatible backend (local emulation for now).
@@ -356,6 +387,13 @@ impl ChipStoreBackend for S3Backend {
     async fn scan_all(&self) -> Result<Vec<StoredChip>, ChipStoreError> {
         self.fs.scan_all().await
     }
+
+    async fn for_each_entry(
+        &self,
+        visit: &mut (dyn FnMut(String, Result<StoredChip, ChipStoreError>) + Send),
+    ) -> Result<(), ChipStoreError> {
+        self.fs.for_each_entry(visit).await
+    }
 }
 
 #[async_trait]
@@ -473,26 +511,22 @@ impl ChipStoreBackend for InMemoryBackend {
     }
 
     async fn rebuild_indexes(&self) -> Result<(), ChipStoreError> {
-        {
-            let mut type_index = self.type_index.write().await;
-            type_index.clear();
-        }
-        {
-            let mut tag_index = self.tag_index.write().await;
-            tag_index.clear();
-        }
-        {
-            let mut executor_index = self.executor_index.write().await;
-            executor_index.clear();
-        }
-
-        let chips_snapshot: Vec<StoredChip> = {
-            let chips = self.chips.read().await;
-            chips.values().cloned().collect()
-        };
-        for chip in &chips_snapshot {
-            self.index_chip(chip).await;
-        }
+        // Hold the primary map for the whole rebuild so writers queue behind
+        // it; the fresh maps are swapped in under the index write locks.
+        let chips = self.chips.read().await;
+        let fresh = IndexMaps::build(chips.values());
+        let receipts: HashMap<TypedCid, TypedCid> = chips
+            .values()
+            .map(|chip| (chip.receipt_cid.clone(), chip.cid.clone()))
+            .collect();
+        let mut receipt_index = self.receipt_index.write().await;
+        let mut type_index = self.type_index.write().await;
+        let mut tag_index = self.tag_index.write().await;
+        let mut executor_index = self.executor_index.write().await;
+        *receipt_index = receipts;
+        *type_index = fresh.type_index;
+        *tag_index = fresh.tag_index;
+        *executor_index = fresh.executor_index;
         Ok(())
     }
 
@@ -502,6 +536,62 @@ impl ChipStoreBackend for InMemoryBackend {
     }
 }
 
+/// Sled tuning knobs for the persistent chip CAS.
+///
+/// `flush_every_ms` controls how often sled's background thread fsyncs dirty
+/// pages. A larger interval raises write throughput but widens the window of
+/// acknowledged writes that can be lost on a crash or power failure: anything
+/// written since the last flush is gone. `None` disables periodic flushing
+/// entirely, leaving durability to explicit flushes and clean shutdown.
+///
+/// `cache_capacity` is the page cache size in bytes. It trades memory for
+/// read throughput and has no effect on durability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SledConfig {
+    pub flush_every_ms: Option<u64>,
+    pub cache_capacity: u64,
+}
+
+impl Default for SledConfig {
+    /// Matches sled's own defaults (500ms flush, 1 GiB cache).
+    fn default() -> Self {
+        Self {
+            flush_every_ms: Some(500),
+            cache_capacity: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+impl SledConfig {
+    /// Read tuning from `UBL_SLED_FLUSH_MS` and `UBL_SLED_CACHE_BYTES`.
+    ///
+    /// `UBL_SLED_FLUSH_MS=0` (or `off`) disables periodic flushing.
+    /// Unset or unparsable values fall back to [`SledConfig::default`].
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let flush_every_ms = match std::env::var("UBL_SLED_FLUSH_MS")
+            .ok()
+            .map(|v| v.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("0") | Some("off") => None,
+            Some(v) => v
+                .parse::<u64>()
+                .map(Some)
+                .unwrap_or(defaults.flush_every_ms),
+            None => defaults.flush_every_ms,
+        };
+        let cache_capacity = std::env::var("UBL_SLED_CACHE_BYTES")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(defaults.cache_capacity);
+        Self {
+            flush_every_ms,
+            cache_capacity,
+        }
+    }
+}
+
 /// Sled (embedded database) backend
 pub struct SledBackend {
     db: sled::Db,
@@ -515,33 +605,42 @@ impl SledBackend {
     pub fn new(path: &str) -> Result<Self, ChipStoreError> {
         let db = sled::open(path)
             .map_err(|e| ChipStoreError::Backend(format!("Failed to open sled DB: {}", e)))?;
-        let receipt_index = db
-            .open_tree("receipt_index")
-            .map_err(|e| ChipStoreError::Backend(format!("Failed to open receipt index: {}", e)))?;
-        let type_index = db
-            .open_tree("type_index")
-            .map_err(|e| ChipStoreError::Backend(format!("Failed to open type index: {}", e)))?;
-        let tag_index = db
-            .open_tree("tag_index")
-            .map_err(|e| ChipStoreError::Backend(format!("Failed to open tag index: {}", e)))?;
-        let executor_index = db.open_tree("executor_index").map_err(|e| {
-            ChipStoreError::Backend(format!("Failed to open executor index: {}", e))
-        })?;
-        let backend = Self {
-            db,
-            receipt_index,
-            type_index,
-            tag_index,
-            executor_index,
-        };
-        backend.rebuild_indexes()?;
-        Ok(backend)
+        Self::from_db(db)
+    }
+
+    /// Open a sled DB at `path` with explicit flush/cache tuning.
+    pub fn with_config(path: &str, config: SledConfig) -> Result<Self, ChipStoreError> {
+        let db = sled::Config::new()
+            .path(path)
+            .flush_every_ms(config.flush_every_ms)
+            .cache_capacity(config.cache_capacity)
+            .open()
+            .map_err(|e| ChipStoreError::Backend(format!("Failed to open sled DB: {}", e)))?;
+        Self::from_db(db)
     }
 
     pub fn in_memory() -> Result<Self, ChipStoreError> {
         let db = sled::Config::new().temporary(true).open().map_err(|e| {
             ChipStoreError::Backend(format!("Failed to create in-memory sled DB: {}", e))
         })?;
+        Self::from_db(db)
+    }
+
+    /// Open without rebuilding secondary indexes, for maintenance scans
+    /// (`verify_all`) of a store whose entries may not all decode.
+    pub fn open_unindexed(path: &str) -> Result<Self, ChipStoreError> {
+        let db = sled::open(path)
+            .map_err(|e| ChipStoreError::Backend(format!("Failed to open sled DB: {}", e)))?;
+        Self::open_trees(db)
+    }
+
+    fn from_db(db: sled::Db) -> Result<Self, ChipStoreError> {
+        let backend = Self::open_trees(db)?;
+        backend.rebuild_indexes()?;
+        Ok(backend)
+    }
+
+    fn open_trees(db: sled::Db) -> Result<Self, ChipStoreError> {
         let receipt_index = db
             .open_tree("receipt_index")
             .map_err(|e| ChipStoreError::Backend(format!("Failed to open receipt index: {}", e)))?;
@@ -554,42 +653,82 @@ impl SledBackend {
         let executor_index = db.open_tree("executor_index").map_err(|e| {
             ChipStoreError::Backend(format!("Failed to open executor index: {}", e))
         })?;
-        let backend = Self {
+        Ok(Self {
             db,
             receipt_index,
             type_index,
             tag_index,
             executor_index,
-        };
-        backend.rebuild_indexes()?;
-        Ok(backend)
+        })
     }
 
+    /// Reconcile the index trees with primary storage without clearing
+    /// them: missing entries are inserted first, then entries that no
+    /// longer match a stored chip are removed. Lookups stay answerable
+    /// throughout, and chips written during the pass keep their entries.
     fn rebuild_indexes(&self) -> Result<(), ChipStoreError> {
-        self.receipt_index
-            .clear()
-            .map_err(|e| ChipStoreError::Backend(e.to_string()))?;
-        self.type_index
-            .clear()
-            .map_err(|e| ChipStoreError::Backend(e.to_string()))?;
-        self.tag_index
-            .clear()
-            .map_err(|e| ChipStoreError::Backend(e.to_string()))?;
-        self.executor_index
-            .clear()
-            .map_err(|e| ChipStoreError::Backend(e.to_string()))?;
-
+        let backend_err = |e: sled::Error| ChipStoreError::Backend(e.to_string());
+        let mut receipts: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+        let mut expected: [HashSet<Vec<u8>>; 3] = Default::default();
         for entry in self.db.iter() {
-            let (_cid, value) = entry.map_err(|e| ChipStoreError::Backend(e.to_string()))?;
+            let (_cid, value) = entry.map_err(backend_err)?;
             let chip: StoredChip = serde_json::from_slice(&value)
                 .map_err(|e| ChipStoreError::Serialization(e.to_string()))?;
+            let cid = chip.cid.as_str();
+            receipts.insert(
+                chip.receipt_cid.as_str().as_bytes().to_vec(),
+                cid.as_bytes().to_vec(),
+            );
+            expected[0].insert(index_composite_key(&chip.chip_type, cid));
+            for tag in &chip.tags {
+                expected[1].insert(index_composite_key(tag, cid));
+            }
+            expected[2].insert(index_composite_key(
+                chip.execution_metadata.executor_did.as_str(),
+                cid,
+            ));
+        }
+
+        for (receipt_cid, chip_cid) in &receipts {
             self.receipt_index
-                .insert(
-                    chip.receipt_cid.as_str().as_bytes(),
-                    chip.cid.as_str().as_bytes(),
-                )
-                .map_err(|e| ChipStoreError::Backend(e.to_string()))?;
-            self.add_chip_to_indexes(&chip)?;
+                .insert(receipt_cid.as_slice(), chip_cid.as_slice())
+                .map_err(backend_err)?;
+        }
+        let trees = [&self.type_index, &self.tag_index, &self.executor_index];
+        for (tree, keys) in trees.iter().zip(&expected) {
+            for key in keys {
+                tree.insert(key.as_slice(), &[]).map_err(backend_err)?;
+            }
+        }
+
+        let scanned: HashSet<&[u8]> = receipts.values().map(Vec::as_slice).collect();
+        // An unexpected entry survives only if it belongs to a chip stored
+        // after the scan above.
+        let stale = |chip_cid: &[u8]| -> Result<bool, ChipStoreError> {
+            Ok(scanned.contains(chip_cid)
+                || !self.db.contains_key(chip_cid).map_err(backend_err)?)
+        };
+        for item in self.receipt_index.iter() {
+            let (key, chip_cid) = item.map_err(backend_err)?;
+            if !receipts.contains_key(key.as_ref()) && stale(&chip_cid)? {
+                self.receipt_index.remove(key).map_err(backend_err)?;
+            }
+        }
+        for (tree, keys) in trees.iter().zip(&expected) {
+            for item in tree.iter() {
+                let (key, _) = item.map_err(backend_err)?;
+                if keys.contains(key.as_ref()) {
+                    continue;
+                }
+                let chip_cid = key
+                    .iter()
+                    .position(|b| *b == 0)
+                    .map(|sep| &key[sep + 1..])
+                    .unwrap_or_default();
+                if stale(chip_cid)? {
+                    tree.remove(&key).map_err(backend_err)?;
+                }
+            }
         }
 
         Ok(())
@@ -838,6 +977,19 @@ impl ChipStoreBackend for SledBackend {
     async fn scan_all(&self) -> Result<Vec<StoredChip>, ChipStoreError> {
         self.scan_all_chips()
     }
+
+    async fn for_each_entry(
+        &self,
+        visit: &mut (dyn FnMut(String, Result<StoredChip, ChipStoreError>) + Send),
+    ) -> Result<(), ChipStoreError> {
+        for item in self.db.iter() {
+            let (key, value) = item.map_err(|e| ChipStoreError::Backend(e.to_string()))?;
+            let chip = serde_json::from_slice(&value)
+                .map_err(|e| ChipStoreError::Serialization(e.to_string()));
+            visit(String::from_utf8_lossy(&key).into_owned(), chip);
+        }
+        Ok(())
+    }
 }
 
 fn apply_pagination(results: &mut Vec<StoredChip>, query: &ChipQuery) -> QueryResult {
@@ -1031,6 +1183,179 @@ mod tests {
         let _ = std::fs::remove_dir_all(&path);
     }
 
+    #[tokio::test]
+    async fn sled_rebuild_indexes_prunes_entries_without_a_chip() {
+        let backend = Arc::new(SledBackend::in_memory().expect("open sled"));
+        let store = ChipStore::new(backend.clone());
+        store
+            .store_executed_chip(
+                json!({
+                    "@type": "ubl/advisory",
+                    "@id": "adv-prune",
+                    "@ver": "1.0",
+                    "@world": "a/rebuild/t/prod"
+                }),
+                "b3:eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee".to_string(),
+                test_metadata(),
+            )
+            .await
+            .expect("store advisory");
+        backend
+            .add_index_entry(&backend.type_index, "ubl/advisory", "b3:gone")
+            .expect("plant stale entry");
+        assert_eq!(
+            backend
+                .read_index_set(&backend.type_index, "ubl/advisory")
+                .unwrap()
+                .len(),
+            2
+        );
+
+        store.reindex().await.expect("reindex");
+
+        let live = backend
+            .read_index_set(&backend.type_index, "ubl/advisory")
+            .unwrap();
+        assert_eq!(live.len(), 1);
+        assert!(!live.contains("b3:gone"));
+    }
+
+    #[tokio::test]
+    async fn sled_with_config_persists_across_reopen() {
+        let mut path = std::env::temp_dir();
+        let nonce = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        path.push(format!(
+            "ubl_chipstore_tuned_{}_{}",
+            std::process::id(),
+            nonce
+        ));
+        let path_str = path.to_string_lossy().to_string();
+        let config = SledConfig {
+            flush_every_ms: Some(5_000),
+            cache_capacity: 8 * 1024 * 1024,
+        };
+
+        let receipt_cid = "b3:3333333333333333333333333333333333333333333333333333333333333333";
+        let cid = {
+            let store = ChipStore::new(Arc::new(
+                SledBackend::with_config(&path_str, config).expect("open tuned sled"),
+            ));
+            store
+                .store_executed_chip(
+                    json!({
+                        "@type": "ubl/document",
+                        "@id": "tuned-1",
+                        "@ver": "1.0",
+                        "@world": "a/tuned/t/prod",
+                        "status": "ok"
+                    }),
+                    receipt_cid.to_string(),
+                    test_metadata(),
+                )
+                .await
+                .expect("store")
+        };
+
+        let store = ChipStore::new(Arc::new(
+            SledBackend::with_config(&path_str, config).expect("reopen tuned sled"),
+        ));
+        assert!(store.get_chip(&cid).await.expect("get by cid").is_some());
+        assert!(store
+            .get_chip_by_receipt_cid(receipt_cid)
+            .await
+            .expect("get by receipt")
+            .is_some());
+        drop(store);
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[tokio::test]
+    async fn sled_config_from_env_parses_overrides() {
+        let _guard = env_lock().lock().await;
+        std::env::set_var("UBL_SLED_FLUSH_MS", "off");
+        std::env::set_var("UBL_SLED_CACHE_BYTES", "4096");
+        let config = SledConfig::from_env();
+        assert_eq!(config.flush_every_ms, None);
+        assert_eq!(config.cache_capacity, 4096);
+
+        std::env::set_var("UBL_SLED_FLUSH_MS", "2000");
+        std::env::remove_var("UBL_SLED_CACHE_BYTES");
+        let config = SledConfig::from_env();
+        assert_eq!(config.flush_every_ms, Some(2000));
+        assert_eq!(config.cache_capacity, SledConfig::default().cache_capacity);
+        std::env::remove_var("UBL_SLED_FLUSH_MS");
+    }
+
+    #[tokio::test]
+    async fn verify_all_flags_corrupted_sled_entries() {
+        let backend = Arc::new(SledBackend::in_memory().expect("sled"));
+        let store = ChipStore::new(backend.clone());
+        let mut cids = Vec::new();
+        for (i, id) in ["verify-ok", "verify-rot"].iter().enumerate() {
+            let cid = store
+                .store_executed_chip(
+                    json!({
+                        "@type": "ubl/document",
+                        "@id": id,
+                        "@ver": "1.0",
+                        "@world": "a/verify/t/prod",
+                        "status": "ok"
+                    }),
+                    format!("b3:{:064}", i),
+                    test_metadata(),
+                )
+                .await
+                .expect("store");
+            cids.push(cid);
+        }
+        assert!(store
+            .verify_all()
+            .await
+            .expect("verify clean store")
+            .is_clean());
+
+        let mut rotted = store.get_chip(&cids[1]).await.unwrap().unwrap();
+        rotted.chip_data["status"] = json!("tampered");
+        backend
+            .db
+            .insert(cids[1].as_bytes(), serde_json::to_vec(&rotted).unwrap())
+            .unwrap();
+        backend
+            .db
+            .insert(b"b3:garbage".as_slice(), b"{not json".as_slice())
+            .unwrap();
+
+        let mut streamed = Vec::new();
+        let report = store
+            .verify_all_with(|f| streamed.push(f.key.clone()))
+            .await
+            .expect("verify");
+        assert_eq!(report.scanned, 3);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(
+            streamed,
+            report
+                .failures
+                .iter()
+                .map(|f| f.key.clone())
+                .collect::<Vec<_>>()
+        );
+        let rot = report.failures.iter().find(|f| f.key == cids[1]).unwrap();
+        assert!(rot.computed_cid.as_deref().is_some_and(|c| c != cids[1]));
+        let garbage = report
+            .failures
+            .iter()
+            .find(|f| f.key == "b3:garbage")
+            .unwrap();
+        assert!(garbage.computed_cid.is_none());
+        assert!(garbage.reason.starts_with("undecodable entry"));
+        assert!(report.failures.iter().all(|f| f.key != cids[0]));
+    }
+
     #[tokio::test]
     async fn fs_backend_roundtrip() {
         let mut path = std::env::temp_dir();
@@ -1,6 +1,7 @@
 //! Indexing system for efficient chip queries
 
 use crate::{ChipStoreBackend, ChipStoreError, StoredChip};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -13,6 +14,7 @@ pub struct ChipIndexer {
     type_index: Arc<RwLock<HashMap<String, HashSet<TypedCid>>>>, // chip_type -> CIDs
     tag_index: Arc<RwLock<HashMap<String, HashSet<TypedCid>>>>,  // tag -> CIDs
     executor_index: Arc<RwLock<HashMap<String, HashSet<TypedCid>>>>, // executor_did -> CIDs
+    children_index: Arc<RwLock<HashMap<String, HashSet<TypedCid>>>>, // parent_cid -> child CIDs
 }
 
 impl ChipIndexer {
@@ -22,6 +24,7 @@ impl ChipIndexer {
             type_index: Arc::new(RwLock::new(HashMap::new())),
             tag_index: Arc::new(RwLock::new(HashMap::new())),
             executor_index: Arc::new(RwLock::new(HashMap::new())),
+            children_index: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -64,6 +67,17 @@ impl ChipIndexer {
                 .insert(chip.cid.clone());
         }
 
+        // Index by declared parent
+        {
+            let mut children_index = self.children_index.write().await;
+            for parent in chip.lineage_parents() {
+                children_index
+                    .entry(parent)
+                    .or_insert_with(HashSet::new)
+                    .insert(chip.cid.clone());
+            }
+        }
+
         Ok(())
     }
 
@@ -97,6 +111,18 @@ impl ChipIndexer {
             .unwrap_or_default())
     }
 
+    /// Get CIDs for chips that declare `parent_cid` as a parent
+    pub async fn get_cids_by_parent(
+        &self,
+        parent_cid: &str,
+    ) -> Result<Vec<TypedCid>, ChipStoreError> {
+        let children_index = self.children_index.read().await;
+        Ok(children_index
+            .get(parent_cid)
+            .map(|cids| cids.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
     /// Find intersection of CIDs across multiple criteria
     pub async fn find_intersection(
         &self,
@@ -180,31 +206,171 @@ impl ChipIndexer {
             }
         }
 
+        // Remove from children index
+        {
+            let mut children_index = self.children_index.write().await;
+            for parent in chip.lineage_parents() {
+                if let Some(cids) = children_index.get_mut(&parent) {
+                    cids.remove(&chip.cid);
+                    if cids.is_empty() {
+                        children_index.remove(&parent);
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 
     /// Rebuild all indexes from storage
     pub async fn rebuild_indexes(&self) -> Result<(), ChipStoreError> {
-        // Clear existing indexes
+        self.reindex().await.map(|_| ())
+    }
+
+    /// Rebuild all indexes into fresh maps, then swap them in under the
+    /// write locks, so readers never observe a half-built index. Chips
+    /// indexed while the scan was running are carried over; entries for
+    /// chips no longer in storage are dropped.
+    pub async fn reindex(&self) -> Result<ReindexReport, ChipStoreError> {
+        let chips = self.backend.scan_all().await?;
+        let mut fresh = IndexMaps::build(&chips);
+        let scanned: HashSet<TypedCid> = chips.iter().map(|c| c.cid.clone()).collect();
+
+        let mut type_index = self.type_index.write().await;
+        let mut tag_index = self.tag_index.write().await;
+        let mut executor_index = self.executor_index.write().await;
+        let mut children_index = self.children_index.write().await;
+        let mut late = HashSet::new();
+        for cid in [
+            &*type_index,
+            &*tag_index,
+            &*executor_index,
+            &*children_index,
+        ]
+        .into_iter()
+        .flat_map(|index| index.values().flatten())
         {
-            let mut type_index = self.type_index.write().await;
-            type_index.clear();
+            if !scanned.contains(cid)
+                && !late.contains(cid)
+                && self.backend.exists(cid.as_str()).await?
+            {
+                late.insert(cid.clone());
+            }
         }
-        {
-            let mut tag_index = self.tag_index.write().await;
-            tag_index.clear();
+        fresh.carry_over(
+            &type_index,
+            &tag_index,
+            &executor_index,
+            &children_index,
+            &late,
+        );
+        let report = fresh.report(chips.len());
+        *type_index = fresh.type_index;
+        *tag_index = fresh.tag_index;
+        *executor_index = fresh.executor_index;
+        *children_index = fresh.children_index;
+        Ok(report)
+    }
+}
+
+pub(crate) type CidIndex = HashMap<String, HashSet<TypedCid>>;
+
+/// Key and entry count of one rebuilt index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexCount {
+    pub keys: usize,
+    pub entries: usize,
+}
+
+impl IndexCount {
+    fn of(index: &CidIndex) -> Self {
+        Self {
+            keys: index.len(),
+            entries: index.values().map(HashSet::len).sum(),
         }
-        {
-            let mut executor_index = self.executor_index.write().await;
-            executor_index.clear();
+    }
+}
+
+/// Outcome of [`ChipIndexer::reindex`] / [`crate::ChipStore::reindex`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReindexReport {
+    /// Chips scanned from primary storage.
+    pub chips: usize,
+    pub type_index: IndexCount,
+    pub tag_index: IndexCount,
+    pub executor_index: IndexCount,
+    pub children_index: IndexCount,
+}
+
+/// Secondary index maps built off-lock during a rebuild.
+#[derive(Default)]
+pub(crate) struct IndexMaps {
+    pub(crate) type_index: CidIndex,
+    pub(crate) tag_index: CidIndex,
+    pub(crate) executor_index: CidIndex,
+    pub(crate) children_index: CidIndex,
+}
+
+impl IndexMaps {
+    pub(crate) fn build<'a>(chips: impl IntoIterator<Item = &'a StoredChip>) -> Self {
+        let mut maps = Self::default();
+        for chip in chips {
+            let cid = &chip.cid;
+            maps.type_index
+                .entry(chip.chip_type.clone())
+                .or_default()
+                .insert(cid.clone());
+            for tag in &chip.tags {
+                maps.tag_index
+                    .entry(tag.clone())
+                    .or_default()
+                    .insert(cid.clone());
+            }
+            maps.executor_index
+                .entry(chip.execution_metadata.executor_did.as_str().to_string())
+                .or_default()
+                .insert(cid.clone());
+            for parent in chip.lineage_parents() {
+                maps.children_index
+                    .entry(parent)
+                    .or_default()
+                    .insert(cid.clone());
+            }
         }
+        maps
+    }
 
-        let chips = self.backend.scan_all().await?;
-        for chip in &chips {
-            self.index_chip(chip).await?;
+    /// Copy the `live` entries for `late` chips (stored after the scan).
+    fn carry_over(
+        &mut self,
+        type_index: &CidIndex,
+        tag_index: &CidIndex,
+        executor_index: &CidIndex,
+        children_index: &CidIndex,
+        late: &HashSet<TypedCid>,
+    ) {
+        for (fresh, live) in [
+            (&mut self.type_index, type_index),
+            (&mut self.tag_index, tag_index),
+            (&mut self.executor_index, executor_index),
+            (&mut self.children_index, children_index),
+        ] {
+            for (key, cids) in live {
+                for cid in cids.iter().filter(|cid| late.contains(*cid)) {
+                    fresh.entry(key.clone()).or_default().insert(cid.clone());
+                }
+            }
         }
+    }
 
-        Ok(())
+    fn report(&self, chips: usize) -> ReindexReport {
+        ReindexReport {
+            chips,
+            type_index: IndexCount::of(&self.type_index),
+            tag_index: IndexCount::of(&self.tag_index),
+            executor_index: IndexCount::of(&self.executor_index),
+            children_index: IndexCount::of(&self.children_index),
+        }
     }
 }
 
@@ -237,6 +403,9 @@ mod tests {
             },
             tags: vec![tag.to_string()],
             related_chips: vec![],
+            parents: vec![],
+            quarantined: false,
+            author: None,
         }
     }
 
@@ -277,6 +446,88 @@ mod tests {
         assert_eq!(cids.len(), 2);
     }
 
+    #[tokio::test]
+    async fn rebuild_indexes_children_by_declared_parent() {
+        let backend = Arc::new(InMemoryBackend::new());
+        let parent = "b3:ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+        let mut child = make_chip(
+            "b3:cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc",
+            "b3:3333333333333333333333333333333333333333333333333333333333333333",
+            "ubl/advisory",
+            "status:ok",
+        );
+        child.chip_data["parents"] = json!([parent]);
+        backend.put_chip(&child).await.expect("store child");
+
+        let indexer = ChipIndexer::new_with_rebuild(backend)
+            .await
+            .expect("rebuild");
+        assert_eq!(
+            indexer.get_cids_by_parent(parent).await.unwrap(),
+            vec![child.cid.clone()]
+        );
+
+        indexer.remove_from_indexes(&child).await.unwrap();
+        assert!(indexer.get_cids_by_parent(parent).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reindex_reports_counts_and_drops_entries_missing_from_storage() {
+        let backend = Arc::new(InMemoryBackend::new());
+        let stored = make_chip(
+            "b3:dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd",
+            "b3:4444444444444444444444444444444444444444444444444444444444444444",
+            "ubl/advisory",
+            "status:ok",
+        );
+        backend.put_chip(&stored).await.expect("store chip d");
+        let indexer = ChipIndexer::new(backend.clone());
+        indexer.index_chip(&stored).await.expect("index chip d");
+        let orphan = make_chip(
+            "b3:eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee",
+            "b3:5555555555555555555555555555555555555555555555555555555555555555",
+            "ubl/report",
+            "status:ok",
+        );
+        indexer.index_chip(&orphan).await.expect("index orphan");
+
+        let report = indexer.reindex().await.expect("reindex");
+        assert_eq!(report.chips, 1);
+        assert_eq!(
+            report.type_index,
+            IndexCount {
+                keys: 1,
+                entries: 1
+            }
+        );
+        assert_eq!(
+            report.tag_index,
+            IndexCount {
+                keys: 1,
+                entries: 1
+            }
+        );
+        assert_eq!(
+            report.executor_index,
+            IndexCount {
+                keys: 1,
+                entries: 1
+            }
+        );
+        assert!(indexer
+            .get_cids_by_type("ubl/report")
+            .await
+            .expect("lookup orphan type")
+            .is_empty());
+        assert_eq!(
+            indexer
+                .get_cids_by_tag("status:ok")
+                .await
+                .expect("lookup by tag"),
+            vec![stored.cid.clone()]
+        );
+    }
+
     #[tokio::test]
     async fn new_with_rebuild_populates_indexes() {
         let backend = Arc::new(InMemoryBackend::new());
@@ -29,6 +29,60 @@ pub struct StoredChip {
     pub execution_metadata: ExecutionMetadata,
     pub tags: Vec<String>,
     pub related_chips: Vec<String>, // CIDs of related chips
+    /// Declared parent CIDs: the body's `parents` plus any the submitter
+    /// named outside the body.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parents: Vec<String>,
+    /// Held for operator review; not emitted to live streams until released.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub quarantined: bool,
+    /// Author the writing transport verified; never read from the body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<ChipAuthor>,
+}
+
+/// How the author of a stored chip was verified at write time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChipAuthor {
+    /// An operator presenting an admin API key.
+    Admin,
+    /// The chip's own signature verified to `did`.
+    Signer { did: String },
+    /// A bearer token holder, scoped to the token's world. Names the token
+    /// chip by CID; the bearer string itself is never stored.
+    Token {
+        token_cid: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        did: Option<String>,
+        world: String,
+    },
+}
+
+impl StoredChip {
+    /// Parent CIDs of this chip. Chips stored before `parents` was recorded
+    /// fall back to the body's `parents` field.
+    pub fn lineage_parents(&self) -> Vec<String> {
+        if self.parents.is_empty() {
+            body_parents(&self.chip_data)
+        } else {
+            self.parents.clone()
+        }
+    }
+}
+
+/// String entries of the body's `parents` array.
+fn body_parents(chip_data: &serde_json::Value) -> Vec<String> {
+    chip_data
+        .get("parents")
+        .and_then(|v| v.as_array())
+        .map(|parents| {
+            parents
+                .iter()
+                .filter_map(|p| p.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Metadata about chip execution
@@ -97,12 +151,66 @@ pub trait ChipStoreBackend: Send + Sync {
 
     /// Full scan of all stored chips from primary storage.
     async fn scan_all(&self) -> Result<Vec<StoredChip>, ChipStoreError>;
+
+    /// Visit every primary entry as `(key, decoded chip)`, one at a time.
+    /// Entries that fail to decode are passed as `Err` rather than aborting
+    /// the scan. Backends that can iterate storage lazily override this; the
+    /// default falls back to [`scan_all`](Self::scan_all).
+    async fn for_each_entry(
+        &self,
+        visit: &mut (dyn FnMut(String, Result<StoredChip, ChipStoreError>) + Send),
+    ) -> Result<(), ChipStoreError> {
+        for chip in self.scan_all().await? {
+            visit(chip.cid.as_str().to_string(), Ok(chip));
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of [`ChipStore::verify_all`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyReport {
+    pub scanned: usize,
+    pub failures: Vec<VerifyFailure>,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// A stored entry whose bytes no longer hash to its key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyFailure {
+    /// Backend key (the CID the entry is stored under).
+    pub key: String,
+    /// CID recomputed from the stored body, when it could be decoded.
+    pub computed_cid: Option<String>,
+    pub reason: String,
+}
+
+/// Index prefix for tags a chip declares in its body: `tags: ["k:v"]` is
+/// queryable as `tag:k:v`, apart from the tags the store derives itself.
+pub const DECLARED_TAG_PREFIX: &str = "tag:";
+
+/// Default ceiling for a single stored chip body (canonical NRF-1 bytes).
+pub const DEFAULT_MAX_STORED_CHIP_BYTES: usize = 8 * 1024 * 1024;
+
+/// Resolve the stored-chip size ceiling from `UBL_MAX_STORED_CHIP_BYTES`.
+pub fn max_stored_chip_bytes_from_env() -> usize {
+    std::env::var("UBL_MAX_STORED_CHIP_BYTES")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_STORED_CHIP_BYTES)
 }
 
 /// The main ChipStore interface
 pub struct ChipStore {
     backend: Arc<dyn ChipStoreBackend>,
     indexer: Arc<indexing::ChipIndexer>,
+    max_chip_bytes: usize,
 }
 
 impl ChipStore {
@@ -111,6 +219,7 @@ impl ChipStore {
         Self {
             backend: backend.clone(),
             indexer: Arc::new(indexing::ChipIndexer::new(backend)),
+            max_chip_bytes: max_stored_chip_bytes_from_env(),
         }
     }
 
@@ -120,7 +229,22 @@ impl ChipStore {
     ) -> Result<Self, ChipStoreError> {
         let indexer = Arc::new(indexing::ChipIndexer::new(backend.clone()));
         indexer.rebuild_indexes().await?;
-        Ok(Self { backend, indexer })
+        Ok(Self {
+            backend,
+            indexer,
+            max_chip_bytes: max_stored_chip_bytes_from_env(),
+        })
+    }
+
+    /// Override the stored-chip size ceiling (defaults to `UBL_MAX_STORED_CHIP_BYTES`).
+    pub fn with_max_chip_bytes(mut self, max_chip_bytes: usize) -> Self {
+        self.max_chip_bytes = max_chip_bytes.max(1);
+        self
+    }
+
+    /// Maximum canonical body size accepted by `store_executed_chip`.
+    pub fn max_chip_bytes(&self) -> usize {
+        self.max_chip_bytes
     }
 
     /// Store a chip after execution
@@ -129,10 +253,78 @@ impl ChipStore {
         chip_data: serde_json::Value,
         receipt_cid: String,
         metadata: ExecutionMetadata,
+    ) -> Result<String, ChipStoreError> {
+        self.store_chip(chip_data, receipt_cid, metadata, &[], None, false)
+            .await
+    }
+
+    /// Store a chip flagged `quarantined` (CHECK decided `Quarantine`).
+    pub async fn store_quarantined_chip(
+        &self,
+        chip_data: serde_json::Value,
+        receipt_cid: String,
+        metadata: ExecutionMetadata,
+    ) -> Result<String, ChipStoreError> {
+        self.store_chip(chip_data, receipt_cid, metadata, &[], None, true)
+            .await
+    }
+
+    /// Store a chip whose lineage also names `parents` beyond the body's own
+    /// `parents` field (e.g. `ChipRequest::parents`), recording its verified
+    /// `author` when the transport established one.
+    pub async fn store_chip_with_parents(
+        &self,
+        chip_data: serde_json::Value,
+        receipt_cid: String,
+        metadata: ExecutionMetadata,
+        parents: &[String],
+        author: Option<ChipAuthor>,
+        quarantined: bool,
+    ) -> Result<String, ChipStoreError> {
+        self.store_chip(
+            chip_data,
+            receipt_cid,
+            metadata,
+            parents,
+            author,
+            quarantined,
+        )
+        .await
+    }
+
+    /// Flip the `quarantined` flag on a stored chip. Returns the updated chip,
+    /// or `None` when `cid` is unknown.
+    pub async fn set_quarantined(
+        &self,
+        cid: &str,
+        quarantined: bool,
+    ) -> Result<Option<StoredChip>, ChipStoreError> {
+        let Some(mut chip) = self.backend.get_chip(cid).await? else {
+            return Ok(None);
+        };
+        chip.quarantined = quarantined;
+        self.backend.put_chip(&chip).await?;
+        Ok(Some(chip))
+    }
+
+    async fn store_chip(
+        &self,
+        chip_data: serde_json::Value,
+        receipt_cid: String,
+        metadata: ExecutionMetadata,
+        extra_parents: &[String],
+        author: Option<ChipAuthor>,
+        quarantined: bool,
     ) -> Result<String, ChipStoreError> {
         // Compute CID for the chip data
         let nrf1_bytes = ubl_ai_nrf1::to_nrf1_bytes(&chip_data)
             .map_err(|e| ChipStoreError::Serialization(e.to_string()))?;
+        if nrf1_bytes.len() > self.max_chip_bytes {
+            return Err(ChipStoreError::TooLarge {
+                size: nrf1_bytes.len(),
+                max: self.max_chip_bytes,
+            });
+        }
         let cid_str = ubl_ai_nrf1::compute_cid(&nrf1_bytes)
             .map_err(|e| ChipStoreError::Serialization(e.to_string()))?;
         let cid = TypedCid::new_unchecked(&cid_str);
@@ -145,7 +337,14 @@ impl ChipStore {
             .unwrap_or("unknown")
             .to_string();
 
-        let tags = self.extract_tags(&chip_data, &chip_type);
+        let mut tags = self.extract_tags(&chip_data, &chip_type);
+        let mut parents = body_parents(&chip_data);
+        for parent in extra_parents {
+            if !parents.contains(parent) {
+                parents.push(parent.clone());
+                tags.push(format!("parent:{}", parent));
+            }
+        }
         let related_chips = self.extract_relationships(&chip_data);
 
         let stored_chip = StoredChip {
@@ -157,6 +356,9 @@ impl ChipStore {
             execution_metadata: metadata,
             tags,
             related_chips,
+            parents,
+            quarantined,
+            author,
         };
 
         // Store the chip
@@ -199,6 +401,22 @@ impl ChipStore {
         self.backend.get_chips_by_type(chip_type).await
     }
 
+    /// Chips that declare `parent_cid` as a parent, oldest first.
+    pub async fn get_children(&self, parent_cid: &str) -> Result<Vec<StoredChip>, ChipStoreError> {
+        let mut children = Vec::new();
+        for cid in self.indexer.get_cids_by_parent(parent_cid).await? {
+            if let Some(chip) = self.backend.get_chip(cid.as_str()).await? {
+                children.push(chip);
+            }
+        }
+        children.sort_by(|a, b| {
+            a.created_at
+                .cmp(&b.created_at)
+                .then_with(|| a.cid.as_str().cmp(b.cid.as_str()))
+        });
+        Ok(children)
+    }
+
     /// Get all customers (example business logic)
     pub async fn get_customers(&self) -> Result<Vec<StoredChip>, ChipStoreError> {
         self.backend
@@ -208,8 +426,41 @@ impl ChipStore {
 
     /// Rebuild backend indexes from primary storage.
     pub async fn rebuild_indexes(&self) -> Result<(), ChipStoreError> {
+        self.reindex().await.map(|_| ())
+    }
+
+    /// Online [`Self::rebuild_indexes`]: backend and in-memory indexes are
+    /// rebuilt alongside the live ones and swapped in, so queries keep
+    /// answering during the pass. Returns the rebuilt index sizes.
+    pub async fn reindex(&self) -> Result<ReindexReport, ChipStoreError> {
         self.backend.rebuild_indexes().await?;
-        self.indexer.rebuild_indexes().await
+        self.indexer.reindex().await
+    }
+
+    /// Recompute every stored chip's CID and report entries that no longer
+    /// hash to their key (bit-rot, backend corruption, undecodable records).
+    pub async fn verify_all(&self) -> Result<VerifyReport, ChipStoreError> {
+        self.verify_all_with(|_| {}).await
+    }
+
+    /// [`verify_all`](Self::verify_all), calling `on_failure` as each bad
+    /// entry is found. Chips are checked one at a time and only failures are
+    /// retained.
+    pub async fn verify_all_with(
+        &self,
+        mut on_failure: impl FnMut(&VerifyFailure) + Send,
+    ) -> Result<VerifyReport, ChipStoreError> {
+        let mut report = VerifyReport::default();
+        self.backend
+            .for_each_entry(&mut |key, entry| {
+                report.scanned += 1;
+                if let Some(failure) = verify_entry(key, entry) {
+                    on_failure(&failure);
+                    report.failures.push(failure);
+                }
+            })
+            .await?;
+        Ok(report)
     }
 
     /// Get customer by email (example index lookup)
@@ -238,6 +489,7 @@ impl ChipStore {
         // Extract common direct fields as tags.
         for field in [
             "email",
+            "did",
             "id",
             "slug",
             "status",
@@ -254,12 +506,30 @@ impl ChipStore {
             "new_kid",
             "rotation_chip_cid",
             "rotation_receipt_cid",
+            "supersedes",
         ] {
             if let Some(value) = chip_data.get(field).and_then(|v| v.as_str()) {
                 tags.push(format!("{}:{}", field, value));
             }
         }
 
+        // Declared governance tags (`key:value`) are indexed under `tag:` so
+        // a body cannot forge reserved tags such as `world:` or `app:`.
+        if let Some(declared) = chip_data.get("tags").and_then(|v| v.as_array()) {
+            for tag in declared.iter().filter_map(|t| t.as_str()) {
+                if tag.contains(':') {
+                    tags.push(format!("{}{}", DECLARED_TAG_PREFIX, tag));
+                }
+            }
+        }
+
+        // Declared lineage: one tag per parent CID for reverse lookup.
+        if let Some(parents) = chip_data.get("parents").and_then(|v| v.as_array()) {
+            for parent in parents.iter().filter_map(|p| p.as_str()) {
+                tags.push(format!("parent:{}", parent));
+            }
+        }
+
         // Anchored identity fields.
         if let Some(id) = chip_data.get("@id").and_then(|v| v.as_str()) {
             tags.push(format!("id:{}", id));
@@ -299,10 +569,8 @@ impl ChipStore {
     /// Recursively extract CIDs from nested data
     fn extract_cids_recursive(&self, value: &serde_json::Value, cids: &mut Vec<String>) {
         match value {
-            serde_json::Value::String(s) => {
-                if s.starts_with("b3:") {
-                    cids.push(s.clone());
-                }
+            serde_json::Value::String(s) if s.starts_with("b3:") => {
+                cids.push(s.clone());
             }
             serde_json::Value::Object(obj) => {
                 for val in obj.values() {
@@ -332,6 +600,40 @@ pub enum ChipStoreError {
     InvalidCid(String),
     #[error("Index error: {0}")]
     Index(String),
+    #[error("Chip too large: {size} bytes exceeds stored limit of {max} bytes")]
+    TooLarge { size: usize, max: usize },
+}
+
+fn verify_entry(key: String, entry: Result<StoredChip, ChipStoreError>) -> Option<VerifyFailure> {
+    let chip = match entry {
+        Ok(chip) => chip,
+        Err(e) => {
+            return Some(VerifyFailure {
+                key,
+                computed_cid: None,
+                reason: format!("undecodable entry: {}", e),
+            })
+        }
+    };
+    let computed = ubl_ai_nrf1::to_nrf1_bytes(&chip.chip_data)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| ubl_ai_nrf1::compute_cid(&bytes).map_err(|e| e.to_string()));
+    let reason = match &computed {
+        Err(e) => format!("chip body cannot be canonicalized: {}", e),
+        Ok(cid) if *cid != key => format!("stored body hashes to {}, not its key", cid),
+        Ok(_) if chip.cid.as_str() != key => {
+            format!(
+                "record claims cid {}, stored under a different key",
+                chip.cid.as_str()
+            )
+        }
+        Ok(_) => return None,
+    };
+    Some(VerifyFailure {
+        key,
+        computed_cid: computed.ok(),
+        reason,
+    })
 }
 
 #[cfg(test)]
@@ -381,6 +683,66 @@ mod tests {
         assert_eq!(found.receipt_cid.as_str(), receipt_cid);
     }
 
+    #[tokio::test]
+    async fn quarantine_flag_is_stored_and_released() {
+        let store = ChipStore::new(Arc::new(InMemoryBackend::new()));
+        let receipt_cid = "b3:dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd";
+
+        let cid = store
+            .store_quarantined_chip(test_chip(), receipt_cid.to_string(), test_metadata())
+            .await
+            .expect("store chip");
+        assert!(store.get_chip(&cid).await.unwrap().unwrap().quarantined);
+
+        let released = store.set_quarantined(&cid, false).await.unwrap().unwrap();
+        assert!(!released.quarantined);
+        assert!(!store.get_chip(&cid).await.unwrap().unwrap().quarantined);
+        assert!(store
+            .set_quarantined("b3:missing", false)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn store_rejects_body_above_max_stored_bytes() {
+        let store = ChipStore::new(Arc::new(InMemoryBackend::new())).with_max_chip_bytes(512);
+        let receipt_cid = "b3:cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc";
+
+        store
+            .store_executed_chip(test_chip(), receipt_cid.to_string(), test_metadata())
+            .await
+            .expect("normal body fits");
+
+        let mut oversized = test_chip();
+        oversized["@id"] = json!("chip-huge");
+        oversized["payload"] = json!("x".repeat(4096));
+        let err = store
+            .store_executed_chip(oversized, receipt_cid.to_string(), test_metadata())
+            .await
+            .expect_err("oversized body must be refused");
+        match err {
+            ChipStoreError::TooLarge { size, max } => {
+                assert!(size > max);
+                assert_eq!(max, 512);
+            }
+            other => panic!("expected TooLarge, got {other:?}"),
+        }
+        let result = store
+            .query(&ChipQuery {
+                chip_type: Some("ubl/test".to_string()),
+                tags: vec![],
+                created_after: None,
+                created_before: None,
+                executor_did: None,
+                limit: None,
+                offset: None,
+            })
+            .await
+            .expect("query");
+        assert_eq!(result.chips.len(), 1);
+    }
+
     #[tokio::test]
     async fn sled_lookup_by_receipt_cid() {
         let store = ChipStore::new(Arc::new(SledBackend::in_memory().expect("sled backend")));
@@ -413,6 +775,83 @@ mod tests {
         assert!(found.is_none());
     }
 
+    #[tokio::test]
+    async fn lineage_fields_are_tagged_for_reverse_lookup() {
+        let store = ChipStore::new(Arc::new(InMemoryBackend::new()));
+        let mut child = test_chip();
+        child["parents"] = json!(["b3:parent-a", "b3:parent-b"]);
+        child["supersedes"] = json!("b3:older");
+        child["tags"] = json!([
+            "classification:public",
+            "untagged",
+            "world:a/victim",
+            "app:victim"
+        ]);
+        let cid = store
+            .store_executed_chip(child, "b3:r-lineage".to_string(), test_metadata())
+            .await
+            .expect("store chip");
+
+        let stored = store.get_chip(&cid).await.unwrap().unwrap();
+        assert!(stored.tags.contains(&"parent:b3:parent-a".to_string()));
+        assert!(stored.tags.contains(&"parent:b3:parent-b".to_string()));
+        assert!(stored.tags.contains(&"supersedes:b3:older".to_string()));
+        assert!(stored
+            .tags
+            .contains(&"tag:classification:public".to_string()));
+        assert!(stored.tags.contains(&"tag:world:a/victim".to_string()));
+        assert!(!stored.tags.contains(&"classification:public".to_string()));
+        assert!(!stored.tags.contains(&"world:a/victim".to_string()));
+        assert!(!stored.tags.contains(&"app:victim".to_string()));
+        assert!(!stored.tags.contains(&"untagged".to_string()));
+    }
+
+    #[tokio::test]
+    async fn parents_named_outside_the_body_are_recorded_and_tagged() {
+        let store = ChipStore::new(Arc::new(InMemoryBackend::new()));
+        let mut child = test_chip();
+        child["parents"] = json!(["b3:parent-a"]);
+        let extra = vec!["b3:parent-a".to_string(), "b3:parent-b".to_string()];
+        let cid = store
+            .store_chip_with_parents(
+                child.clone(),
+                "b3:r-parents".to_string(),
+                test_metadata(),
+                &extra,
+                Some(ChipAuthor::Admin),
+                false,
+            )
+            .await
+            .expect("store chip");
+
+        let stored = store.get_chip(&cid).await.unwrap().unwrap();
+        assert_eq!(stored.chip_data, child);
+        assert_eq!(stored.lineage_parents(), extra);
+        assert_eq!(stored.author, Some(ChipAuthor::Admin));
+        let parent_tags = stored
+            .tags
+            .iter()
+            .filter(|t| t.starts_with("parent:"))
+            .count();
+        assert_eq!(parent_tags, 2);
+        let children = store
+            .query(&ChipQuery {
+                chip_type: None,
+                tags: vec!["parent:b3:parent-b".to_string()],
+                created_after: None,
+                created_before: None,
+                executor_did: None,
+                limit: None,
+                offset: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(children.chips.len(), 1);
+        let children = store.get_children("b3:parent-b").await.unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].cid.as_str(), cid);
+    }
+
     #[tokio::test]
     async fn query_by_target_cid_tag_returns_revocation() {
         let store = ChipStore::new(Arc::new(InMemoryBackend::new()));
@@ -1,6 +1,11 @@
 //! ublx - UBL Chip-as-Code CLI
+//!
+//! With the global `--json` flag every subcommand prints exactly one JSON
+//! object on stdout. Failures still go to stderr with a non-zero exit; checks
+//! that fail after producing a report (`receipt verify`, `store verify`)
+//! print the report first.
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use serde_json::{json, Value};
 use std::sync::Arc;
 use ubl_ai_nrf1::{compute_cid, to_nrf1_bytes, ChipFile};
@@ -9,6 +14,9 @@ use ubl_ai_nrf1::{compute_cid, to_nrf1_bytes, ChipFile};
 #[command(name = "ublx")]
 #[command(about = "UBL Chip-as-Code CLI")]
 struct Cli {
+    /// Emit a single JSON object on stdout instead of human-readable text
+    #[arg(long, global = true)]
+    json: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -32,15 +40,25 @@ enum Commands {
         /// Path to a JSON file
         file: String,
     },
-    /// Submit a chip JSON file to a running UBL gate
+    /// Submit a chip JSON file (or an NDJSON batch) to a running UBL gate
     Submit {
-        /// Path to chip JSON file
+        /// Path to chip JSON file (one chip per line with --ndjson)
         #[arg(short, long)]
         input: String,
+        /// Treat the input as NDJSON and submit every line; prints a per-line
+        /// table and a final allow/deny/error count line
+        #[arg(long)]
+        ndjson: bool,
+        /// Max in-flight requests for --ndjson batches
+        #[arg(long, default_value = "1")]
+        concurrency: usize,
+        /// Keep submitting the batch after a deny or error
+        #[arg(long)]
+        continue_on_error: bool,
         /// Base URL of the gate (e.g. http://127.0.0.1:4000)
         #[arg(long, default_value = "http://127.0.0.1:4000")]
         gate: String,
-        /// Optional path to write raw gate response JSON
+        /// Optional path to write raw gate response JSON (NDJSON for batches)
         #[arg(short, long)]
         output: Option<String>,
         /// Optional API key sent as X-API-Key for write-protected lanes
@@ -51,12 +69,37 @@ enum Commands {
         #[arg(long, default_value = "30")]
         timeout_secs: u64,
     },
+    /// Load-test a gate by submitting a chip repeatedly; prints a JSON summary
+    Bench {
+        /// Base URL of the gate (e.g. http://127.0.0.1:4000)
+        #[arg(long, default_value = "http://127.0.0.1:4000")]
+        gate: String,
+        /// Number of concurrent submitters
+        #[arg(short, long, default_value = "4")]
+        concurrency: usize,
+        /// How long to run, in seconds
+        #[arg(short, long, default_value = "10")]
+        duration_secs: u64,
+        /// Path to chip JSON file used as the submission template
+        #[arg(long)]
+        chip_file: String,
+        /// Submit the chip unchanged (exercises idempotent replay) instead of varying @id
+        #[arg(long)]
+        fixed_id: bool,
+        /// Optional API key sent as X-API-Key
+        /// (fallback envs: SOURCE_GATE_API_KEY, UBL_GATE_API_KEY, UBL_API_KEY)
+        #[arg(long)]
+        api_key: Option<String>,
+        /// Per-request HTTP timeout in seconds
+        #[arg(long, default_value = "30")]
+        timeout_secs: u64,
+    },
     /// Explain a WF receipt: print RB tree with PASS/DENY per node
     Explain {
         /// CID of the receipt, or path to a receipt JSON file
         target: String,
     },
-    /// Search ChipStore by type, tag, or date range
+    /// Search a running gate's ChipStore by type, tag, or date range
     Search {
         /// Filter by chip type (e.g. "ubl/user")
         #[arg(short = 't', long)]
@@ -73,6 +116,19 @@ enum Commands {
         /// Max results
         #[arg(short, long, default_value = "20")]
         limit: u64,
+        /// Results to skip (for paging)
+        #[arg(long)]
+        offset: Option<u64>,
+        /// Base URL of the gate to query (e.g. http://127.0.0.1:4000)
+        #[arg(long, conflicts_with = "demo")]
+        gate: Option<String>,
+        /// Run the query against an empty in-memory store instead of a gate
+        #[arg(long)]
+        demo: bool,
+        /// Admin API key or session token, sent as Authorization: Bearer
+        /// (fallback envs: SOURCE_GATE_API_KEY, UBL_GATE_API_KEY, UBL_API_KEY)
+        #[arg(long)]
+        api_key: Option<String>,
     },
     /// Generate receipt fixtures for integration testing
     Fixture {
@@ -114,6 +170,28 @@ enum Commands {
         #[command(subcommand)]
         command: SiliconCommands,
     },
+    /// ChipStore maintenance
+    Store {
+        #[command(subcommand)]
+        command: StoreCommands,
+    },
+    /// Offline receipt checks
+    Receipt {
+        #[command(subcommand)]
+        command: ReceiptCommands,
+    },
+    /// Print a shell completion script to stdout
+    ///
+    /// Install, for example:
+    ///   bash:       ublx completions bash > ~/.local/share/bash-completion/completions/ublx
+    ///   zsh:        ublx completions zsh > "${fpath[1]}/_ublx"
+    ///   fish:       ublx completions fish > ~/.config/fish/completions/ublx.fish
+    ///   powershell: ublx completions powershell >> $PROFILE
+    #[command(verbatim_doc_comment)]
+    Completions {
+        /// Target shell
+        shell: clap_complete::Shell,
+    },
 }
 
 #[derive(Subcommand)]
@@ -163,6 +241,12 @@ enum CapCommands {
         /// Optional expires_at timestamp (RFC3339; default now+365d)
         #[arg(long)]
         expires_at: Option<String>,
+        /// DID allowed to issue narrower sub-capabilities of this one
+        #[arg(long)]
+        delegate: Option<String>,
+        /// Capability JSON this one is delegated from (embedded as `parent`)
+        #[arg(long)]
+        parent: Option<String>,
         /// Write JSON output to file
         #[arg(short, long)]
         output: Option<String>,
@@ -178,6 +262,62 @@ enum CapCommands {
         /// World to validate against (e.g. a/chip-registry or a/chip-registry/t/logline)
         #[arg(long)]
         world: String,
+        /// JSON array of the capability's ancestors, root first, for a cap
+        /// that does not embed its `parent` chain
+        #[arg(long)]
+        chain: Option<String>,
+    },
+    /// Re-sign a capability with a fresh expiry, keeping its action and audience
+    Renew {
+        /// Path to capability JSON file (either raw cap object or chip body containing @cap)
+        #[arg(long)]
+        input: String,
+        /// 64-char Ed25519 private seed hex of the key named by `issued_by`
+        #[arg(long)]
+        signing_key_hex: String,
+        /// Days from now until the renewed capability expires
+        #[arg(long, default_value_t = 365)]
+        extend_days: i64,
+        /// Write JSON output to file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Re-issue every capability signed by an old key under a new signing key
+    Rotate {
+        /// Directory of capability JSON files (raw cap objects or chip bodies containing @cap)
+        #[arg(long)]
+        input_dir: String,
+        /// 64-char Ed25519 private seed hex of the retiring key
+        #[arg(long)]
+        old_key_hex: String,
+        /// 64-char Ed25519 private seed hex of the replacement key
+        #[arg(long)]
+        new_key_hex: String,
+        /// Directory to write re-signed capabilities into (same file names)
+        #[arg(long)]
+        output_dir: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum StoreCommands {
+    /// Recompute every chip's CID and report entries whose stored bytes no
+    /// longer hash to their key. Exits non-zero when any entry fails.
+    Verify {
+        /// Path to the Sled ChipStore directory
+        #[arg(long, default_value = "./data/chips")]
+        store_path: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReceiptCommands {
+    /// Replay a receipt's per-stage auth_token HMAC chain with
+    /// UBL_STAGE_SECRET (and UBL_STAGE_SECRET_PREV), exactly as the gate
+    /// does on every receipt read. Exits non-zero on tamper.
+    Verify {
+        /// Path to a receipt JSON file (as returned by GET /v1/receipts/:cid)
+        file: String,
     },
 }
 
@@ -228,13 +368,17 @@ enum SiliconCommands {
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
+    let json = cli.json;
 
     match cli.command {
-        Commands::Verify { chip_file } => cmd_verify(&chip_file)?,
-        Commands::Build { input, output } => cmd_build(&input, output)?,
-        Commands::Cid { file } => cmd_cid(&file)?,
+        Commands::Verify { chip_file } => cmd_verify(&chip_file, json)?,
+        Commands::Build { input, output } => cmd_build(&input, output, json)?,
+        Commands::Cid { file } => cmd_cid(&file, json)?,
         Commands::Submit {
             input,
+            ndjson,
+            concurrency,
+            continue_on_error,
             gate,
             output,
             api_key,
@@ -244,37 +388,101 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .or_else(|| std::env::var("SOURCE_GATE_API_KEY").ok())
                 .or_else(|| std::env::var("UBL_GATE_API_KEY").ok())
                 .or_else(|| std::env::var("UBL_API_KEY").ok());
-            cmd_submit(
-                &input,
+            if ndjson {
+                cmd_submit_ndjson(
+                    &input,
+                    &gate,
+                    output,
+                    resolved_api_key,
+                    timeout_secs,
+                    concurrency,
+                    continue_on_error,
+                    json,
+                )
+                .await?
+            } else {
+                cmd_submit(
+                    &input,
+                    &gate,
+                    output,
+                    resolved_api_key.as_deref(),
+                    timeout_secs,
+                    json,
+                )
+                .await?
+            }
+        }
+        Commands::Bench {
+            gate,
+            concurrency,
+            duration_secs,
+            chip_file,
+            fixed_id,
+            api_key,
+            timeout_secs,
+        } => {
+            let resolved_api_key = api_key
+                .or_else(|| std::env::var("SOURCE_GATE_API_KEY").ok())
+                .or_else(|| std::env::var("UBL_GATE_API_KEY").ok())
+                .or_else(|| std::env::var("UBL_API_KEY").ok());
+            cmd_bench(
                 &gate,
-                output,
-                resolved_api_key.as_deref(),
+                concurrency,
+                duration_secs,
+                &chip_file,
+                fixed_id,
+                resolved_api_key,
                 timeout_secs,
+                json,
             )
             .await?
         }
-        Commands::Explain { target } => cmd_explain(&target)?,
+        Commands::Explain { target } => cmd_explain(&target, json)?,
         Commands::Search {
             chip_type,
             tag,
             after,
             before,
             limit,
+            offset,
+            gate,
+            demo,
+            api_key,
         } => {
-            cmd_search(chip_type, tag, after, before, limit).await?;
+            let resolved_api_key = api_key
+                .or_else(|| std::env::var("SOURCE_GATE_API_KEY").ok())
+                .or_else(|| std::env::var("UBL_GATE_API_KEY").ok())
+                .or_else(|| std::env::var("UBL_API_KEY").ok());
+            let query = ubl_chipstore::ChipQuery {
+                chip_type,
+                tags: tag,
+                created_after: after,
+                created_before: before,
+                executor_did: None,
+                limit: Some(limit as usize),
+                offset: offset.map(|o| o as usize),
+            };
+            cmd_search(
+                query,
+                gate.as_deref(),
+                resolved_api_key.as_deref(),
+                demo,
+                json,
+            )
+            .await?;
         }
-        Commands::Fixture { output_dir, count } => cmd_fixture(&output_dir, count)?,
-        Commands::Url { receipt_cid, host } => cmd_url(&receipt_cid, &host)?,
-        Commands::Disasm { input, hex } => cmd_disasm(&input, hex)?,
+        Commands::Fixture { output_dir, count } => cmd_fixture(&output_dir, count, json)?,
+        Commands::Url { receipt_cid, host } => cmd_url(&receipt_cid, &host, json)?,
+        Commands::Disasm { input, hex } => cmd_disasm(&input, hex, json)?,
         Commands::Did { command } => match command {
             DidCommands::Generate { output, strict } => {
-                cmd_did_generate(output.as_deref(), strict)?
+                cmd_did_generate(output.as_deref(), strict, json)?
             }
             DidCommands::FromKey {
                 signing_key_hex,
                 output,
                 strict,
-            } => cmd_did_from_key(&signing_key_hex, output.as_deref(), strict)?,
+            } => cmd_did_from_key(&signing_key_hex, output.as_deref(), strict, json)?,
         },
         Commands::Cap { command } => match command {
             CapCommands::Issue {
@@ -284,6 +492,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 issued_by,
                 issued_at,
                 expires_at,
+                delegate,
+                parent,
                 output,
             } => cmd_cap_issue(
                 &action,
@@ -292,13 +502,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 issued_by.as_deref(),
                 issued_at.as_deref(),
                 expires_at.as_deref(),
+                delegate.as_deref(),
+                parent.as_deref(),
                 output.as_deref(),
+                json,
             )?,
             CapCommands::Verify {
                 input,
                 action,
                 world,
-            } => cmd_cap_verify(&input, &action, &world)?,
+                chain,
+            } => cmd_cap_verify(&input, &action, &world, chain.as_deref(), json)?,
+            CapCommands::Renew {
+                input,
+                signing_key_hex,
+                extend_days,
+                output,
+            } => cmd_cap_renew(
+                &input,
+                &signing_key_hex,
+                extend_days,
+                output.as_deref(),
+                json,
+            )?,
+            CapCommands::Rotate {
+                input_dir,
+                old_key_hex,
+                new_key_hex,
+                output_dir,
+            } => cmd_cap_rotate(&input_dir, &old_key_hex, &new_key_hex, &output_dir, json)?,
         },
         Commands::Silicon { command } => match command {
             SiliconCommands::Compile {
@@ -312,23 +544,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     from_store.as_deref(),
                     &store_path,
                     hex_only,
+                    json,
                 )
                 .await?
             }
-            SiliconCommands::Disasm { input, file } => cmd_silicon_disasm(&input, file)?,
+            SiliconCommands::Disasm { input, file } => cmd_silicon_disasm(&input, file, json)?,
+        },
+        Commands::Store { command } => match command {
+            StoreCommands::Verify { store_path } => cmd_store_verify(&store_path, json).await?,
         },
+        Commands::Receipt { command } => match command {
+            ReceiptCommands::Verify { file } => cmd_receipt_verify(&file, json)?,
+        },
+        // Completion scripts are shell source; `--json` does not apply.
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "ublx", &mut std::io::stdout())
+        }
     }
 
     Ok(())
 }
 
+/// `--json` output: the command's single result object on one line.
+fn print_json(value: &Value) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
+
 // ── verify ──────────────────────────────────────────────────────
 
-fn cmd_verify(chip_file: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_verify(chip_file: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     let chip_yaml = std::fs::read_to_string(chip_file)?;
     let chip: ChipFile = serde_yaml::from_str(&chip_yaml)?;
     let compiled = chip.compile()?;
 
+    if json {
+        return print_json(&json!({
+            "cid": compiled.cid,
+            "chip_type": compiled.chip_type,
+            "id": compiled.logical_id,
+            "size": compiled.nrf1_bytes.len(),
+        }));
+    }
     println!("Chip verified successfully");
     println!("  Type: {}", compiled.chip_type);
     println!("  ID:   {}", compiled.logical_id);
@@ -339,7 +596,11 @@ fn cmd_verify(chip_file: &str) -> Result<(), Box<dyn std::error::Error>> {
 
 // ── build ───────────────────────────────────────────────────────
 
-fn cmd_build(input: &str, output: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_build(
+    input: &str,
+    output: Option<String>,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let chip_yaml = std::fs::read_to_string(input)?;
     let chip: ChipFile = serde_yaml::from_str(&chip_yaml)?;
     let compiled = chip.compile()?;
@@ -347,6 +608,14 @@ fn cmd_build(input: &str, output: Option<String>) -> Result<(), Box<dyn std::err
     let output_path = output.unwrap_or_else(|| format!("{}.bin", compiled.cid));
     std::fs::write(&output_path, &compiled.nrf1_bytes)?;
 
+    if json {
+        return print_json(&json!({
+            "input": input,
+            "output": output_path,
+            "cid": compiled.cid,
+            "size": compiled.nrf1_bytes.len(),
+        }));
+    }
     println!("Compiled: {} -> {}", input, output_path);
     println!("  CID: {}", compiled.cid);
     Ok(())
@@ -354,15 +623,142 @@ fn cmd_build(input: &str, output: Option<String>) -> Result<(), Box<dyn std::err
 
 // ── cid ─────────────────────────────────────────────────────────
 
-fn cmd_cid(file: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_cid(file: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     let content = std::fs::read_to_string(file)?;
-    let json: Value = serde_json::from_str(&content)?;
-    let nrf_bytes = to_nrf1_bytes(&json)?;
+    let value: Value = serde_json::from_str(&content)?;
+    let nrf_bytes = to_nrf1_bytes(&value)?;
     let cid = compute_cid(&nrf_bytes)?;
+    if json {
+        return print_json(&json!({"file": file, "cid": cid, "size": nrf_bytes.len()}));
+    }
     println!("{}", cid);
     Ok(())
 }
 
+// ── store verify ────────────────────────────────────────────────
+
+async fn cmd_store_verify(store_path: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use ubl_chipstore::{ChipStore, SledBackend};
+
+    let store = ChipStore::new(Arc::new(SledBackend::open_unindexed(store_path)?));
+    if !json {
+        println!("Verifying ChipStore at {}...", store_path);
+    }
+    let report = store
+        .verify_all_with(|failure| {
+            if !json {
+                println!("  CORRUPT {}: {}", failure.key, failure.reason);
+            }
+        })
+        .await?;
+    if json {
+        print_json(&json!({
+            "store_path": store_path,
+            "scanned": report.scanned,
+            "clean": report.is_clean(),
+            "failures": report.failures,
+        }))?;
+    } else {
+        println!(
+            "Scanned {} chip(s), {} corrupt",
+            report.scanned,
+            report.failures.len()
+        );
+    }
+    if !report.is_clean() {
+        return Err(format!(
+            "{} of {} chip(s) failed verification",
+            report.failures.len(),
+            report.scanned
+        )
+        .into());
+    }
+    Ok(())
+}
+
+// ── receipt verify ──────────────────────────────────────────────
+
+fn cmd_receipt_verify(file: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(file)?;
+    let value: Value = serde_json::from_str(&content)?;
+    let receipt = ubl_receipt::UnifiedReceipt::from_json(&value)?;
+    let receipt_cid = receipt.receipt_cid.as_str().to_string();
+
+    if !json {
+        println!(
+            "Verifying receipt {} ({} stage(s))...",
+            receipt_cid,
+            receipt.stages.len()
+        );
+    }
+    if let Err(e) = receipt.check_chain_len(ubl_receipt::max_chain_len_from_env()) {
+        if json {
+            print_json(&json!({
+                "receipt_cid": receipt_cid,
+                "verdict": "FAIL",
+                "error": format!("chain length: {}", e),
+            }))?;
+        } else {
+            println!("  FAIL  chain length: {}", e);
+        }
+        return Err(format!("receipt {} rejected: {}", receipt_cid, e).into());
+    }
+
+    let report = receipt.auth_chain_report()?;
+    let verdict = if report.verified() { "PASS" } else { "FAIL" };
+    if json {
+        let stages: Vec<Value> = report
+            .stages
+            .iter()
+            .enumerate()
+            .map(|(i, check)| {
+                json!({
+                    "index": i,
+                    "stage": check.stage.as_str(),
+                    "prev_cid": check.prev_cid,
+                    "secret": check.matched,
+                    "pass": check.matched.is_some(),
+                })
+            })
+            .collect();
+        print_json(&json!({
+            "receipt_cid": receipt_cid,
+            "stages": stages,
+            "cid_matches": report.cid_matches,
+            "replayed_cid": report.replayed_cid,
+            "verdict": verdict,
+        }))?;
+    } else {
+        for (i, check) in report.stages.iter().enumerate() {
+            match check.matched {
+                Some(secret) => println!(
+                    "  PASS  [{}] {} ({} secret)",
+                    i,
+                    check.stage.as_str(),
+                    secret
+                ),
+                None => println!(
+                    "  FAIL  [{}] {} (auth_token does not match prev {})",
+                    i,
+                    check.stage.as_str(),
+                    check.prev_cid
+                ),
+            }
+        }
+        if report.cid_matches {
+            println!("  PASS  receipt_cid");
+        } else {
+            println!("  FAIL  receipt_cid (replayed {})", report.replayed_cid);
+        }
+        println!("Verdict: {}", verdict);
+    }
+
+    if !report.verified() {
+        return Err(format!("receipt {} auth chain broken", receipt_cid).into());
+    }
+    Ok(())
+}
+
 // ── did / cap helpers ──────────────────────────────────────────
 
 fn did_material_json(
@@ -392,33 +788,46 @@ fn did_material_json(
 fn write_or_print_json(
     value: &Value,
     output: Option<&str>,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let text = serde_json::to_string_pretty(value)?;
-    if let Some(path) = output {
-        std::fs::write(path, text.as_bytes())?;
-        println!("wrote {}", path);
-    } else {
-        println!("{}", text);
+    match output {
+        Some(path) => {
+            std::fs::write(path, text.as_bytes())?;
+            if json {
+                print_json(&json!({"wrote": path}))?;
+            } else {
+                println!("wrote {}", path);
+            }
+        }
+        None if json => print_json(value)?,
+        None => println!("{}", text),
     }
     Ok(())
 }
 
-fn cmd_did_generate(output: Option<&str>, strict: bool) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_did_generate(
+    output: Option<&str>,
+    strict: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let sk = ubl_kms::generate_signing_key();
     let out = did_material_json(&sk, strict)?;
-    write_or_print_json(&out, output)
+    write_or_print_json(&out, output, json)
 }
 
 fn cmd_did_from_key(
     signing_key_hex: &str,
     output: Option<&str>,
     strict: bool,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let sk = ubl_kms::signing_key_from_hex(signing_key_hex)?;
     let out = did_material_json(&sk, strict)?;
-    write_or_print_json(&out, output)
+    write_or_print_json(&out, output, json)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cmd_cap_issue(
     action: &str,
     audience: &str,
@@ -426,8 +835,13 @@ fn cmd_cap_issue(
     issued_by: Option<&str>,
     issued_at: Option<&str>,
     expires_at: Option<&str>,
+    delegate: Option<&str>,
+    parent: Option<&str>,
     output: Option<&str>,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    use ubl_runtime::capability::{cap_signing_payload, Capability};
+
     let sk = ubl_kms::signing_key_from_hex(signing_key_hex)?;
     let vk = ubl_kms::verifying_key(&sk);
     let derived_issuer = ubl_kms::did_from_verifying_key_strict(&vk);
@@ -448,42 +862,212 @@ fn cmd_cap_issue(
             .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
     });
 
-    let payload = json!({
-        "action": action,
-        "audience": audience,
-        "issued_by": issuer,
-        "issued_at": issued_at_ts,
-        "expires_at": expires_at_ts,
-    });
-    let signature = ubl_kms::sign_canonical(&sk, &payload, ubl_kms::domain::CAPABILITY)?;
-
-    let cap = json!({
-        "action": action,
-        "audience": audience,
-        "issued_by": issuer,
-        "issued_at": issued_at_ts,
-        "expires_at": expires_at_ts,
-        "signature": signature,
-    });
-    write_or_print_json(&cap, output)
+    let parent = parent
+        .map(|path| read_cap(std::path::Path::new(path)).map(|(_, cap)| Box::new(cap)))
+        .transpose()?;
+    let mut cap = Capability {
+        action: action.to_string(),
+        audience: audience.to_string(),
+        issued_by: issuer.to_string(),
+        issued_at: issued_at_ts,
+        expires_at: expires_at_ts,
+        signature: String::new(),
+        delegate: delegate.unwrap_or_default().to_string(),
+        parent,
+    };
+    cap.signature =
+        ubl_kms::sign_canonical(&sk, &cap_signing_payload(&cap), ubl_kms::domain::CAPABILITY)?;
+    write_or_print_json(&serde_json::to_value(cap)?, output, json)
 }
 
 fn cmd_cap_verify(
     input: &str,
     required_action: &str,
     world: &str,
+    chain: Option<&str>,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let raw = std::fs::read_to_string(input)?;
-    let value: Value = serde_json::from_str(&raw)?;
+    use ubl_runtime::capability::{cap_clock_skew, check_cap_window, CapError, Capability};
+
+    let (_, mut cap) = read_cap(std::path::Path::new(input))?;
+    if let Some(chain) = chain {
+        if cap.parent.is_some() {
+            return Err("--chain given for a capability that already embeds its parent".into());
+        }
+        let ancestors: Vec<Capability> = serde_json::from_str(&std::fs::read_to_string(chain)?)?;
+        cap.parent = ancestors.into_iter().fold(None, |parent, mut ancestor| {
+            ancestor.parent = parent;
+            Some(Box::new(ancestor))
+        });
+    }
+    // Report expiry on its own, ahead of the action/audience checks.
+    if let Err(e @ CapError::Expired { .. }) =
+        check_cap_window(&cap, chrono::Utc::now(), cap_clock_skew())
+    {
+        if json {
+            print_json(&json!({
+                "valid": false,
+                "expired": true,
+                "expires_at": cap.expires_at,
+            }))?;
+        }
+        return Err(e.to_string().into());
+    }
+    ubl_runtime::capability::validate_cap_chain(&cap, required_action, world)?;
+    let chain_depth = std::iter::successors(Some(&cap), |c| c.parent.as_deref()).count();
+    if json {
+        return print_json(&json!({
+            "valid": true,
+            "action": cap.action,
+            "audience": cap.audience,
+            "world": world,
+            "expires_at": cap.expires_at,
+            "chain_depth": chain_depth,
+        }));
+    }
+    let expires = if cap.expires_at.is_empty() {
+        "never"
+    } else {
+        cap.expires_at.as_str()
+    };
+    println!(
+        "capability ok action='{}' audience='{}' world='{}' expires_at='{}' chain_depth={}",
+        cap.action, cap.audience, world, expires, chain_depth
+    );
+    Ok(())
+}
+
+/// Read a capability file: a raw cap object or a chip body carrying `@cap`.
+/// Returns the file's JSON alongside the parsed capability.
+fn read_cap(
+    path: &std::path::Path,
+) -> Result<(Value, ubl_runtime::capability::Capability), Box<dyn std::error::Error>> {
+    let value: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
     let cap = if value.get("@cap").is_some() {
         ubl_runtime::capability::extract_cap(&value)?
     } else {
-        serde_json::from_value::<ubl_runtime::capability::Capability>(value)?
+        serde_json::from_value(value.clone())?
     };
-    ubl_runtime::capability::validate_cap(&cap, required_action, world)?;
+    Ok((value, cap))
+}
+
+/// Put a re-signed capability back where [`read_cap`] found it.
+fn replace_cap(
+    original: Value,
+    cap: ubl_runtime::capability::Capability,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let cap = serde_json::to_value(cap)?;
+    Ok(if original.get("@cap").is_some() {
+        let mut body = original;
+        body["@cap"] = cap;
+        body
+    } else {
+        cap
+    })
+}
+
+fn cmd_cap_renew(
+    input: &str,
+    signing_key_hex: &str,
+    extend_days: i64,
+    output: Option<&str>,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use ubl_runtime::capability::{cap_signing_payload, verify_cap_signature};
+
+    if extend_days <= 0 {
+        return Err("--extend-days must be positive".into());
+    }
+    let (value, cap) = read_cap(std::path::Path::new(input))?;
+    verify_cap_signature(&cap)?;
+    let sk = ubl_kms::signing_key_from_hex(signing_key_hex)?;
+    let issuer_vk = ubl_kms::verifying_key_from_did(&cap.issued_by)
+        .map_err(|e| format!("issued_by '{}': {}", cap.issued_by, e))?;
+    if issuer_vk != ubl_kms::verifying_key(&sk) {
+        return Err(format!("signing key does not match issued_by '{}'", cap.issued_by).into());
+    }
+
+    let now = chrono::Utc::now();
+    let mut renewed = cap;
+    renewed.issued_at = now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    renewed.expires_at = (now + chrono::Duration::days(extend_days))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    renewed.signature = ubl_kms::sign_canonical(
+        &sk,
+        &cap_signing_payload(&renewed),
+        ubl_kms::domain::CAPABILITY,
+    )?;
+    write_or_print_json(&replace_cap(value, renewed)?, output, json)
+}
+
+fn cmd_cap_rotate(
+    input_dir: &str,
+    old_key_hex: &str,
+    new_key_hex: &str,
+    output_dir: &str,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use ubl_runtime::capability::{cap_signing_payload, verify_cap_signature};
+
+    let old_vk = ubl_kms::verifying_key(&ubl_kms::signing_key_from_hex(old_key_hex)?);
+    let new_sk = ubl_kms::signing_key_from_hex(new_key_hex)?;
+    let new_issuer = ubl_kms::did_from_verifying_key_strict(&ubl_kms::verifying_key(&new_sk));
+
+    let mut paths: Vec<std::path::PathBuf> = std::fs::read_dir(input_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    // Verify everything before writing anything, so a bad input never leaves
+    // a half-rotated output dir behind.
+    let mut rotated = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let (value, cap) = read_cap(path)?;
+        let issuer_vk = ubl_kms::verifying_key_from_did(&cap.issued_by)
+            .map_err(|e| format!("{}: issued_by '{}': {}", path.display(), cap.issued_by, e))?;
+        if issuer_vk != old_vk {
+            return Err(format!(
+                "{}: issued_by '{}' does not match the old signing key",
+                path.display(),
+                cap.issued_by
+            )
+            .into());
+        }
+        verify_cap_signature(&cap).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        let mut resigned = cap;
+        resigned.issued_by = new_issuer.clone();
+        resigned.signature = ubl_kms::sign_canonical(
+            &new_sk,
+            &cap_signing_payload(&resigned),
+            ubl_kms::domain::CAPABILITY,
+        )?;
+        let out = replace_cap(value, resigned)?;
+        rotated.push((path.file_name().unwrap_or_default().to_owned(), out));
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    let mut written = Vec::with_capacity(rotated.len());
+    for (name, value) in &rotated {
+        let dest = std::path::Path::new(output_dir).join(name);
+        std::fs::write(&dest, serde_json::to_string_pretty(value)?.as_bytes())?;
+        if !json {
+            println!("wrote {}", dest.display());
+        }
+        written.push(dest.display().to_string());
+    }
+    if json {
+        return print_json(&json!({
+            "rotated": rotated.len(),
+            "issuer": new_issuer,
+            "written": written,
+        }));
+    }
     println!(
-        "capability ok action='{}' audience='{}' world='{}'",
-        cap.action, cap.audience, world
+        "rotated {} capabilities to issuer '{}'",
+        rotated.len(),
+        new_issuer
     );
     Ok(())
 }
@@ -496,6 +1080,7 @@ async fn cmd_submit(
     output: Option<String>,
     api_key: Option<&str>,
     timeout_secs: u64,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let payload = std::fs::read(input)?;
     let endpoint = format!("{}/v1/chips", gate.trim_end_matches('/'));
@@ -523,6 +1108,9 @@ async fn cmd_submit(
         std::fs::write(out, serde_json::to_vec_pretty(&response_json)?)?;
     }
 
+    if json {
+        return print_json(&response_json);
+    }
     if let Some(receipt_cid) = response_json.get("receipt_cid").and_then(|v| v.as_str()) {
         println!("receipt_cid={}", receipt_cid);
     }
@@ -533,9 +1121,416 @@ async fn cmd_submit(
     Ok(())
 }
 
+/// Outcome of one NDJSON batch line.
+enum BatchOutcome {
+    Submitted {
+        decision: String,
+        receipt_cid: String,
+        replayed: bool,
+        response: Value,
+    },
+    Failed(String),
+    Skipped,
+}
+
+impl BatchOutcome {
+    /// Deny and error lines stop the batch unless --continue-on-error.
+    fn stops_batch(&self) -> bool {
+        match self {
+            BatchOutcome::Submitted { decision, .. } => decision.eq_ignore_ascii_case("deny"),
+            BatchOutcome::Failed(_) => true,
+            BatchOutcome::Skipped => false,
+        }
+    }
+}
+
+async fn submit_batch_line(
+    client: &reqwest::Client,
+    endpoint: &str,
+    api_key: Option<&str>,
+    line: &str,
+) -> BatchOutcome {
+    let chip: Value = match serde_json::from_str(line) {
+        Ok(chip) => chip,
+        Err(e) => return BatchOutcome::Failed(format!("invalid JSON: {}", e)),
+    };
+    let mut req = client.post(endpoint).json(&chip);
+    if let Some(key) = api_key {
+        req = req.header("X-API-Key", key);
+    }
+    let resp = match req.send().await {
+        Ok(resp) => resp,
+        Err(e) => return BatchOutcome::Failed(format!("transport: {}", e)),
+    };
+    let status = resp.status();
+    let response: Value = match resp.text().await {
+        Ok(text) => serde_json::from_str(&text).unwrap_or(Value::String(text)),
+        Err(e) => return BatchOutcome::Failed(format!("read body: {}", e)),
+    };
+    if !status.is_success() {
+        let code = response
+            .get("code")
+            .and_then(|v| v.as_str())
+            .unwrap_or("HTTP_ERROR");
+        return BatchOutcome::Failed(format!("{} {}", status.as_u16(), code));
+    }
+    BatchOutcome::Submitted {
+        decision: response["decision"].as_str().unwrap_or("?").to_string(),
+        receipt_cid: response["receipt_cid"].as_str().unwrap_or("-").to_string(),
+        replayed: response["replayed"].as_bool().unwrap_or(false),
+        response,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn cmd_submit_ndjson(
+    input: &str,
+    gate: &str,
+    output: Option<String>,
+    api_key: Option<String>,
+    timeout_secs: u64,
+    concurrency: usize,
+    continue_on_error: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(input)?;
+    let lines: Vec<(usize, String)> = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line.to_string()))
+        .collect();
+    let endpoint = Arc::new(format!("{}/v1/chips", gate.trim_end_matches('/')));
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .build()?;
+    let api_key: Option<Arc<str>> = api_key
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty())
+        .map(Arc::from);
+    let permits = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+    let stopped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    // Permits are taken in line order, so `--concurrency 1` submits strictly
+    // sequentially and a stop never skips an earlier line.
+    let mut handles = Vec::with_capacity(lines.len());
+    for (line_no, line) in lines {
+        let permit = permits.clone().acquire_owned().await?;
+        if stopped.load(std::sync::atomic::Ordering::SeqCst) {
+            handles.push((line_no, None));
+            continue;
+        }
+        let client = client.clone();
+        let endpoint = endpoint.clone();
+        let api_key = api_key.clone();
+        let stopped = stopped.clone();
+        handles.push((
+            line_no,
+            Some(tokio::spawn(async move {
+                let _permit = permit;
+                let outcome =
+                    submit_batch_line(&client, &endpoint, api_key.as_deref(), &line).await;
+                if !continue_on_error && outcome.stops_batch() {
+                    stopped.store(true, std::sync::atomic::Ordering::SeqCst);
+                }
+                outcome
+            })),
+        ));
+    }
+
+    let mut responses = Vec::new();
+    let mut rows = Vec::new();
+    let (mut allow, mut deny, mut quarantine, mut errors, mut skipped) = (0, 0, 0, 0, 0);
+    if !json {
+        println!(
+            "{:<6} {:<10} {:<7} RECEIPT_CID / ERROR",
+            "LINE", "DECISION", "REPLAY"
+        );
+    }
+    for (line_no, handle) in handles {
+        let outcome = match handle {
+            Some(handle) => handle.await?,
+            None => BatchOutcome::Skipped,
+        };
+        let row = match outcome {
+            BatchOutcome::Submitted {
+                decision,
+                receipt_cid,
+                replayed,
+                response,
+            } => {
+                match decision.to_ascii_lowercase().as_str() {
+                    "allow" => allow += 1,
+                    "deny" => deny += 1,
+                    "quarantine" => quarantine += 1,
+                    _ => errors += 1,
+                }
+                responses.push(response);
+                json!({
+                    "line": line_no,
+                    "decision": decision,
+                    "receipt_cid": receipt_cid,
+                    "replayed": replayed,
+                })
+            }
+            BatchOutcome::Failed(reason) => {
+                errors += 1;
+                json!({"line": line_no, "decision": "error", "error": reason})
+            }
+            BatchOutcome::Skipped => {
+                skipped += 1;
+                json!({"line": line_no, "decision": "skipped"})
+            }
+        };
+        if !json {
+            let detail = row
+                .get("receipt_cid")
+                .or_else(|| row.get("error"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("-");
+            let replayed = row
+                .get("replayed")
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            println!(
+                "{:<6} {:<10} {:<7} {}",
+                line_no,
+                row["decision"].as_str().unwrap_or("?"),
+                replayed,
+                detail
+            );
+        }
+        rows.push(row);
+    }
+
+    if let Some(out) = output {
+        let mut ndjson = String::new();
+        for response in &responses {
+            ndjson.push_str(&serde_json::to_string(response)?);
+            ndjson.push('\n');
+        }
+        std::fs::write(out, ndjson)?;
+    }
+    let total = allow + deny + quarantine + errors + skipped;
+    if json {
+        print_json(&json!({
+            "@type": "ublx/submit.batch",
+            "lines": rows,
+            "total": total,
+            "allow": allow,
+            "deny": deny,
+            "quarantine": quarantine,
+            "error": errors,
+            "skipped": skipped,
+        }))?;
+    } else {
+        println!(
+            "total={} allow={} deny={} quarantine={} error={} skipped={}",
+            total, allow, deny, quarantine, errors, skipped
+        );
+    }
+    if stopped.load(std::sync::atomic::Ordering::SeqCst) {
+        return Err("batch stopped at the first deny/error (use --continue-on-error)".into());
+    }
+    Ok(())
+}
+
+// ── bench ───────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct BenchWorkerStats {
+    latencies_ms: Vec<f64>,
+    succeeded: u64,
+    rate_limited: u64,
+    errors: std::collections::BTreeMap<String, u64>,
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn cmd_bench(
+    gate: &str,
+    concurrency: usize,
+    duration_secs: u64,
+    chip_file: &str,
+    fixed_id: bool,
+    api_key: Option<String>,
+    timeout_secs: u64,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let template: Value = serde_json::from_str(&std::fs::read_to_string(chip_file)?)?;
+    if !template.is_object() {
+        return Err("chip file must contain a JSON object".into());
+    }
+    let concurrency = concurrency.max(1);
+    let endpoint = format!("{}/v1/chips", gate.trim_end_matches('/'));
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .build()?;
+    let template = Arc::new(template);
+    let api_key = api_key
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty());
+    let run_tag = chrono::Utc::now().timestamp_millis();
+
+    let started = std::time::Instant::now();
+    let deadline = started + std::time::Duration::from_secs(duration_secs);
+    let mut handles = Vec::with_capacity(concurrency);
+    for worker in 0..concurrency {
+        let client = client.clone();
+        let endpoint = endpoint.clone();
+        let template = template.clone();
+        let api_key = api_key.clone();
+        handles.push(tokio::spawn(async move {
+            bench_worker(
+                client, endpoint, template, api_key, fixed_id, worker, run_tag, deadline,
+            )
+            .await
+        }));
+    }
+
+    let mut latencies_ms = Vec::new();
+    let mut succeeded = 0u64;
+    let mut rate_limited = 0u64;
+    let mut errors: std::collections::BTreeMap<String, u64> = std::collections::BTreeMap::new();
+    for handle in handles {
+        let stats = handle.await?;
+        latencies_ms.extend(stats.latencies_ms);
+        succeeded += stats.succeeded;
+        rate_limited += stats.rate_limited;
+        for (k, v) in stats.errors {
+            *errors.entry(k).or_insert(0) += v;
+        }
+    }
+    let elapsed = started.elapsed().as_secs_f64();
+    latencies_ms.sort_by(|a, b| a.total_cmp(b));
+    let requests = latencies_ms.len() as u64;
+    let failed: u64 = errors.values().sum();
+
+    let summary = json!({
+        "@type": "ublx/bench.summary",
+        "gate": gate,
+        "concurrency": concurrency,
+        "duration_secs": duration_secs,
+        "elapsed_secs": elapsed,
+        "fixed_id": fixed_id,
+        "requests": requests,
+        "succeeded": succeeded,
+        "failed": failed,
+        "rate_limited": rate_limited,
+        "throughput_rps": if elapsed > 0.0 { succeeded as f64 / elapsed } else { 0.0 },
+        "latency_ms": {
+            "p50": percentile(&latencies_ms, 50.0),
+            "p95": percentile(&latencies_ms, 95.0),
+            "p99": percentile(&latencies_ms, 99.0),
+            "max": latencies_ms.last().copied().unwrap_or(0.0),
+        },
+        "errors": errors,
+    });
+    if json {
+        return print_json(&summary);
+    }
+    println!("{}", serde_json::to_string_pretty(&summary)?);
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn bench_worker(
+    client: reqwest::Client,
+    endpoint: String,
+    template: Arc<Value>,
+    api_key: Option<String>,
+    fixed_id: bool,
+    worker: usize,
+    run_tag: i64,
+    deadline: std::time::Instant,
+) -> BenchWorkerStats {
+    let mut stats = BenchWorkerStats::default();
+    let base_id = template
+        .get("@id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("bench")
+        .to_string();
+    let mut seq = 0u64;
+    let mut backoff_ms = 100u64;
+
+    while std::time::Instant::now() < deadline {
+        let mut chip = (*template).clone();
+        if !fixed_id {
+            chip["@id"] = json!(format!("{}-bench-{}-{}-{}", base_id, run_tag, worker, seq));
+        }
+        seq += 1;
+
+        let mut req = client.post(&endpoint).json(&chip);
+        if let Some(key) = api_key.as_deref() {
+            req = req.header("X-API-Key", key);
+        }
+        let t0 = std::time::Instant::now();
+        let resp = req.send().await;
+        let latency_ms = t0.elapsed().as_secs_f64() * 1000.0;
+
+        let resp = match resp {
+            Ok(r) => r,
+            Err(e) => {
+                stats.latencies_ms.push(latency_ms);
+                let kind = if e.is_timeout() {
+                    "timeout"
+                } else {
+                    "transport"
+                };
+                *stats.errors.entry(kind.to_string()).or_insert(0) += 1;
+                continue;
+            }
+        };
+        let status = resp.status();
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            // Throttled attempts are not latency samples; back off and retry.
+            stats.rate_limited += 1;
+            let wait_ms = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(|secs| secs.saturating_mul(1000))
+                .unwrap_or(backoff_ms);
+            backoff_ms = (backoff_ms * 2).min(5_000);
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            tokio::time::sleep(std::time::Duration::from_millis(wait_ms).min(remaining)).await;
+            continue;
+        }
+        backoff_ms = 100;
+        stats.latencies_ms.push(latency_ms);
+
+        if status.is_success() {
+            stats.succeeded += 1;
+        } else {
+            let code = resp
+                .json::<Value>()
+                .await
+                .ok()
+                .and_then(|v| v.get("code").and_then(|c| c.as_str()).map(str::to_string));
+            let key = match code {
+                Some(code) => format!("{} {}", status.as_u16(), code),
+                None => status.as_u16().to_string(),
+            };
+            *stats.errors.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    stats
+}
+
+/// Nearest-rank percentile over an ascending-sorted slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
 // ── explain ─────────────────────────────────────────────────────
 
-fn cmd_explain(target: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_explain(target: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     // If target is a file path, read it; otherwise treat as inline JSON or CID
     let receipt_json: Value = if std::path::Path::new(target).exists() {
         let content = std::fs::read_to_string(target)?;
@@ -544,11 +1539,18 @@ fn cmd_explain(target: &str) -> Result<(), Box<dyn std::error::Error>> {
         serde_json::from_str(target)?
     } else {
         // CID-only mode: print what we know
+        if json {
+            return print_json(&json!({"receipt_cid": target, "explained": false}));
+        }
         println!("Receipt CID: {}", target);
         println!("  (Pass a receipt JSON file for full explanation)");
         return Ok(());
     };
 
+    if json {
+        return print_json(&explain_json(&receipt_json)?);
+    }
+
     // Print envelope
     println!("=== Receipt Explanation ===");
     if let Some(t) = receipt_json.get("@type").and_then(|v| v.as_str()) {
@@ -578,6 +1580,7 @@ fn cmd_explain(target: &str) -> Result<(), Box<dyn std::error::Error>> {
                 "allow" => "PASS",
                 "deny" => "DENY",
                 "require" => "REQUIRE",
+                "quarantine" => "QUARANTINE",
                 _ => decision,
             };
             println!("  [{}] {} -> {}", i + 1, policy_id, marker);
@@ -617,36 +1620,90 @@ fn cmd_explain(target: &str) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// `--json` form of `explain`: envelope, policy trace, VM state and the
+/// recomputed CID, with the raw decision strings.
+fn explain_json(receipt_json: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+    let policy_trace = receipt_json
+        .get("policy_trace")
+        .and_then(|v| v.as_array())
+        .map(|trace| {
+            trace
+                .iter()
+                .map(|entry| {
+                    let rb_results: Vec<Value> = entry
+                        .get("rb_results")
+                        .and_then(|v| v.as_array())
+                        .into_iter()
+                        .flatten()
+                        .map(|rb| json!({"rb_id": rb.get("rb_id"), "decision": rb.get("decision")}))
+                        .collect();
+                    json!({
+                        "policy_id": entry.get("policy_id"),
+                        "decision": entry.get("decision"),
+                        "rb_results": rb_results,
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+    let vm_state = receipt_json
+        .get("vm_state")
+        .map(|vm| json!({"fuel_used": vm.get("fuel_used"), "steps": vm.get("steps")}));
+    let cid = compute_cid(&to_nrf1_bytes(receipt_json)?)?;
+    Ok(json!({
+        "@type": receipt_json.get("@type"),
+        "decision": receipt_json.get("decision"),
+        "reason": receipt_json.get("reason"),
+        "policy_trace": policy_trace,
+        "vm_state": vm_state,
+        "computed_cid": cid,
+    }))
+}
+
 // ── search ──────────────────────────────────────────────────────
 
 async fn cmd_search(
-    chip_type: Option<String>,
-    tags: Vec<String>,
-    after: Option<String>,
-    before: Option<String>,
-    limit: u64,
+    query: ubl_chipstore::ChipQuery,
+    gate: Option<&str>,
+    api_key: Option<&str>,
+    demo: bool,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    use ubl_chipstore::{ChipQuery, ChipStore, InMemoryBackend};
+    use ubl_chipstore::{ChipStore, InMemoryBackend, QueryResult};
 
-    // In a real deployment, this would connect to the running ChipStore.
-    // For now, demonstrate the query API with an in-memory store.
-    let backend = Arc::new(InMemoryBackend::new());
-    let store = ChipStore::new(backend);
-
-    let query = ChipQuery {
-        chip_type,
-        tags,
-        created_after: after,
-        created_before: before,
-        executor_did: None,
-        limit: Some(limit as usize),
-        offset: None,
+    let results: QueryResult = match gate {
+        Some(gate) => search_gate(gate, api_key, &query).await?,
+        None if demo => {
+            let store = ChipStore::new(Arc::new(InMemoryBackend::new()));
+            store.query(&query).await?
+        }
+        None => return Err("pass --gate <url> to search a running gate, or --demo".into()),
     };
 
-    println!("Searching ChipStore...");
-    println!("  Query: {}", serde_json::to_string_pretty(&query)?);
+    if json {
+        let chips: Vec<Value> = results
+            .chips
+            .iter()
+            .map(|chip| {
+                json!({
+                    "cid": chip.cid,
+                    "chip_type": chip.chip_type,
+                    "receipt_cid": chip.receipt_cid,
+                })
+            })
+            .collect();
+        return print_json(&json!({
+            "query": query,
+            "total_count": results.total_count,
+            "has_more": results.has_more,
+            "chips": chips,
+        }));
+    }
 
-    let results = store.query(&query).await?;
+    match gate {
+        Some(gate) => println!("Searching ChipStore at {}...", gate),
+        None => println!("Searching demo in-memory ChipStore..."),
+    }
+    println!("  Query: {}", serde_json::to_string_pretty(&query)?);
     println!(
         "\n  Found: {} chips (total: {})",
         results.chips.len(),
@@ -660,17 +1717,70 @@ async fn cmd_search(
         println!("    Receipt: {}", chip.receipt_cid);
     }
 
-    if results.total_count == 0 {
-        println!("  (No chips found. In production, connect to a running ChipStore.)");
+    if results.has_more {
+        println!("\n  (More results available; page with --offset.)");
     }
 
     Ok(())
 }
 
+/// `GET /v1/chips/search` on a running gate.
+async fn search_gate(
+    gate: &str,
+    api_key: Option<&str>,
+    query: &ubl_chipstore::ChipQuery,
+) -> Result<ubl_chipstore::QueryResult, Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/v1/chips/search", gate.trim_end_matches('/'));
+    let mut params: Vec<(&str, String)> = Vec::new();
+    if let Some(chip_type) = &query.chip_type {
+        params.push(("chip_type", chip_type.clone()));
+    }
+    for tag in &query.tags {
+        params.push(("tag", tag.clone()));
+    }
+    if let Some(after) = &query.created_after {
+        params.push(("after", after.clone()));
+    }
+    if let Some(before) = &query.created_before {
+        params.push(("before", before.clone()));
+    }
+    if let Some(limit) = query.limit {
+        params.push(("limit", limit.to_string()));
+    }
+    if let Some(offset) = query.offset {
+        params.push(("offset", offset.to_string()));
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()?;
+    let mut req = client.get(&endpoint).query(&params);
+    if let Some(key) = api_key.map(str::trim).filter(|k| !k.is_empty()) {
+        req = req.bearer_auth(key);
+    }
+    let resp = req.send().await?;
+    let status = resp.status();
+    let body_text = resp.text().await?;
+    if !status.is_success() {
+        return Err(format!("gate search failed: {} {}", status, body_text).into());
+    }
+    let mut response: Value = serde_json::from_str(&body_text)?;
+    Ok(ubl_chipstore::QueryResult {
+        chips: serde_json::from_value(response["chips"].take())?,
+        total_count: response["total"].as_u64().unwrap_or(0) as usize,
+        has_more: response["has_more"].as_bool().unwrap_or(false),
+    })
+}
+
 // ── fixture ─────────────────────────────────────────────────────
 
-fn cmd_fixture(output_dir: &str, count: usize) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_fixture(
+    output_dir: &str,
+    count: usize,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     std::fs::create_dir_all(output_dir)?;
+    let mut written = Vec::with_capacity(count);
 
     let chip_types = [
         "ubl/user",
@@ -735,6 +1845,16 @@ fn cmd_fixture(output_dir: &str, count: usize) -> Result<(), Box<dyn std::error:
         let receipt_path = format!("{}/receipt-{:04}.json", output_dir, i);
         std::fs::write(&receipt_path, serde_json::to_string_pretty(&receipt)?)?;
 
+        if json {
+            written.push(json!({
+                "id": id,
+                "chip_type": chip_type,
+                "cid": cid,
+                "chip_path": chip_path,
+                "receipt_path": receipt_path,
+            }));
+            continue;
+        }
         println!(
             "  [{}/{}] {} type={} cid={}",
             i + 1,
@@ -745,6 +1865,13 @@ fn cmd_fixture(output_dir: &str, count: usize) -> Result<(), Box<dyn std::error:
         );
     }
 
+    if json {
+        return print_json(&json!({
+            "output_dir": output_dir,
+            "count": count,
+            "fixtures": written,
+        }));
+    }
     println!(
         "\nGenerated {} chip + receipt fixture pairs in {}/",
         count, output_dir
@@ -754,7 +1881,7 @@ fn cmd_fixture(output_dir: &str, count: usize) -> Result<(), Box<dyn std::error:
 
 // ── url ─────────────────────────────────────────────────────────
 
-fn cmd_url(receipt_cid: &str, host: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_url(receipt_cid: &str, host: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     use ubl_runtime::rich_url::HostedUrl;
 
     // Parse world from CID or use defaults
@@ -769,6 +1896,13 @@ fn cmd_url(receipt_cid: &str, host: &str) -> Result<(), Box<dyn std::error::Erro
         "sig:placeholder",
     );
 
+    if json {
+        return print_json(&json!({
+            "url": url.to_url_string(),
+            "signing_payload": String::from_utf8_lossy(&url.signing_payload()),
+            "placeholders": true,
+        }));
+    }
     println!("Hosted URL:");
     println!("  {}", url.to_url_string());
     println!("\nSigning payload ({} bytes):", url.signing_payload().len());
@@ -780,13 +1914,16 @@ fn cmd_url(receipt_cid: &str, host: &str) -> Result<(), Box<dyn std::error::Erro
 
 // ── disasm ──────────────────────────────────────────────────────
 
-fn cmd_disasm(input: &str, is_hex: bool) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_disasm(input: &str, is_hex: bool, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     let bytecode = if is_hex {
         let clean = input.replace([' ', '\n', '\t'], "");
         hex::decode(&clean)?
     } else {
         std::fs::read(input)?
     };
+    if json {
+        return print_json(&disasm_json(&bytecode)?);
+    }
 
     println!("=== RB-VM Disassembly ({} bytes) ===\n", bytecode.len());
     match rb_vm::disassemble(&bytecode) {
@@ -796,6 +1933,16 @@ fn cmd_disasm(input: &str, is_hex: bool) -> Result<(), Box<dyn std::error::Error
     Ok(())
 }
 
+/// `--json` form of a disassembly; a malformed program is an error.
+fn disasm_json(bytecode: &[u8]) -> Result<Value, Box<dyn std::error::Error>> {
+    let listing = rb_vm::disassemble(bytecode).map_err(|e| format!("disassembly error: {}", e))?;
+    Ok(json!({
+        "size": bytecode.len(),
+        "instructions": rb_vm::count_tlv_instrs(bytecode),
+        "listing": listing,
+    }))
+}
+
 // ── silicon compile ─────────────────────────────────────────────
 //
 // Bundle format (self-contained JSON):
@@ -818,6 +1965,7 @@ async fn cmd_silicon_compile(
     from_store: Option<&str>,
     store_path: &str,
     hex_only: bool,
+    json: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use std::collections::HashMap;
     use std::sync::Arc;
@@ -858,6 +2006,14 @@ async fn cmd_silicon_compile(
         let bc_cid = format!("b3:{}", hex::encode(bc_hash.as_bytes()));
         let bc_hex = hex::encode(&bytecode);
 
+        if json {
+            let mut out = disasm_json(&bytecode)?;
+            out["chip_cid"] = json!(chip_cid);
+            out["store_path"] = json!(store_path);
+            out["bytecode_cid"] = json!(bc_cid);
+            out["bytecode_hex"] = json!(bc_hex);
+            return print_json(&out);
+        }
         if hex_only {
             println!("{}", bc_hex);
         } else {
@@ -869,7 +2025,7 @@ async fn cmd_silicon_compile(
             println!(
                 "Bytecode size:       {} bytes ({} instructions)",
                 bytecode.len(),
-                count_tlv_instrs(&bytecode)
+                rb_vm::count_tlv_instrs(&bytecode)
             );
             println!();
             println!("=== Bytecode (hex) ===");
@@ -1035,6 +2191,14 @@ async fn cmd_silicon_compile(
     let bc_cid = format!("b3:{}", hex::encode(bc_hash.as_bytes()));
     let bc_hex = hex::encode(&bytecode);
 
+    if json {
+        let mut out = disasm_json(&bytecode)?;
+        out["chip_cid"] = json!(chip_content_cid);
+        out["store_cid"] = json!(chip_store_cid);
+        out["bytecode_cid"] = json!(bc_cid);
+        out["bytecode_hex"] = json!(bc_hex);
+        return print_json(&out);
+    }
     if hex_only {
         println!("{}", bc_hex);
     } else {
@@ -1046,7 +2210,7 @@ async fn cmd_silicon_compile(
         println!(
             "Bytecode size:       {} bytes ({} instructions)",
             bytecode.len(),
-            count_tlv_instrs(&bytecode)
+            rb_vm::count_tlv_instrs(&bytecode)
         );
         println!();
         println!("=== Bytecode (hex) ===");
@@ -1062,32 +2226,27 @@ async fn cmd_silicon_compile(
     Ok(())
 }
 
-/// Count TLV instructions in a bytecode buffer (each is 3-byte header + payload).
-fn count_tlv_instrs(bytecode: &[u8]) -> usize {
-    let mut count = 0;
-    let mut i = 0;
-    while i + 2 < bytecode.len() {
-        let len = u16::from_be_bytes([bytecode[i + 1], bytecode[i + 2]]) as usize;
-        i += 3 + len;
-        count += 1;
-    }
-    count
-}
-
 // ── silicon disasm ───────────────────────────────────────────────
 
-fn cmd_silicon_disasm(input: &str, is_file: bool) -> Result<(), Box<dyn std::error::Error>> {
+fn cmd_silicon_disasm(
+    input: &str,
+    is_file: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let bytecode = if is_file {
         std::fs::read(input)?
     } else {
         let clean = input.replace([' ', '\n', '\t'], "");
         hex::decode(&clean)?
     };
+    if json {
+        return print_json(&disasm_json(&bytecode)?);
+    }
 
     println!(
         "=== Silicon Chip Disassembly ({} bytes, {} instructions) ===\n",
         bytecode.len(),
-        count_tlv_instrs(&bytecode),
+        rb_vm::count_tlv_instrs(&bytecode),
     );
     match rb_vm::disassemble(&bytecode) {
         Ok(listing) => print!("{}", listing),
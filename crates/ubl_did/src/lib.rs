@@ -2,23 +2,29 @@ use serde_json::json;
 use ubl_receipt::ISSUER_DID;
 
 pub fn runtime_did_document() -> serde_json::Value {
-    let multibase = format!(
-        "z{}",
-        bs58::encode(&ubl_receipt::VERIFYING_KEY.to_bytes()).into_string()
-    );
+    ed25519_did_document(ISSUER_DID.as_str(), &ubl_receipt::VERIFYING_KEY.to_bytes())
+}
+
+/// Minimal DID document with one Ed25519 verification method (`#ed25519`).
+pub fn ed25519_did_document(did: &str, public_key: &[u8; 32]) -> serde_json::Value {
+    let multibase = format!("z{}", bs58::encode(public_key).into_string());
+    let method_id = format!("{}#ed25519", did);
     json!({
-        "id": ISSUER_DID.as_str(),
+        "@context": [
+            "https://www.w3.org/ns/did/v1",
+            "https://w3id.org/security/suites/ed25519-2020/v1"
+        ],
+        "id": did,
         "verificationMethod": [
             {
-                "id": format!("{}#ed25519", *ISSUER_DID),
+                "id": method_id,
                 "type": "Ed25519VerificationKey2020",
-                "controller": ISSUER_DID.as_str(),
+                "controller": did,
                 "publicKeyMultibase": multibase,
             }
         ],
-        "assertionMethod": [
-            format!("{}#ed25519", *ISSUER_DID)
-        ]
+        "authentication": [method_id],
+        "assertionMethod": [method_id]
     })
 }
 
@@ -2,6 +2,7 @@ use chrono::DateTime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sled::IVec;
+use std::ops::Bound;
 use std::path::Path;
 
 const TREE_EVENTS: &str = "events";
@@ -12,6 +13,7 @@ const TREE_IDX_TYPE: &str = "idx_type";
 const TREE_IDX_DECISION: &str = "idx_decision";
 const TREE_IDX_CODE: &str = "idx_code";
 const TREE_IDX_ACTOR: &str = "idx_actor";
+const TREE_WORLDS: &str = "worlds";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EventQuery {
@@ -19,9 +21,14 @@ pub struct EventQuery {
     pub stage: Option<String>,
     pub decision: Option<String>,
     pub code: Option<String>,
+    /// Matches any code starting with this (e.g. `check.policy.`).
+    pub code_prefix: Option<String>,
     pub chip_type: Option<String>,
     pub actor: Option<String>,
+    /// Inclusive lower bound: RFC-3339 or epoch milliseconds.
     pub since: Option<String>,
+    /// Inclusive upper bound, same formats as `since`.
+    pub until: Option<String>,
     pub limit: Option<usize>,
 }
 
@@ -32,6 +39,14 @@ pub struct EventRecord {
     pub event: Value,
 }
 
+/// One entry of the world directory: a `@world` seen in ingested events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorldSummary {
+    pub world: String,
+    pub last_activity_ms: i64,
+    pub event_count: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct EventStore {
     db: sled::Db,
@@ -52,6 +67,7 @@ impl EventStore {
         let db = sled::open(path).map_err(|e| EventStoreError::Sled(e.to_string()))?;
         let store = Self { db };
         store.ensure_trees()?;
+        store.backfill_world_directory()?;
         Ok(store)
     }
 
@@ -77,6 +93,7 @@ impl EventStore {
             TREE_IDX_DECISION,
             TREE_IDX_CODE,
             TREE_IDX_ACTOR,
+            TREE_WORLDS,
         ] {
             self.db
                 .open_tree(t)
@@ -122,6 +139,7 @@ impl EventStore {
             TREE_IDX_DECISION,
             TREE_IDX_CODE,
             TREE_IDX_ACTOR,
+            TREE_WORLDS,
         ] {
             let tree = self
                 .db
@@ -149,8 +167,88 @@ impl EventStore {
         Ok(())
     }
 
+    /// Distinct worlds seen in ingested events, in world order, optionally
+    /// restricted to a `prefix` (exact match or path prefix). At most `limit`
+    /// entries (clamped to 1..=1000).
+    pub fn worlds(
+        &self,
+        prefix: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<WorldSummary>, EventStoreError> {
+        let worlds = self
+            .db
+            .open_tree(TREE_WORLDS)
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
+        let limit = limit.clamp(1, 1_000);
+        let prefix = prefix.unwrap_or("").trim_end_matches('/');
+        let mut out = Vec::new();
+        for item in worlds.scan_prefix(prefix.as_bytes()) {
+            let (k, v) = item.map_err(|e| EventStoreError::Sled(e.to_string()))?;
+            let world = String::from_utf8_lossy(&k).into_owned();
+            if !prefix.is_empty() && world != prefix && !world[prefix.len()..].starts_with('/') {
+                continue;
+            }
+            let (last_activity_ms, event_count) = decode_world_entry(&v);
+            out.push(WorldSummary {
+                world,
+                last_activity_ms,
+                event_count,
+            });
+            if out.len() >= limit {
+                break;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Stores written before the world directory existed only have
+    /// `idx_world`; derive the directory from it once.
+    fn backfill_world_directory(&self) -> Result<(), EventStoreError> {
+        let worlds = self
+            .db
+            .open_tree(TREE_WORLDS)
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
+        if !worlds.is_empty() {
+            return Ok(());
+        }
+        let idx_world = self
+            .db
+            .open_tree(TREE_IDX_WORLD)
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
+        for item in idx_world.iter() {
+            let (k, _v) = item.map_err(|e| EventStoreError::Sled(e.to_string()))?;
+            let Ok(key) = std::str::from_utf8(&k) else {
+                continue;
+            };
+            let mut parts = key.splitn(3, '\x1f');
+            let (Some(world), Some(when_ms)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let Ok(when_ms) = when_ms.parse::<i64>() else {
+                continue;
+            };
+            self.touch_world(world, when_ms)?;
+        }
+        Ok(())
+    }
+
+    fn touch_world(&self, world: &str, when_ms: i64) -> Result<(), EventStoreError> {
+        let worlds = self
+            .db
+            .open_tree(TREE_WORLDS)
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
+        worlds
+            .fetch_and_update(world.as_bytes(), |old| {
+                let (last, count) = old.map(decode_world_entry).unwrap_or((i64::MIN, 0));
+                Some(encode_world_entry(last.max(when_ms), count + 1))
+            })
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
+        Ok(())
+    }
+
     /// Choose the most selective dimensional index available for this query.
     /// Returns `(tree_name, value)` or `None` to fall back to time-scan.
+    /// A `code_prefix` is served by [`Self::scan_code_prefix`] instead.
     fn choose_best_index(&self, q: &EventQuery) -> Option<(&'static str, String)> {
         // Prefer the most selective / most common audit filters first.
         // chip_type supports glob in matches_query(); only use the index for exact matches.
@@ -183,12 +281,14 @@ impl EventStore {
         None
     }
 
-    /// Scan a dimensional index for all event IDs >= start_ms that share `value`.
+    /// Scan a dimensional index for all event IDs in `start_ms..end_ms` that
+    /// share `value`.
     fn scan_dim_index(
         &self,
         tree_name: &str,
         value: &str,
         start_ms: i64,
+        end_ms: i64,
     ) -> Result<Vec<String>, EventStoreError> {
         let idx = self
             .db
@@ -196,9 +296,10 @@ impl EventStore {
             .map_err(|e| EventStoreError::Sled(e.to_string()))?;
         let prefix = format!("{}\x1f", value).into_bytes();
         let start_key = format!("{}\x1f{:020}\x1f", value, start_ms).into_bytes();
+        let end_key = format!("{}\x1f{:020}\x1f", value, end_ms).into_bytes();
 
         let mut ids = Vec::new();
-        for item in idx.range(start_key..) {
+        for item in idx.range(start_key..end_key) {
             let (k, _v) = item.map_err(|e| EventStoreError::Sled(e.to_string()))?;
             if !k.starts_with(&prefix) {
                 break;
@@ -210,6 +311,40 @@ impl EventStore {
         Ok(ids)
     }
 
+    /// Event IDs in `start_ms..end_ms` whose code starts with `prefix`, in
+    /// time order. Only index keys are read; codes sort apart in `idx_code`,
+    /// so the hits are merged by time before any event is loaded.
+    fn scan_code_prefix(
+        &self,
+        prefix: &str,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<Vec<String>, EventStoreError> {
+        let idx = self
+            .db
+            .open_tree(TREE_IDX_CODE)
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
+        let mut hits = Vec::new();
+        for item in idx.scan_prefix(prefix.as_bytes()) {
+            let (k, _v) = item.map_err(|e| EventStoreError::Sled(e.to_string()))?;
+            let Ok(key) = std::str::from_utf8(&k) else {
+                continue;
+            };
+            let mut parts = key.rsplitn(3, '\x1f');
+            let (Some(event_id), Some(when_ms)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let Ok(when_ms) = when_ms.parse::<i64>() else {
+                continue;
+            };
+            if (start_ms..end_ms).contains(&when_ms) {
+                hits.push((when_ms, event_id.to_string()));
+            }
+        }
+        hits.sort();
+        Ok(hits.into_iter().map(|(_, id)| id).collect())
+    }
+
     pub fn query(&self, query: &EventQuery) -> Result<Vec<Value>, EventStoreError> {
         let events = self
             .db
@@ -218,12 +353,19 @@ impl EventStore {
 
         let limit = query.limit.unwrap_or(200).clamp(1, 2_000);
         let start_ms = parse_since_to_ms(query.since.as_deref()).unwrap_or(0);
+        // Exclusive bound for the key ranges below.
+        let end_ms =
+            parse_since_to_ms(query.until.as_deref()).map_or(i64::MAX, |ms| ms.saturating_add(1));
 
         let mut out = Vec::with_capacity(limit);
 
         // Fast path: use a dimensional index when one matches the query.
-        if let Some((tree, value)) = self.choose_best_index(query) {
-            let ids = self.scan_dim_index(tree, &value, start_ms)?;
+        let ids = match (self.choose_best_index(query), query.code_prefix.as_deref()) {
+            (Some((tree, value)), _) => Some(self.scan_dim_index(tree, &value, start_ms, end_ms)?),
+            (None, Some(prefix)) => Some(self.scan_code_prefix(prefix, start_ms, end_ms)?),
+            (None, None) => None,
+        };
+        if let Some(ids) = ids {
             for event_id in ids {
                 let Some(raw) = events
                     .get(event_id.as_bytes())
@@ -244,12 +386,75 @@ impl EventStore {
         }
 
         // Fallback: time-scan (original behaviour; handles glob chip_type, mixed-case, etc.)
+        self.scan_time_range(
+            Bound::Included(time_bound_key(start_ms)),
+            Bound::Excluded(time_bound_key(end_ms)),
+            query,
+            limit,
+        )
+    }
+
+    /// Events strictly after `cursor` (see [`event_cursor`]), in time order
+    /// and filtered like [`Self::query`]; `since` only raises the start. A
+    /// bare event id is accepted too and resumes after that event.
+    pub fn query_after(
+        &self,
+        cursor: &str,
+        query: &EventQuery,
+    ) -> Result<Vec<Value>, EventStoreError> {
+        let (when_ms, event_id) = self
+            .resolve_cursor(cursor)?
+            .ok_or_else(|| EventStoreError::InvalidEvent(format!("unknown cursor: {}", cursor)))?;
+        let limit = query.limit.unwrap_or(200).clamp(1, 2_000);
+        let after = time_index_key(when_ms, &event_id);
+        let since = parse_since_to_ms(query.since.as_deref()).map(time_bound_key);
+        let lower = match since {
+            Some(since) if since > after => Bound::Included(since),
+            _ => Bound::Excluded(after),
+        };
+        let end_ms =
+            parse_since_to_ms(query.until.as_deref()).map_or(i64::MAX, |ms| ms.saturating_add(1));
+        self.scan_time_range(lower, Bound::Excluded(time_bound_key(end_ms)), query, limit)
+    }
+
+    /// `(when_ms, event_id)` for a cursor, or for a stored event's id.
+    fn resolve_cursor(&self, cursor: &str) -> Result<Option<(i64, String)>, EventStoreError> {
+        if let Some((when_ms, event_id)) = parse_cursor(cursor) {
+            return Ok(Some((when_ms, event_id.to_string())));
+        }
+        let events = self
+            .db
+            .open_tree(TREE_EVENTS)
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
+        let Some(raw) = events
+            .get(cursor.as_bytes())
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let event: Value =
+            serde_json::from_slice(&raw).map_err(|e| EventStoreError::Serde(e.to_string()))?;
+        let record = normalize_event(&event)?;
+        Ok(Some((record.when_ms, record.event_id)))
+    }
+
+    fn scan_time_range(
+        &self,
+        lower: Bound<Vec<u8>>,
+        upper: Bound<Vec<u8>>,
+        query: &EventQuery,
+        limit: usize,
+    ) -> Result<Vec<Value>, EventStoreError> {
+        let events = self
+            .db
+            .open_tree(TREE_EVENTS)
+            .map_err(|e| EventStoreError::Sled(e.to_string()))?;
         let idx_time = self
             .db
             .open_tree(TREE_IDX_TIME)
             .map_err(|e| EventStoreError::Sled(e.to_string()))?;
-        let start_key = format!("{:020}\x1f", start_ms);
-        for item in idx_time.range(start_key.as_bytes()..) {
+        let mut out = Vec::with_capacity(limit);
+        for item in idx_time.range((lower, upper)) {
             let (k, _v) = item.map_err(|e| EventStoreError::Sled(e.to_string()))?;
             let Some(event_id) = extract_event_id_from_index_key(&k) else {
                 continue;
@@ -286,6 +491,7 @@ impl EventStore {
 
         let world = event_world(&record.event).unwrap_or_else(|| "a/system".into());
         self.insert_dim(TREE_IDX_WORLD, &world, record.when_ms, &record.event_id)?;
+        self.touch_world(&world, record.when_ms)?;
         if let Some(stage) = event_stage(&record.event) {
             self.insert_dim(TREE_IDX_STAGE, &stage, record.when_ms, &record.event_id)?;
         }
@@ -394,10 +600,51 @@ fn time_index_key(when_ms: i64, event_id: &str) -> Vec<u8> {
     format!("{:020}\x1f{}", when_ms, event_id).into_bytes()
 }
 
+/// Sorts before every `idx_time` key at `when_ms`.
+fn time_bound_key(when_ms: i64) -> Vec<u8> {
+    format!("{:020}\x1f", when_ms).into_bytes()
+}
+
+/// Resume cursor for an event: its `idx_time` position rendered as
+/// `<when_ms, 20 digits>:<event_id>`, so cursors sort like the events do.
+/// `None` when the event has no parsable `when`.
+pub fn event_cursor(event: &Value) -> Option<String> {
+    let record = normalize_event(event).ok()?;
+    Some(format!("{:020}:{}", record.when_ms, record.event_id))
+}
+
+fn parse_cursor(cursor: &str) -> Option<(i64, &str)> {
+    let (when_ms, event_id) = cursor.split_once(':')?;
+    if when_ms.len() != 20 || !when_ms.bytes().all(|b| b.is_ascii_digit()) || event_id.is_empty() {
+        return None;
+    }
+    Some((when_ms.parse().ok()?, event_id))
+}
+
 fn dim_index_key(value: &str, when_ms: i64, event_id: &str) -> Vec<u8> {
     format!("{}\x1f{:020}\x1f{}", value, when_ms, event_id).into_bytes()
 }
 
+/// World directory value: `last_activity_ms` (i64 BE) ‖ `event_count` (u64 BE).
+fn encode_world_entry(last_activity_ms: i64, event_count: u64) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(16);
+    buf.extend_from_slice(&last_activity_ms.to_be_bytes());
+    buf.extend_from_slice(&event_count.to_be_bytes());
+    buf
+}
+
+fn decode_world_entry(raw: &[u8]) -> (i64, u64) {
+    let last = raw
+        .get(..8)
+        .and_then(|b| b.try_into().ok())
+        .map_or(0, i64::from_be_bytes);
+    let count = raw
+        .get(8..16)
+        .and_then(|b| b.try_into().ok())
+        .map_or(0, u64::from_be_bytes);
+    (last, count)
+}
+
 fn extract_event_id_from_index_key(key: &[u8]) -> Option<String> {
     let s = std::str::from_utf8(key).ok()?;
     let mut parts = s.rsplitn(2, '\x1f');
@@ -516,6 +763,11 @@ fn matches_query(event: &Value, q: &EventQuery) -> bool {
             return false;
         }
     }
+    if let Some(prefix) = &q.code_prefix {
+        if !event_code(event).is_some_and(|c| c.starts_with(prefix.as_str())) {
+            return false;
+        }
+    }
     if let Some(actor) = &q.actor {
         if event_actor(event).as_deref() != Some(actor.as_str()) {
             return false;
@@ -611,6 +863,106 @@ mod tests {
         assert_eq!(only_deny[0]["@id"], "evt-2");
     }
 
+    #[test]
+    fn code_prefix_and_until_bound_the_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EventStore::open(dir.path()).unwrap();
+        for (id, second, code) in [
+            ("evt-1", 0, "check.policy.genesis"),
+            ("evt-2", 1, "check.policy.tenant"),
+            ("evt-3", 2, "check.type"),
+            ("evt-4", 3, "check.policy.genesis"),
+        ] {
+            let mut e = sample_event(
+                id,
+                &format!("2026-02-18T12:00:0{second}.000Z"),
+                "a/acme/t/prod",
+                "CHECK",
+                "DENY",
+            );
+            e["receipt"]["code"] = serde_json::json!(code);
+            store.append_event_json(&e).unwrap();
+        }
+        let ids = |q: EventQuery| -> Vec<String> {
+            store
+                .query(&q)
+                .unwrap()
+                .iter()
+                .map(|e| e["@id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // Prefix scan merges codes back into time order.
+        assert_eq!(
+            ids(EventQuery {
+                code_prefix: Some("check.policy.".into()),
+                ..Default::default()
+            }),
+            ["evt-1", "evt-2", "evt-4"]
+        );
+        // Both bounds are inclusive, on the prefix, index and time paths.
+        let window = |q: EventQuery| EventQuery {
+            since: Some("2026-02-18T12:00:01.000Z".into()),
+            until: Some("2026-02-18T12:00:02.000Z".into()),
+            ..q
+        };
+        assert_eq!(
+            ids(window(EventQuery {
+                code_prefix: Some("check.policy.".into()),
+                ..Default::default()
+            })),
+            ["evt-2"]
+        );
+        assert_eq!(
+            ids(window(EventQuery {
+                world: Some("a/acme/t/prod".into()),
+                code_prefix: Some("check.".into()),
+                ..Default::default()
+            })),
+            ["evt-2", "evt-3"]
+        );
+        assert_eq!(ids(window(EventQuery::default())), ["evt-2", "evt-3"]);
+    }
+
+    #[test]
+    fn query_after_resumes_strictly_after_the_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EventStore::open(dir.path()).unwrap();
+        let events: Vec<Value> = ["evt-a", "evt-b", "evt-c"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                sample_event(
+                    id,
+                    &format!("2026-02-18T12:00:0{i}.000Z"),
+                    "a/acme",
+                    "WF",
+                    "ALLOW",
+                )
+            })
+            .collect();
+        for e in &events {
+            store.append_event_json(e).unwrap();
+        }
+        let cursor = event_cursor(&events[0]).unwrap();
+        assert!(cursor < event_cursor(&events[1]).unwrap());
+        let ids = |cursor: &str| -> Vec<String> {
+            store
+                .query_after(cursor, &EventQuery::default())
+                .unwrap()
+                .iter()
+                .map(|e| e["@id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(ids(&cursor), ["evt-b", "evt-c"]);
+        // A bare event id resumes after that event.
+        assert_eq!(ids("evt-b"), ["evt-c"]);
+        assert!(ids(&event_cursor(&events[2]).unwrap()).is_empty());
+        assert!(store
+            .query_after("evt-missing", &EventQuery::default())
+            .is_err());
+    }
+
     #[test]
     fn rebuild_indexes_from_events() {
         let dir = tempfile::tempdir().unwrap();
@@ -626,5 +978,55 @@ mod tests {
             })
             .unwrap();
         assert_eq!(found.len(), 1);
+        assert_eq!(store.worlds(None, 10).unwrap()[0].event_count, 1);
+    }
+
+    #[test]
+    fn world_directory_tracks_counts_and_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EventStore::open(dir.path()).unwrap();
+        for (id, when, world) in [
+            ("evt-1", "2026-02-18T12:00:00.000Z", "a/acme/t/prod"),
+            ("evt-2", "2026-02-18T12:00:05.000Z", "a/acme/t/prod"),
+            ("evt-3", "2026-02-18T12:00:01.000Z", "a/acme/t/dev"),
+            ("evt-4", "2026-02-18T12:00:02.000Z", "a/acmex"),
+        ] {
+            store
+                .append_event_json(&sample_event(id, when, world, "WF", "ALLOW"))
+                .unwrap();
+        }
+        // Duplicates are not counted.
+        store
+            .append_event_json(&sample_event(
+                "evt-1",
+                "2026-02-18T12:00:00.000Z",
+                "a/acme/t/prod",
+                "WF",
+                "ALLOW",
+            ))
+            .unwrap();
+
+        let acme = store.worlds(Some("a/acme"), 10).unwrap();
+        assert_eq!(acme.len(), 2);
+        assert_eq!(acme[0].world, "a/acme/t/dev");
+        assert_eq!(acme[1].world, "a/acme/t/prod");
+        assert_eq!(acme[1].event_count, 2);
+        assert_eq!(
+            acme[1].last_activity_ms,
+            DateTime::parse_from_rfc3339("2026-02-18T12:00:05.000Z")
+                .unwrap()
+                .timestamp_millis()
+        );
+        assert_eq!(store.worlds(None, 10).unwrap().len(), 3);
+        assert_eq!(store.worlds(None, 1).unwrap().len(), 1);
+
+        // Older stores without the directory get it derived on open.
+        store.db.open_tree(TREE_WORLDS).unwrap().clear().unwrap();
+        drop(store);
+        let store = EventStore::open(dir.path()).unwrap();
+        assert_eq!(
+            store.worlds(Some("a/acme/t/prod"), 10).unwrap()[0].event_count,
+            2
+        );
     }
 }
@@ -16,8 +16,9 @@ pub use pipeline_types::{
     UblReceiptType, WaReceiptBody, WfReceiptBody,
 };
 pub use unified::{
-    BuildMeta, CryptoMode, PipelineStage, ReceiptError, RuntimeInfo, StageExecution,
-    UnifiedReceipt, VerifyMode, VerifyReport,
+    max_chain_len_from_env, AuthChainReport, BuildMeta, CryptoMode, PipelineStage, ReceiptError,
+    RuntimeInfo, StageAuthCheck, StageExecution, UnifiedReceipt, VerifyMode, VerifyReport,
+    DEFAULT_MAX_CHAIN_LEN,
 };
 
 // Re-export leaf newtypes for downstream crates
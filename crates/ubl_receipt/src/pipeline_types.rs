@@ -8,6 +8,8 @@ pub enum Decision {
     Allow,
     Deny,
     Require,
+    /// Structurally valid but held for operator review: stored, not emitted.
+    Quarantine,
 }
 
 /// Receipt types for the UBL MASTER pipeline
@@ -43,12 +45,23 @@ pub struct WaReceiptBody {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WfReceiptBody {
     pub decision: Decision,
+    /// Empty when WA was skipped (`wa_skipped`).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub wa_cid: String,
+    /// WA ghost receipt suppressed for this chip type.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub wa_skipped: bool,
     pub tr_cid: Option<String>,
     pub artifacts: std::collections::HashMap<String, String>,
     pub duration_ms: i64,
     pub policy_trace: Vec<PolicyTraceEntry>,
     pub short_circuited: bool,
+    /// TR fuel limit in effect; absent when no transition ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuel_limit: Option<u64>,
+    /// TR fuel actually consumed; absent when no transition ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuel_used: Option<u64>,
 }
 
 /// Advisory receipt body (LLM advice - unsigned)
@@ -97,6 +97,32 @@ pub struct VerifyReport {
     pub v2_valid: bool,
 }
 
+/// Replay outcome for one stage's `auth_token`.
+#[derive(Debug, Clone)]
+pub struct StageAuthCheck {
+    pub stage: PipelineStage,
+    /// Receipt CID the token was bound to (`genesis` for the first stage).
+    pub prev_cid: String,
+    /// Which secret reproduced the token: `current`, `previous`, or none.
+    pub matched: Option<&'static str>,
+}
+
+/// Stage-by-stage replay of a receipt's auth chain.
+#[derive(Debug, Clone)]
+pub struct AuthChainReport {
+    pub stages: Vec<StageAuthCheck>,
+    /// CID after replaying every stage.
+    pub replayed_cid: String,
+    /// `replayed_cid` equals the receipt's `receipt_cid`.
+    pub cid_matches: bool,
+}
+
+impl AuthChainReport {
+    pub fn verified(&self) -> bool {
+        self.cid_matches && self.stages.iter().all(|s| s.matched.is_some())
+    }
+}
+
 /// Build provenance metadata — collected at compile time and startup.
 /// Supports PF-01 invariant I-03: every receipt carries build provenance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -313,6 +339,20 @@ pub struct UnifiedReceipt {
 const STAGE_SECRET_ENV: &str = "UBL_STAGE_SECRET";
 const STAGE_SECRET_PREV_ENV: &str = "UBL_STAGE_SECRET_PREV";
 const RECEIPT_DOMAIN_ENV: &str = "UBL_SIGN_DOMAIN_RECEIPT";
+const MAX_CHAIN_LEN_ENV: &str = "UBL_MAX_RECEIPT_CHAIN_LEN";
+
+/// Longest stage chain accepted for verification. The pipeline appends at
+/// most one entry per stage, so anything near this is externally crafted.
+pub const DEFAULT_MAX_CHAIN_LEN: usize = 16;
+
+/// `UBL_MAX_RECEIPT_CHAIN_LEN`, falling back to [`DEFAULT_MAX_CHAIN_LEN`].
+pub fn max_chain_len_from_env() -> usize {
+    std::env::var(MAX_CHAIN_LEN_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_MAX_CHAIN_LEN)
+}
 
 impl UnifiedReceipt {
     /// Create a new receipt at the start of pipeline processing.
@@ -448,12 +488,28 @@ impl UnifiedReceipt {
 
         let vk = ubl_kms::verifying_key_from_did(self.did.as_str())
             .map_err(|e| ReceiptError::Signature(e.to_string()))?;
+        self.verify_signature_with_key(&vk, mode)
+    }
+
+    /// Verify the receipt signature against an explicit key, e.g. one resolved
+    /// from a keyring by the receipt's `kid`.
+    pub fn verify_signature_with_key(
+        &self,
+        vk: &ubl_kms::Ed25519VerifyingKey,
+        mode: VerifyMode,
+    ) -> Result<VerifyReport, ReceiptError> {
+        if self.sig.is_empty() {
+            return Err(ReceiptError::Signature(
+                "receipt signature is empty".to_string(),
+            ));
+        }
+
         let payload = self.signature_payload_value()?;
         let domain = receipt_sign_domain();
 
-        let v1_valid = ubl_canon::verify_domain_v1(&payload, &domain, &vk, &self.sig)
+        let v1_valid = ubl_canon::verify_domain_v1(&payload, &domain, vk, &self.sig)
             .map_err(|e| ReceiptError::Signature(e.to_string()))?;
-        let v2_valid = ubl_canon::verify_domain_v2_hash_first(&payload, &domain, &vk, &self.sig)
+        let v2_valid = ubl_canon::verify_domain_v2_hash_first(&payload, &domain, vk, &self.sig)
             .map_err(|e| ReceiptError::Signature(e.to_string()))?;
 
         let valid = match mode {
@@ -472,15 +528,31 @@ impl UnifiedReceipt {
     /// Mark the receipt as denied.
     pub fn deny(&mut self, reason: &str) {
         self.decision = Decision::Deny;
+        self.set_effect("deny_reason", serde_json::Value::String(reason.to_string()));
+    }
+
+    /// Mark as quarantined and record the reason in effects.
+    pub fn quarantine(&mut self, reason: &str) {
+        self.decision = Decision::Quarantine;
+        self.set_effect(
+            "quarantine_reason",
+            serde_json::Value::String(reason.to_string()),
+        );
+    }
+
+    /// Record a side-effect field (e.g. `policy_set_hash`).
+    pub fn set_effect(&mut self, key: &str, value: serde_json::Value) {
         if let Some(obj) = self.effects.as_object_mut() {
-            obj.insert(
-                "deny_reason".to_string(),
-                serde_json::Value::String(reason.to_string()),
-            );
+            obj.insert(key.to_string(), value);
+        }
+        // Before the first stage there is no CID yet: the first stage must
+        // still chain from "genesis".
+        if self.stages.is_empty() {
+            return;
         }
         // Decision/effects are mutable across the pipeline. Rebuild prior stage
         // auth tokens against the new state so the chain remains internally
-        // consistent before appending the WF stage.
+        // consistent before appending the next stage.
         self.rebuild_auth_chain_with_current_key();
         let _ = self.recompute_cid();
         self.id = self.receipt_cid.as_str().to_string();
@@ -519,6 +591,12 @@ impl UnifiedReceipt {
         self.stages = rebuilt;
     }
 
+    /// True when `receipt_cid` matches a fresh recomputation over the body.
+    pub fn cid_matches(&self) -> bool {
+        let mut shadow = self.clone();
+        shadow.recompute_cid().is_ok() && shadow.receipt_cid == self.receipt_cid
+    }
+
     /// Get the current stage count.
     pub fn stage_count(&self) -> usize {
         self.stages.len()
@@ -534,8 +612,23 @@ impl UnifiedReceipt {
         self.stages.last().map(|s| s.auth_token.as_str())
     }
 
-    /// Verify the auth chain is intact.
+    /// Reject receipts with more than `max` stages before replaying them.
+    pub fn check_chain_len(&self, max: usize) -> Result<(), ReceiptError> {
+        if self.stages.len() > max {
+            return Err(ReceiptError::ChainTooLong {
+                len: self.stages.len(),
+                max,
+            });
+        }
+        Ok(())
+    }
+
+    /// Verify the auth chain is intact. Chains longer than
+    /// [`max_chain_len_from_env`] are not replayed and fail.
     pub fn verify_auth_chain(&self) -> bool {
+        if self.check_chain_len(max_chain_len_from_env()).is_err() {
+            return false;
+        }
         let current_key = match load_required_stage_secret_key() {
             Ok(k) => k,
             Err(_) => return false,
@@ -549,12 +642,32 @@ impl UnifiedReceipt {
             .unwrap_or(false)
     }
 
+    /// Itemized [`Self::verify_auth_chain`] with the stage secrets from env:
+    /// every stage is replayed, so a report can show each tampered stage.
+    /// The chain length limit is not applied here.
+    pub fn auth_chain_report(&self) -> Result<AuthChainReport, ReceiptError> {
+        let current_key = load_required_stage_secret_key()?;
+        let previous_key = load_optional_stage_secret_key(STAGE_SECRET_PREV_ENV)?;
+        self.auth_chain_report_with_keys(&current_key, previous_key.as_ref())
+    }
+
     fn verify_auth_chain_with_keys(
         &self,
         current_key: &[u8; 32],
         previous_key: Option<&[u8; 32]>,
     ) -> Result<bool, ReceiptError> {
+        Ok(self
+            .auth_chain_report_with_keys(current_key, previous_key)?
+            .verified())
+    }
+
+    fn auth_chain_report_with_keys(
+        &self,
+        current_key: &[u8; 32],
+        previous_key: Option<&[u8; 32]>,
+    ) -> Result<AuthChainReport, ReceiptError> {
         // Replay the receipt CID evolution stage by stage and verify token at each step.
+        let mut checks = Vec::with_capacity(self.stages.len());
         let mut shadow = self.clone();
         shadow.stages.clear();
         shadow.receipt_cid = TypedCid::new_unchecked("");
@@ -572,18 +685,29 @@ impl UnifiedReceipt {
             let expected_previous = previous_key
                 .map(|k| compute_auth_token_with_key(prev_cid, stage.stage.as_str(), k));
 
-            let token_matches = stage.auth_token == expected_current
-                || expected_previous.as_deref() == Some(stage.auth_token.as_str());
-            if !token_matches {
-                return Ok(false);
-            }
+            let matched = if stage.auth_token == expected_current {
+                Some("current")
+            } else if expected_previous.as_deref() == Some(stage.auth_token.as_str()) {
+                Some("previous")
+            } else {
+                None
+            };
+            checks.push(StageAuthCheck {
+                stage: stage.stage,
+                prev_cid: prev_cid.to_string(),
+                matched,
+            });
 
             shadow.stages.push(stage.clone());
             shadow.recompute_cid()?;
             shadow.id = shadow.receipt_cid.as_str().to_string();
         }
 
-        Ok(shadow.receipt_cid == self.receipt_cid)
+        Ok(AuthChainReport {
+            stages: checks,
+            replayed_cid: shadow.receipt_cid.as_str().to_string(),
+            cid_matches: shadow.receipt_cid == self.receipt_cid,
+        })
     }
 
     /// Serialize to Universal Envelope JSON.
@@ -666,6 +790,7 @@ pub enum ReceiptError {
     InvalidStageOrder(String),
     AuthChainBroken(String),
     Signature(String),
+    ChainTooLong { len: usize, max: usize },
 }
 
 impl std::fmt::Display for ReceiptError {
@@ -675,6 +800,9 @@ impl std::fmt::Display for ReceiptError {
             Self::InvalidStageOrder(s) => write!(f, "Invalid stage order: {}", s),
             Self::AuthChainBroken(s) => write!(f, "Auth chain broken: {}", s),
             Self::Signature(s) => write!(f, "Signature error: {}", s),
+            Self::ChainTooLong { len, max } => {
+                write!(f, "Stage chain too long: {} stages (max {})", len, max)
+            }
         }
     }
 }
@@ -821,6 +949,44 @@ mod tests {
         assert!(!r.verify_auth_chain());
     }
 
+    #[test]
+    fn auth_chain_report_flags_each_tampered_stage() {
+        let mut r = make_receipt();
+        r.append_stage(make_stage(PipelineStage::WriteAhead, "b3:wa"))
+            .unwrap();
+        r.append_stage(make_stage(PipelineStage::Check, "b3:check"))
+            .unwrap();
+        let key = key_from_secret_str(&format!("hex:{}", TEST_STAGE_SECRET_HEX)).unwrap();
+        let report = r.auth_chain_report_with_keys(&key, None).unwrap();
+        assert!(report.verified());
+        assert_eq!(report.stages[0].prev_cid, "genesis");
+        assert_eq!(report.stages[1].matched, Some("current"));
+
+        r.stages[1].auth_token = "hmac:deadbeefdeadbeefdeadbeefdeadbeef".to_string();
+        let report = r.auth_chain_report_with_keys(&key, None).unwrap();
+        assert!(!report.verified());
+        assert_eq!(report.stages[0].matched, Some("current"));
+        assert_eq!(report.stages[1].matched, None);
+    }
+
+    #[test]
+    fn verify_auth_chain_rejects_over_long_chain() {
+        let mut r = make_receipt();
+        for i in 0..=DEFAULT_MAX_CHAIN_LEN {
+            r.append_stage(make_stage(PipelineStage::Check, &format!("b3:in-{}", i)))
+                .unwrap();
+        }
+        let key = key_from_secret_str(&format!("hex:{}", TEST_STAGE_SECRET_HEX)).unwrap();
+        assert!(r.verify_auth_chain_with_keys(&key, None).unwrap());
+
+        assert!(matches!(
+            r.check_chain_len(DEFAULT_MAX_CHAIN_LEN),
+            Err(ReceiptError::ChainTooLong { len, max })
+                if len == DEFAULT_MAX_CHAIN_LEN + 1 && max == DEFAULT_MAX_CHAIN_LEN
+        ));
+        assert!(!r.verify_auth_chain());
+    }
+
     #[test]
     fn verify_auth_chain_accepts_previous_secret_after_rotation() {
         // Build receipt with the test key.
@@ -8,6 +8,8 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
 
 /// An advisory chip — the output of an LLM action.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -154,6 +156,94 @@ impl std::fmt::Display for AdvisoryError {
 
 impl std::error::Error for AdvisoryError {}
 
+pub const DENY_SPIKE_DEFAULT_WINDOW: usize = 50;
+pub const DENY_SPIKE_DEFAULT_BASELINE: usize = 500;
+pub const DENY_SPIKE_DEFAULT_THRESHOLD_PCT: u32 = 200;
+pub const DENY_SPIKE_DEFAULT_MIN_RATE_PCT: u32 = 20;
+const DENY_SPIKE_TOP_CODES: usize = 5;
+
+/// When a run of denies counts as a spike.
+///
+/// The engine keeps the last `baseline + window` WF decisions. A spike is
+/// the deny rate of the newest `window` receipts exceeding the rate of the
+/// `baseline` receipts before them by `threshold_pct` percent of it (200 =
+/// twice the baseline) while being at least `min_rate_pct`. Nothing fires
+/// until the baseline is full, and after a spike the next one needs a whole
+/// fresh window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenySpikePolicy {
+    pub window: usize,
+    pub baseline: usize,
+    pub threshold_pct: u32,
+    pub min_rate_pct: u32,
+}
+
+impl Default for DenySpikePolicy {
+    fn default() -> Self {
+        Self {
+            window: DENY_SPIKE_DEFAULT_WINDOW,
+            baseline: DENY_SPIKE_DEFAULT_BASELINE,
+            threshold_pct: DENY_SPIKE_DEFAULT_THRESHOLD_PCT,
+            min_rate_pct: DENY_SPIKE_DEFAULT_MIN_RATE_PCT,
+        }
+    }
+}
+
+impl DenySpikePolicy {
+    /// `UBL_DENY_SPIKE_WINDOW`, `UBL_DENY_SPIKE_BASELINE`,
+    /// `UBL_DENY_SPIKE_THRESHOLD_PCT` and `UBL_DENY_SPIKE_MIN_RATE_PCT`.
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::parse(
+            var("UBL_DENY_SPIKE_WINDOW").as_deref(),
+            var("UBL_DENY_SPIKE_BASELINE").as_deref(),
+            var("UBL_DENY_SPIKE_THRESHOLD_PCT").as_deref(),
+            var("UBL_DENY_SPIKE_MIN_RATE_PCT").as_deref(),
+        )
+    }
+
+    /// Missing, unparsable or zero values fall back to the defaults; the
+    /// minimum rate is capped at 100.
+    pub fn parse(
+        window: Option<&str>,
+        baseline: Option<&str>,
+        threshold_pct: Option<&str>,
+        min_rate_pct: Option<&str>,
+    ) -> Self {
+        fn positive<T: std::str::FromStr + PartialOrd + Default>(v: Option<&str>) -> Option<T> {
+            v.and_then(|v| v.trim().parse::<T>().ok())
+                .filter(|n| *n > T::default())
+        }
+        Self {
+            window: positive(window).unwrap_or(DENY_SPIKE_DEFAULT_WINDOW),
+            baseline: positive(baseline).unwrap_or(DENY_SPIKE_DEFAULT_BASELINE),
+            threshold_pct: positive(threshold_pct).unwrap_or(DENY_SPIKE_DEFAULT_THRESHOLD_PCT),
+            min_rate_pct: positive(min_rate_pct)
+                .unwrap_or(DENY_SPIKE_DEFAULT_MIN_RATE_PCT)
+                .min(100),
+        }
+    }
+}
+
+/// Rolling WF decisions: `Some(code)` for a deny, `None` otherwise.
+#[derive(Debug, Default)]
+struct DenySpikeWindow {
+    samples: VecDeque<Option<String>>,
+    /// Receipts still to see before another spike may fire.
+    cooldown: usize,
+}
+
+/// Deny rate in basis points (NRF-1 has no floats).
+fn deny_rate_bps<'a>(samples: impl ExactSizeIterator<Item = &'a Option<String>>) -> i64 {
+    let total = samples.len() as i64;
+    let denies = samples.filter(|s| s.is_some()).count() as i64;
+    if total == 0 {
+        0
+    } else {
+        denies * 10_000 / total
+    }
+}
+
 /// The AdvisoryEngine produces advisory chips from pipeline events.
 /// It holds a reference to the active AI Passport and emits advisories
 /// as non-blocking background tasks.
@@ -166,6 +256,8 @@ pub struct AdvisoryEngine {
     pub world: String,
     /// Counter for generating advisory IDs
     counter: std::sync::atomic::AtomicU64,
+    deny_spike_policy: DenySpikePolicy,
+    deny_spike: Mutex<DenySpikeWindow>,
 }
 
 impl AdvisoryEngine {
@@ -176,9 +268,17 @@ impl AdvisoryEngine {
             model,
             world,
             counter: std::sync::atomic::AtomicU64::new(0),
+            deny_spike_policy: DenySpikePolicy::default(),
+            deny_spike: Mutex::new(DenySpikeWindow::default()),
         }
     }
 
+    /// Replace the deny-spike policy (defaults to [`DenySpikePolicy::default`]).
+    pub fn with_deny_spike_policy(mut self, policy: DenySpikePolicy) -> Self {
+        self.deny_spike_policy = policy;
+        self
+    }
+
     /// Generate a unique advisory ID.
     fn next_id(&self) -> String {
         let n = self
@@ -250,10 +350,119 @@ impl AdvisoryEngine {
         )
     }
 
+    /// Feed one WF decision into the deny-spike window.
+    ///
+    /// `deny_code` names why a deny was denied and is ignored for other
+    /// decisions. Returns a `deny_spike` advisory, pinned to this receipt,
+    /// when the window crosses the policy threshold.
+    pub fn observe_receipt(
+        &self,
+        receipt_cid: &str,
+        decision: &str,
+        deny_code: Option<&str>,
+    ) -> Option<Advisory> {
+        let policy = &self.deny_spike_policy;
+        let mut state = self.deny_spike.lock().unwrap_or_else(|e| e.into_inner());
+        let sample = decision
+            .eq_ignore_ascii_case("deny")
+            .then(|| deny_code.unwrap_or("UNKNOWN").to_string());
+        state.samples.push_back(sample);
+        while state.samples.len() > policy.baseline + policy.window {
+            state.samples.pop_front();
+        }
+        state.cooldown = state.cooldown.saturating_sub(1);
+        if state.cooldown > 0 || state.samples.len() < policy.baseline + policy.window {
+            return None;
+        }
+
+        let baseline_bps = deny_rate_bps(state.samples.iter().take(policy.baseline));
+        let observed_bps = deny_rate_bps(state.samples.iter().skip(policy.baseline));
+        let spiking = observed_bps * 100 > baseline_bps * i64::from(policy.threshold_pct)
+            && observed_bps >= i64::from(policy.min_rate_pct) * 100;
+        if !spiking {
+            return None;
+        }
+        state.cooldown = policy.window;
+
+        let mut codes: BTreeMap<&str, i64> = BTreeMap::new();
+        for code in state.samples.iter().skip(policy.baseline).flatten() {
+            *codes.entry(code.as_str()).or_default() += 1;
+        }
+        let window_denies: i64 = codes.values().sum();
+        let mut top: Vec<(&str, i64)> = codes.into_iter().collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        top.truncate(DENY_SPIKE_TOP_CODES);
+        let top_deny_codes: Vec<Value> = top
+            .iter()
+            .map(|(code, count)| json!({"code": code, "count": count}))
+            .collect();
+
+        let output = json!({
+            "observed_rate_bps": observed_bps,
+            "baseline_rate_bps": baseline_bps,
+            "window": policy.window,
+            "baseline_window": policy.baseline,
+            "threshold_pct": policy.threshold_pct,
+            "window_denies": window_denies,
+            "top_deny_codes": top_deny_codes,
+            "narration": format!(
+                "{} of the last {} receipts were denied ({}.{:02}% vs a {}.{:02}% baseline); top code {}",
+                window_denies,
+                policy.window,
+                observed_bps / 100,
+                observed_bps % 100,
+                baseline_bps / 100,
+                baseline_bps % 100,
+                top.first().map(|(code, _)| *code).unwrap_or("UNKNOWN"),
+            ),
+        });
+
+        Some(Advisory::new(
+            self.passport_cid.clone(),
+            "deny_spike".to_string(),
+            receipt_cid.to_string(),
+            output,
+            80,
+            self.model.clone(),
+            AdvisoryHook::PostWf,
+        ))
+    }
+
     /// Convert an advisory into a chip body ready for pipeline submission.
     pub fn advisory_to_chip_body(&self, advisory: &Advisory) -> Value {
         advisory.to_chip_body(&self.next_id(), &self.world)
     }
+
+    /// Chip body for a persisted narration advisory.
+    ///
+    /// Unlike [`Self::advisory_to_chip_body`], the body is a pure function of
+    /// `(input_cid, model, summary)`: the `@id` and the narration's `nonce`
+    /// are derived from those inputs and the wall-clock `generated_at` is
+    /// dropped, so re-persisting the same narration yields the same CID.
+    pub fn narration_to_chip_body(&self, advisory: &Advisory) -> Value {
+        let summary = advisory
+            .output
+            .get("summary")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let nonce = narration_nonce(&advisory.input_cid, &advisory.model, summary);
+        let mut stable = advisory.clone();
+        if let Some(output) = stable.output.as_object_mut() {
+            output.remove("generated_at");
+            output.insert("nonce".to_string(), json!(nonce));
+        }
+        stable.to_chip_body(&format!("advisory-narrate-{}", nonce), &self.world)
+    }
+}
+
+/// Deterministic nonce for a narration of `receipt_cid` by `model`.
+pub fn narration_nonce(receipt_cid: &str, model: &str, summary: &str) -> String {
+    let mut hasher = blake3::Hasher::new();
+    for part in [receipt_cid, model, summary] {
+        hasher.update(part.as_bytes());
+        hasher.update(&[0]);
+    }
+    hex::encode(&hasher.finalize().as_bytes()[..16])
 }
 
 /// Simple chip type classifier (will be replaced by real LLM in production).
@@ -316,6 +525,34 @@ mod tests {
         assert_eq!(parsed.confidence, 95);
     }
 
+    #[test]
+    fn narration_chip_body_is_deterministic() {
+        let engine = AdvisoryEngine::new(
+            "b3:passport".into(),
+            "claude-sonnet-4".into(),
+            "a/acme/t/prod".into(),
+        );
+        let narration = |generated_at: &str| {
+            Advisory::new(
+                "b3:passport".into(),
+                "narrate".into(),
+                "b3:receipt".into(),
+                json!({"summary": "ok", "generated_at": generated_at}),
+                90,
+                "claude-sonnet-4".into(),
+                AdvisoryHook::OnDemand,
+            )
+        };
+        let first = engine.narration_to_chip_body(&narration("2026-01-01T00:00:00Z"));
+        let second = engine.narration_to_chip_body(&narration("2026-01-01T00:00:05Z"));
+        assert_eq!(first, second);
+        assert!(first["output"].get("generated_at").is_none());
+        assert_eq!(
+            first["output"]["nonce"],
+            narration_nonce("b3:receipt", "claude-sonnet-4", "ok")
+        );
+    }
+
     #[test]
     fn advisory_missing_passport_cid_fails() {
         let body = json!({"action": "classify", "input_cid": "b3:x"});
@@ -360,6 +597,65 @@ mod tests {
         assert_ne!(a1, a2);
     }
 
+    #[test]
+    fn deny_spike_fires_once_over_baseline_with_top_codes() {
+        let engine = AdvisoryEngine::new("b3:p".into(), "m".into(), "a/x/t/y".into())
+            .with_deny_spike_policy(DenySpikePolicy {
+                window: 4,
+                baseline: 8,
+                threshold_pct: 200,
+                min_rate_pct: 50,
+            });
+        // Baseline: 1 deny in 8 (12.5%).
+        for i in 0..8 {
+            let decision = if i == 0 { "deny" } else { "allow" };
+            assert!(engine
+                .observe_receipt("b3:r", decision, Some("OLD"))
+                .is_none());
+        }
+        assert!(engine
+            .observe_receipt("b3:r", "deny", Some("CAP"))
+            .is_none());
+        assert!(engine.observe_receipt("b3:r", "allow", None).is_none());
+        assert!(engine
+            .observe_receipt("b3:r", "deny", Some("TYPE"))
+            .is_none());
+        let adv = engine
+            .observe_receipt("b3:last", "DENY", Some("CAP"))
+            .expect("3 of 4 denied over a 12.5% baseline");
+        assert_eq!(adv.action, "deny_spike");
+        assert_eq!(adv.input_cid, "b3:last");
+        assert_eq!(adv.output["observed_rate_bps"], 7_500);
+        assert_eq!(adv.output["baseline_rate_bps"], 1_250);
+        assert_eq!(adv.output["window"], 4);
+        assert_eq!(
+            adv.output["top_deny_codes"],
+            json!([{"code": "CAP", "count": 2}, {"code": "TYPE", "count": 1}])
+        );
+        // Cooldown: still spiking, but a fresh window must pass first.
+        for _ in 0..3 {
+            assert!(engine
+                .observe_receipt("b3:r", "deny", Some("CAP"))
+                .is_none());
+        }
+        assert!(engine
+            .observe_receipt("b3:r", "deny", Some("CAP"))
+            .is_some());
+    }
+
+    #[test]
+    fn deny_spike_policy_parse_falls_back_on_bad_values() {
+        let p = DenySpikePolicy::parse(Some("10"), Some("x"), Some("0"), Some("150"));
+        assert_eq!(p.window, 10);
+        assert_eq!(p.baseline, DENY_SPIKE_DEFAULT_BASELINE);
+        assert_eq!(p.threshold_pct, DENY_SPIKE_DEFAULT_THRESHOLD_PCT);
+        assert_eq!(p.min_rate_pct, 100);
+        assert_eq!(
+            DenySpikePolicy::parse(None, None, None, None),
+            DenySpikePolicy::default()
+        );
+    }
+
     #[test]
     fn classify_chip_type_works() {
         assert_eq!(classify_chip_type("ubl/user"), "identity");
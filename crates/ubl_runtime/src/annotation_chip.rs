@@ -0,0 +1,122 @@
+//! `ubl/annotation` — mutable operational metadata for an immutable chip.
+//!
+//! An annotation names its `target_cid` and carries string `notes` (review
+//! notes, ticket links). The target's CID is untouched: annotations are
+//! separate, append-only chips found through the `target_cid:` tag index.
+//! CHECK requires the target to exist and live in the annotation's `@world`,
+//! so the target's world write access governs who may annotate it.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub const TYPE_ANNOTATION: &str = "ubl/annotation";
+/// Most notes one annotation chip may carry.
+pub const MAX_ANNOTATION_NOTES: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AnnotationError {
+    #[error("ubl/annotation target_cid must be a b3: CID")]
+    InvalidTarget,
+    #[error("ubl/annotation notes must be an object of 1..=32 string values")]
+    InvalidNotes,
+    #[error("ubl/annotation note '{0}' has an empty key or a non-string value")]
+    InvalidNote(String),
+    #[error("ubl/annotation target '{cid}' is in world '{world}', expected '{expected}'")]
+    WorldMismatch {
+        cid: String,
+        world: String,
+        expected: String,
+    },
+}
+
+/// Parsed body of a `ubl/annotation` chip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotationChip {
+    pub target_cid: String,
+    pub notes: BTreeMap<String, String>,
+}
+
+impl AnnotationChip {
+    pub fn parse(body: &Value) -> Result<Self, AnnotationError> {
+        let target_cid = body
+            .get("target_cid")
+            .and_then(|v| v.as_str())
+            .filter(|s| s.starts_with("b3:"))
+            .ok_or(AnnotationError::InvalidTarget)?
+            .to_string();
+        let raw = body
+            .get("notes")
+            .and_then(|v| v.as_object())
+            .filter(|o| !o.is_empty() && o.len() <= MAX_ANNOTATION_NOTES)
+            .ok_or(AnnotationError::InvalidNotes)?;
+        let mut notes = BTreeMap::new();
+        for (key, value) in raw {
+            match value.as_str() {
+                Some(text) if !key.trim().is_empty() => {
+                    notes.insert(key.clone(), text.to_string());
+                }
+                _ => return Err(AnnotationError::InvalidNote(key.clone())),
+            }
+        }
+        Ok(Self { target_cid, notes })
+    }
+
+    /// The target must share the annotation's `world`.
+    pub fn check_target_world(
+        &self,
+        world: &str,
+        target_world: &str,
+    ) -> Result<(), AnnotationError> {
+        if target_world != world {
+            return Err(AnnotationError::WorldMismatch {
+                cid: self.target_cid.clone(),
+                world: target_world.to_string(),
+                expected: world.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_requires_target_and_string_notes() {
+        let ok = AnnotationChip::parse(&json!({
+            "target_cid": "b3:doc",
+            "notes": {"ticket": "OPS-12", "review": "approved"},
+        }))
+        .unwrap();
+        assert_eq!(ok.target_cid, "b3:doc");
+        assert_eq!(ok.notes["ticket"], "OPS-12");
+
+        assert_eq!(
+            AnnotationChip::parse(&json!({"target_cid": "doc", "notes": {"a": "b"}})),
+            Err(AnnotationError::InvalidTarget)
+        );
+        assert_eq!(
+            AnnotationChip::parse(&json!({"target_cid": "b3:doc", "notes": {}})),
+            Err(AnnotationError::InvalidNotes)
+        );
+        assert_eq!(
+            AnnotationChip::parse(&json!({"target_cid": "b3:doc", "notes": {"n": 7}})),
+            Err(AnnotationError::InvalidNote("n".into()))
+        );
+    }
+
+    #[test]
+    fn target_world_must_match() {
+        let chip =
+            AnnotationChip::parse(&json!({"target_cid": "b3:doc", "notes": {"a": "b"}})).unwrap();
+        assert!(chip
+            .check_target_world("a/acme/t/prod", "a/acme/t/prod")
+            .is_ok());
+        assert!(matches!(
+            chip.check_target_world("a/acme/t/prod", "a/other/t/prod"),
+            Err(AnnotationError::WorldMismatch { .. })
+        ));
+    }
+}
@@ -16,9 +16,13 @@ fn all_error_codes() -> Vec<ErrorCode> {
         ErrorCode::KnockMalformedNum,
         ErrorCode::KnockNumericLiteralNotAllowed,
         ErrorCode::KnockInputNormalization,
+        ErrorCode::KnockSchemaValidation,
+        ErrorCode::KnockSignatureRequired,
+        ErrorCode::KnockSignatureInvalid,
         ErrorCode::PolicyDenied,
         ErrorCode::InvalidChip,
         ErrorCode::DependencyMissing,
+        ErrorCode::RuntimeTooOld,
         ErrorCode::WasmAbiMissingVersion,
         ErrorCode::WasmAbiUnsupportedVersion,
         ErrorCode::WasmAbiInvalidPayload,
@@ -45,8 +49,10 @@ fn all_error_codes() -> Vec<ErrorCode> {
         ErrorCode::IdempotencyConflict,
         ErrorCode::DurableCommitFailed,
         ErrorCode::TamperDetected,
+        ErrorCode::ChainTooLong,
         ErrorCode::InternalError,
         ErrorCode::Unauthorized,
+        ErrorCode::TokenRevoked,
         ErrorCode::NotFound,
         ErrorCode::TooManyRequests,
         ErrorCode::Unavailable,
@@ -68,9 +74,13 @@ fn assert_exhaustive(code: ErrorCode) {
         | ErrorCode::KnockMalformedNum
         | ErrorCode::KnockNumericLiteralNotAllowed
         | ErrorCode::KnockInputNormalization
+        | ErrorCode::KnockSchemaValidation
+        | ErrorCode::KnockSignatureRequired
+        | ErrorCode::KnockSignatureInvalid
         | ErrorCode::PolicyDenied
         | ErrorCode::InvalidChip
         | ErrorCode::DependencyMissing
+        | ErrorCode::RuntimeTooOld
         | ErrorCode::WasmAbiMissingVersion
         | ErrorCode::WasmAbiUnsupportedVersion
         | ErrorCode::WasmAbiInvalidPayload
@@ -97,8 +107,10 @@ fn assert_exhaustive(code: ErrorCode) {
         | ErrorCode::IdempotencyConflict
         | ErrorCode::DurableCommitFailed
         | ErrorCode::TamperDetected
+        | ErrorCode::ChainTooLong
         | ErrorCode::InternalError
         | ErrorCode::Unauthorized
+        | ErrorCode::TokenRevoked
         | ErrorCode::NotFound
         | ErrorCode::TooManyRequests
         | ErrorCode::Unavailable => {}
@@ -19,6 +19,11 @@
 //!   }
 //! }
 //! ```
+//!
+//! A cap naming a `delegate` DID lets that delegate issue narrower sub-caps;
+//! a sub-cap carries its issuer's grant under `parent` and is checked link
+//! by link with [`validate_cap_chain`], so the root key never leaves its
+//! owner.
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -40,6 +45,12 @@ pub struct Capability {
     /// Ed25519 signature over the canonical capability fields.
     #[serde(default)]
     pub signature: String,
+    /// DID allowed to issue sub-capabilities of this one. Empty = not delegable.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub delegate: String,
+    /// The capability this one was delegated from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Box<Capability>>,
 }
 
 /// Errors from capability validation.
@@ -55,8 +66,12 @@ pub enum CapError {
     WrongAudience { required: String, got: String },
     /// Capability has expired.
     Expired { expires_at: String, now: String },
+    /// Capability is dated in the future.
+    NotYetValid { issued_at: String, now: String },
     /// Signature is missing or invalid.
     InvalidSignature(String),
+    /// A `parent` link does not authorize its child.
+    InvalidDelegation(String),
 }
 
 impl std::fmt::Display for CapError {
@@ -77,7 +92,15 @@ impl std::fmt::Display for CapError {
             Self::Expired { expires_at, now } => {
                 write!(f, "capability expired at {} (now: {})", expires_at, now)
             }
+            Self::NotYetValid { issued_at, now } => {
+                write!(
+                    f,
+                    "capability not valid before {} (now: {})",
+                    issued_at, now
+                )
+            }
             Self::InvalidSignature(msg) => write!(f, "invalid capability signature: {}", msg),
+            Self::InvalidDelegation(msg) => write!(f, "invalid capability delegation: {}", msg),
         }
     }
 }
@@ -113,15 +136,46 @@ pub fn extract_cap(body: &Value) -> Result<Capability, CapError> {
         .map_err(|e| CapError::Malformed(e.to_string()))
 }
 
+/// Default tolerance, in seconds, for clocks that disagree with the issuer's.
+pub const DEFAULT_CAP_CLOCK_SKEW_SECS: i64 = 60;
+
+/// Clock-skew tolerance from `UBL_CAP_CLOCK_SKEW_SECS`; unset, unparsable
+/// or negative values fall back to [`DEFAULT_CAP_CLOCK_SKEW_SECS`].
+pub fn cap_clock_skew() -> chrono::Duration {
+    let secs = std::env::var("UBL_CAP_CLOCK_SKEW_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|s| *s >= 0)
+        .unwrap_or(DEFAULT_CAP_CLOCK_SKEW_SECS);
+    chrono::Duration::seconds(secs)
+}
+
 /// Validate a capability against requirements.
 ///
 /// Checks:
 /// 1. Action matches the required action.
 /// 2. Audience matches the chip's @world scope boundary.
 /// 3. issued_at/expires_at (if present) are valid RFC-3339.
-/// 4. Not expired (if expires_at is set).
+/// 4. Inside its validity window, within [`cap_clock_skew`].
 /// 5. Signature is valid over canonical capability payload.
 pub fn validate_cap(cap: &Capability, required_action: &str, world: &str) -> Result<(), CapError> {
+    validate_cap_at(
+        cap,
+        required_action,
+        world,
+        chrono::Utc::now(),
+        cap_clock_skew(),
+    )
+}
+
+/// [`validate_cap`] against an explicit clock and skew tolerance.
+pub fn validate_cap_at(
+    cap: &Capability,
+    required_action: &str,
+    world: &str,
+    now: chrono::DateTime<chrono::Utc>,
+    skew: chrono::Duration,
+) -> Result<(), CapError> {
     // 1. Action check
     if cap.action != required_action {
         return Err(CapError::WrongAction {
@@ -139,14 +193,24 @@ pub fn validate_cap(cap: &Capability, required_action: &str, world: &str) -> Res
         });
     }
 
-    // 3. Timestamp parse/validation
+    // 3–4. Timestamps and validity window
+    check_cap_window(cap, now, skew)?;
+
+    // 5. Signature verification
+    verify_cap_signature(cap)
+}
+
+/// Check `issued_at - skew <= now <= expires_at + skew`. An empty
+/// `expires_at` never expires.
+pub fn check_cap_window(
+    cap: &Capability,
+    now: chrono::DateTime<chrono::Utc>,
+    skew: chrono::Duration,
+) -> Result<(), CapError> {
     let issued_at = chrono::DateTime::parse_from_rfc3339(&cap.issued_at)
         .map_err(|e| CapError::Malformed(format!("issued_at must be RFC-3339: {}", e)))?
         .with_timezone(&chrono::Utc);
 
-    let now = chrono::Utc::now();
-
-    // 4. Expiration check
     if !cap.expires_at.is_empty() {
         let expires_at = chrono::DateTime::parse_from_rfc3339(&cap.expires_at)
             .map_err(|e| CapError::Malformed(format!("expires_at must be RFC-3339: {}", e)))?
@@ -158,7 +222,7 @@ pub fn validate_cap(cap: &Capability, required_action: &str, world: &str) -> Res
             ));
         }
 
-        if expires_at <= now {
+        if now > expires_at + skew {
             return Err(CapError::Expired {
                 expires_at: cap.expires_at.clone(),
                 now: now.to_rfc3339(),
@@ -166,7 +230,18 @@ pub fn validate_cap(cap: &Capability, required_action: &str, world: &str) -> Res
         }
     }
 
-    // 5. Signature verification
+    if now + skew < issued_at {
+        return Err(CapError::NotYetValid {
+            issued_at: cap.issued_at.clone(),
+            now: now.to_rfc3339(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Verify `cap.signature` against the key behind `cap.issued_by`.
+pub fn verify_cap_signature(cap: &Capability) -> Result<(), CapError> {
     if cap.signature.is_empty() {
         return Err(CapError::InvalidSignature("signature is empty".to_string()));
     }
@@ -204,14 +279,95 @@ fn audience_matches_world(audience: &str, world: &str) -> bool {
     false
 }
 
-fn cap_signing_payload(cap: &Capability) -> Value {
-    json!({
+/// Longest delegation chain [`validate_cap_chain`] walks, root included.
+pub const MAX_CAP_CHAIN_DEPTH: usize = 8;
+
+/// Validate a capability and every ancestor in its `parent` chain.
+///
+/// The leaf gets the full [`validate_cap`] checks. Each parent must be
+/// inside its own validity window with a good signature, name the child's
+/// issuer as `delegate`, and cover the child: the same action (or a
+/// `prefix*` matching it) over an audience containing the child's.
+pub fn validate_cap_chain(
+    cap: &Capability,
+    required_action: &str,
+    world: &str,
+) -> Result<(), CapError> {
+    validate_cap_chain_at(
+        cap,
+        required_action,
+        world,
+        chrono::Utc::now(),
+        cap_clock_skew(),
+    )
+}
+
+/// [`validate_cap_chain`] against an explicit clock and skew tolerance.
+pub fn validate_cap_chain_at(
+    cap: &Capability,
+    required_action: &str,
+    world: &str,
+    now: chrono::DateTime<chrono::Utc>,
+    skew: chrono::Duration,
+) -> Result<(), CapError> {
+    validate_cap_at(cap, required_action, world, now, skew)?;
+
+    let mut child = cap;
+    let mut depth = 1;
+    while let Some(parent) = child.parent.as_deref() {
+        depth += 1;
+        if depth > MAX_CAP_CHAIN_DEPTH {
+            return Err(CapError::InvalidDelegation(format!(
+                "chain is longer than {} capabilities",
+                MAX_CAP_CHAIN_DEPTH
+            )));
+        }
+        check_cap_window(parent, now, skew)?;
+        verify_cap_signature(parent)?;
+        if parent.delegate.is_empty() || parent.delegate != child.issued_by {
+            return Err(CapError::InvalidDelegation(format!(
+                "parent issued by '{}' does not delegate to '{}'",
+                parent.issued_by, child.issued_by
+            )));
+        }
+        if !action_covers(&parent.action, &child.action) {
+            return Err(CapError::InvalidDelegation(format!(
+                "action '{}' is not within parent action '{}'",
+                child.action, parent.action
+            )));
+        }
+        if !audience_matches_world(&parent.audience, &child.audience) {
+            return Err(CapError::InvalidDelegation(format!(
+                "audience '{}' is not within parent audience '{}'",
+                child.audience, parent.audience
+            )));
+        }
+        child = parent;
+    }
+    Ok(())
+}
+
+fn action_covers(parent: &str, child: &str) -> bool {
+    match parent.strip_suffix('*') {
+        Some(prefix) => child.starts_with(prefix),
+        None => parent == child,
+    }
+}
+
+/// The fields `signature` covers. `delegate` is signed when set; `parent`
+/// never is, since each link is verified on its own.
+pub fn cap_signing_payload(cap: &Capability) -> Value {
+    let mut payload = json!({
         "action": cap.action,
         "audience": cap.audience,
         "issued_by": cap.issued_by,
         "issued_at": cap.issued_at,
         "expires_at": cap.expires_at,
-    })
+    });
+    if !cap.delegate.is_empty() {
+        payload["delegate"] = json!(cap.delegate);
+    }
+    payload
 }
 
 /// Validate that a chip body carries the required capability.
@@ -222,7 +378,7 @@ pub fn require_cap(
     world: &str,
 ) -> Result<Capability, CapError> {
     let cap = extract_cap(body)?;
-    validate_cap(&cap, required_action, world)?;
+    validate_cap_chain(&cap, required_action, world)?;
     Ok(cap)
 }
 
@@ -360,6 +516,65 @@ mod tests {
         assert!(matches!(err, CapError::Expired { .. }));
     }
 
+    fn at(ts: &str) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    }
+
+    #[test]
+    fn validate_future_dated_cap_is_not_yet_valid() {
+        let body = make_signed_cap(
+            "registry:init",
+            "a/acme",
+            "2030-01-01T00:00:00Z",
+            "2031-01-01T00:00:00Z",
+        );
+        let cap: Capability = serde_json::from_value(body).unwrap();
+        let skew = chrono::Duration::seconds(60);
+        let err = validate_cap_at(
+            &cap,
+            "registry:init",
+            "a/acme",
+            at("2029-12-31T23:58:00Z"),
+            skew,
+        )
+        .unwrap_err();
+        assert!(matches!(err, CapError::NotYetValid { .. }));
+        assert!(err
+            .to_string()
+            .contains("not valid before 2030-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn validate_window_edges_within_skew_are_ok() {
+        let body = make_signed_cap(
+            "registry:init",
+            "a/acme",
+            "2030-01-01T00:00:00Z",
+            "2031-01-01T00:00:00Z",
+        );
+        let cap: Capability = serde_json::from_value(body).unwrap();
+        let skew = chrono::Duration::seconds(60);
+        let validate = |now: &str| validate_cap_at(&cap, "registry:init", "a/acme", at(now), skew);
+        assert!(validate("2029-12-31T23:59:30Z").is_ok());
+        assert!(validate("2031-01-01T00:00:30Z").is_ok());
+        assert!(matches!(
+            validate("2031-01-01T00:01:01Z"),
+            Err(CapError::Expired { .. })
+        ));
+        assert!(matches!(
+            validate_cap_at(
+                &cap,
+                "registry:init",
+                "a/acme",
+                at("2031-01-01T00:00:30Z"),
+                chrono::Duration::zero(),
+            ),
+            Err(CapError::Expired { .. })
+        ));
+    }
+
     #[test]
     fn validate_no_expiry_is_ok() {
         let body = make_signed_cap("registry:init", "a/acme", "2025-01-01T00:00:00Z", "");
@@ -396,6 +611,107 @@ mod tests {
         assert_eq!(cap.action, "registry:init");
     }
 
+    fn draft_cap(action: &str, audience: &str, delegate: &str) -> Capability {
+        Capability {
+            action: action.to_string(),
+            audience: audience.to_string(),
+            issued_by: String::new(),
+            issued_at: "2025-01-01T00:00:00Z".to_string(),
+            expires_at: "2099-12-31T23:59:59Z".to_string(),
+            signature: String::new(),
+            delegate: delegate.to_string(),
+            parent: None,
+        }
+    }
+
+    fn sign_cap(sk: &ubl_kms::Ed25519SigningKey, mut cap: Capability) -> Capability {
+        cap.issued_by = ubl_kms::did_from_verifying_key(&ubl_kms::verifying_key(sk));
+        cap.signature =
+            ubl_kms::sign_canonical(sk, &cap_signing_payload(&cap), ubl_kms::domain::CAPABILITY)
+                .unwrap();
+        cap
+    }
+
+    fn did_of(sk: &ubl_kms::Ed25519SigningKey) -> String {
+        ubl_kms::did_from_verifying_key(&ubl_kms::verifying_key(sk))
+    }
+
+    #[test]
+    fn delegated_sub_cap_validates_through_its_chain() {
+        let root_sk = ubl_kms::generate_signing_key();
+        let delegate_sk = ubl_kms::generate_signing_key();
+        let root = sign_cap(
+            &root_sk,
+            draft_cap("registry:*", "a/acme", &did_of(&delegate_sk)),
+        );
+        let mut sub = draft_cap("registry:init", "a/acme/t/prod", "");
+        sub.parent = Some(Box::new(root));
+        let sub = sign_cap(&delegate_sk, sub);
+
+        assert!(validate_cap_chain(&sub, "registry:init", "a/acme/t/prod").is_ok());
+        let body = json!({
+            "@type": "ubl/app",
+            "@world": "a/acme/t/prod",
+            "@cap": serde_json::to_value(&sub).unwrap(),
+        });
+        let cap = require_cap(&body, "registry:init", "a/acme/t/prod").unwrap();
+        assert_eq!(cap, sub);
+        assert!(matches!(
+            validate_cap_chain(&sub, "registry:init", "a/acme"),
+            Err(CapError::WrongAudience { .. })
+        ));
+    }
+
+    #[test]
+    fn delegation_chain_rejects_unauthorized_links() {
+        let root_sk = ubl_kms::generate_signing_key();
+        let delegate_sk = ubl_kms::generate_signing_key();
+        let delegate = did_of(&delegate_sk);
+        let chain = |root: Capability, sub: Capability| {
+            let mut sub = sub;
+            sub.parent = Some(Box::new(root));
+            let sub = sign_cap(&delegate_sk, sub);
+            validate_cap_chain(&sub, &sub.action, &sub.audience)
+        };
+        let sub = || draft_cap("registry:init", "a/acme/t/prod", "");
+
+        let other = did_of(&ubl_kms::generate_signing_key());
+        let err = chain(
+            sign_cap(&root_sk, draft_cap("registry:init", "a/acme", &other)),
+            sub(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("does not delegate to"));
+
+        let err = chain(
+            sign_cap(&root_sk, draft_cap("membership:grant", "a/acme", &delegate)),
+            sub(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not within parent action"));
+
+        let err = chain(
+            sign_cap(
+                &root_sk,
+                draft_cap("registry:init", "a/acme/t/dev", &delegate),
+            ),
+            sub(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not within parent audience"));
+
+        let mut expired = draft_cap("registry:init", "a/acme", &delegate);
+        expired.issued_at = "2020-01-01T00:00:00Z".to_string();
+        expired.expires_at = "2020-12-31T23:59:59Z".to_string();
+        let err = chain(sign_cap(&root_sk, expired), sub()).unwrap_err();
+        assert!(matches!(err, CapError::Expired { .. }));
+
+        let mut forged = sign_cap(&root_sk, draft_cap("registry:init", "a/acme", &other));
+        forged.delegate = delegate.clone();
+        let err = chain(forged, sub()).unwrap_err();
+        assert!(matches!(err, CapError::InvalidSignature(_)));
+    }
+
     #[test]
     fn require_cap_missing_fails() {
         let body = json!({"@type": "ubl/app", "@world": "a/acme"});
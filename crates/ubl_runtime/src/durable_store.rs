@@ -4,6 +4,7 @@
 //! - Single transaction for `receipts + idempotency + outbox`.
 //! - Persistent idempotency replay across restarts.
 //! - Outbox claim/ack/nack primitives for reliable dispatch.
+//! - Dead-letter state for outbox events that ran out of attempts.
 
 use crate::idempotency::CachedResult;
 use chrono::TimeZone;
@@ -15,6 +16,14 @@ use std::time::Duration;
 
 const DEFAULT_DSN: &str = "file:./data/ubl.db?mode=rwc&_journal_mode=WAL";
 
+/// Seconds an inflight outbox claim is held before it becomes reclaimable.
+/// A worker that crashes between delivery and ack leaves the row inflight;
+/// once the lease lapses the event is redelivered with the same delivery id.
+pub const DEFAULT_OUTBOX_LEASE_SECS: i64 = 60;
+
+/// Delivery attempts before an outbox event is moved to `dead`.
+pub const DEFAULT_OUTBOX_MAX_ATTEMPTS: i64 = 10;
+
 #[derive(Debug, Clone)]
 pub struct DurableStore {
     dsn: String,
@@ -36,6 +45,11 @@ pub struct CommitInput {
     pub rt_hash: String,
     pub decision: String,
     pub idem_key: Option<String>,
+    /// Unix timestamp seconds after which `idem_key` stops replaying.
+    pub idem_expires_at: Option<i64>,
+    /// Client `Idempotency-Key`, recorded next to `idem_key` with the same
+    /// replay window.
+    pub client_idem: Option<ClientIdempotency>,
     pub chain: Vec<String>,
     pub outbox_events: Vec<NewOutboxEvent>,
     /// Unix timestamp seconds.
@@ -44,6 +58,14 @@ pub struct CommitInput {
     pub fail_after_receipt_write: bool,
 }
 
+/// A client-supplied idempotency key and the canonical CID of the request
+/// it was first used with.
+#[derive(Debug, Clone)]
+pub struct ClientIdempotency {
+    pub key: String,
+    pub fingerprint: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct CommitResult {
     pub committed: bool,
@@ -58,12 +80,101 @@ pub struct NewOutboxEvent {
 #[derive(Debug, Clone)]
 pub struct OutboxEvent {
     pub id: i64,
+    /// Stable idempotency token, identical across every redelivery of this
+    /// event. Receivers dedup on it (sent as `X-UBL-Delivery-Id`).
+    pub delivery_id: String,
     pub event_type: String,
     pub payload_json: Value,
     pub attempts: i64,
     pub next_attempt_at: i64,
 }
 
+/// An outbox event that exhausted its attempts, as listed by
+/// [`DurableStore::list_dead_outbox`].
+#[derive(Debug, Clone)]
+pub struct DeadOutboxEvent {
+    pub id: i64,
+    pub delivery_id: String,
+    pub event_type: String,
+    pub payload_json: Value,
+    pub attempts: i64,
+    /// Unix timestamp seconds.
+    pub created_at: i64,
+    /// Handler error from the final attempt.
+    pub last_error: Option<String>,
+}
+
+/// One row of [`DurableStore::list_receipts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptSummary {
+    pub receipt_cid: String,
+    /// `allow`, `deny` or `quarantine`.
+    pub decision: String,
+    pub did: String,
+    /// Unix timestamp seconds.
+    pub created_at: i64,
+    /// `None` for rows written before receipts recorded their world.
+    pub world: Option<String>,
+}
+
+/// SQL-level filters for [`DurableStore::list_receipts`]. Rows written before
+/// the `world` column existed have no world and never match a `world` filter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReceiptFilter {
+    /// `allow`, `deny` or `quarantine`.
+    pub decision: Option<String>,
+    pub world: Option<String>,
+}
+
+/// One row of [`DurableStore::export_receipts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedReceipt {
+    pub receipt_cid: String,
+    /// Unix timestamp seconds.
+    pub created_at: i64,
+    /// The persisted receipt, compact JSON on a single line.
+    pub body_json: String,
+}
+
+/// Position after the last row of a receipt page. List pages are ordered by
+/// `(created_at, receipt_cid)` descending; export pages ascending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptCursor {
+    pub created_at: i64,
+    pub receipt_cid: String,
+}
+
+impl ReceiptCursor {
+    /// Opaque wire form: base64url of `created_at:receipt_cid`.
+    pub fn encode(&self) -> String {
+        use base64::Engine;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(format!("{}:{}", self.created_at, self.receipt_cid))
+    }
+
+    pub fn decode(token: &str) -> Option<Self> {
+        use base64::Engine;
+        let raw = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token.trim())
+            .ok()?;
+        let raw = String::from_utf8(raw).ok()?;
+        let (created_at, receipt_cid) = raw.split_once(':')?;
+        Some(Self {
+            created_at: created_at.parse().ok()?,
+            receipt_cid: receipt_cid.to_string(),
+        })
+        .filter(|c| !c.receipt_cid.is_empty())
+    }
+}
+
+/// Delivery progress of one outbox event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxDeliveryState {
+    /// `pending`, `inflight`, `done` or `dead`.
+    pub status: String,
+    pub attempts: i64,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum DurableError {
     #[error("sqlite: {0}")]
@@ -112,16 +223,19 @@ impl DurableStore {
         let conn = self.open_conn()?;
         self.apply_pragmas(&conn)?;
 
-        let row: Option<(String, String, String, i64)> = conn
+        #[allow(clippy::type_complexity)]
+        let row: Option<(String, String, String, i64, Option<i64>, Option<String>)> = conn
             .query_row(
-                "SELECT receipt_cid, response_json, chain_json, created_at FROM idempotency WHERE idem_key = ?1",
+                "SELECT receipt_cid, response_json, chain_json, created_at, expires_at, fingerprint FROM idempotency WHERE idem_key = ?1",
                 params![idem_key],
-                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)),
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?, r.get(4)?, r.get(5)?)),
             )
             .optional()
             .map_err(|e| DurableError::Sqlite(e.to_string()))?;
 
-        let Some((receipt_cid, response_json, chain_json, created_at)) = row else {
+        let Some((receipt_cid, response_json, chain_json, created_at, expires_at, fingerprint)) =
+            row
+        else {
             return Ok(None);
         };
 
@@ -146,6 +260,8 @@ impl DurableStore {
             decision,
             chain,
             created_at,
+            expires_at,
+            fingerprint,
         }))
     }
 
@@ -172,20 +288,165 @@ impl DurableStore {
         Ok(Some(receipt_json))
     }
 
+    /// Receipts matching `filter`, newest first, starting after `cursor` when
+    /// given. Callers pass the same filter with every page's cursor.
+    pub fn list_receipts(
+        &self,
+        filter: &ReceiptFilter,
+        cursor: Option<&ReceiptCursor>,
+        limit: usize,
+    ) -> Result<Vec<ReceiptSummary>, DurableError> {
+        use rusqlite::types::Value as SqlValue;
+
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        // Only bound filters reach the WHERE clause so SQLite can pick the
+        // matching `(column, created_at, receipt_cid)` index.
+        let mut clauses: Vec<String> = Vec::new();
+        let mut args: Vec<SqlValue> = Vec::new();
+        for (column, value) in [("decision", &filter.decision), ("world", &filter.world)] {
+            if let Some(value) = value {
+                args.push(SqlValue::Text(value.clone()));
+                clauses.push(format!("{} = ?{}", column, args.len()));
+            }
+        }
+        if let Some(c) = cursor {
+            args.push(SqlValue::Integer(c.created_at));
+            args.push(SqlValue::Text(c.receipt_cid.clone()));
+            let (at, cid) = (args.len() - 1, args.len());
+            clauses.push(format!(
+                "(created_at < ?{at} OR (created_at = ?{at} AND receipt_cid < ?{cid}))"
+            ));
+        }
+        args.push(SqlValue::Integer(limit as i64));
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT receipt_cid, decision, did, created_at, world FROM receipts {}
+             ORDER BY created_at DESC, receipt_cid DESC
+             LIMIT ?{}",
+            where_sql,
+            args.len()
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(args), |r| {
+                Ok(ReceiptSummary {
+                    receipt_cid: r.get(0)?,
+                    decision: r.get(1)?,
+                    did: r.get(2)?,
+                    created_at: r.get(3)?,
+                    world: r.get(4)?,
+                })
+            })
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| DurableError::Sqlite(e.to_string()))
+    }
+
+    /// Full receipts with `created_at` in `[from, to)`, oldest first,
+    /// starting after `cursor` when given. Either bound may be open.
+    pub fn export_receipts(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+        cursor: Option<&ReceiptCursor>,
+        limit: usize,
+    ) -> Result<Vec<ExportedReceipt>, DurableError> {
+        use rusqlite::types::Value as SqlValue;
+
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        let mut clauses: Vec<String> = Vec::new();
+        let mut args: Vec<SqlValue> = Vec::new();
+        if let Some(from) = from {
+            args.push(SqlValue::Integer(from));
+            clauses.push(format!("created_at >= ?{}", args.len()));
+        }
+        if let Some(to) = to {
+            args.push(SqlValue::Integer(to));
+            clauses.push(format!("created_at < ?{}", args.len()));
+        }
+        if let Some(c) = cursor {
+            args.push(SqlValue::Integer(c.created_at));
+            args.push(SqlValue::Text(c.receipt_cid.clone()));
+            let (at, cid) = (args.len() - 1, args.len());
+            clauses.push(format!(
+                "(created_at > ?{at} OR (created_at = ?{at} AND receipt_cid > ?{cid}))"
+            ));
+        }
+        args.push(SqlValue::Integer(limit as i64));
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT receipt_cid, created_at, body_json FROM receipts {}
+             ORDER BY created_at ASC, receipt_cid ASC
+             LIMIT ?{}",
+            where_sql,
+            args.len()
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(args), |r| {
+                Ok(ExportedReceipt {
+                    receipt_cid: r.get(0)?,
+                    created_at: r.get(1)?,
+                    body_json: r.get(2)?,
+                })
+            })
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| DurableError::Sqlite(e.to_string()))
+    }
+
     pub fn commit_wf_atomically(&self, input: &CommitInput) -> Result<CommitResult, DurableError> {
+        self.commit_wf_batch_atomically(std::slice::from_ref(input))
+    }
+
+    /// Commit several WF results in one transaction: either every receipt,
+    /// idempotency row and outbox event lands, or none does.
+    pub fn commit_wf_batch_atomically(
+        &self,
+        inputs: &[CommitInput],
+    ) -> Result<CommitResult, DurableError> {
         let mut conn = self.open_conn()?;
         self.apply_pragmas(&conn)?;
 
         let tx = conn
             .transaction()
             .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))?;
+        for input in inputs {
+            self.commit_wf_in_tx(&tx, input)?;
+        }
+        tx.commit()
+            .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))?;
+
+        Ok(CommitResult { committed: true })
+    }
 
+    fn commit_wf_in_tx(
+        &self,
+        tx: &rusqlite::Transaction<'_>,
+        input: &CommitInput,
+    ) -> Result<(), DurableError> {
         let body_json = serde_json::to_string(&input.receipt_json)
             .map_err(|e| DurableError::Serde(e.to_string()))?;
 
+        let world = input.receipt_json.get("@world").and_then(|w| w.as_str());
+
         tx.execute(
-            "INSERT OR IGNORE INTO receipts (receipt_cid, body_json, created_at, did, kid, rt_hash, decision)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT OR IGNORE INTO receipts (receipt_cid, body_json, created_at, did, kid, rt_hash, decision, world)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 input.receipt_cid,
                 body_json,
@@ -194,6 +455,7 @@ impl DurableStore {
                 input.kid,
                 input.rt_hash,
                 input.decision,
+                world,
             ],
         )
         .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))?;
@@ -205,20 +467,79 @@ impl DurableStore {
         }
 
         if let Some(idem_key) = input.idem_key.as_deref() {
-            self.put_idempotent_in_tx(&tx, idem_key, input)?;
+            self.put_idempotent_in_tx(tx, idem_key, None, input)?;
+        }
+        if let Some(client) = input.client_idem.as_ref() {
+            self.put_idempotent_in_tx(tx, &client.key, Some(&client.fingerprint), input)?;
         }
 
-        for event in &input.outbox_events {
-            self.enqueue_outbox_in_tx(&tx, event, input.created_at)?;
+        for (index, event) in input.outbox_events.iter().enumerate() {
+            let delivery_id = outbox_delivery_id(&input.receipt_cid, &event.event_type, index);
+            self.enqueue_outbox_in_tx(tx, event, &delivery_id, input.created_at)?;
         }
+        Ok(())
+    }
 
-        tx.commit()
+    /// Enqueue one outbox event outside a WF commit (e.g. a deferred emit
+    /// after quarantine release). Uses the same delivery id a WF commit would.
+    pub fn enqueue_outbox(
+        &self,
+        receipt_cid: &str,
+        event: &NewOutboxEvent,
+        created_at: i64,
+    ) -> Result<(), DurableError> {
+        let mut conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        let tx = conn
+            .transaction()
             .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))?;
+        let delivery_id = outbox_delivery_id(receipt_cid, &event.event_type, 0);
+        self.enqueue_outbox_in_tx(&tx, event, &delivery_id, created_at)?;
+        tx.commit()
+            .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))
+    }
 
-        Ok(CommitResult { committed: true })
+    /// [`Self::enqueue_outbox`] unless an event already holds its delivery
+    /// id. The check and insert share one immediate transaction, so of two
+    /// racing callers exactly one gets `true`.
+    pub fn enqueue_outbox_once(
+        &self,
+        receipt_cid: &str,
+        event: &NewOutboxEvent,
+        created_at: i64,
+    ) -> Result<bool, DurableError> {
+        let mut conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))?;
+        let delivery_id = outbox_delivery_id(receipt_cid, &event.event_type, 0);
+        let exists: bool = tx
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM outbox WHERE delivery_id = ?1)",
+                params![delivery_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        if exists {
+            return Ok(false);
+        }
+        self.enqueue_outbox_in_tx(&tx, event, &delivery_id, created_at)?;
+        tx.commit()
+            .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))?;
+        Ok(true)
     }
 
     pub fn claim_outbox(&self, limit: usize) -> Result<Vec<OutboxEvent>, DurableError> {
+        self.claim_outbox_with_lease(limit, DEFAULT_OUTBOX_LEASE_SECS)
+    }
+
+    /// Claim due events, including inflight rows whose lease has lapsed.
+    pub fn claim_outbox_with_lease(
+        &self,
+        limit: usize,
+        lease_secs: i64,
+    ) -> Result<Vec<OutboxEvent>, DurableError> {
         let mut conn = self.open_conn()?;
         self.apply_pragmas(&conn)?;
         let tx = conn
@@ -229,9 +550,10 @@ impl DurableStore {
 
         let mut stmt = tx
             .prepare(
-                "SELECT id, event_type, payload_json, attempts, next_attempt_at
+                "SELECT id, COALESCE(delivery_id, 'dlv:legacy-' || id), event_type, payload_json,
+                        attempts, next_attempt_at
                  FROM outbox
-                 WHERE status = 'pending' AND next_attempt_at <= ?1
+                 WHERE status IN ('pending', 'inflight') AND next_attempt_at <= ?1
                  ORDER BY id ASC
                  LIMIT ?2",
             )
@@ -243,20 +565,22 @@ impl DurableStore {
                     r.get::<_, i64>(0)?,
                     r.get::<_, String>(1)?,
                     r.get::<_, String>(2)?,
-                    r.get::<_, i64>(3)?,
+                    r.get::<_, String>(3)?,
                     r.get::<_, i64>(4)?,
+                    r.get::<_, i64>(5)?,
                 ))
             })
             .map_err(|e| DurableError::Sqlite(e.to_string()))?;
 
         let mut events = Vec::new();
         for row in rows {
-            let (id, event_type, payload_json_raw, attempts, next_attempt_at) =
+            let (id, delivery_id, event_type, payload_json_raw, attempts, next_attempt_at) =
                 row.map_err(|e| DurableError::Sqlite(e.to_string()))?;
             let payload_json: Value = serde_json::from_str(&payload_json_raw)
                 .map_err(|e| DurableError::Serde(e.to_string()))?;
             events.push(OutboxEvent {
                 id,
+                delivery_id,
                 event_type,
                 payload_json,
                 attempts,
@@ -265,10 +589,12 @@ impl DurableStore {
         }
         drop(stmt);
 
+        let lease_until = now.saturating_add(lease_secs.max(0));
         for event in &events {
             tx.execute(
-                "UPDATE outbox SET status = 'inflight', attempts = attempts + 1 WHERE id = ?1",
-                params![event.id],
+                "UPDATE outbox SET status = 'inflight', attempts = attempts + 1, next_attempt_at = ?2
+                 WHERE id = ?1",
+                params![event.id, lease_until],
             )
             .map_err(|e| DurableError::Sqlite(e.to_string()))?;
         }
@@ -278,12 +604,14 @@ impl DurableStore {
         Ok(events)
     }
 
+    /// Record delivery confirmation. Status and `delivered_at` flip in one
+    /// statement, so a crash leaves the row either inflight or done.
     pub fn ack_outbox(&self, id: i64) -> Result<(), DurableError> {
         let conn = self.open_conn()?;
         self.apply_pragmas(&conn)?;
         conn.execute(
-            "UPDATE outbox SET status = 'done' WHERE id = ?1",
-            params![id],
+            "UPDATE outbox SET status = 'done', delivered_at = ?2 WHERE id = ?1",
+            params![id, chrono::Utc::now().timestamp()],
         )
         .map_err(|e| DurableError::Sqlite(e.to_string()))?;
         Ok(())
@@ -300,6 +628,103 @@ impl DurableStore {
         Ok(())
     }
 
+    /// Stop retrying an event: it stays `dead`, with `last_error`, until
+    /// [`Self::retry_dead_outbox`] requeues it.
+    pub fn dead_outbox(&self, id: i64, last_error: &str) -> Result<(), DurableError> {
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        conn.execute(
+            "UPDATE outbox SET status = 'dead', last_error = ?2 WHERE id = ?1",
+            params![id, last_error],
+        )
+        .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Dead events, oldest first.
+    pub fn list_dead_outbox(&self, limit: usize) -> Result<Vec<DeadOutboxEvent>, DurableError> {
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, COALESCE(delivery_id, 'dlv:legacy-' || id), event_type, payload_json,
+                        attempts, created_at, last_error
+                 FROM outbox
+                 WHERE status = 'dead'
+                 ORDER BY id ASC
+                 LIMIT ?1",
+            )
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![limit as i64], |r| {
+                Ok((
+                    r.get::<_, i64>(0)?,
+                    r.get::<_, String>(1)?,
+                    r.get::<_, String>(2)?,
+                    r.get::<_, String>(3)?,
+                    r.get::<_, i64>(4)?,
+                    r.get::<_, i64>(5)?,
+                    r.get::<_, Option<String>>(6)?,
+                ))
+            })
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            let (id, delivery_id, event_type, payload_json_raw, attempts, created_at, last_error) =
+                row.map_err(|e| DurableError::Sqlite(e.to_string()))?;
+            let payload_json: Value = serde_json::from_str(&payload_json_raw)
+                .map_err(|e| DurableError::Serde(e.to_string()))?;
+            events.push(DeadOutboxEvent {
+                id,
+                delivery_id,
+                event_type,
+                payload_json,
+                attempts,
+                created_at,
+                last_error,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Requeue a dead event for immediate delivery with a fresh attempt
+    /// budget. `false` when `id` is not dead.
+    pub fn retry_dead_outbox(&self, id: i64) -> Result<bool, DurableError> {
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        let changed = conn
+            .execute(
+                "UPDATE outbox SET status = 'pending', attempts = 0, next_attempt_at = ?2,
+                        last_error = NULL
+                 WHERE id = ?1 AND status = 'dead'",
+                params![id, chrono::Utc::now().timestamp()],
+            )
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        Ok(changed > 0)
+    }
+
+    /// State of the event enqueued under `delivery_id`, if any.
+    pub fn outbox_delivery_state(
+        &self,
+        delivery_id: &str,
+    ) -> Result<Option<OutboxDeliveryState>, DurableError> {
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        conn.query_row(
+            "SELECT status, attempts FROM outbox WHERE delivery_id = ?1",
+            params![delivery_id],
+            |r| {
+                Ok(OutboxDeliveryState {
+                    status: r.get(0)?,
+                    attempts: r.get(1)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| DurableError::Sqlite(e.to_string()))
+    }
+
     pub fn outbox_pending(&self) -> Result<i64, DurableError> {
         let conn = self.open_conn()?;
         self.apply_pragmas(&conn)?;
@@ -311,10 +736,24 @@ impl DurableStore {
         .map_err(|e| DurableError::Sqlite(e.to_string()))
     }
 
+    /// Pending outbox rows whose payload names `world` (`payload_json.world`).
+    pub fn outbox_pending_for_world(&self, world: &str) -> Result<i64, DurableError> {
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        conn.query_row(
+            "SELECT COUNT(*) FROM outbox
+             WHERE status = 'pending' AND json_extract(payload_json, '$.world') = ?1",
+            params![world],
+            |r| r.get(0),
+        )
+        .map_err(|e| DurableError::Sqlite(e.to_string()))
+    }
+
     fn put_idempotent_in_tx(
         &self,
         tx: &rusqlite::Transaction<'_>,
         idem_key: &str,
+        fingerprint: Option<&str>,
         input: &CommitInput,
     ) -> Result<(), DurableError> {
         let response_json = serde_json::to_string(&input.receipt_json)
@@ -322,17 +761,33 @@ impl DurableStore {
         let chain_json =
             serde_json::to_string(&input.chain).map_err(|e| DurableError::Serde(e.to_string()))?;
 
+        // An existing row is only replaced once its replay window has closed.
         match tx.execute(
-            "INSERT INTO idempotency (idem_key, receipt_cid, response_json, chain_json, created_at, expires_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+            "INSERT INTO idempotency (idem_key, receipt_cid, response_json, chain_json, created_at, expires_at, fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(idem_key) DO UPDATE SET
+               receipt_cid = excluded.receipt_cid,
+               response_json = excluded.response_json,
+               chain_json = excluded.chain_json,
+               created_at = excluded.created_at,
+               expires_at = excluded.expires_at,
+               fingerprint = excluded.fingerprint
+             WHERE idempotency.expires_at IS NOT NULL
+               AND idempotency.expires_at <= excluded.created_at",
             params![
                 idem_key,
                 input.receipt_cid,
                 response_json,
                 chain_json,
                 input.created_at,
+                input.idem_expires_at,
+                fingerprint,
             ],
         ) {
+            Ok(0) => Err(DurableError::IdempotencyConflict(format!(
+                "idempotency key already exists: {}",
+                idem_key
+            ))),
             Ok(_) => Ok(()),
             Err(rusqlite::Error::SqliteFailure(err, _))
                 if err.code == rusqlite::ErrorCode::ConstraintViolation =>
@@ -350,14 +805,15 @@ impl DurableStore {
         &self,
         tx: &rusqlite::Transaction<'_>,
         event: &NewOutboxEvent,
+        delivery_id: &str,
         created_at: i64,
     ) -> Result<(), DurableError> {
         let payload = serde_json::to_string(&event.payload_json)
             .map_err(|e| DurableError::Serde(e.to_string()))?;
         tx.execute(
-            "INSERT INTO outbox (event_type, payload_json, status, attempts, next_attempt_at, created_at)
-             VALUES (?1, ?2, 'pending', 0, ?3, ?4)",
-            params![event.event_type, payload, created_at, created_at],
+            "INSERT INTO outbox (delivery_id, event_type, payload_json, status, attempts, next_attempt_at, created_at)
+             VALUES (?1, ?2, ?3, 'pending', 0, ?4, ?5)",
+            params![delivery_id, event.event_type, payload, created_at, created_at],
         )
         .map_err(|e| DurableError::DurableCommitFailed(e.to_string()))?;
         Ok(())
@@ -393,7 +849,8 @@ impl DurableStore {
               did         TEXT NOT NULL,
               kid         TEXT NOT NULL,
               rt_hash     TEXT NOT NULL,
-              decision    TEXT NOT NULL CHECK (decision IN ('allow','deny'))
+              decision    TEXT NOT NULL CHECK (decision IN ('allow','deny','quarantine')),
+              world       TEXT
             );
 
             CREATE TABLE IF NOT EXISTS idempotency (
@@ -402,17 +859,21 @@ impl DurableStore {
               response_json TEXT NOT NULL,
               chain_json    TEXT NOT NULL,
               created_at    INTEGER NOT NULL,
-              expires_at    INTEGER
+              expires_at    INTEGER,
+              fingerprint   TEXT
             );
 
             CREATE TABLE IF NOT EXISTS outbox (
               id              INTEGER PRIMARY KEY AUTOINCREMENT,
+              delivery_id     TEXT,
               event_type      TEXT NOT NULL,
               payload_json    TEXT NOT NULL,
               status          TEXT NOT NULL CHECK (status IN ('pending','inflight','done','dead')) DEFAULT 'pending',
               attempts        INTEGER NOT NULL DEFAULT 0,
               next_attempt_at INTEGER NOT NULL,
-              created_at      INTEGER NOT NULL
+              created_at      INTEGER NOT NULL,
+              delivered_at    INTEGER,
+              last_error      TEXT
             );
 
             CREATE INDEX IF NOT EXISTS idx_outbox_status_next
@@ -434,11 +895,151 @@ impl DurableStore {
               prev       TEXT,
               rotated_at INTEGER NOT NULL
             );
+
+            -- Policy chains evaluated at CHECK, keyed by policy_set_hash
+            CREATE TABLE IF NOT EXISTS policy_snapshots (
+              policy_set_hash TEXT PRIMARY KEY,
+              policies_json   TEXT NOT NULL,
+              created_at      INTEGER NOT NULL
+            );
+            ",
+        )
+        .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        self.migrate_receipts_decision_check(conn)?;
+        self.migrate_receipts_world_column(conn)?;
+        self.migrate_idempotency_fingerprint_column(conn)?;
+        // After the migrations, which rebuild `receipts` and drop its indexes.
+        conn.execute_batch(
+            "
+            CREATE INDEX IF NOT EXISTS idx_receipts_created ON receipts (created_at, receipt_cid);
+            CREATE INDEX IF NOT EXISTS idx_receipts_decision
+            ON receipts (decision, created_at, receipt_cid);
+            CREATE INDEX IF NOT EXISTS idx_receipts_world ON receipts (world, created_at, receipt_cid);
+            ",
+        )
+        .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        self.migrate_outbox_columns(conn)
+    }
+
+    /// Databases created before quarantine restrict `receipts.decision` to
+    /// allow/deny. SQLite cannot alter a CHECK, so rebuild the table.
+    fn migrate_receipts_decision_check(
+        &self,
+        conn: &rusqlite::Connection,
+    ) -> Result<(), DurableError> {
+        let ddl: String = conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'receipts'",
+                [],
+                |r| r.get(0),
+            )
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        if ddl.contains("'quarantine'") {
+            return Ok(());
+        }
+        conn.execute_batch(
+            "
+            BEGIN;
+            ALTER TABLE receipts RENAME TO receipts_pre_quarantine;
+            CREATE TABLE receipts (
+              receipt_cid TEXT PRIMARY KEY,
+              body_json   TEXT NOT NULL,
+              created_at  INTEGER NOT NULL,
+              did         TEXT NOT NULL,
+              kid         TEXT NOT NULL,
+              rt_hash     TEXT NOT NULL,
+              decision    TEXT NOT NULL CHECK (decision IN ('allow','deny','quarantine'))
+            );
+            INSERT INTO receipts (receipt_cid, body_json, created_at, did, kid, rt_hash, decision)
+              SELECT receipt_cid, body_json, created_at, did, kid, rt_hash, decision
+              FROM receipts_pre_quarantine;
+            DROP TABLE receipts_pre_quarantine;
+            COMMIT;
             ",
         )
         .map_err(|e| DurableError::Sqlite(e.to_string()))
     }
 
+    /// Receipts written before the world filter have no `world` column; the
+    /// added column stays NULL for those rows.
+    fn migrate_receipts_world_column(
+        &self,
+        conn: &rusqlite::Connection,
+    ) -> Result<(), DurableError> {
+        let mut stmt = conn
+            .prepare("PRAGMA table_info(receipts)")
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        let columns: Vec<String> = stmt
+            .query_map([], |r| r.get::<_, String>(1))
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?
+            .collect::<Result<_, _>>()
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        if !columns.iter().any(|c| c == "world") {
+            conn.execute("ALTER TABLE receipts ADD COLUMN world TEXT", [])
+                .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Rows written before client idempotency keys have no `fingerprint`;
+    /// the added column stays NULL for those rows.
+    fn migrate_idempotency_fingerprint_column(
+        &self,
+        conn: &rusqlite::Connection,
+    ) -> Result<(), DurableError> {
+        let mut stmt = conn
+            .prepare("PRAGMA table_info(idempotency)")
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        let columns: Vec<String> = stmt
+            .query_map([], |r| r.get::<_, String>(1))
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?
+            .collect::<Result<_, _>>()
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        if !columns.iter().any(|c| c == "fingerprint") {
+            conn.execute("ALTER TABLE idempotency ADD COLUMN fingerprint TEXT", [])
+                .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Databases created before delivery ids existed lack the columns;
+    /// `CREATE TABLE IF NOT EXISTS` does not add them, so patch in place.
+    fn migrate_outbox_columns(&self, conn: &rusqlite::Connection) -> Result<(), DurableError> {
+        let mut stmt = conn
+            .prepare("PRAGMA table_info(outbox)")
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        let columns: Vec<String> = stmt
+            .query_map([], |r| r.get::<_, String>(1))
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?
+            .collect::<Result<_, _>>()
+            .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        for (column, ddl) in [
+            (
+                "delivery_id",
+                "ALTER TABLE outbox ADD COLUMN delivery_id TEXT",
+            ),
+            (
+                "delivered_at",
+                "ALTER TABLE outbox ADD COLUMN delivered_at INTEGER",
+            ),
+            (
+                "last_error",
+                "ALTER TABLE outbox ADD COLUMN last_error TEXT",
+            ),
+        ] {
+            if !columns.iter().any(|c| c == column) {
+                conn.execute(ddl, [])
+                    .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+            }
+        }
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_outbox_delivery_id ON outbox (delivery_id)",
+            [],
+        )
+        .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        Ok(())
+    }
+
     /// GAP-6: insert nonce if not already seen (and not expired). Returns `true` if newly inserted.
     /// Also prunes expired nonces on each call (index-assisted, cheap).
     pub fn nonce_mark_if_new(&self, nonce: &str, ttl: Duration) -> Result<bool, DurableError> {
@@ -502,6 +1103,43 @@ impl DurableStore {
         Ok(row.map(|(current, prev)| StageSecretsRow { current, prev }))
     }
 
+    /// Record the policy chain behind `policy_set_hash`; the first write wins.
+    pub fn put_policy_snapshot(
+        &self,
+        policy_set_hash: &str,
+        policies_json: &str,
+    ) -> Result<(), DurableError> {
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        conn.execute(
+            "INSERT OR IGNORE INTO policy_snapshots (policy_set_hash, policies_json, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![
+                policy_set_hash,
+                policies_json,
+                chrono::Utc::now().timestamp()
+            ],
+        )
+        .map_err(|e| DurableError::Sqlite(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Policy chain JSON recorded for `policy_set_hash`, if any.
+    pub fn get_policy_snapshot(
+        &self,
+        policy_set_hash: &str,
+    ) -> Result<Option<String>, DurableError> {
+        let conn = self.open_conn()?;
+        self.apply_pragmas(&conn)?;
+        conn.query_row(
+            "SELECT policies_json FROM policy_snapshots WHERE policy_set_hash = ?1",
+            params![policy_set_hash],
+            |r| r.get(0),
+        )
+        .optional()
+        .map_err(|e| DurableError::Sqlite(e.to_string()))
+    }
+
     fn ensure_parent_dir(&self) -> Result<(), DurableError> {
         if !self.dsn.starts_with("file:") {
             return Ok(());
@@ -522,6 +1160,18 @@ impl DurableStore {
     }
 }
 
+/// Deterministic delivery id for the `index`-th outbox event of a receipt.
+/// A replayed WF commit for the same receipt yields the same token.
+pub fn outbox_delivery_id(receipt_cid: &str, event_type: &str, index: usize) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(receipt_cid.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(event_type.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(index.to_string().as_bytes());
+    format!("dlv:{}", &hasher.finalize().to_hex()[..32])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -549,6 +1199,8 @@ mod tests {
             rt_hash: "b3:runtime".to_string(),
             decision: "allow".to_string(),
             idem_key: idem_key.map(|s| s.to_string()),
+            idem_expires_at: None,
+            client_idem: None,
             chain: vec![
                 "b3:wa".to_string(),
                 "b3:tr".to_string(),
@@ -563,6 +1215,196 @@ mod tests {
         }
     }
 
+    #[test]
+    fn enqueue_outbox_once_enqueues_a_delivery_id_once() {
+        let store = make_store("enqueue_once.db");
+        let event = NewOutboxEvent {
+            event_type: "emit_receipt".to_string(),
+            payload_json: serde_json::json!({"receipt_cid": "b3:held", "released": true}),
+        };
+        assert!(store.enqueue_outbox_once("b3:held", &event, 100).unwrap());
+        assert!(!store.enqueue_outbox_once("b3:held", &event, 101).unwrap());
+        assert_eq!(store.outbox_pending().unwrap(), 1);
+    }
+
+    #[test]
+    fn list_receipts_pages_newest_first_by_cursor() {
+        let store = make_store("list_receipts.db");
+        for (cid, created_at) in [
+            ("b3:r-a", 100),
+            ("b3:r-b", 200),
+            ("b3:r-c", 200),
+            ("b3:r-d", 300),
+        ] {
+            let mut commit = sample_commit(None);
+            commit.receipt_cid = cid.to_string();
+            commit.created_at = created_at;
+            store.commit_wf_atomically(&commit).unwrap();
+        }
+
+        let first = store
+            .list_receipts(&ReceiptFilter::default(), None, 3)
+            .unwrap();
+        let cids: Vec<&str> = first.iter().map(|r| r.receipt_cid.as_str()).collect();
+        assert_eq!(cids, vec!["b3:r-d", "b3:r-c", "b3:r-b"]);
+        assert_eq!(first[0].decision, "allow");
+
+        let last = &first[2];
+        let cursor = ReceiptCursor {
+            created_at: last.created_at,
+            receipt_cid: last.receipt_cid.clone(),
+        };
+        assert_eq!(
+            ReceiptCursor::decode(&cursor.encode()),
+            Some(cursor.clone())
+        );
+        let rest = store
+            .list_receipts(&ReceiptFilter::default(), Some(&cursor), 3)
+            .unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].receipt_cid, "b3:r-a");
+        assert!(ReceiptCursor::decode("not a cursor").is_none());
+    }
+
+    #[test]
+    fn list_receipts_filters_by_decision_and_world_in_sql() {
+        let store = make_store("list_receipts_filtered.db");
+        for (cid, created_at, decision, world) in [
+            ("b3:f-a", 100, "allow", Some("a/acme/t/prod")),
+            ("b3:f-b", 200, "deny", Some("a/acme/t/prod")),
+            ("b3:f-c", 300, "allow", Some("a/acme/t/dev")),
+            ("b3:f-d", 400, "allow", Some("a/acme/t/prod")),
+            ("b3:f-e", 500, "allow", None),
+        ] {
+            let mut commit = sample_commit(None);
+            commit.receipt_cid = cid.to_string();
+            commit.created_at = created_at;
+            commit.decision = decision.to_string();
+            if let Some(world) = world {
+                commit.receipt_json["@world"] = serde_json::json!(world);
+            }
+            store.commit_wf_atomically(&commit).unwrap();
+        }
+        let cids = |rows: Vec<ReceiptSummary>| -> Vec<String> {
+            rows.into_iter().map(|r| r.receipt_cid).collect()
+        };
+
+        let allow = ReceiptFilter {
+            decision: Some("allow".into()),
+            world: None,
+        };
+        assert_eq!(
+            cids(store.list_receipts(&allow, None, 10).unwrap()),
+            vec!["b3:f-e", "b3:f-d", "b3:f-c", "b3:f-a"]
+        );
+
+        // Rows without a world are excluded, not an error.
+        let prod_allow = ReceiptFilter {
+            decision: Some("allow".into()),
+            world: Some("a/acme/t/prod".into()),
+        };
+        let first = store.list_receipts(&prod_allow, None, 1).unwrap();
+        assert_eq!(cids(first.clone()), vec!["b3:f-d"]);
+        let cursor = ReceiptCursor {
+            created_at: first[0].created_at,
+            receipt_cid: first[0].receipt_cid.clone(),
+        };
+        assert_eq!(
+            cids(store.list_receipts(&prod_allow, Some(&cursor), 10).unwrap()),
+            vec!["b3:f-a"]
+        );
+
+        let none = ReceiptFilter {
+            decision: Some("deny".into()),
+            world: Some("a/acme/t/dev".into()),
+        };
+        assert!(store.list_receipts(&none, None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn export_receipts_pages_oldest_first_within_bounds() {
+        let store = make_store("export_receipts.db");
+        for (cid, created_at) in [
+            ("b3:x-a", 100),
+            ("b3:x-c", 200),
+            ("b3:x-b", 200),
+            ("b3:x-d", 300),
+        ] {
+            let mut commit = sample_commit(None);
+            commit.receipt_cid = cid.to_string();
+            commit.created_at = created_at;
+            store.commit_wf_atomically(&commit).unwrap();
+        }
+        let cids = |rows: &[ExportedReceipt]| -> Vec<String> {
+            rows.iter().map(|r| r.receipt_cid.clone()).collect()
+        };
+
+        let first = store.export_receipts(None, None, None, 2).unwrap();
+        assert_eq!(cids(&first), vec!["b3:x-a", "b3:x-b"]);
+        let body: serde_json::Value = serde_json::from_str(&first[0].body_json).unwrap();
+        assert!(body.is_object());
+        assert!(!first[0].body_json.contains('\n'));
+        let last = first.last().unwrap();
+        let cursor = ReceiptCursor {
+            created_at: last.created_at,
+            receipt_cid: last.receipt_cid.clone(),
+        };
+        assert_eq!(
+            cids(
+                &store
+                    .export_receipts(None, None, Some(&cursor), 10)
+                    .unwrap()
+            ),
+            vec!["b3:x-c", "b3:x-d"]
+        );
+
+        // `[from, to)`: the lower bound is inclusive, the upper exclusive.
+        assert_eq!(
+            cids(
+                &store
+                    .export_receipts(Some(200), Some(300), None, 10)
+                    .unwrap()
+            ),
+            vec!["b3:x-b", "b3:x-c"]
+        );
+        assert!(store
+            .export_receipts(Some(400), None, None, 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn receipts_world_column_is_added_to_legacy_schema() {
+        let store = DurableStore {
+            dsn: temp_dsn("legacy_world.db"),
+        };
+        let conn = store.open_conn().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE receipts (
+               receipt_cid TEXT PRIMARY KEY,
+               body_json   TEXT NOT NULL,
+               created_at  INTEGER NOT NULL,
+               did         TEXT NOT NULL,
+               kid         TEXT NOT NULL,
+               rt_hash     TEXT NOT NULL,
+               decision    TEXT NOT NULL CHECK (decision IN ('allow','deny','quarantine'))
+             );
+             INSERT INTO receipts VALUES ('b3:old', '{\"@world\":\"a/x/t/y\"}', 1, 'did:key:z1', 'did:key:z1#k', 'b3:rt', 'allow');",
+        )
+        .unwrap();
+        store.ensure_initialized().unwrap();
+
+        let world = ReceiptFilter {
+            decision: None,
+            world: Some("a/x/t/y".into()),
+        };
+        assert!(store.list_receipts(&world, None, 10).unwrap().is_empty());
+        let all = store
+            .list_receipts(&ReceiptFilter::default(), None, 10)
+            .unwrap();
+        assert_eq!(all.len(), 1);
+    }
+
     #[test]
     fn idempotency_survives_restart() {
         let dsn = temp_dsn("idem_restart.db");
@@ -578,6 +1420,67 @@ mod tests {
         assert_eq!(cached.receipt_cid, "b3:receipt-1");
     }
 
+    #[test]
+    fn expired_idempotency_row_is_replaced_live_row_conflicts() {
+        let store = make_store("idem_ttl.db");
+        let mut first = sample_commit(Some("idem-ttl"));
+        first.idem_expires_at = Some(first.created_at + 3600);
+        store.commit_wf_atomically(&first).unwrap();
+
+        let mut second = sample_commit(Some("idem-ttl"));
+        second.receipt_cid = "b3:receipt-2".to_string();
+        assert!(matches!(
+            store.commit_wf_atomically(&second),
+            Err(DurableError::IdempotencyConflict(_))
+        ));
+
+        let conn = store.open_conn().unwrap();
+        conn.execute(
+            "UPDATE idempotency SET expires_at = ?1 WHERE idem_key = 'idem-ttl'",
+            params![second.created_at - 1],
+        )
+        .unwrap();
+        assert!(store
+            .get_idempotent("idem-ttl")
+            .unwrap()
+            .unwrap()
+            .is_expired());
+        store.commit_wf_atomically(&second).unwrap();
+        let cached = store.get_idempotent("idem-ttl").unwrap().unwrap();
+        assert_eq!(cached.receipt_cid, "b3:receipt-2");
+        assert_eq!(cached.expires_at, None);
+    }
+
+    #[test]
+    fn quarantine_decision_commits_on_legacy_schema() {
+        let store = DurableStore {
+            dsn: temp_dsn("legacy_decision.db"),
+        };
+        let conn = store.open_conn().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE receipts (
+               receipt_cid TEXT PRIMARY KEY,
+               body_json   TEXT NOT NULL,
+               created_at  INTEGER NOT NULL,
+               did         TEXT NOT NULL,
+               kid         TEXT NOT NULL,
+               rt_hash     TEXT NOT NULL,
+               decision    TEXT NOT NULL CHECK (decision IN ('allow','deny'))
+             );
+             INSERT INTO receipts VALUES ('b3:old', '{}', 1, 'did:key:z1', 'did:key:z1#k', 'b3:rt', 'allow');",
+        )
+        .unwrap();
+        drop(conn);
+
+        store.ensure_initialized().unwrap();
+        let mut commit = sample_commit(None);
+        commit.decision = "quarantine".to_string();
+        commit.outbox_events.clear();
+        store.commit_wf_atomically(&commit).unwrap();
+        assert!(store.get_receipt("b3:old").unwrap().is_some());
+        assert!(store.get_receipt("b3:receipt-1").unwrap().is_some());
+    }
+
     #[test]
     fn crash_between_writes_no_dup_no_loss() {
         let store = make_store("crash.db");
@@ -602,6 +1505,39 @@ mod tests {
         assert_eq!(cached.receipt_cid, "b3:receipt-crash");
     }
 
+    #[test]
+    fn batch_commit_rolls_back_every_entry_when_one_fails() {
+        let store = make_store("batch_rollback.db");
+        let first = sample_commit(Some("idem-batch-1"));
+        let mut second = sample_commit(Some("idem-batch-2"));
+        second.receipt_cid = "b3:receipt-2".to_string();
+        second.fail_after_receipt_write = true;
+        assert!(matches!(
+            store.commit_wf_batch_atomically(&[first.clone(), second.clone()]),
+            Err(DurableError::DurableCommitFailed(_))
+        ));
+        assert!(store.get_receipt("b3:receipt-1").unwrap().is_none());
+        assert!(store.get_idempotent("idem-batch-1").unwrap().is_none());
+        assert_eq!(store.outbox_pending().unwrap(), 0);
+
+        second.fail_after_receipt_write = false;
+        store.commit_wf_batch_atomically(&[first, second]).unwrap();
+        assert!(store.get_receipt("b3:receipt-1").unwrap().is_some());
+        assert!(store.get_receipt("b3:receipt-2").unwrap().is_some());
+        assert!(store.get_idempotent("idem-batch-2").unwrap().is_some());
+    }
+
+    #[test]
+    fn outbox_pending_counts_by_world() {
+        let store = make_store("outbox_world.db");
+        let mut commit = sample_commit(None);
+        commit.outbox_events[0].payload_json =
+            serde_json::json!({"receipt_cid": "b3:receipt-1", "world": "a/acme/t/prod"});
+        store.commit_wf_atomically(&commit).unwrap();
+        assert_eq!(store.outbox_pending_for_world("a/acme/t/prod").unwrap(), 1);
+        assert_eq!(store.outbox_pending_for_world("a/other/t/prod").unwrap(), 0);
+    }
+
     #[test]
     fn outbox_retries_and_acks() {
         let store = make_store("outbox.db");
@@ -623,6 +1559,44 @@ mod tests {
         store.ack_outbox(claimed2[0].id).unwrap();
 
         assert_eq!(store.outbox_pending().unwrap(), 0);
+        let delivery_id = outbox_delivery_id("b3:receipt-1", "emit_receipt", 0);
+        assert_eq!(
+            store.outbox_delivery_state(&delivery_id).unwrap(),
+            Some(OutboxDeliveryState {
+                status: "done".to_string(),
+                attempts: 2,
+            })
+        );
+        assert!(store
+            .outbox_delivery_state("dlv:missing")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn dead_outbox_is_listed_and_requeued_with_fresh_attempts() {
+        let store = make_store("outbox_dead.db");
+        store
+            .commit_wf_atomically(&sample_commit(Some("idem-dead")))
+            .unwrap();
+        let ev = store.claim_outbox(10).unwrap().remove(0);
+        store.dead_outbox(ev.id, "endpoint returned 500").unwrap();
+
+        assert_eq!(store.outbox_pending().unwrap(), 0);
+        assert!(store.claim_outbox_with_lease(10, 0).unwrap().is_empty());
+        let dead = store.list_dead_outbox(10).unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].id, ev.id);
+        assert_eq!(dead[0].delivery_id, ev.delivery_id);
+        assert_eq!(dead[0].attempts, 1);
+        assert_eq!(dead[0].last_error.as_deref(), Some("endpoint returned 500"));
+
+        assert!(store.retry_dead_outbox(ev.id).unwrap());
+        assert!(!store.retry_dead_outbox(ev.id).unwrap());
+        assert!(store.list_dead_outbox(10).unwrap().is_empty());
+        let requeued = store.claim_outbox(10).unwrap();
+        assert_eq!(requeued.len(), 1);
+        assert_eq!(requeued[0].attempts, 0);
     }
 
     #[test]
@@ -36,6 +36,12 @@ pub enum ErrorCode {
     KnockInputNormalization,
     #[serde(rename = "KNOCK_SCHEMA_VALIDATION")]
     KnockSchemaValidation,
+    /// `UBL_REQUIRE_CHIP_SIGNATURE` is on and the chip carries no signature.
+    #[serde(rename = "SIGNATURE_REQUIRED")]
+    KnockSignatureRequired,
+    /// The chip's own signature does not verify against its `kid`.
+    #[serde(rename = "SIGNATURE_INVALID")]
+    KnockSignatureInvalid,
 
     // Pipeline errors (produce DENY receipt)
     #[serde(rename = "POLICY_DENIED")]
@@ -44,6 +50,8 @@ pub enum ErrorCode {
     InvalidChip,
     #[serde(rename = "DEPENDENCY_MISSING")]
     DependencyMissing,
+    #[serde(rename = "RUNTIME_TOO_OLD")]
+    RuntimeTooOld,
 
     // WASM canonical conformance errors (produce DENY receipt)
     #[serde(rename = "WASM_ABI_MISSING_VERSION")]
@@ -102,6 +110,9 @@ pub enum ErrorCode {
     DurableCommitFailed,
     #[serde(rename = "TAMPER_DETECTED")]
     TamperDetected,
+    /// Receipt stage chain exceeds the verification limit.
+    #[serde(rename = "CHAIN_TOO_LONG")]
+    ChainTooLong,
 
     #[serde(rename = "INTERNAL_ERROR")]
     InternalError,
@@ -110,6 +121,9 @@ pub enum ErrorCode {
     /// Authentication required or invalid credentials.
     #[serde(rename = "UNAUTHORIZED")]
     Unauthorized,
+    /// Bearer token named by a `ubl/token.revoke` chip.
+    #[serde(rename = "TOKEN_REVOKED")]
+    TokenRevoked,
     /// Resource not found.
     #[serde(rename = "NOT_FOUND")]
     NotFound,
@@ -137,9 +151,11 @@ impl ErrorCode {
             | Self::KnockNumericLiteralNotAllowed
             | Self::KnockInputNormalization
             | Self::KnockSchemaValidation => 400,
+            Self::KnockSignatureRequired | Self::KnockSignatureInvalid => 401,
 
             Self::PolicyDenied => 403,
             Self::DependencyMissing => 409,
+            Self::RuntimeTooOld => 409,
             Self::ReplayDetected => 409,
             Self::InvalidChip => 422,
             Self::FuelExhausted => 422,
@@ -167,8 +183,10 @@ impl ErrorCode {
             Self::IdempotencyConflict => 409,
             Self::DurableCommitFailed => 500,
             Self::TamperDetected => 422,
+            Self::ChainTooLong => 422,
             Self::InternalError => 500,
             Self::Unauthorized => 401,
+            Self::TokenRevoked => 401,
             Self::NotFound => 404,
             Self::TooManyRequests => 429,
             Self::Unavailable => 503,
@@ -193,6 +211,7 @@ impl ErrorCode {
             | Self::KnockMalformedNum
             | Self::KnockNumericLiteralNotAllowed
             | Self::KnockInputNormalization
+            | Self::KnockSchemaValidation
             | Self::InvalidChip
             | Self::CanonError
             | Self::FuelExhausted
@@ -209,15 +228,20 @@ impl ErrorCode {
             | Self::WasmReceiptBindingMissingClaim
             | Self::TypeMismatch
             | Self::StackUnderflow
-            | Self::CasNotFound => "BadInput",
+            | Self::CasNotFound
+            | Self::ChainTooLong => "BadInput",
             Self::InvalidSignature | Self::RuntimeHashMismatch => "BadInput",
 
-            Self::Unauthorized | Self::SignError => "Unauthorized",
+            Self::Unauthorized
+            | Self::TokenRevoked
+            | Self::KnockSignatureRequired
+            | Self::KnockSignatureInvalid
+            | Self::SignError => "Unauthorized",
             Self::PolicyDenied | Self::WasmCapabilityDenied | Self::WasmCapabilityDeniedNetwork => {
                 "Forbidden"
             }
             Self::NotFound | Self::DependencyMissing => "NotFound",
-            Self::ReplayDetected | Self::IdempotencyConflict => "Conflict",
+            Self::ReplayDetected | Self::IdempotencyConflict | Self::RuntimeTooOld => "Conflict",
             Self::TamperDetected => "Conflict",
             Self::TooManyRequests => "TooManyRequests",
             Self::StorageError | Self::DurableCommitFailed | Self::InternalError => "Internal",
@@ -263,10 +287,14 @@ impl ErrorCode {
                 | Self::KnockNumericLiteralNotAllowed
                 | Self::KnockInputNormalization
                 | Self::KnockSchemaValidation
+                | Self::KnockSignatureRequired
+                | Self::KnockSignatureInvalid
                 | Self::Unauthorized
+                | Self::TokenRevoked
                 | Self::NotFound
                 | Self::TooManyRequests
                 | Self::TamperDetected
+                | Self::ChainTooLong
                 | Self::Unavailable
         )
     }
@@ -321,6 +349,7 @@ impl UblError {
                 msg.clone(),
             ),
             PipelineError::DependencyMissing(msg) => (ErrorCode::DependencyMissing, msg.clone()),
+            PipelineError::RuntimeTooOld(msg) => (ErrorCode::RuntimeTooOld, msg.clone()),
             PipelineError::FuelExhausted(msg) => (
                 classify_wasm_error(msg).unwrap_or(ErrorCode::FuelExhausted),
                 msg.clone(),
@@ -395,6 +424,10 @@ fn classify_knock_error(msg: &str) -> ErrorCode {
         ErrorCode::KnockInputNormalization
     } else if msg.contains("KNOCK-012") {
         ErrorCode::KnockSchemaValidation
+    } else if msg.contains("SIGNATURE_REQUIRED") {
+        ErrorCode::KnockSignatureRequired
+    } else if msg.contains("SIGNATURE_INVALID") {
+        ErrorCode::KnockSignatureInvalid
     } else {
         ErrorCode::KnockInvalidUtf8 // fallback
     }
@@ -630,6 +663,23 @@ mod tests {
         assert!(!ubl_err.code.produces_receipt());
     }
 
+    #[test]
+    fn knock_signature_errors_map_to_401() {
+        let err = PipelineError::Knock(
+            "SIGNATURE_REQUIRED: chip must carry `signature` and `kid`".to_string(),
+        );
+        let ubl_err = UblError::from_pipeline_error(&err);
+        assert_eq!(ubl_err.code, ErrorCode::KnockSignatureRequired);
+        assert_eq!(ubl_err.code.http_status(), 401);
+        assert!(!ubl_err.code.produces_receipt());
+        let err = PipelineError::Knock(
+            "SIGNATURE_INVALID: signature does not verify for kid \"did:key:z1\"".to_string(),
+        );
+        let ubl_err = UblError::from_pipeline_error(&err);
+        assert_eq!(ubl_err.code, ErrorCode::KnockSignatureInvalid);
+        assert_eq!(ubl_err.code.category(), "Unauthorized");
+    }
+
     #[test]
     fn error_link_contains_code() {
         let err = PipelineError::Knock("KNOCK-004: duplicate key \"name\"".to_string());
@@ -815,18 +865,22 @@ mod tests {
     #[test]
     fn new_codes_have_correct_http_status() {
         assert_eq!(ErrorCode::Unauthorized.http_status(), 401);
+        assert_eq!(ErrorCode::TokenRevoked.http_status(), 401);
         assert_eq!(ErrorCode::NotFound.http_status(), 404);
         assert_eq!(ErrorCode::TooManyRequests.http_status(), 429);
         assert_eq!(ErrorCode::TamperDetected.http_status(), 422);
+        assert_eq!(ErrorCode::ChainTooLong.http_status(), 422);
         assert_eq!(ErrorCode::Unavailable.http_status(), 503);
     }
 
     #[test]
     fn new_codes_do_not_produce_receipts() {
         assert!(!ErrorCode::Unauthorized.produces_receipt());
+        assert!(!ErrorCode::TokenRevoked.produces_receipt());
         assert!(!ErrorCode::NotFound.produces_receipt());
         assert!(!ErrorCode::TooManyRequests.produces_receipt());
         assert!(!ErrorCode::TamperDetected.produces_receipt());
+        assert!(!ErrorCode::ChainTooLong.produces_receipt());
         assert!(!ErrorCode::Unavailable.produces_receipt());
     }
 
@@ -869,6 +923,7 @@ mod tests {
         assert_eq!(ErrorCode::NotFound.category(), "NotFound");
         assert_eq!(ErrorCode::ReplayDetected.category(), "Conflict");
         assert_eq!(ErrorCode::TamperDetected.category(), "Conflict");
+        assert_eq!(ErrorCode::RuntimeTooOld.category(), "Conflict");
         assert_eq!(ErrorCode::TooManyRequests.category(), "TooManyRequests");
         assert_eq!(ErrorCode::InternalError.category(), "Internal");
         assert_eq!(ErrorCode::StorageError.category(), "Internal");
@@ -909,12 +964,16 @@ mod tests {
     fn new_codes_serialize_correctly() {
         let json = serde_json::to_value(ErrorCode::Unauthorized).unwrap();
         assert_eq!(json, "UNAUTHORIZED");
+        let json = serde_json::to_value(ErrorCode::TokenRevoked).unwrap();
+        assert_eq!(json, "TOKEN_REVOKED");
         let json = serde_json::to_value(ErrorCode::NotFound).unwrap();
         assert_eq!(json, "NOT_FOUND");
         let json = serde_json::to_value(ErrorCode::TooManyRequests).unwrap();
         assert_eq!(json, "TOO_MANY_REQUESTS");
         let json = serde_json::to_value(ErrorCode::TamperDetected).unwrap();
         assert_eq!(json, "TAMPER_DETECTED");
+        let json = serde_json::to_value(ErrorCode::ChainTooLong).unwrap();
+        assert_eq!(json, "CHAIN_TOO_LONG");
         let json = serde_json::to_value(ErrorCode::Unavailable).unwrap();
         assert_eq!(json, "UNAVAILABLE");
     }
@@ -85,6 +85,9 @@ pub struct ReceiptEvent {
     /// Knock/envelope CID
     #[serde(skip_serializing_if = "Option::is_none")]
     pub knock_cid: Option<String>,
+    /// Client-supplied correlation id, echoed verbatim
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
     /// Stage latency in milliseconds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub latency_ms: Option<i64>,
@@ -101,6 +104,7 @@ pub struct StageEventContext {
     pub actor: Option<String>,
     pub subject_did: Option<String>,
     pub knock_cid: Option<String>,
+    pub correlation_id: Option<String>,
 }
 
 impl ReceiptEvent {
@@ -135,6 +139,7 @@ impl ReceiptEvent {
             actor: None,
             subject_did: None,
             knock_cid: None,
+            correlation_id: None,
             latency_ms: None,
         }
     }
@@ -164,6 +169,7 @@ impl ReceiptEvent {
         event.actor = ctx.actor;
         event.subject_did = ctx.subject_did;
         event.knock_cid = ctx.knock_cid;
+        event.correlation_id = ctx.correlation_id;
         event.latency_ms = event.duration_ms;
 
         // Extract fuel_used and rb_count from receipt body if present.
@@ -236,6 +242,11 @@ impl From<&UnifiedReceipt> for ReceiptEvent {
         event.actor = Some(receipt.did.as_str().to_string());
         event.subject_did = receipt.subject_did.clone();
         event.knock_cid = receipt.knock_cid.as_ref().map(|c| c.as_str().to_string());
+        event.correlation_id = receipt
+            .effects
+            .get("correlation_id")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
         event.latency_ms = duration_ms;
         if let Some(rt) = &receipt.rt {
             event.binary_hash = Some(rt.binary_hash.clone());
@@ -260,6 +271,7 @@ fn decision_to_wire(decision: &ReceiptDecision) -> &'static str {
         ReceiptDecision::Allow => "allow",
         ReceiptDecision::Deny => "deny",
         ReceiptDecision::Require => "require",
+        ReceiptDecision::Quarantine => "quarantine",
     }
 }
 
@@ -444,6 +456,7 @@ mod tests {
                 actor: Some("did:key:zX".to_string()),
                 subject_did: Some("did:ubl:anon:b3:test".to_string()),
                 knock_cid: Some("b3:knock".to_string()),
+                correlation_id: None,
             },
         );
 
@@ -0,0 +1,104 @@
+//! Signed hub events.
+//!
+//! With `UBL_SIGN_EVENTS=true` the gate signs each hub event before it is
+//! stored or streamed: `@kid` names the gate key and `@sig` is an Ed25519
+//! signature over the NRF-1 form of the event without `@sig`, under the
+//! `ubl/event/v1` domain. Consumers verify against keys they already trust
+//! (the gate's keyring): `@kid` only picks among those, since anyone can
+//! sign an event with a key of their own and name it there.
+
+use serde_json::Value;
+use ubl_kms::Ed25519SigningKey as SigningKey;
+use ubl_kms::Ed25519VerifyingKey as VerifyingKey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventSignatureError {
+    #[error("event is not a JSON object")]
+    NotAnObject,
+    #[error("event is unsigned")]
+    Unsigned,
+    #[error("event signed by untrusted key '{0}'")]
+    UntrustedKid(String),
+    #[error("signature error: {0}")]
+    Signature(String),
+}
+
+/// Set `@kid` and `@sig` on `event`, replacing any previous signature.
+pub fn sign_event(
+    event: &mut Value,
+    kid: &str,
+    sk: &SigningKey,
+) -> Result<(), EventSignatureError> {
+    let obj = event
+        .as_object_mut()
+        .ok_or(EventSignatureError::NotAnObject)?;
+    obj.remove("@sig");
+    obj.insert("@kid".to_string(), Value::String(kid.to_string()));
+    let sig = ubl_canon::sign_domain_v1(event, ubl_canon::domains::EVENT, sk)
+        .map_err(|e| EventSignatureError::Signature(e.to_string()))?;
+    if let Some(obj) = event.as_object_mut() {
+        obj.insert("@sig".to_string(), Value::String(sig));
+    }
+    Ok(())
+}
+
+/// Check `@sig` against the trusted key `key_for` returns for `@kid`. A kid
+/// it does not know is [`EventSignatureError::UntrustedKid`], never a pass.
+pub fn verify_event(
+    event: &Value,
+    key_for: impl Fn(&str) -> Option<VerifyingKey>,
+) -> Result<bool, EventSignatureError> {
+    let obj = event.as_object().ok_or(EventSignatureError::NotAnObject)?;
+    let (Some(sig), Some(kid)) = (
+        obj.get("@sig").and_then(|v| v.as_str()),
+        obj.get("@kid").and_then(|v| v.as_str()),
+    ) else {
+        return Err(EventSignatureError::Unsigned);
+    };
+    let vk = key_for(kid).ok_or_else(|| EventSignatureError::UntrustedKid(kid.to_string()))?;
+    let mut payload = obj.clone();
+    payload.remove("@sig");
+    ubl_canon::verify_domain_v1(&Value::Object(payload), ubl_canon::domains::EVENT, &vk, sig)
+        .map_err(|e| EventSignatureError::Signature(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sign_then_verify_and_detect_tamper() {
+        let sk = ubl_kms::generate_signing_key();
+        let vk = ubl_kms::verifying_key(&sk);
+        let kid = ubl_kms::kid_from_verifying_key(&vk);
+        let mut event = json!({"@type": "ubl/event", "@id": "evt-1", "stage": "WF"});
+        let pinned = |k: &str| (k == kid).then_some(vk);
+        sign_event(&mut event, &kid, &sk).unwrap();
+        assert_eq!(event["@kid"], json!(kid));
+        assert!(verify_event(&event, pinned).unwrap());
+
+        event["stage"] = json!("TR");
+        assert!(!verify_event(&event, pinned).unwrap());
+        assert!(matches!(
+            verify_event(&json!({"@type": "ubl/event"}), pinned),
+            Err(EventSignatureError::Unsigned)
+        ));
+    }
+
+    #[test]
+    fn self_signed_forgery_names_an_untrusted_kid() {
+        let gate_sk = ubl_kms::generate_signing_key();
+        let gate_vk = ubl_kms::verifying_key(&gate_sk);
+        let gate_kid = ubl_kms::kid_from_verifying_key(&gate_vk);
+        let forger_sk = ubl_kms::generate_signing_key();
+        let forger_kid = ubl_kms::kid_from_verifying_key(&ubl_kms::verifying_key(&forger_sk));
+
+        let mut forged = json!({"@type": "ubl/event", "@id": "evt-forged", "stage": "WF"});
+        sign_event(&mut forged, &forger_kid, &forger_sk).unwrap();
+        assert!(matches!(
+            verify_event(&forged, |k: &str| (k == gate_kid).then_some(gate_vk)),
+            Err(EventSignatureError::UntrustedKid(kid)) if kid == forger_kid
+        ));
+    }
+}
@@ -66,13 +66,17 @@ fn create_type_validation_rb() -> ReasoningBit {
             Expression::TypeEquals("ubl/invite".to_string()),
             Expression::TypeEquals("ubl/ai.passport".to_string()),
             Expression::TypeEquals("ubl/wasm.module".to_string()),
+            Expression::TypeEquals("ubl/wasm.allowlist".to_string()),
             Expression::TypeEquals("ubl/verification".to_string()),
             Expression::TypeEquals("ubl/advisory".to_string()),
             Expression::TypeEquals("ubl/adapter".to_string()),
             Expression::TypeEquals("ubl/membership".to_string()),
             Expression::TypeEquals("ubl/revoke".to_string()),
+            Expression::TypeEquals("ubl/token.revoke".to_string()),
             Expression::TypeEquals("ubl/key.rotate".to_string()),
             Expression::TypeEquals("ubl/document".to_string()),
+            Expression::TypeEquals("ubl/merge".to_string()),
+            Expression::TypeEquals("ubl/annotation".to_string()),
             Expression::TypeEquals("audit/report.request.v1".to_string()),
             Expression::TypeEquals("audit/ledger.snapshot.request.v1".to_string()),
             Expression::TypeEquals("ledger/segment.compact.v1".to_string()),
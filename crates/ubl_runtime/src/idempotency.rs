@@ -4,6 +4,11 @@
 //! - Key = `(@type, @ver, @world, @id)` extracted from chip body.
 //! - On replay, return the **same receipt/output** — no re-execution.
 //! - Lookup happens in Gate/Pipeline before TR/WF.
+//! - A chip may bound its replay window with `_idem_ttl_secs`; once the
+//!   window closes the key executes afresh and the new result replaces it.
+//! - A client may also send an `Idempotency-Key` header, scoped to `@world`.
+//!   It replays only the payload it was first used with; any other payload
+//!   under a live key is a conflict.
 //!
 //! The store is in-memory (HashMap behind RwLock). Production deployments
 //! can swap in a persistent backend via the `IdempotencyBackend` trait.
@@ -13,6 +18,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// `@type` slot of a key built by [`IdempotencyKey::from_client_key`].
+pub const CLIENT_KEY_TYPE: &str = "idempotency-key";
+
 /// The four-part idempotency key for command chips.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct IdempotencyKey {
@@ -43,6 +51,17 @@ impl IdempotencyKey {
         })
     }
 
+    /// Key for a client `Idempotency-Key` header within `world`. The empty
+    /// `@ver` keeps it apart from every chip key, which needs a version.
+    pub fn from_client_key(world: &str, key: &str) -> Self {
+        Self {
+            at_type: CLIENT_KEY_TYPE.to_string(),
+            at_ver: String::new(),
+            at_world: world.to_string(),
+            at_id: key.to_string(),
+        }
+    }
+
     /// Canonical string representation for logging/metrics.
     pub fn to_string_key(&self) -> String {
         format!(
@@ -82,6 +101,19 @@ pub struct CachedResult {
     pub chain: Vec<String>,
     /// Timestamp of original execution.
     pub created_at: String,
+    /// End of the replay window (unix seconds), set by `_idem_ttl_secs`;
+    /// `None` replays indefinitely.
+    pub expires_at: Option<i64>,
+    /// Canonical request CID, set for client `Idempotency-Key` entries.
+    pub fingerprint: Option<String>,
+}
+
+impl CachedResult {
+    /// Whether the replay window has closed, so the key may be re-executed.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|at| at <= chrono::Utc::now().timestamp())
+    }
 }
 
 /// In-memory idempotency store.
@@ -235,6 +267,8 @@ mod tests {
             decision: "Allow".into(),
             chain: vec!["b3:wa".into(), "b3:tr".into(), "b3:wf".into()],
             created_at: chrono::Utc::now().to_rfc3339(),
+            expires_at: None,
+            fingerprint: None,
         };
 
         assert!(!store.contains(&key).await);
@@ -268,6 +302,8 @@ mod tests {
                         decision: "Allow".into(),
                         chain: vec![],
                         created_at: chrono::Utc::now().to_rfc3339(),
+                        expires_at: None,
+                        fingerprint: None,
                     },
                 )
                 .await;
@@ -294,6 +330,8 @@ mod tests {
                     decision: "Allow".into(),
                     chain: vec![],
                     created_at: chrono::Utc::now().to_rfc3339(),
+                    expires_at: None,
+                    fingerprint: None,
                 },
             )
             .await;
@@ -308,6 +346,8 @@ mod tests {
                     decision: "Deny".into(),
                     chain: vec![],
                     created_at: chrono::Utc::now().to_rfc3339(),
+                    expires_at: None,
+                    fingerprint: None,
                 },
             )
             .await;
@@ -335,6 +375,8 @@ mod tests {
             decision: "Allow".into(),
             chain: vec!["b3:wa1".into(), "b3:tr1".into(), "b3:wf1".into()],
             created_at: chrono::Utc::now().to_rfc3339(),
+            expires_at: None,
+            fingerprint: None,
         };
 
         store.put(key.clone(), original).await;
@@ -9,13 +9,18 @@
 //! 3. Array length ≤ MAX_ARRAY_LEN (10_000)
 //! 4. No duplicate keys
 //! 5. Valid UTF-8 (enforced by serde_json, but we check raw bytes too)
-//! 6. Required anchors: @type, @world
+//! 6. Required anchors: @type, @world (or `@world_candidates`)
 //! 7. No raw floats (UNC-1 §3/§6: use @num atoms instead)
 //! 8. Strict `@num` atom validation (UNC-1 shape + field types)
-//! 9. Reject integer literals outside i64 range (to match NRF canonical encoder)
+//! 9. Numbers outside i64 follow `UBL_CANON_NUMBER_POLICY`: rejected under
+//!    `strict_i64` (the default), accepted under `big_decimal_string` and
+//!    `json_number`, which the NRF canonical encoder can represent
+//! 10. With `UBL_REQUIRE_CHIP_SIGNATURE=1`, the chip's own `signature` must
+//!     verify against its `kid` (see [`verify_chip_signature`])
 
 use serde_json::Value;
 use std::collections::HashSet;
+use ubl_ai_nrf1::CanonNumberPolicy;
 
 pub const MAX_BODY_BYTES: usize = 1_048_576; // 1 MB
 pub const MAX_DEPTH: usize = 32;
@@ -62,6 +67,10 @@ pub enum KnockError {
     InputNormalization(String),
     #[error("KNOCK-012: schema validation failed: {0}")]
     SchemaValidation(String),
+    #[error("SIGNATURE_REQUIRED: chip must carry `signature` and `kid`")]
+    SignatureRequired,
+    #[error("SIGNATURE_INVALID: {0}")]
+    SignatureInvalid(String),
 }
 
 /// Validate raw bytes before JSON parsing.
@@ -86,17 +95,21 @@ pub fn knock_parsed(value: &Value) -> Result<(), KnockError> {
         .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
         .unwrap_or(false);
 
-    knock_parsed_with_options(value, require_unc1)
+    knock_parsed_with_options(value, require_unc1, CanonNumberPolicy::from_env())
 }
 
-fn knock_parsed_with_options(value: &Value, require_unc1: bool) -> Result<(), KnockError> {
+fn knock_parsed_with_options(
+    value: &Value,
+    require_unc1: bool,
+    policy: CanonNumberPolicy,
+) -> Result<(), KnockError> {
     let obj = value.as_object().ok_or(KnockError::NotObject)?;
 
     // Required anchors
     if !obj.contains_key("@type") {
         return Err(KnockError::MissingAnchor("@type"));
     }
-    if !obj.contains_key("@world") {
+    if !obj.contains_key("@world") && !obj.contains_key("@world_candidates") {
         return Err(KnockError::MissingAnchor("@world"));
     }
 
@@ -107,7 +120,8 @@ fn knock_parsed_with_options(value: &Value, require_unc1: bool) -> Result<(), Kn
     // UNC-1 strict path:
     // - validate @num objects
     // - optionally require every numeric literal to be represented as @num
-    check_numeric_nodes(value, "$", require_unc1)?;
+    // - otherwise non-i64 literals must be encodable under `policy`
+    check_numeric_nodes(value, "$", require_unc1, policy)?;
     validate_type_specific_schema(value)?;
 
     Ok(())
@@ -116,6 +130,20 @@ fn knock_parsed_with_options(value: &Value, require_unc1: bool) -> Result<(), Kn
 /// Full KNOCK: raw bytes → parse → structural validation.
 /// Returns the parsed Value on success.
 pub fn knock(bytes: &[u8]) -> Result<Value, KnockError> {
+    knock_with_signer(bytes).map(|(value, _)| value)
+}
+
+/// [`knock`], also returning the DID that signed the chip. Signatures are
+/// only checked when `UBL_REQUIRE_CHIP_SIGNATURE` is on; otherwise the
+/// signer is `None` even for a body that carries one.
+pub fn knock_with_signer(bytes: &[u8]) -> Result<(Value, Option<String>), KnockError> {
+    knock_with_signature_policy(bytes, require_chip_signature())
+}
+
+fn knock_with_signature_policy(
+    bytes: &[u8],
+    require_signature: bool,
+) -> Result<(Value, Option<String>), KnockError> {
     knock_raw(bytes)?;
 
     // Parse JSON (also validates UTF-8 at serde level)
@@ -133,7 +161,53 @@ pub fn knock(bytes: &[u8]) -> Result<Value, KnockError> {
     // Check for duplicate keys (requires re-scanning raw bytes)
     check_duplicate_keys(bytes)?;
 
-    Ok(value)
+    let signer_did = if require_signature {
+        Some(verify_chip_signature(&value)?)
+    } else {
+        None
+    };
+
+    Ok((value, signer_did))
+}
+
+fn require_chip_signature() -> bool {
+    std::env::var("UBL_REQUIRE_CHIP_SIGNATURE")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Verify a chip's own signature and return the signer DID.
+///
+/// The body carries `kid` (`did:key:z...#ed25519`, or the bare DID) and
+/// `signature`, an `ubl_kms::sign_canonical` signature in the
+/// `ubl-chip/v1` domain over the normalized body without `signature`.
+pub fn verify_chip_signature(value: &Value) -> Result<String, KnockError> {
+    let obj = value.as_object().ok_or(KnockError::NotObject)?;
+    let (Some(signature), Some(kid)) = (
+        obj.get("signature").and_then(|v| v.as_str()),
+        obj.get("kid").and_then(|v| v.as_str()),
+    ) else {
+        return Err(KnockError::SignatureRequired);
+    };
+    let did = kid.split_once('#').map_or(kid, |(did, _)| did);
+    let vk = ubl_kms::verifying_key_from_did(did)
+        .map_err(|e| KnockError::SignatureInvalid(format!("kid {:?}: {}", kid, e)))?;
+
+    let mut unsigned = obj.clone();
+    unsigned.remove("signature");
+    match ubl_kms::verify_canonical(
+        &vk,
+        &Value::Object(unsigned),
+        ubl_kms::domain::CHIP,
+        signature,
+    ) {
+        Ok(true) => Ok(did.to_string()),
+        Ok(false) => Err(KnockError::SignatureInvalid(format!(
+            "signature does not verify for kid {:?}",
+            kid
+        ))),
+        Err(e) => Err(KnockError::SignatureInvalid(e.to_string())),
+    }
 }
 
 fn map_normalization_error(err: anyhow::Error) -> KnockError {
@@ -277,25 +351,32 @@ fn check_depth(value: &Value, depth: usize) -> Result<(), KnockError> {
     Ok(())
 }
 
-fn check_numeric_nodes(value: &Value, path: &str, require_unc1: bool) -> Result<(), KnockError> {
+fn check_numeric_nodes(
+    value: &Value,
+    path: &str,
+    require_unc1: bool,
+    policy: CanonNumberPolicy,
+) -> Result<(), KnockError> {
     match value {
         Value::Number(n) => {
             if n.is_i64() {
                 if require_unc1 {
                     return Err(KnockError::NumericLiteralNotAllowed(path.to_string()));
                 }
-            } else if n.is_u64() {
-                return Err(KnockError::InputNormalization(format!(
-                    "numeric literal out of i64 range at {}",
-                    path
-                )));
-            } else {
+            } else if require_unc1 || policy == CanonNumberPolicy::StrictI64 {
+                // The other policies encode every finite JSON number.
+                if n.is_u64() {
+                    return Err(KnockError::InputNormalization(format!(
+                        "numeric literal out of i64 range at {}",
+                        path
+                    )));
+                }
                 return Err(KnockError::RawFloat(format!("{} at {}", n, path)));
             }
         }
         Value::Array(arr) => {
             for (idx, v) in arr.iter().enumerate() {
-                check_numeric_nodes(v, &format!("{}[{}]", path, idx), require_unc1)?;
+                check_numeric_nodes(v, &format!("{}[{}]", path, idx), require_unc1, policy)?;
             }
         }
         Value::Object(map) => {
@@ -305,7 +386,7 @@ fn check_numeric_nodes(value: &Value, path: &str, require_unc1: bool) -> Result<
             }
 
             for (k, v) in map {
-                check_numeric_nodes(v, &format!("{}.{}", path, k), require_unc1)?;
+                check_numeric_nodes(v, &format!("{}.{}", path, k), require_unc1, policy)?;
             }
         }
         _ => {}
@@ -852,6 +933,49 @@ mod tests {
         });
     }
 
+    #[test]
+    fn knock_number_policy_governs_non_i64_literals() {
+        let body = |raw: &str| -> Value {
+            serde_json::from_str(&format!(
+                r#"{{"@type":"ubl/test","@world":"a/x/t/y","n":{}}}"#,
+                raw
+            ))
+            .unwrap()
+        };
+        let boundary = ["9007199254740992", "-0.0", "1e10", "9223372036854775808"];
+
+        // 2^53 is an i64 literal, and serde_json reads `-0` as `0`:
+        // canonical in every mode.
+        for policy in [
+            CanonNumberPolicy::StrictI64,
+            CanonNumberPolicy::BigDecimalString,
+            CanonNumberPolicy::JsonNumber,
+        ] {
+            for raw in [boundary[0], "-0"] {
+                assert!(knock_parsed_with_options(&body(raw), false, policy).is_ok());
+            }
+        }
+        for raw in &boundary[1..] {
+            let strict = knock_parsed_with_options(&body(raw), false, CanonNumberPolicy::StrictI64);
+            assert!(
+                matches!(
+                    strict,
+                    Err(KnockError::RawFloat(_) | KnockError::InputNormalization(_))
+                ),
+                "strict_i64 must reject {}",
+                raw
+            );
+            for policy in [
+                CanonNumberPolicy::BigDecimalString,
+                CanonNumberPolicy::JsonNumber,
+            ] {
+                assert!(knock_parsed_with_options(&body(raw), false, policy).is_ok());
+                // REQUIRE_UNC1_NUMERIC still wins over the policy.
+                assert!(knock_parsed_with_options(&body(raw), true, policy).is_err());
+            }
+        }
+    }
+
     #[test]
     fn knock_require_unc1_numeric_accepts_num_atoms() {
         with_env_var("REQUIRE_UNC1_NUMERIC", Some("true"), || {
@@ -949,4 +1073,37 @@ mod tests {
         let err = knock(&bytes).unwrap_err();
         assert!(matches!(err, KnockError::SchemaValidation(_)));
     }
+
+    #[test]
+    fn knock_chip_signature_policy_verifies_kid_and_returns_signer() {
+        let sk = ubl_kms::Ed25519SigningKey::from_bytes(&[7u8; 32]);
+        let kid = ubl_kms::kid_from_verifying_key(&sk.verifying_key());
+        let mut body = json!({
+            "@type": "ubl/document",
+            "@id": "signed-doc",
+            "@ver": "1.0",
+            "@world": "a/app/t/ten",
+            "kid": kid,
+            "title": "signed"
+        });
+        let signature = ubl_kms::sign_canonical(&sk, &body, ubl_kms::domain::CHIP).unwrap();
+        body["signature"] = json!(signature);
+        let signed = serde_json::to_vec(&body).unwrap();
+        body["title"] = json!("tampered");
+        let tampered = serde_json::to_vec(&body).unwrap();
+
+        let (_, signer) = knock_with_signature_policy(&signed, true).unwrap();
+        assert_eq!(signer.as_deref(), kid.strip_suffix("#ed25519"));
+        assert!(matches!(
+            knock_with_signature_policy(&tampered, true).unwrap_err(),
+            KnockError::SignatureInvalid(_)
+        ));
+        let err = knock_with_signature_policy(&valid_chip(), true).unwrap_err();
+        assert!(matches!(err, KnockError::SignatureRequired));
+        assert!(err.to_string().starts_with("SIGNATURE_REQUIRED:"));
+        assert_eq!(
+            knock_with_signature_policy(&tampered, false).unwrap().1,
+            None
+        );
+    }
 }
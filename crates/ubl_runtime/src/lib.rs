@@ -5,6 +5,7 @@
 
 pub mod advisory;
 pub mod ai_passport;
+pub mod annotation_chip;
 pub mod audit_chip;
 pub mod auth;
 pub mod authorship;
@@ -13,6 +14,7 @@ pub mod circuit;
 pub mod durable_store;
 pub mod error_response;
 pub mod event_bus;
+pub mod event_signature;
 pub mod genesis;
 pub mod idempotency;
 pub mod key_rotation;
@@ -20,17 +22,23 @@ pub mod knock;
 pub mod ledger;
 pub mod llm_observer;
 pub mod manifest;
+pub mod merge_chip;
 pub mod meta_chip;
 pub mod outbox_dispatcher;
 pub mod pipeline;
 pub mod policy_bit;
+pub mod policy_counters;
 pub mod policy_loader;
 pub mod policy_lock;
+pub mod policy_snapshot;
+pub mod post_wf_hook;
 pub mod rate_limit;
 pub mod reasoning_bit;
+pub mod receipt_bundle;
 pub mod rich_url;
 pub mod runtime_cert;
 pub mod silicon_chip;
+pub mod token_revoke;
 pub mod transition_registry;
 pub mod wasm_adapter;
 
@@ -40,7 +48,7 @@ pub use policy_bit::{PolicyBit, PolicyScope};
 pub use reasoning_bit::{Decision, Expression, ReasoningBit};
 
 // Re-export receipt types for convenience
-pub use advisory::{Advisory, AdvisoryEngine, AdvisoryHook};
+pub use advisory::{Advisory, AdvisoryEngine, AdvisoryHook, DenySpikePolicy};
 pub use ai_passport::AiPassport;
 pub use auth::{
     is_onboarding_type, validate_onboarding_chip, AppRegistration, AuthEngine, AuthError,
@@ -232,6 +232,49 @@ pub fn default_chip_types() -> Vec<ChipTypeSpec> {
             ],
             required_cap: None,
         },
+        ChipTypeSpec {
+            chip_type: "ubl/merge".into(),
+            description: "Merge two or more lineages (same world and type) into one chip".into(),
+            required_fields: vec![FieldSpec {
+                name: "parents".into(),
+                field_type: "array".into(),
+                description: "CIDs of the merged parent chips (at least two)".into(),
+            }],
+            optional_fields: vec![],
+            required_cap: None,
+        },
+        ChipTypeSpec {
+            chip_type: "ubl/annotation".into(),
+            description: "Append-only operational notes attached to a chip in the same world"
+                .into(),
+            required_fields: vec![
+                FieldSpec {
+                    name: "target_cid".into(),
+                    field_type: "string".into(),
+                    description: "CID of the annotated chip".into(),
+                },
+                FieldSpec {
+                    name: "notes".into(),
+                    field_type: "object".into(),
+                    description: "Key/value string notes (review notes, ticket links)".into(),
+                },
+            ],
+            optional_fields: vec![],
+            required_cap: None,
+        },
+        ChipTypeSpec {
+            chip_type: "ubl/wasm.allowlist".into(),
+            description:
+                "WASM adapter hashes allowed to run in this world when UBL_WASM_ALLOWLIST is unset"
+                    .into(),
+            required_fields: vec![FieldSpec {
+                name: "wasm_sha256".into(),
+                field_type: "array".into(),
+                description: "Approved adapter module SHA-256 hashes (64 hex chars each)".into(),
+            }],
+            optional_fields: vec![],
+            required_cap: Some("wasm:allowlist".into()),
+        },
         ChipTypeSpec {
             chip_type: "audit/report.request.v1".into(),
             description: "Request an on-demand audit report from aggregated views".into(),
@@ -364,6 +407,50 @@ impl GateManifest {
             }
         }));
 
+        // POST /v1/chips/simulate
+        paths.insert(
+            "/v1/chips/simulate".into(),
+            json!({
+                "post": {
+                    "operationId": "simulateChip",
+                    "summary": "Run a chip through the pipeline without persisting it; idempotency and the world and canon rate limits are bypassed",
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": { "type": "object" } } }
+                    },
+                    "responses": {
+                        "200": { "description": "Simulated decision with signed receipt (`simulated: true`)" },
+                        "401": { "description": "Write authorization required" },
+                        "422": { "description": "Invalid chip or KNOCK failure" },
+                        "429": { "description": "Per-client-IP rate limit exceeded" },
+                        "500": { "description": "Internal error" }
+                    }
+                }
+            }),
+        );
+
+        // POST /v1/chips/batch
+        paths.insert(
+            "/v1/chips/batch".into(),
+            json!({
+                "post": {
+                    "operationId": "createChipBatch",
+                    "summary": "Submit an array of chips whose receipts commit in one transaction; any deny or error rolls the whole batch back",
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": { "type": "array", "items": { "type": "object" }, "maxItems": crate::pipeline::MAX_BATCH_CHIPS } } }
+                    },
+                    "responses": {
+                        "200": { "description": "Every entry committed; per-entry decision and receipt CID in `results`" },
+                        "401": { "description": "Write authorization required (`failed_index` names the entry)" },
+                        "403": { "description": "An entry was denied; nothing was committed" },
+                        "422": { "description": "Invalid or oversized batch, `Idempotency-Key` sent, or a chip or KNOCK failure; nothing was committed" },
+                        "500": { "description": "Internal error" }
+                    }
+                }
+            }),
+        );
+
         // GET /v1/chips/{cid}
         paths.insert(
             "/v1/chips/{cid}".into(),
@@ -378,12 +465,18 @@ impl GateManifest {
                         "schema": { "type": "string", "pattern": "^b3:" }
                     }],
                     "responses": {
-                        "200": { "description": "Chip data", "headers": {
+                        "200": { "description": "Chip data; `Accept: application/x-nrf1` or `application/cbor` returns the chip body's NRF-1.1 bytes, the input of its CID", "headers": {
                             "ETag": { "schema": { "type": "string" } },
-                            "Cache-Control": { "schema": { "type": "string" } }
+                            "Cache-Control": { "schema": { "type": "string" } },
+                            "Vary": { "schema": { "type": "string" } }
+                        }, "content": {
+                            "application/json": {},
+                            "application/x-nrf1": { "schema": { "type": "string", "format": "binary" } },
+                            "application/cbor": { "schema": { "type": "string", "format": "binary" } }
                         }},
                         "304": { "description": "Not Modified (ETag match)" },
-                        "404": { "description": "Chip not found" }
+                        "404": { "description": "Chip not found" },
+                        "406": { "description": "Accept allows none of application/json, application/x-nrf1 and application/cbor" }
                     }
                 }
             }),
@@ -433,6 +526,56 @@ impl GateManifest {
             }),
         );
 
+        // GET /v1/chips/{cid}/lineage
+        paths.insert(
+            "/v1/chips/{cid}/lineage".into(),
+            json!({
+                "get": {
+                    "operationId": "getChipLineage",
+                    "summary": "Bounded provenance graph (parent/child/supersedes/tombstone edges)",
+                    "parameters": [
+                        {
+                            "name": "cid", "in": "path", "required": true,
+                            "schema": { "type": "string" }
+                        },
+                        {
+                            "name": "depth", "in": "query", "required": false,
+                            "schema": { "type": "integer", "minimum": 0, "maximum": 10, "default": 3 }
+                        }
+                    ],
+                    "responses": {
+                        "200": { "description": "Lineage nodes (chip type, decision) and edges" },
+                        "404": { "description": "Chip not found" }
+                    }
+                }
+            }),
+        );
+
+        // GET /v1/chips/{cid}/children
+        paths.insert(
+            "/v1/chips/{cid}/children".into(),
+            json!({
+                "get": {
+                    "operationId": "getChipChildren",
+                    "summary": "Chips that declare this chip as a parent (optionally every descendant)",
+                    "parameters": [
+                        {
+                            "name": "cid", "in": "path", "required": true,
+                            "schema": { "type": "string" }
+                        },
+                        {
+                            "name": "recursive", "in": "query", "required": false,
+                            "schema": { "type": "boolean", "default": false }
+                        }
+                    ],
+                    "responses": {
+                        "200": { "description": "Children with chip type and created_at" },
+                        "404": { "description": "Chip not found" }
+                    }
+                }
+            }),
+        );
+
         // GET /v1/runtime/attestation
         paths.insert(
             "/v1/runtime/attestation".into(),
@@ -460,12 +603,18 @@ impl GateManifest {
                         "schema": { "type": "string", "pattern": "^b3:" }
                     }],
                     "responses": {
-                        "200": { "description": "Receipt JSON", "headers": {
+                        "200": { "description": "Receipt JSON; `Accept: application/x-nrf1` or `application/cbor` returns its NRF-1.1 bytes (not the CID input: the CID blanks `sig`, `@id` and `receipt_cid`)", "headers": {
                             "ETag": { "schema": { "type": "string" } },
-                            "Cache-Control": { "schema": { "type": "string" } }
+                            "Cache-Control": { "schema": { "type": "string" } },
+                            "Vary": { "schema": { "type": "string" } }
+                        }, "content": {
+                            "application/json": {},
+                            "application/x-nrf1": { "schema": { "type": "string", "format": "binary" } },
+                            "application/cbor": { "schema": { "type": "string", "format": "binary" } }
                         }},
                         "304": { "description": "Not Modified (ETag match)" },
                         "404": { "description": "Receipt not found" },
+                        "406": { "description": "Accept allows none of application/json, application/x-nrf1 and application/cbor" },
                         "503": { "description": "Receipt store unavailable" }
                     }
                 }
@@ -491,6 +640,46 @@ impl GateManifest {
             }),
         );
 
+        // GET /v1/receipts/{cid}/bundle
+        paths.insert(
+            "/v1/receipts/{cid}/bundle".into(),
+            json!({
+                "get": {
+                    "operationId": "getReceiptBundle",
+                    "summary": "Download a gate-signed bundle (chip, receipt, stage chain) for offline audit",
+                    "parameters": [{
+                        "name": "cid", "in": "path", "required": true,
+                        "schema": { "type": "string" }
+                    }],
+                    "responses": {
+                        "200": { "description": "Signed receipt bundle" },
+                        "404": { "description": "Receipt not found" },
+                        "422": { "description": "Auth chain or chip CID tampered" }
+                    }
+                }
+            }),
+        );
+
+        // POST /v1/verify/bundle
+        paths.insert(
+            "/v1/verify/bundle".into(),
+            json!({
+                "post": {
+                    "operationId": "verifyReceiptBundle",
+                    "summary": "Verify a receipt bundle signature, auth chain and chip CID",
+                    "requestBody": {
+                        "required": true,
+                        "content": { "application/json": { "schema": { "type": "object" } } }
+                    },
+                    "responses": {
+                        "200": { "description": "Bundle verified" },
+                        "400": { "description": "Malformed bundle" },
+                        "422": { "description": "Bundle failed verification" }
+                    }
+                }
+            }),
+        );
+
         // GET /v1/receipts/{cid}/narrate
         paths.insert(
             "/v1/receipts/{cid}/narrate".into(),
@@ -603,6 +792,40 @@ impl GateManifest {
             }
         }));
 
+        // ubl.chip.search / ubl.receipt.list — read-only enumeration
+        tools.push(json!({
+            "name": "ubl.chip.search",
+            "description": "Search stored chips by type, tags and creation time (same filters as GET /v1/chips/search). Needs a bearer token or admin key; results stay within the token's world and never include token chips.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "chip_type": { "type": "string", "description": "Exact chip @type" },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Tags that must all be present (e.g. world:a/acme/t/prod)"
+                    },
+                    "after": { "type": "string", "description": "RFC-3339 lower bound on creation time" },
+                    "before": { "type": "string", "description": "RFC-3339 upper bound on creation time" },
+                    "limit": { "type": "integer", "description": "Page size" },
+                    "offset": { "type": "integer", "description": "Rows to skip" }
+                }
+            }
+        }));
+        tools.push(json!({
+            "name": "ubl.receipt.list",
+            "description": "List persisted receipt summaries, newest first (same filters as GET /v1/receipts).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "decision": { "type": "string", "enum": ["allow", "deny", "quarantine"] },
+                    "world": { "type": "string", "description": "Receipt @world" },
+                    "limit": { "type": "integer", "description": "Page size" },
+                    "cursor": { "type": "string", "description": "next_cursor from the previous page" }
+                }
+            }
+        }));
+
         // ubl.verify — verify chip integrity
         tools.push(json!({
             "name": "ubl.verify",
@@ -678,7 +901,24 @@ impl GateManifest {
                 "type": "object",
                 "properties": {
                     "bytecode_hex": { "type": "string", "description": "TLV bytecode as hex string" },
-                    "fuel_limit": { "type": "integer", "description": "Optional VM fuel limit" }
+                    "fuel_limit": { "type": "integer", "description": "Optional VM fuel limit" },
+                    "inputs": {
+                        "type": "array",
+                        "description": "JSON values seeded as VM inputs (NRF-1 bytes in CAS; PushInput index order)"
+                    },
+                    "ghost": { "type": "boolean", "description": "Run with VmConfig.ghost (default false)" }
+                },
+                "required": ["bytecode_hex"]
+            }
+        }));
+
+        tools.push(json!({
+            "name": "ubl.disasm",
+            "description": "Disassemble RB-VM bytecode into an opcode listing (same output as `ublx disasm --json`).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "bytecode_hex": { "type": "string", "description": "TLV bytecode as hex string" }
                 },
                 "required": ["bytecode_hex"]
             }
@@ -807,7 +1047,9 @@ impl GateManifest {
                         "issued_by": { "type": "string" },
                         "issued_at": { "type": "string", "format": "date-time" },
                         "expires_at": { "type": "string", "format": "date-time" },
-                        "signature": { "type": "string" }
+                        "signature": { "type": "string" },
+                        "delegate": { "type": "string" },
+                        "parent": { "type": "object" }
                     },
                     "required": ["action", "audience", "issued_by", "signature"]
                 }),
@@ -878,12 +1120,18 @@ mod tests {
         let spec = m.to_openapi();
         let paths = spec["paths"].as_object().unwrap();
         assert!(paths.contains_key("/v1/chips"));
+        assert!(paths.contains_key("/v1/chips/simulate"));
+        assert!(paths.contains_key("/v1/chips/batch"));
         assert!(paths.contains_key("/v1/chips/{cid}"));
         assert!(paths.contains_key("/v1/cas/{cid}"));
         assert!(paths.contains_key("/v1/chips/{cid}/verify"));
+        assert!(paths.contains_key("/v1/chips/{cid}/lineage"));
+        assert!(paths.contains_key("/v1/chips/{cid}/children"));
         assert!(paths.contains_key("/v1/runtime/attestation"));
         assert!(paths.contains_key("/v1/receipts/{cid}"));
         assert!(paths.contains_key("/v1/receipts/{cid}/trace"));
+        assert!(paths.contains_key("/v1/receipts/{cid}/bundle"));
+        assert!(paths.contains_key("/v1/verify/bundle"));
         assert!(paths.contains_key("/v1/receipts/{cid}/narrate"));
     }
 
@@ -929,6 +1177,9 @@ mod tests {
         let headers = &get_chip["responses"]["200"]["headers"];
         assert!(headers.get("ETag").is_some());
         assert!(headers.get("Cache-Control").is_some());
+        let content = &get_chip["responses"]["200"]["content"];
+        assert!(content.get("application/x-nrf1").is_some());
+        assert!(content.get("application/cbor").is_some());
     }
 
     #[test]
@@ -944,6 +1195,8 @@ mod tests {
         assert!(tool_names.contains(&"ubl.verify"));
         assert!(tool_names.contains(&"registry.listTypes"));
         assert!(tool_names.contains(&"ubl.narrate"));
+        assert!(tool_names.contains(&"ubl.chip.search"));
+        assert!(tool_names.contains(&"ubl.receipt.list"));
     }
 
     #[test]
@@ -0,0 +1,176 @@
+//! `ubl/merge` — join two or more lineages into one DAG node.
+//!
+//! A merge chip lists its `parents` (at least two distinct chip CIDs). CHECK
+//! requires every parent to exist, live in the merge chip's `@world` and
+//! share a single `@type`; the receipt records the merge under
+//! `effects.merge`.
+
+use serde_json::{json, Value};
+
+pub const TYPE_MERGE: &str = "ubl/merge";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MergeError {
+    #[error("ubl/merge requires at least two parents")]
+    TooFewParents,
+    #[error("ubl/merge parent '{0}' is not a b3: CID")]
+    InvalidParent(String),
+    #[error("ubl/merge lists parent '{0}' more than once")]
+    DuplicateParent(String),
+    #[error("ubl/merge parent '{cid}' is in world '{world}', expected '{expected}'")]
+    WorldMismatch {
+        cid: String,
+        world: String,
+        expected: String,
+    },
+    #[error("ubl/merge parent '{cid}' is '{chip_type}', expected '{expected}'")]
+    TypeMismatch {
+        cid: String,
+        chip_type: String,
+        expected: String,
+    },
+}
+
+/// Parsed body of a `ubl/merge` chip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeChip {
+    pub parents: Vec<String>,
+}
+
+/// What CHECK learned about one parent.
+#[derive(Debug, Clone)]
+pub struct MergeParent {
+    pub cid: String,
+    pub chip_type: String,
+    pub world: String,
+}
+
+impl MergeChip {
+    pub fn parse(body: &Value) -> Result<Self, MergeError> {
+        let raw = body
+            .get("parents")
+            .and_then(|v| v.as_array())
+            .ok_or(MergeError::TooFewParents)?;
+        let mut parents: Vec<String> = Vec::with_capacity(raw.len());
+        for entry in raw {
+            let cid = entry
+                .as_str()
+                .filter(|s| s.starts_with("b3:"))
+                .ok_or_else(|| MergeError::InvalidParent(entry.to_string()))?;
+            if parents.iter().any(|p| p == cid) {
+                return Err(MergeError::DuplicateParent(cid.to_string()));
+            }
+            parents.push(cid.to_string());
+        }
+        if parents.len() < 2 {
+            return Err(MergeError::TooFewParents);
+        }
+        Ok(Self { parents })
+    }
+
+    /// Check that resolved parents share `world` and one chip type; returns
+    /// that type.
+    pub fn check_compatible(
+        &self,
+        world: &str,
+        resolved: &[MergeParent],
+    ) -> Result<String, MergeError> {
+        let Some(first) = resolved.first() else {
+            return Err(MergeError::TooFewParents);
+        };
+        for parent in resolved {
+            if parent.world != world {
+                return Err(MergeError::WorldMismatch {
+                    cid: parent.cid.clone(),
+                    world: parent.world.clone(),
+                    expected: world.to_string(),
+                });
+            }
+            if parent.chip_type != first.chip_type {
+                return Err(MergeError::TypeMismatch {
+                    cid: parent.cid.clone(),
+                    chip_type: parent.chip_type.clone(),
+                    expected: first.chip_type.clone(),
+                });
+            }
+        }
+        Ok(first.chip_type.clone())
+    }
+
+    /// Receipt effect recorded for an accepted merge.
+    pub fn effect(&self, parent_type: &str) -> Value {
+        json!({
+            "parents": self.parents,
+            "parent_type": parent_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parent(cid: &str, chip_type: &str, world: &str) -> MergeParent {
+        MergeParent {
+            cid: cid.to_string(),
+            chip_type: chip_type.to_string(),
+            world: world.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_requires_two_distinct_cids() {
+        let ok = MergeChip::parse(&json!({"parents": ["b3:a", "b3:b"]})).unwrap();
+        assert_eq!(ok.parents, vec!["b3:a", "b3:b"]);
+        assert_eq!(
+            MergeChip::parse(&json!({"parents": ["b3:a"]})),
+            Err(MergeError::TooFewParents)
+        );
+        assert_eq!(
+            MergeChip::parse(&json!({"parents": ["b3:a", "b3:a"]})),
+            Err(MergeError::DuplicateParent("b3:a".into()))
+        );
+        assert!(matches!(
+            MergeChip::parse(&json!({"parents": ["b3:a", 7]})),
+            Err(MergeError::InvalidParent(_))
+        ));
+    }
+
+    #[test]
+    fn compatibility_rejects_mixed_world_or_type() {
+        let merge = MergeChip::parse(&json!({"parents": ["b3:a", "b3:b"]})).unwrap();
+        let world = "a/acme/t/prod";
+        assert_eq!(
+            merge
+                .check_compatible(
+                    world,
+                    &[
+                        parent("b3:a", "acme/doc", world),
+                        parent("b3:b", "acme/doc", world)
+                    ]
+                )
+                .unwrap(),
+            "acme/doc"
+        );
+        assert!(matches!(
+            merge.check_compatible(
+                world,
+                &[
+                    parent("b3:a", "acme/doc", world),
+                    parent("b3:b", "acme/note", world)
+                ]
+            ),
+            Err(MergeError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            merge.check_compatible(
+                world,
+                &[
+                    parent("b3:a", "acme/doc", world),
+                    parent("b3:b", "acme/doc", "a/acme/t/dev")
+                ]
+            ),
+            Err(MergeError::WorldMismatch { .. })
+        ));
+    }
+}
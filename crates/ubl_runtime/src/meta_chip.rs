@@ -20,9 +20,12 @@ pub struct Kat {
     pub input: Value,
     /// Expected decision: "allow" or "deny".
     pub expected_decision: String,
-    /// Optional: expected error code if decision is "deny".
+    /// Optional: expected error code if decision is "deny" (exact match).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub expected_error: Option<String>,
+    /// Optional: substring of the error code or message if decision is "deny".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_error_contains: Option<String>,
 }
 
 /// Schema definition for a chip type (simplified JSON Schema subset).
@@ -38,6 +41,51 @@ pub struct TypeSchema {
     pub required_cap: Option<String>,
 }
 
+impl TypeSchema {
+    /// Required fields that `body` lacks (absent or `null`) or carries with a
+    /// value not of the declared `field_type`. Field types this subset does
+    /// not know only check presence.
+    pub fn violations(&self, body: &Value) -> Vec<String> {
+        let mut out = Vec::new();
+        for field in &self.required_fields {
+            match body.get(&field.name).filter(|v| !v.is_null()) {
+                None => out.push(format!("missing '{}'", field.name)),
+                Some(value) if !field_type_matches(&field.field_type, value) => out.push(format!(
+                    "'{}' expected {}, got {}",
+                    field.name,
+                    field.field_type,
+                    json_type_name(value)
+                )),
+                Some(_) => {}
+            }
+        }
+        out
+    }
+}
+
+fn field_type_matches(field_type: &str, value: &Value) -> bool {
+    match field_type {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" | "bool" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
 /// A field in a type schema.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SchemaField {
@@ -314,6 +362,21 @@ mod tests {
         assert_eq!(reg.type_version, "1.0");
     }
 
+    #[test]
+    fn schema_violations_report_missing_and_mistyped_fields() {
+        let schema = parse_register(&valid_register_body()).unwrap().schema;
+        assert!(schema
+            .violations(&json!({"amount": "1.00", "currency": "USD"}))
+            .is_empty());
+        assert_eq!(
+            schema.violations(&json!({"amount": 100, "currency": null, "notes": 7})),
+            vec![
+                "'amount' expected string, got number".to_string(),
+                "missing 'currency'".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn register_missing_target_type() {
         let body = json!({
@@ -406,12 +469,23 @@ mod tests {
                 "input": { "@type": "acme/invoice", "@id": "inv-2", "@ver": "1.0", "@world": "a/acme" },
                 "expected_decision": "deny",
                 "expected_error": "INVALID_CHIP"
+            },
+            {
+                "label": "missing amount names the field",
+                "input": { "@type": "acme/invoice", "@id": "inv-3", "@ver": "1.0", "@world": "a/acme" },
+                "expected_decision": "deny",
+                "expected_error_contains": "amount"
             }
         ]);
         let reg = parse_register(&body).unwrap();
-        assert_eq!(reg.kats.len(), 2);
+        assert_eq!(reg.kats.len(), 3);
         assert_eq!(reg.kats[1].expected_decision, "deny");
         assert_eq!(reg.kats[1].expected_error.as_deref(), Some("INVALID_CHIP"));
+        assert_eq!(reg.kats[1].expected_error_contains, None);
+        assert_eq!(
+            reg.kats[2].expected_error_contains.as_deref(),
+            Some("amount")
+        );
     }
 
     #[test]
@@ -494,6 +568,7 @@ mod tests {
             input: json!({"@type": "acme/test"}),
             expected_decision: "allow".into(),
             expected_error: None,
+            expected_error_contains: None,
         };
         let json = serde_json::to_value(&kat).unwrap();
         let kat2: Kat = serde_json::from_value(json).unwrap();
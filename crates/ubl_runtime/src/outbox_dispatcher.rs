@@ -1,13 +1,27 @@
 //! Durable outbox dispatcher with retry/backoff.
+//!
+//! Delivery contract: at-least-once plus receiver-side dedup. Every event
+//! carries a stable `delivery_id` that is reused on each retry. If a worker
+//! crashes after the receiver accepted the event but before the ack commits,
+//! the claim lease lapses and the event is redelivered with the same id; the
+//! receiver must treat a repeated `X-UBL-Delivery-Id` as already applied.
+//!
+//! An event whose `max_attempts`-th delivery fails is moved to `dead` with
+//! the handler's error instead of being retried forever.
 
-use crate::durable_store::{DurableError, DurableStore, OutboxEvent};
+use crate::durable_store::{
+    DurableError, DurableStore, OutboxEvent, DEFAULT_OUTBOX_LEASE_SECS, DEFAULT_OUTBOX_MAX_ATTEMPTS,
+};
 use std::future::Future;
+use tracing::warn;
 
 #[derive(Clone)]
 pub struct OutboxDispatcher {
     store: DurableStore,
     base_backoff_secs: i64,
     max_backoff_secs: i64,
+    lease_secs: i64,
+    max_attempts: i64,
 }
 
 impl OutboxDispatcher {
@@ -16,6 +30,8 @@ impl OutboxDispatcher {
             store,
             base_backoff_secs: 2,
             max_backoff_secs: 300,
+            lease_secs: DEFAULT_OUTBOX_LEASE_SECS,
+            max_attempts: DEFAULT_OUTBOX_MAX_ATTEMPTS,
         }
     }
 
@@ -25,6 +41,38 @@ impl OutboxDispatcher {
         self
     }
 
+    /// How long a claimed event stays inflight before another run may
+    /// reclaim it. Should exceed the worst-case delivery time.
+    pub fn with_lease(mut self, lease_secs: i64) -> Self {
+        self.lease_secs = lease_secs.max(0);
+        self
+    }
+
+    /// Failed deliveries allowed before an event goes `dead` (at least 1).
+    pub fn with_max_attempts(mut self, max_attempts: i64) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// `attempts` is the count before this claim: retry with backoff, or
+    /// dead-letter once this was the last allowed attempt.
+    fn fail(&self, event_id: i64, attempts: i64, error: &str) -> Result<(), DurableError> {
+        let tries = attempts.saturating_add(1);
+        if tries >= self.max_attempts {
+            warn!(
+                event_id,
+                attempts = tries,
+                error,
+                "outbox event dead-lettered"
+            );
+            return self.store.dead_outbox(event_id, error);
+        }
+        let factor = 2i64.saturating_pow((tries as u32).min(16));
+        let backoff = (self.base_backoff_secs.saturating_mul(factor)).min(self.max_backoff_secs);
+        let next = chrono::Utc::now().timestamp().saturating_add(backoff);
+        self.store.nack_outbox(event_id, next)
+    }
+
     /// Process a single outbox batch.
     ///
     /// `handler` returns `Ok(())` on delivered event, error string otherwise.
@@ -32,20 +80,13 @@ impl OutboxDispatcher {
     where
         F: FnMut(&OutboxEvent) -> Result<(), String>,
     {
-        let events = self.store.claim_outbox(limit)?;
+        let events = self.store.claim_outbox_with_lease(limit, self.lease_secs)?;
         let mut processed = 0usize;
 
         for event in events {
             match handler(&event) {
                 Ok(_) => self.store.ack_outbox(event.id)?,
-                Err(_) => {
-                    let attempts = event.attempts.saturating_add(1) as u32;
-                    let factor = 2i64.saturating_pow(attempts.min(16));
-                    let backoff =
-                        (self.base_backoff_secs.saturating_mul(factor)).min(self.max_backoff_secs);
-                    let next = chrono::Utc::now().timestamp().saturating_add(backoff);
-                    self.store.nack_outbox(event.id, next)?;
-                }
+                Err(e) => self.fail(event.id, event.attempts, &e)?,
             }
             processed += 1;
         }
@@ -63,7 +104,7 @@ impl OutboxDispatcher {
         F: FnMut(OutboxEvent) -> Fut,
         Fut: Future<Output = Result<(), String>>,
     {
-        let events = self.store.claim_outbox(limit)?;
+        let events = self.store.claim_outbox_with_lease(limit, self.lease_secs)?;
         let mut processed = 0usize;
 
         for event in events {
@@ -71,14 +112,7 @@ impl OutboxDispatcher {
             let attempts = event.attempts;
             match handler(event).await {
                 Ok(_) => self.store.ack_outbox(event_id)?,
-                Err(_) => {
-                    let tries = attempts.saturating_add(1) as u32;
-                    let factor = 2i64.saturating_pow(tries.min(16));
-                    let backoff =
-                        (self.base_backoff_secs.saturating_mul(factor)).min(self.max_backoff_secs);
-                    let next = chrono::Utc::now().timestamp().saturating_add(backoff);
-                    self.store.nack_outbox(event_id, next)?;
-                }
+                Err(e) => self.fail(event_id, attempts, &e)?,
             }
             processed += 1;
         }
@@ -108,6 +142,8 @@ mod tests {
             rt_hash: "b3:runtime".to_string(),
             decision: "allow".to_string(),
             idem_key: Some(idem_key.to_string()),
+            idem_expires_at: None,
+            client_idem: None,
             chain: vec!["b3:wa".into(), "b3:tr".into(), "b3:wf".into()],
             outbox_events: vec![NewOutboxEvent {
                 event_type: "emit_receipt".to_string(),
@@ -145,6 +181,79 @@ mod tests {
         assert_eq!(store.outbox_pending().unwrap(), 1);
     }
 
+    #[test]
+    fn dispatcher_dead_letters_after_max_attempts() {
+        let store = DurableStore::new(temp_dsn("dispatcher_dead.db")).unwrap();
+        seed_store_with_one_event(&store, "dead-1");
+        let dispatcher = OutboxDispatcher::new(store.clone())
+            .with_backoff(1, 1)
+            .with_max_attempts(2);
+
+        dispatcher
+            .run_once(8, |_event| Err("boom 1".to_string()))
+            .unwrap();
+        assert_eq!(store.outbox_pending().unwrap(), 1);
+        assert!(store.list_dead_outbox(8).unwrap().is_empty());
+
+        // Make the backoff due, then fail the last allowed attempt.
+        assert!(
+            store.claim_outbox_with_lease(8, 0).unwrap().is_empty(),
+            "still backing off"
+        );
+        store.nack_outbox(1, 0).unwrap();
+        dispatcher
+            .run_once(8, |_event| Err("boom 2".to_string()))
+            .unwrap();
+
+        assert_eq!(store.outbox_pending().unwrap(), 0);
+        let dead = store.list_dead_outbox(8).unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, 2);
+        assert_eq!(dead[0].last_error.as_deref(), Some("boom 2"));
+        assert_eq!(dispatcher.run_once(8, |_event| Ok(())).unwrap(), 0);
+    }
+
+    #[test]
+    fn redelivery_after_crash_is_deduplicated_by_delivery_id() {
+        use std::collections::HashSet;
+
+        let store = DurableStore::new(temp_dsn("dispatcher_crash.db")).unwrap();
+        seed_store_with_one_event(&store, "crash-1");
+
+        // Receiver: applies each delivery id at most once.
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut effects = 0usize;
+        let mut receive = |event: &OutboxEvent| {
+            if seen.insert(event.delivery_id.clone()) {
+                effects += 1;
+            }
+        };
+
+        // Worker claims and delivers, then crashes before ack.
+        let claimed = store.claim_outbox_with_lease(8, 0).unwrap();
+        assert_eq!(claimed.len(), 1);
+        let first_delivery_id = claimed[0].delivery_id.clone();
+        receive(&claimed[0]);
+        assert_eq!(store.outbox_pending().unwrap(), 0);
+
+        // Restarted worker reclaims the lapsed lease and redelivers.
+        let dispatcher = OutboxDispatcher::new(store.clone()).with_lease(0);
+        let mut redelivered = Vec::new();
+        let processed = dispatcher
+            .run_once(8, |event| {
+                redelivered.push(event.delivery_id.clone());
+                receive(event);
+                Ok(())
+            })
+            .expect("dispatcher run");
+        assert_eq!(processed, 1);
+        assert_eq!(redelivered, vec![first_delivery_id]);
+        assert_eq!(effects, 1);
+
+        // Acked: nothing left to claim.
+        assert!(store.claim_outbox_with_lease(8, 0).unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn dispatcher_async_handler_acks_success() {
         let store = DurableStore::new(temp_dsn("dispatcher_async_ack.db")).unwrap();
@@ -0,0 +1,219 @@
+//! Batch submission — chips that land together or not at all.
+//!
+//! Every entry runs KNOCK→WA→CHECK→TR→WF in order, holding back its stage
+//! events, policy counters and advisories. Once all entries pass, their WF
+//! receipts, idempotency rows and outbox events are written in a single
+//! [`DurableStore`] transaction and the held side effects follow. A deny or
+//! error on any entry discards the batch. Entries are checked against
+//! committed state only, so one entry cannot depend on another in the same
+//! batch.
+
+use super::processing::{durable_commit_error, spawn_advisory_store};
+use super::*;
+use crate::event_bus::ReceiptEvent;
+
+/// Most entries accepted in one batch.
+pub const MAX_BATCH_CHIPS: usize = 64;
+
+/// A pipeline run. `pending` is set when the run was staged for a batch:
+/// nothing has been written yet.
+pub(super) struct ChipRun {
+    pub(super) result: PipelineResult,
+    pub(super) pending: Option<Box<StagedWrite>>,
+}
+
+impl ChipRun {
+    /// A committed, replayed, denied or simulated run.
+    pub(super) fn finished(result: PipelineResult) -> Self {
+        Self {
+            result,
+            pending: None,
+        }
+    }
+}
+
+/// What a staged entry still writes once its batch commits.
+pub(super) struct StagedWrite {
+    pub(super) body: serde_json::Value,
+    pub(super) chip_type: String,
+    pub(super) parents: Vec<String>,
+    pub(super) author: Option<ubl_chipstore::ChipAuthor>,
+    pub(super) world: String,
+    pub(super) idem_key: IdempotencyKey,
+    pub(super) client_idem: Option<(IdempotencyKey, String)>,
+    pub(super) idem_ttl_secs: Option<u64>,
+    pub(super) metadata: ExecutionMetadata,
+    pub(super) total_ms: i64,
+    pub(super) counters: Vec<CounterEmit>,
+    pub(super) events: Vec<ReceiptEvent>,
+    /// `(runtime_version, body)` of advisories to store.
+    pub(super) advisories: Vec<(&'static str, serde_json::Value)>,
+}
+
+/// Why a batch was discarded; nothing in it was committed.
+#[derive(Debug)]
+pub struct BatchRejection {
+    /// Entry that failed; `None` when the batch itself was refused or its
+    /// shared commit failed.
+    pub index: Option<usize>,
+    pub error: PipelineError,
+}
+
+impl UblPipeline {
+    /// Run each entry through the pipeline, then commit them all in one
+    /// transaction. Results come back in entry order; an entry that was
+    /// already committed replays as usual. `simulate` on an entry's context
+    /// is ignored.
+    pub async fn process_batch(
+        &self,
+        entries: Vec<(ChipRequest, AuthorshipContext)>,
+    ) -> Result<Vec<PipelineResult>, BatchRejection> {
+        let whole = |error| BatchRejection { index: None, error };
+        if entries.is_empty() {
+            return Err(whole(PipelineError::InvalidChip(
+                "batch has no entries".to_string(),
+            )));
+        }
+        if entries.len() > MAX_BATCH_CHIPS {
+            return Err(whole(PipelineError::InvalidChip(format!(
+                "batch has {} entries; at most {} are accepted",
+                entries.len(),
+                MAX_BATCH_CHIPS
+            ))));
+        }
+
+        let mut runs = Vec::with_capacity(entries.len());
+        let mut anchors: HashMap<String, usize> = HashMap::new();
+        for (index, (request, mut ctx)) in entries.into_iter().enumerate() {
+            let at = |error| BatchRejection {
+                index: Some(index),
+                error,
+            };
+            ctx.simulate = false;
+            let run = self
+                .run_chip_pipeline(request, ctx, true)
+                .await
+                .map_err(at)?;
+            if let Some(staged) = &run.pending {
+                if let Some(first) = anchors.insert(staged.idem_key.to_durable_key(), index) {
+                    return Err(at(PipelineError::InvalidChip(format!(
+                        "same @type, @ver, @world and @id as batch entry {}",
+                        first
+                    ))));
+                }
+            }
+            runs.push(run);
+        }
+
+        let staged: Vec<(&PipelineResult, &StagedWrite)> = runs
+            .iter()
+            .filter_map(|run| Some((&run.result, run.pending.as_deref()?)))
+            .collect();
+        self.commit_staged(&staged).await.map_err(whole)?;
+        info!(
+            entries = runs.len(),
+            committed = staged.len(),
+            "batch committed"
+        );
+
+        let mut results = Vec::with_capacity(runs.len());
+        for run in runs {
+            if let Some(staged) = run.pending {
+                self.apply_staged(&run.result, *staged).await;
+            }
+            results.push(run.result);
+        }
+        Ok(results)
+    }
+
+    /// Every staged WF result in one durable transaction.
+    async fn commit_staged(
+        &self,
+        staged: &[(&PipelineResult, &StagedWrite)],
+    ) -> Result<(), PipelineError> {
+        let Some(durable) = self.durable_store.as_ref() else {
+            for (result, entry) in staged {
+                self.persist_final_result(
+                    Some(&entry.idem_key),
+                    entry.client_idem.as_ref(),
+                    entry.idem_ttl_secs,
+                    &entry.world,
+                    result,
+                )
+                .await?;
+            }
+            return Ok(());
+        };
+        let inputs = staged
+            .iter()
+            .map(|(result, entry)| {
+                self.commit_input(
+                    Some(&entry.idem_key),
+                    entry.client_idem.as_ref(),
+                    entry.idem_ttl_secs,
+                    &entry.world,
+                    result,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        durable
+            .commit_wf_batch_atomically(&inputs)
+            .map(|_| ())
+            .map_err(durable_commit_error)
+    }
+
+    /// The writes a single submission makes after WF, for a committed entry.
+    async fn apply_staged(&self, result: &PipelineResult, staged: StagedWrite) {
+        let StagedWrite {
+            body,
+            chip_type,
+            parents,
+            author,
+            world,
+            metadata,
+            total_ms,
+            counters,
+            events,
+            advisories,
+            ..
+        } = staged;
+        for emit in &counters {
+            let _ = self.policy_counters.record(emit);
+        }
+        for event in events {
+            if let Err(e) = self.event_bus.publish_stage_event(event).await {
+                warn!(error = %e, "Failed to publish receipt event");
+            }
+        }
+
+        let quarantined = matches!(result.decision, Decision::Quarantine);
+        let receipt_cid = result.receipt.receipt_cid.as_str().to_string();
+        if let Some(store) = &self.chip_store {
+            let stored = store
+                .store_chip_with_parents(
+                    body.clone(),
+                    receipt_cid.clone(),
+                    metadata,
+                    &parents,
+                    author,
+                    quarantined,
+                )
+                .await;
+            if let Err(e) = stored {
+                warn!(error = %e, "ChipStore persist failed (non-fatal)");
+            }
+            self.invalidate_schema_index(&chip_type).await;
+            for (runtime_version, advisory) in advisories {
+                spawn_advisory_store(store.clone(), advisory, runtime_version);
+            }
+        }
+
+        let wf_cid = result.final_receipt.body_cid.as_str();
+        self.append_receipt_ledger(&world, wf_cid, &receipt_cid, &result.decision)
+            .await;
+        self.post_wf_advisory(wf_cid, &chip_type, &result.decision, total_ms);
+        if !quarantined {
+            self.spawn_post_wf_hook(result.receipt.clone(), body);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Signing keyring — several Ed25519 keys addressed by `kid`.
+//!
+//! One key is active and signs new receipts, attestations, bundles and hub
+//! events. Every key in the ring still verifies: a receipt or attestation is
+//! checked against the key named by its own `kid`, so rotating the active key
+//! never invalidates what was signed before the rotation.
+
+use super::*;
+use ubl_kms::Ed25519VerifyingKey as VerifyingKey;
+use ubl_receipt::VerifyMode;
+
+impl UblPipeline {
+    /// Replace the signing keys with `keys` and sign with `active_kid` from
+    /// now on. Each entry must be named by its own key's `kid`.
+    pub fn with_keyring(
+        mut self,
+        keys: HashMap<String, SigningKey>,
+        active_kid: &str,
+    ) -> Result<Self, PipelineError> {
+        for (kid, key) in &keys {
+            let derived = kid_from_verifying_key(&key.verifying_key());
+            if *kid != derived {
+                return Err(PipelineError::SignError(format!(
+                    "keyring entry '{}' holds the key for '{}'",
+                    kid, derived
+                )));
+            }
+        }
+        self.keyring = Arc::new(
+            keys.into_iter()
+                .map(|(kid, key)| (kid, Arc::new(key)))
+                .collect(),
+        );
+        self.set_active_kid(active_kid)?;
+        Ok(self)
+    }
+
+    /// Switch new signatures to another key already in the ring.
+    pub fn set_active_kid(&mut self, kid: &str) -> Result<(), PipelineError> {
+        let key = self.keyring.get(kid).cloned().ok_or_else(|| {
+            PipelineError::SignError(format!("kid '{}' is not in the keyring", kid))
+        })?;
+        self.did = did_from_verifying_key(&key.verifying_key());
+        self.kid = kid.to_string();
+        self.signing_key = key;
+        Ok(())
+    }
+
+    /// Kids in the ring, sorted.
+    pub fn keyring_kids(&self) -> Vec<String> {
+        let mut kids: Vec<String> = self.keyring.keys().cloned().collect();
+        kids.sort();
+        kids
+    }
+
+    /// Verifying key for `kid`, when the ring holds it.
+    pub fn verifying_key_for(&self, kid: &str) -> Option<VerifyingKey> {
+        self.keyring.get(kid).map(|key| key.verifying_key())
+    }
+
+    /// Check a signed hub event against the ring key named by its `@kid`;
+    /// a kid outside the ring is an error.
+    pub fn verify_hub_event(
+        &self,
+        event: &serde_json::Value,
+    ) -> Result<bool, crate::event_signature::EventSignatureError> {
+        crate::event_signature::verify_event(event, |kid| self.verifying_key_for(kid))
+    }
+
+    /// Check a receipt's `sig` against the ring key named by its `kid`.
+    /// `None` when the kid is not in the ring (a foreign signer); a known kid
+    /// with a different `did`, a missing or a bad signature is `Some(false)`.
+    pub fn verify_receipt_signature(&self, receipt: &UnifiedReceipt) -> Option<bool> {
+        let vk = self.verifying_key_for(receipt.kid.as_str())?;
+        if receipt.did.as_str() != did_from_verifying_key(&vk) {
+            return Some(false);
+        }
+        Some(
+            receipt
+                .verify_signature_with_key(&vk, VerifyMode::Dual)
+                .is_ok_and(|report| report.valid),
+        )
+    }
+
+    /// Verify a runtime attestation against the ring key named by its `kid`;
+    /// unknown kids do not verify.
+    pub fn verify_runtime_attestation(&self, attestation: &SelfAttestation) -> bool {
+        let Some(vk) = self.verifying_key_for(&attestation.kid) else {
+            return false;
+        };
+        attestation.did == did_from_verifying_key(&vk)
+            && attestation.verify_with_key(&vk).unwrap_or(false)
+    }
+}
+
+pub(super) fn single_keyring(
+    kid: &str,
+    key: &Arc<SigningKey>,
+) -> Arc<HashMap<String, Arc<SigningKey>>> {
+    Arc::new(HashMap::from([(kid.to_string(), key.clone())]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy_loader::InMemoryPolicyStorage;
+
+    fn key(seed: u8) -> (String, SigningKey) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        (kid_from_verifying_key(&key.verifying_key()), key)
+    }
+
+    fn pipeline() -> UblPipeline {
+        UblPipeline::new(Box::new(InMemoryPolicyStorage::new()))
+    }
+
+    fn signed_receipt(pipeline: &UblPipeline) -> UnifiedReceipt {
+        let mut receipt =
+            UnifiedReceipt::new("a/acme/t/prod", &pipeline.did, &pipeline.kid, "00aa");
+        receipt
+            .finalize_and_sign(&pipeline.signing_key, CryptoMode::from_env())
+            .unwrap();
+        receipt
+    }
+
+    #[test]
+    fn with_keyring_rejects_unknown_active_and_mislabeled_keys() {
+        let (kid_a, key_a) = key(1);
+        let (kid_b, key_b) = key(2);
+        let err = pipeline()
+            .with_keyring(HashMap::from([(kid_a.clone(), key_a.clone())]), &kid_b)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("not in the keyring"));
+        let err = pipeline()
+            .with_keyring(HashMap::from([(kid_a, key_b)]), &kid_b)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("holds the key for"));
+    }
+
+    #[test]
+    fn old_kid_still_verifies_after_active_key_rotates() {
+        let (kid_a, key_a) = key(1);
+        let (kid_b, key_b) = key(2);
+        let mut pipeline = pipeline()
+            .with_keyring(
+                HashMap::from([(kid_a.clone(), key_a), (kid_b.clone(), key_b)]),
+                &kid_a,
+            )
+            .unwrap();
+        let receipt = signed_receipt(&pipeline);
+        let attestation = pipeline.runtime_self_attestation().unwrap();
+        assert_eq!(receipt.kid.as_str(), kid_a);
+
+        pipeline.set_active_kid(&kid_b).unwrap();
+        assert_eq!(pipeline.kid, kid_b);
+        assert_eq!(pipeline.keyring_kids().len(), 2);
+        assert_eq!(pipeline.verify_receipt_signature(&receipt), Some(true));
+        assert!(pipeline.verify_runtime_attestation(&attestation));
+
+        let fresh = signed_receipt(&pipeline);
+        assert_eq!(fresh.kid.as_str(), kid_b);
+        assert_eq!(pipeline.verify_receipt_signature(&fresh), Some(true));
+        let mut forged = fresh.clone();
+        forged.sig = receipt.sig.clone();
+        assert_eq!(pipeline.verify_receipt_signature(&forged), Some(false));
+
+        let (kid_c, _) = key(3);
+        let mut foreign = receipt;
+        foreign.kid = ubl_types::Kid::new_unchecked(&kid_c);
+        assert_eq!(pipeline.verify_receipt_signature(&foreign), None);
+    }
+}
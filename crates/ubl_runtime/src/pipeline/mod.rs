@@ -1,21 +1,52 @@
 //! UBL Pipeline - WA→TR→WF processing
+mod batch;
+mod keyring;
+mod policy_reload;
 mod processing;
 mod providers;
+mod quarantine;
+mod receipt_trace;
+mod required_tags;
+mod schema_validation;
+mod self_test;
 mod stages;
 mod types;
+mod wa_ghost;
+mod wasm_allowlist;
+mod world_candidates;
 
+pub use self::batch::{BatchRejection, MAX_BATCH_CHIPS};
+pub use self::policy_reload::{PolicyReload, SkippedPolicyChip};
+pub use self::quarantine::{QuarantinePolicy, QuarantineRelease};
+pub use self::receipt_trace::{ReceiptTracePolicy, RECEIPT_TRACE_DEFAULT_MAX_STEPS};
+pub use self::required_tags::{RequiredTagRule, RequiredTagsPolicy, REQUIRED_TAG_MISSING};
+pub use self::schema_validation::SCHEMA_VALIDATION_FAILED;
+pub use self::self_test::{SelfTestReport, SelfTestStage};
+pub use self::wa_ghost::WaGhostPolicy;
+pub use self::wasm_allowlist::{WasmAllowlist, TYPE_WASM_ALLOWLIST, WASM_MODULE_NOT_ALLOWLISTED};
+pub use self::world_candidates::{world_candidates, MAX_WORLD_CANDIDATES, WORLD_CANDIDATES_FIELD};
+
+use self::batch::{ChipRun, StagedWrite};
 use self::providers::{PipelineCanon, PipelineCas, PipelineSigner};
-use self::types::{decision_to_wire, AdapterRuntimeInfo, CheckResult, ParsedChipRequest};
+use self::types::{
+    decision_from_wire, decision_to_wire, AdapterRuntimeInfo, CheckResult, ParsedChipRequest,
+};
 use crate::advisory::AdvisoryEngine;
-use crate::durable_store::{CommitInput, DurableError, DurableStore, NewOutboxEvent};
+use crate::durable_store::{
+    ClientIdempotency, CommitInput, DurableError, DurableStore, NewOutboxEvent,
+};
 use crate::event_bus::{EventBus, StageEventContext};
 use crate::genesis::genesis_chip_cid;
 use crate::idempotency::{CachedResult, IdempotencyKey, IdempotencyStore};
 use crate::key_rotation::{derive_material, mapping_chip, KeyRotateRequest};
 use crate::ledger::{LedgerWriter, NullLedger};
-use crate::policy_bit::PolicyResult;
+use crate::policy_bit::{PolicyBit, PolicyResult};
+use crate::policy_counters::PolicyCounterRegistry;
 use crate::policy_loader::{ChipRequest as PolicyChipRequest, PolicyLoader, PolicyStorage};
-use crate::reasoning_bit::{Decision, EvalContext};
+use crate::policy_snapshot::PolicySnapshotStore;
+use crate::post_wf_hook::{NullPostWfHook, PostWfHook};
+use crate::reasoning_bit::{CounterEmit, Decision, EvalContext};
+use crate::receipt_bundle::ReceiptBundle;
 use crate::runtime_cert::SelfAttestation;
 use crate::transition_registry::TransitionRegistry;
 use rb_vm::tlv;
@@ -24,7 +55,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 use ubl_chipstore::{ChipStore, ExecutionMetadata};
 use ubl_kms::{did_from_verifying_key, kid_from_verifying_key, Ed25519SigningKey as SigningKey};
 use ubl_receipt::{
@@ -51,15 +82,52 @@ pub struct UblPipeline {
     pub kid: String,
     /// Ed25519 signing key for receipts and JWS
     signing_key: Arc<SigningKey>,
+    /// Every signing key by `kid`, the active one included; verification
+    /// resolves the key named by the receipt or attestation.
+    keyring: Arc<HashMap<String, Arc<SigningKey>>>,
     /// Audit ledger — append-only log of pipeline events
     ledger: Arc<dyn LedgerWriter>,
+    /// Operator side effects after a successful WF commit.
+    post_wf_hook: Arc<dyn PostWfHook>,
     /// Durable persistence boundary for receipts + idempotency + outbox (SQLite).
     durable_store: Option<Arc<DurableStore>>,
     /// Deterministic transition bytecode selector.
     transition_registry: Arc<TransitionRegistry>,
+    /// Policy chains seen at CHECK, keyed by `policy_set_hash`.
+    policy_snapshots: Arc<PolicySnapshotStore>,
+    /// Heuristics that divert an `Allow` into `Quarantine`.
+    quarantine_policy: Arc<QuarantinePolicy>,
+    /// Tag keys chips must declare per `(world, type)`.
+    required_tags_policy: Arc<RequiredTagsPolicy>,
+    /// `ubl/meta.register` schemas by world; `None` until first read.
+    schema_index: Arc<RwLock<Option<Arc<schema_validation::SchemaIndex>>>>,
+    /// Allow-listed counters incremented by `EmitCounter` bits at CHECK.
+    policy_counters: Arc<PolicyCounterRegistry>,
+    /// Which chip types get a WA ghost receipt.
+    wa_ghost_policy: Arc<WaGhostPolicy>,
+    /// Operator-approved WASM adapter hashes (`UBL_WASM_ALLOWLIST`).
+    wasm_allowlist: Arc<WasmAllowlist>,
+    /// Opt-in VM step trace on the TR receipt (`UBL_RECEIPT_TRACE`).
+    receipt_trace_policy: Arc<ReceiptTracePolicy>,
+    /// Sign hub events with the gate key (`UBL_SIGN_EVENTS=true`).
+    sign_events: bool,
+    /// Ceiling for per-chip `fuel_limit` overrides (`UBL_MAX_FUEL_LIMIT`).
+    max_fuel_limit: u64,
 }
 
 const DEFAULT_FUEL_LIMIT: u64 = 1_000_000;
+const DEFAULT_MAX_FUEL_LIMIT: u64 = 10_000_000;
+
+/// Chip body field that overrides the TR fuel limit for one request.
+pub const FUEL_LIMIT_FIELD: &str = "fuel_limit";
+
+fn max_fuel_limit_from_env() -> u64 {
+    std::env::var("UBL_MAX_FUEL_LIMIT")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_MAX_FUEL_LIMIT)
+}
 
 fn load_durable_store() -> Option<Arc<DurableStore>> {
     match DurableStore::from_env() {
@@ -121,6 +189,23 @@ fn is_non_dev_env() -> bool {
     !matches!(env_value.as_str(), "dev" | "local" | "test")
 }
 
+fn sign_events_from_env() -> bool {
+    matches!(
+        std::env::var("UBL_SIGN_EVENTS")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+            .as_str(),
+        "true" | "1" | "on"
+    )
+}
+
+/// Trace-level `ubl.stage` span for one pipeline stage. Disabled, and
+/// nearly free, unless a subscriber (the gate's OTLP exporter) enables it.
+pub fn stage_span(stage: PipelineStage) -> tracing::Span {
+    tracing::trace_span!("ubl.stage", stage = stage.as_str())
+}
+
 /// Request to process a chip
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChipRequest {
@@ -137,6 +222,104 @@ pub struct AuthorshipContext {
     pub subject_did_hint: Option<String>,
     /// Content-addressed knock/envelope CID.
     pub knock_cid: Option<String>,
+    /// Client-supplied correlation id (`X-UBL-Correlation-Id`).
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+    /// Client-supplied idempotency key (`Idempotency-Key`).
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Run every stage but persist nothing: no idempotency, events, chip
+    /// store, ledger or durable commit. The receipt carries `simulated: true`.
+    #[serde(default)]
+    pub simulate: bool,
+    /// Author the transport verified (admin key, chip signature or bearer
+    /// token); recorded on the stored chip.
+    #[serde(default)]
+    pub author: Option<ubl_chipstore::ChipAuthor>,
+}
+
+/// Reserved body field carrying a correlation id; stripped before hashing.
+pub const CORRELATION_ID_FIELD: &str = "_correlation_id";
+/// Longest correlation id accepted.
+pub const MAX_CORRELATION_ID_LEN: usize = 128;
+
+/// Remove `_correlation_id` from the chip body and pick the correlation id,
+/// preferring the transport-supplied one. Ids must be 1..=128 bytes with no
+/// control characters.
+pub(super) fn take_correlation_id(
+    body: &mut serde_json::Value,
+    hint: Option<&str>,
+) -> Result<Option<String>, PipelineError> {
+    let from_body = body
+        .as_object_mut()
+        .and_then(|obj| obj.remove(CORRELATION_ID_FIELD));
+    let id = match (hint, from_body) {
+        (Some(hint), _) => hint.to_string(),
+        (None, Some(serde_json::Value::String(id))) => id,
+        (None, Some(_)) => {
+            return Err(PipelineError::InvalidChip(format!(
+                "{} must be a string",
+                CORRELATION_ID_FIELD
+            )))
+        }
+        (None, None) => return Ok(None),
+    };
+    if id.is_empty() || id.len() > MAX_CORRELATION_ID_LEN || id.chars().any(char::is_control) {
+        return Err(PipelineError::InvalidChip(format!(
+            "correlation id must be 1..={} bytes without control characters",
+            MAX_CORRELATION_ID_LEN
+        )));
+    }
+    Ok(Some(id))
+}
+
+/// Longest `Idempotency-Key` accepted.
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// A client idempotency key must be 1..=255 visible ASCII characters.
+pub(super) fn check_idempotency_key(key: &str) -> Result<&str, PipelineError> {
+    if key.is_empty()
+        || key.len() > MAX_IDEMPOTENCY_KEY_LEN
+        || !key.bytes().all(|b| b.is_ascii_graphic())
+    {
+        return Err(PipelineError::InvalidChip(format!(
+            "Idempotency-Key must be 1..={} visible ASCII characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+    Ok(key)
+}
+
+/// Reserved body field bounding a chip's idempotent replay window, in
+/// seconds; stripped before hashing.
+pub const IDEM_TTL_FIELD: &str = "_idem_ttl_secs";
+
+/// Remove `_idem_ttl_secs` from the chip body. The value must be a positive
+/// integer; absent means replay indefinitely.
+pub(super) fn take_idem_ttl(body: &mut serde_json::Value) -> Result<Option<u64>, PipelineError> {
+    let Some(raw) = body
+        .as_object_mut()
+        .and_then(|obj| obj.remove(IDEM_TTL_FIELD))
+    else {
+        return Ok(None);
+    };
+    match raw.as_u64() {
+        Some(secs) if secs > 0 => Ok(Some(secs)),
+        _ => Err(PipelineError::InvalidChip(format!(
+            "{} must be a positive integer",
+            IDEM_TTL_FIELD
+        ))),
+    }
+}
+
+/// Outcome of re-evaluating a chip against a retained policy snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyDryRun {
+    pub policy_set_hash: String,
+    pub policy_count: usize,
+    pub decision: Decision,
+    pub reason: String,
+    pub trace: Vec<PolicyTraceEntry>,
 }
 
 /// Result from the complete pipeline
@@ -218,8 +401,12 @@ impl UblPipeline {
         let vk = key.verifying_key();
         let did = did_from_verifying_key(&vk);
         let kid = kid_from_verifying_key(&vk);
+        let signing_key = Arc::new(key);
+        let keyring = keyring::single_keyring(&kid, &signing_key);
         let durable_store = load_durable_store();
         apply_persisted_stage_secrets(&durable_store);
+        let policy_snapshots =
+            Arc::new(PolicySnapshotStore::from_env().with_durable(durable_store.clone()));
         Self {
             policy_loader: PolicyLoader::new(storage),
             fuel_limit: DEFAULT_FUEL_LIMIT,
@@ -231,10 +418,22 @@ impl UblPipeline {
             runtime_info: Arc::new(RuntimeInfo::capture()),
             did,
             kid,
-            signing_key: Arc::new(key),
+            signing_key,
+            keyring,
             ledger: Arc::new(NullLedger),
+            post_wf_hook: Arc::new(NullPostWfHook),
             durable_store,
             transition_registry: load_transition_registry(),
+            policy_snapshots,
+            quarantine_policy: Arc::new(QuarantinePolicy::from_env()),
+            required_tags_policy: Arc::new(RequiredTagsPolicy::from_env()),
+            schema_index: Arc::new(RwLock::new(None)),
+            sign_events: sign_events_from_env(),
+            max_fuel_limit: max_fuel_limit_from_env(),
+            policy_counters: Arc::new(PolicyCounterRegistry::from_env()),
+            wa_ghost_policy: Arc::new(WaGhostPolicy::from_env()),
+            wasm_allowlist: Arc::new(WasmAllowlist::from_env()),
+            receipt_trace_policy: Arc::new(ReceiptTracePolicy::from_env()),
         }
     }
 
@@ -244,8 +443,12 @@ impl UblPipeline {
         let vk = key.verifying_key();
         let did = did_from_verifying_key(&vk);
         let kid = kid_from_verifying_key(&vk);
+        let signing_key = Arc::new(key);
+        let keyring = keyring::single_keyring(&kid, &signing_key);
         let durable_store = load_durable_store();
         apply_persisted_stage_secrets(&durable_store);
+        let policy_snapshots =
+            Arc::new(PolicySnapshotStore::from_env().with_durable(durable_store.clone()));
         Self {
             policy_loader: PolicyLoader::new(storage),
             fuel_limit: DEFAULT_FUEL_LIMIT,
@@ -257,10 +460,22 @@ impl UblPipeline {
             runtime_info: Arc::new(RuntimeInfo::capture()),
             did,
             kid,
-            signing_key: Arc::new(key),
+            signing_key,
+            keyring,
             ledger: Arc::new(NullLedger),
+            post_wf_hook: Arc::new(NullPostWfHook),
             durable_store,
             transition_registry: load_transition_registry(),
+            policy_snapshots,
+            quarantine_policy: Arc::new(QuarantinePolicy::from_env()),
+            required_tags_policy: Arc::new(RequiredTagsPolicy::from_env()),
+            schema_index: Arc::new(RwLock::new(None)),
+            sign_events: sign_events_from_env(),
+            max_fuel_limit: max_fuel_limit_from_env(),
+            policy_counters: Arc::new(PolicyCounterRegistry::from_env()),
+            wa_ghost_policy: Arc::new(WaGhostPolicy::from_env()),
+            wasm_allowlist: Arc::new(WasmAllowlist::from_env()),
+            receipt_trace_policy: Arc::new(ReceiptTracePolicy::from_env()),
         }
     }
 
@@ -270,8 +485,12 @@ impl UblPipeline {
         let vk = key.verifying_key();
         let did = did_from_verifying_key(&vk);
         let kid = kid_from_verifying_key(&vk);
+        let signing_key = Arc::new(key);
+        let keyring = keyring::single_keyring(&kid, &signing_key);
         let durable_store = load_durable_store();
         apply_persisted_stage_secrets(&durable_store);
+        let policy_snapshots =
+            Arc::new(PolicySnapshotStore::from_env().with_durable(durable_store.clone()));
         Self {
             policy_loader: PolicyLoader::new(storage),
             fuel_limit: DEFAULT_FUEL_LIMIT,
@@ -283,10 +502,22 @@ impl UblPipeline {
             runtime_info: Arc::new(RuntimeInfo::capture()),
             did,
             kid,
-            signing_key: Arc::new(key),
+            signing_key,
+            keyring,
             ledger: Arc::new(NullLedger),
+            post_wf_hook: Arc::new(NullPostWfHook),
             durable_store,
             transition_registry: load_transition_registry(),
+            policy_snapshots,
+            quarantine_policy: Arc::new(QuarantinePolicy::from_env()),
+            required_tags_policy: Arc::new(RequiredTagsPolicy::from_env()),
+            schema_index: Arc::new(RwLock::new(None)),
+            sign_events: sign_events_from_env(),
+            max_fuel_limit: max_fuel_limit_from_env(),
+            policy_counters: Arc::new(PolicyCounterRegistry::from_env()),
+            wa_ghost_policy: Arc::new(WaGhostPolicy::from_env()),
+            wasm_allowlist: Arc::new(WasmAllowlist::from_env()),
+            receipt_trace_policy: Arc::new(ReceiptTracePolicy::from_env()),
         }
     }
 
@@ -295,6 +526,30 @@ impl UblPipeline {
         self.ledger = ledger;
     }
 
+    /// Attach a hook run after each successful WF commit (default: no-op).
+    pub fn set_post_wf_hook(&mut self, hook: Arc<dyn PostWfHook>) {
+        self.post_wf_hook = hook;
+    }
+
+    /// Replace the durable WF commit store (defaults to `DurableStore::from_env`).
+    /// Policy snapshots follow it; ones retained before the swap are dropped.
+    pub fn set_durable_store(&mut self, store: Option<Arc<DurableStore>>) {
+        self.policy_snapshots = Arc::new(
+            PolicySnapshotStore::new(self.policy_snapshots.capacity()).with_durable(store.clone()),
+        );
+        self.durable_store = store;
+    }
+
+    /// Replace the policy counter allow-list/registry.
+    pub fn set_policy_counters(&mut self, counters: Arc<PolicyCounterRegistry>) {
+        self.policy_counters = counters;
+    }
+
+    /// Counters emitted by policies, for `/metrics`.
+    pub fn policy_counters(&self) -> &Arc<PolicyCounterRegistry> {
+        &self.policy_counters
+    }
+
     /// Attach an AdvisoryEngine for LLM hook points (post-CHECK, post-WF).
     pub fn set_advisory_engine(&mut self, engine: Arc<AdvisoryEngine>) {
         self.advisory_engine = Some(engine);
@@ -311,6 +566,68 @@ impl UblPipeline {
             .map_err(|e| PipelineError::Internal(format!("runtime attestation failed: {}", e)))
     }
 
+    /// Default TR fuel limit for chips without a `fuel_limit` override.
+    pub fn set_fuel_limit(&mut self, fuel_limit: u64) {
+        self.fuel_limit = fuel_limit.max(1);
+    }
+
+    /// Ceiling that per-chip `fuel_limit` overrides are clamped to
+    /// (defaults to `UBL_MAX_FUEL_LIMIT`, else 10M).
+    pub fn set_max_fuel_limit(&mut self, max_fuel_limit: u64) {
+        self.max_fuel_limit = max_fuel_limit.max(1);
+    }
+
+    /// TR fuel limit for `body`: its `fuel_limit` clamped to the ceiling, or
+    /// the pipeline default. A present override must be a positive integer.
+    pub(super) fn effective_fuel_limit(
+        &self,
+        body: &serde_json::Value,
+    ) -> Result<u64, PipelineError> {
+        match body.get(FUEL_LIMIT_FIELD) {
+            None => Ok(self.fuel_limit),
+            Some(raw) => match raw.as_u64().filter(|n| *n > 0) {
+                Some(requested) => Ok(requested.min(self.max_fuel_limit)),
+                None => Err(PipelineError::InvalidChip(format!(
+                    "{} must be a positive integer",
+                    FUEL_LIMIT_FIELD
+                ))),
+            },
+        }
+    }
+
+    /// Enable or disable hub event signing (defaults to `UBL_SIGN_EVENTS`).
+    pub fn set_sign_events(&mut self, enabled: bool) {
+        self.sign_events = enabled;
+    }
+
+    /// Add `@kid`/`@sig` to a hub event when event signing is enabled. A
+    /// signing failure is logged and leaves the event unsigned.
+    pub fn sign_hub_event(&self, event: &mut serde_json::Value) {
+        if !self.sign_events {
+            return;
+        }
+        if let Err(e) = crate::event_signature::sign_event(event, &self.kid, &self.signing_key) {
+            warn!(error = %e, "hub event signing failed");
+        }
+    }
+
+    /// Sign a gate-issued artifact (`@kid` + `@sig`, same scheme as hub
+    /// events) with the active key, whatever `UBL_SIGN_EVENTS` says.
+    pub fn sign_artifact(&self, body: &mut serde_json::Value) -> Result<(), PipelineError> {
+        crate::event_signature::sign_event(body, &self.kid, &self.signing_key)
+            .map_err(|e| PipelineError::SignError(e.to_string()))
+    }
+
+    /// Assemble and sign an offline-verifiable receipt bundle with the gate key.
+    pub fn issue_receipt_bundle(
+        &self,
+        receipt: serde_json::Value,
+        chip: Option<serde_json::Value>,
+    ) -> Result<ReceiptBundle, PipelineError> {
+        ReceiptBundle::issue(receipt, chip, &self.did, &self.kid, &self.signing_key)
+            .map_err(|e| PipelineError::Internal(format!("receipt bundle failed: {}", e)))
+    }
+
     /// Bootstrap the genesis chip: materialize it as a real stored chip in ChipStore.
     ///
     /// This must be called once at startup. The genesis chip is self-signed —
@@ -371,6 +688,8 @@ pub enum PipelineError {
     InvalidChip(String),
     #[error("Dependency missing: {0}")]
     DependencyMissing(String),
+    #[error("Runtime too old: {0}")]
+    RuntimeTooOld(String),
     #[error("Fuel exhausted: {0}")]
     FuelExhausted(String),
     #[error("Type mismatch: {0}")]
@@ -0,0 +1,148 @@
+//! Policy reload — rebuild the CHECK policy set from chips in the chip store.
+//!
+//! Policy chips already committed to the chip store, plus the ancestors they
+//! name in `parents`, are parsed into a fresh [`InMemoryPolicyStorage`] that
+//! replaces the loader's storage in one swap. A chip that does not parse is
+//! left out of the new set and reported in [`PolicyReload::skipped`]; CHECK
+//! refuses such bodies, so only chips written before it did can land there.
+
+use super::*;
+use crate::policy_loader::{is_policy_chip_type, ChipData, InMemoryPolicyStorage};
+
+/// Policy chip types read on reload; `ubl/policy.genesis` is built in.
+const RELOADED_POLICY_TYPES: &[&str] = &["ubl/policy", "ubl/policy.app", "ubl/policy.tenant"];
+
+/// Ancestor levels followed above a policy chip (chip → tenant → app).
+const MAX_POLICY_ANCESTRY_DEPTH: usize = 16;
+
+/// Result of [`UblPipeline::reload_policies`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyReload {
+    /// Policy chips in the new set, genesis excluded.
+    pub policy_count: usize,
+    /// [`crate::policy_snapshot::policy_set_hash`] over the new policies in
+    /// chip CID order.
+    pub policy_set_hash: String,
+    /// Policy chips plus the ancestors they attach to.
+    pub chip_count: usize,
+    /// Policy chips left out because their body does not parse.
+    pub skipped: Vec<SkippedPolicyChip>,
+}
+
+/// A stored policy chip [`UblPipeline::reload_policies`] could not use.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedPolicyChip {
+    pub cid: String,
+    pub error: String,
+}
+
+impl UblPipeline {
+    /// Re-read policy chips from the chip store and swap them in as the
+    /// policy set for every later CHECK.
+    pub async fn reload_policies(&self) -> Result<PolicyReload, PipelineError> {
+        let store = self.chip_store.as_ref().ok_or_else(|| {
+            PipelineError::Internal("policy reload needs a chip store".to_string())
+        })?;
+
+        let mut policy_chips = Vec::new();
+        for chip_type in RELOADED_POLICY_TYPES {
+            let chips = store
+                .get_chips_by_type(chip_type)
+                .await
+                .map_err(|e| PipelineError::Internal(format!("ChipStore: {}", e)))?;
+            policy_chips.extend(
+                chips
+                    .into_iter()
+                    .filter(|chip| !chip.quarantined)
+                    .map(|chip| policy_chip_data(&chip)),
+            );
+        }
+        policy_chips.sort_by(|a, b| a.cid.cmp(&b.cid));
+        policy_chips.dedup_by(|a, b| a.cid == b.cid);
+
+        let mut policies = Vec::with_capacity(policy_chips.len());
+        let mut skipped = Vec::new();
+        policy_chips.retain(|chip| match PolicyLoader::parse_policy_chip(chip) {
+            Ok(policy) => {
+                policies.push(policy);
+                true
+            }
+            Err(e) => {
+                warn!(cid = %chip.cid, error = %e, "policy chip skipped on reload");
+                skipped.push(SkippedPolicyChip {
+                    cid: chip.cid.clone(),
+                    error: e.to_string(),
+                });
+                false
+            }
+        });
+
+        let mut storage = InMemoryPolicyStorage::new();
+        let mut ancestors = 0;
+        let mut seen: HashSet<String> = policy_chips
+            .iter()
+            .map(|c| c.cid.clone())
+            .chain(skipped.iter().map(|s| s.cid.clone()))
+            .collect();
+        let mut frontier: Vec<String> = policy_chips
+            .iter()
+            .flat_map(|c| c.parents.iter().cloned())
+            .collect();
+        for _ in 0..MAX_POLICY_ANCESTRY_DEPTH {
+            let mut next = Vec::new();
+            for cid in frontier {
+                if !seen.insert(cid.clone()) {
+                    continue;
+                }
+                let Some(chip) = store
+                    .get_chip(&cid)
+                    .await
+                    .map_err(|e| PipelineError::Internal(format!("ChipStore: {}", e)))?
+                else {
+                    continue;
+                };
+                let ancestor = policy_chip_data(&chip);
+                if is_policy_chip_type(&ancestor.chip_type)
+                    && PolicyLoader::parse_policy_chip(&ancestor).is_err()
+                {
+                    continue;
+                }
+                next.extend(ancestor.parents.iter().cloned());
+                storage.add_chip(ancestor);
+                ancestors += 1;
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        let reload = PolicyReload {
+            policy_count: policies.len(),
+            policy_set_hash: crate::policy_snapshot::policy_set_hash(&policies),
+            chip_count: policy_chips.len() + ancestors,
+            skipped,
+        };
+        for chip in policy_chips {
+            storage.add_chip(chip);
+        }
+        self.policy_loader.replace_storage(Box::new(storage));
+        info!(
+            policy_count = reload.policy_count,
+            policy_set_hash = %reload.policy_set_hash,
+            skipped = reload.skipped.len(),
+            "policy set reloaded"
+        );
+        Ok(reload)
+    }
+}
+
+/// Loader view of a stored chip; ancestry comes from its recorded parents.
+fn policy_chip_data(chip: &ubl_chipstore::StoredChip) -> ChipData {
+    ChipData {
+        cid: chip.cid.as_str().to_string(),
+        chip_type: chip.chip_type.clone(),
+        body: chip.chip_data.clone(),
+        parents: chip.lineage_parents(),
+    }
+}
@@ -5,9 +5,11 @@ impl UblPipeline {
     /// Use this when you have raw HTTP body bytes (e.g. from the gate).
     pub async fn process_raw(&self, bytes: &[u8]) -> Result<PipelineResult, PipelineError> {
         // Stage 0: KNOCK
-        let value = crate::knock::knock(bytes).map_err(|e| PipelineError::Knock(e.to_string()))?;
+        let (value, signer_did) = crate::knock::knock_with_signer(bytes)
+            .map_err(|e| PipelineError::Knock(e.to_string()))?;
         let knock_cid = crate::authorship::knock_cid_from_bytes(bytes);
-        let subject_did = crate::authorship::resolve_subject_did(Some(&value), None);
+        let subject_did = signer_did
+            .unwrap_or_else(|| crate::authorship::resolve_subject_did(Some(&value), None));
 
         let chip_type = value["@type"].as_str().unwrap_or("").to_string();
         let request = ChipRequest {
@@ -22,6 +24,10 @@ impl UblPipeline {
             AuthorshipContext {
                 subject_did_hint: Some(subject_did),
                 knock_cid: Some(knock_cid),
+                correlation_id: None,
+                idempotency_key: None,
+                simulate: false,
+                author: None,
             },
         )
         .await
@@ -32,6 +38,8 @@ impl UblPipeline {
     ///
     /// **Idempotency:** If the chip has key `(@type, @ver, @world, @id)` and
     /// that key was already processed, returns the cached result immediately.
+    /// A client key in [`AuthorshipContext::idempotency_key`] replays the same
+    /// way, and conflicts when it was first used with a different payload.
     pub async fn process_chip(
         &self,
         request: ChipRequest,
@@ -41,18 +49,68 @@ impl UblPipeline {
     }
 
     /// Process a chip request with transport-resolved authorship context.
+    /// With [`AuthorshipContext::simulate`] every stage runs and the receipt
+    /// is signed, but nothing is persisted, published or replayed.
+    ///
+    /// Runs inside a `ubl.pipeline` span (one `ubl.stage` child per stage)
+    /// that records `receipt_cid` and `decision` once the receipt exists.
     pub async fn process_chip_with_context(
         &self,
         request: ChipRequest,
         authorship_ctx: AuthorshipContext,
     ) -> Result<PipelineResult, PipelineError> {
+        let span = tracing::trace_span!(
+            "ubl.pipeline",
+            chip_type = %request.chip_type,
+            receipt_cid = tracing::field::Empty,
+            decision = tracing::field::Empty,
+        );
+        let result = self
+            .run_chip_pipeline(request, authorship_ctx, false)
+            .instrument(span.clone())
+            .await
+            .map(|run| run.result);
+        if let Ok(result) = &result {
+            span.record("receipt_cid", result.receipt.receipt_cid.as_str());
+            span.record("decision", decision_to_wire(&result.decision));
+        }
+        result
+    }
+
+    /// With `staged` the run stops short of persisting and hands back what
+    /// its batch commits later; a deny fails the run.
+    pub(super) async fn run_chip_pipeline(
+        &self,
+        mut request: ChipRequest,
+        authorship_ctx: AuthorshipContext,
+        staged: bool,
+    ) -> Result<ChipRun, PipelineError> {
         let pipeline_start = std::time::Instant::now();
-        let parsed_request = ParsedChipRequest::parse(&request)?;
+        let simulate = authorship_ctx.simulate;
+        let correlation_id =
+            take_correlation_id(&mut request.body, authorship_ctx.correlation_id.as_deref())?;
+        let idem_ttl_secs = take_idem_ttl(&mut request.body)?;
+        let world_selection = self.select_candidate_world(&mut request).await?;
+        let parsed_request =
+            ParsedChipRequest::parse(&request)?.with_author(authorship_ctx.author.as_ref());
+        if simulate && writes_during_transition(parsed_request.chip_type) {
+            return Err(PipelineError::InvalidChip(format!(
+                "simulation is not available for '{}'",
+                parsed_request.chip_type
+            )));
+        }
+        if staged && writes_during_transition(parsed_request.chip_type) {
+            return Err(PipelineError::InvalidChip(format!(
+                "'{}' cannot be submitted in a batch",
+                parsed_request.chip_type
+            )));
+        }
         let chip_id = parsed_request.chip_id.unwrap_or("-");
         info!(
             chip_type = %parsed_request.chip_type,
             world = %parsed_request.world,
             chip_id = %chip_id,
+            simulate,
             "pipeline request accepted"
         );
 
@@ -62,23 +120,33 @@ impl UblPipeline {
                 "missing strict idempotency anchors: @type, @ver, @world, @id".to_string(),
             )
         })?;
-        let durable_idem_key = idem_key.to_durable_key();
-        let cached = if let Some(durable) = &self.durable_store {
-            durable
-                .get_idempotent(&durable_idem_key)
-                .map_err(|e| PipelineError::StorageError(format!("Idempotency lookup: {}", e)))?
-        } else {
-            self.idempotency_store.get(&idem_key).await
+        let client_idem = match authorship_ctx.idempotency_key.as_deref() {
+            Some(raw) => Some((
+                IdempotencyKey::from_client_key(parsed_request.world, check_idempotency_key(raw)?),
+                crate::authorship::knock_cid_from_value(parsed_request.body()),
+            )),
+            None => None,
         };
+        let mut cached = None;
+        if simulate {
+            // Previews never replay and are never cached.
+        } else if let Some((client_key, fingerprint)) = &client_idem {
+            cached = self.lookup_idempotent(client_key).await?;
+            if let Some(hit) = &cached {
+                if hit.fingerprint.as_deref() != Some(fingerprint.as_str()) {
+                    return Err(PipelineError::IdempotencyConflict(format!(
+                        "Idempotency-Key '{}' was already used with a different payload",
+                        client_key.at_id
+                    )));
+                }
+            }
+        }
+        if cached.is_none() && !simulate {
+            cached = self.lookup_idempotent(&idem_key).await?;
+        }
 
         if let Some(cached) = cached {
-            let decision = if cached.decision.eq_ignore_ascii_case("allow")
-                || cached.decision.contains("Allow")
-            {
-                Decision::Allow
-            } else {
-                Decision::Deny
-            };
+            let decision = decision_from_wire(&cached.decision);
             let receipt = UnifiedReceipt::from_json(&cached.response_json)
                 .unwrap_or_else(|_| UnifiedReceipt::new("", "", "", ""));
             info!(
@@ -87,7 +155,7 @@ impl UblPipeline {
                 receipt_cid = %cached.receipt_cid,
                 "pipeline idempotency replay"
             );
-            return Ok(PipelineResult {
+            return Ok(ChipRun::finished(PipelineResult {
                 final_receipt: PipelineReceipt {
                     body_cid: ubl_types::Cid::new_unchecked(&cached.receipt_cid),
                     receipt_type: "ubl/wf".to_string(),
@@ -97,7 +165,7 @@ impl UblPipeline {
                 decision,
                 receipt,
                 replayed: true,
-            });
+            }));
         }
 
         // `@world` and `@type` already parsed/validated above.
@@ -112,7 +180,8 @@ impl UblPipeline {
 
         // GAP-6: cross-restart nonce tracking (24h TTL) when SQLite is enabled.
         // The in-memory guard is the fast path; SQLite adds cross-restart durability.
-        {
+        // A simulation's nonce is never recorded.
+        if !simulate {
             let mut seen = self.seen_nonces.write().await;
             if seen.contains(&nonce) {
                 return Err(PipelineError::InvalidChip(
@@ -121,7 +190,7 @@ impl UblPipeline {
             }
             seen.insert(nonce.clone());
         }
-        if let Some(ds) = &self.durable_store {
+        if let Some(ds) = self.durable_store.as_ref().filter(|_| !simulate) {
             let ttl = std::time::Duration::from_secs(24 * 60 * 60);
             let is_new = ds
                 .nonce_mark_if_new(&nonce, ttl)
@@ -138,56 +207,99 @@ impl UblPipeline {
             .with_runtime_info((*self.runtime_info).clone())
             .with_subject_did(Some(subject_did.clone()))
             .with_knock_cid(Some(&knock_cid));
+        if let Some(id) = &correlation_id {
+            receipt.set_effect("correlation_id", serde_json::json!(id));
+        }
+        if let Some(selection) = world_selection {
+            receipt.set_effect("world_selection", selection);
+        }
+        if simulate {
+            receipt.set_effect("simulated", serde_json::json!(true));
+        }
+        // A staged run holds its stage events and advisories for the commit.
+        let mut held_events = staged.then(Vec::new);
+        let mut held_advisories = Vec::new();
 
         // Stage 1: WA (Write-Ahead)
         let wa_start = std::time::Instant::now();
-        let wa_receipt = self.stage_write_ahead(&parsed_request).await?;
+        let wa_skipped = !self.wa_ghost_policy.emits_for(parsed_request.chip_type);
+        let wa_receipt = if wa_skipped {
+            PipelineReceipt::skipped_wa(&knock_cid)
+        } else {
+            self.stage_write_ahead(&parsed_request)
+                .instrument(stage_span(PipelineStage::WriteAhead))
+                .await?
+        };
         let wa_ms = wa_start.elapsed().as_millis() as i64;
-        debug!(chip_type = %parsed_request.chip_type, duration_ms = wa_ms, "stage wa completed");
+        debug!(
+            chip_type = %parsed_request.chip_type,
+            duration_ms = wa_ms,
+            wa_skipped,
+            "stage wa completed"
+        );
 
-        receipt
-            .append_stage(StageExecution {
-                stage: PipelineStage::WriteAhead,
-                timestamp: chrono::Utc::now().to_rfc3339(),
-                input_cid: wa_receipt.body_cid.as_str().to_string(),
-                output_cid: Some(wa_receipt.body_cid.as_str().to_string()),
-                fuel_used: None,
-                policy_trace: vec![],
-                vm_sig: None,
-                vm_sig_payload_cid: None,
-                auth_token: String::new(),
-                duration_ms: wa_ms,
-            })
-            .map_err(|e| PipelineError::Internal(format!("Receipt WA: {}", e)))?;
+        if wa_skipped {
+            receipt.set_effect("wa_skipped", serde_json::json!(true));
+        } else {
+            receipt
+                .append_stage(StageExecution {
+                    stage: PipelineStage::WriteAhead,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    input_cid: wa_receipt.body_cid.as_str().to_string(),
+                    output_cid: Some(wa_receipt.body_cid.as_str().to_string()),
+                    fuel_used: None,
+                    policy_trace: vec![],
+                    vm_sig: None,
+                    vm_sig_payload_cid: None,
+                    auth_token: String::new(),
+                    duration_ms: wa_ms,
+                })
+                .map_err(|e| PipelineError::Internal(format!("Receipt WA: {}", e)))?;
 
-        // Publish WA event
-        if let Err(e) = self
-            .event_bus
-            .publish_stage_event(crate::event_bus::ReceiptEvent::from_stage_receipt(
-                wa_receipt.body_cid.as_str(),
-                &wa_receipt.receipt_type,
-                wa_receipt.body.clone(),
-                "wa",
-                StageEventContext {
-                    decision: None,
-                    duration_ms: Some(wa_ms),
-                    world: Some(world.to_string()),
-                    input_cid: None,
-                    binary_hash: Some(self.runtime_info.binary_hash.clone()),
-                    build_meta: serde_json::to_value(&self.runtime_info.build).ok(),
-                    actor: Some(self.did.clone()),
-                    subject_did: Some(subject_did.clone()),
-                    knock_cid: Some(knock_cid.clone()),
-                },
-            ))
-            .await
-        {
-            warn!(error = %e, "Failed to publish receipt event");
+            // Publish WA event
+            if simulate {
+                debug!(chip_type = %parsed_request.chip_type, "simulation: WA event skipped");
+            } else if let Err(e) = self
+                .publish_or_hold(
+                    crate::event_bus::ReceiptEvent::from_stage_receipt(
+                        wa_receipt.body_cid.as_str(),
+                        &wa_receipt.receipt_type,
+                        wa_receipt.body.clone(),
+                        "wa",
+                        StageEventContext {
+                            decision: None,
+                            duration_ms: Some(wa_ms),
+                            world: Some(world.to_string()),
+                            input_cid: None,
+                            binary_hash: Some(self.runtime_info.binary_hash.clone()),
+                            build_meta: serde_json::to_value(&self.runtime_info.build).ok(),
+                            actor: Some(self.did.clone()),
+                            subject_did: Some(subject_did.clone()),
+                            knock_cid: Some(knock_cid.clone()),
+                            correlation_id: correlation_id.clone(),
+                        },
+                    ),
+                    &mut held_events,
+                )
+                .await
+            {
+                warn!(error = %e, "Failed to publish receipt event");
+            }
         }
 
         // Stage 2: CHECK (Policy Evaluation)
         let check_start = std::time::Instant::now();
-        let check = self.stage_check(&parsed_request).await?;
+        // A simulation leaves policy counters untouched; a batch records
+        // them once it commits.
+        let check = async {
+            if simulate || staged {
+                self.evaluate_check(&parsed_request).await
+            } else {
+                self.stage_check(&parsed_request).await
+            }
+        }
+        .instrument(stage_span(PipelineStage::Check))
+        .await?;
         let check_ms = check_start.elapsed().as_millis() as i64;
         debug!(
             chip_type = %parsed_request.chip_type,
@@ -195,6 +307,24 @@ impl UblPipeline {
             decision = ?check.decision,
             "stage check completed"
         );
+        // A denied entry rolls back its whole batch.
+        if staged && matches!(check.decision, Decision::Deny) {
+            return Err(PipelineError::PolicyDenied(check.reason.clone()));
+        }
+
+        // Record which policy set applied so the decision can be replayed later.
+        if let Some(ref policy_set_hash) = check.policy_set_hash {
+            receipt.set_effect("policy_set_hash", serde_json::json!(policy_set_hash));
+        }
+        if let Some(ref merge) = check.merge {
+            receipt.set_effect("merge", merge.clone());
+        }
+        // Quarantined chips are stored but their TR/WF events and outbox
+        // emit wait for `release_quarantine`.
+        let quarantined = matches!(check.decision, Decision::Quarantine);
+        if quarantined {
+            receipt.quarantine(&check.reason);
+        }
 
         receipt
             .append_stage(StageExecution {
@@ -212,14 +342,13 @@ impl UblPipeline {
             .map_err(|e| PipelineError::Internal(format!("Receipt CHECK: {}", e)))?;
 
         // Post-CHECK advisory hook (non-blocking) — explain denial
-        if let (Some(ref engine), Some(ref store)) = (&self.advisory_engine, &self.chip_store) {
+        if let (Some(engine), Some(store)) = (
+            &self.advisory_engine,
+            self.chip_store.as_ref().filter(|_| !simulate),
+        ) {
             let adv = engine.post_check_advisory(
                 wa_receipt.body_cid.as_str(),
-                if matches!(check.decision, Decision::Deny) {
-                    "deny"
-                } else {
-                    "allow"
-                },
+                decision_to_wire(&check.decision),
                 &check.reason,
                 &check
                     .trace
@@ -228,23 +357,11 @@ impl UblPipeline {
                     .collect::<Vec<_>>(),
             );
             let body = engine.advisory_to_chip_body(&adv);
-            let store = store.clone();
-            tokio::spawn(async move {
-                let metadata = ExecutionMetadata {
-                    runtime_version: "advisory/post-check".to_string(),
-                    execution_time_ms: 0,
-                    fuel_consumed: 0,
-                    policies_applied: vec![],
-                    executor_did: ubl_types::Did::new_unchecked("did:key:advisory"),
-                    reproducible: false,
-                };
-                if let Err(e) = store
-                    .store_executed_chip(body, "self".to_string(), metadata)
-                    .await
-                {
-                    warn!(error = %e, "advisory post-CHECK store failed (non-fatal)");
-                }
-            });
+            if staged {
+                held_advisories.push((POST_CHECK_ADVISORY, body));
+            } else {
+                spawn_advisory_store(store.clone(), body, POST_CHECK_ADVISORY);
+            }
         }
 
         // Short-circuit if denied
@@ -274,7 +391,9 @@ impl UblPipeline {
                 .finalize_and_sign(&self.signing_key, CryptoMode::from_env())
                 .map_err(|e| PipelineError::SignError(format!("WF(DENY) sign failed: {}", e)))?;
 
-            if let Err(e) = self
+            if simulate {
+                debug!(chip_type = %parsed_request.chip_type, "simulation: WF event skipped");
+            } else if let Err(e) = self
                 .event_bus
                 .publish_stage_event(crate::event_bus::ReceiptEvent::from_stage_receipt(
                     wf_receipt.body_cid.as_str(),
@@ -291,6 +410,7 @@ impl UblPipeline {
                         actor: Some(self.did.clone()),
                         subject_did: Some(subject_did.clone()),
                         knock_cid: Some(knock_cid.clone()),
+                        correlation_id: correlation_id.clone(),
                     },
                 ))
                 .await
@@ -300,11 +420,7 @@ impl UblPipeline {
 
             let result = PipelineResult {
                 final_receipt: wf_receipt.clone(),
-                chain: vec![
-                    wa_receipt.body_cid.as_str().to_string(),
-                    "no-tr".to_string(),
-                    wf_receipt.body_cid.as_str().to_string(),
-                ],
+                chain: Self::result_chain(&wa_receipt, "no-tr", wf_receipt.body_cid.as_str()),
                 decision: Decision::Deny,
                 receipt,
                 replayed: false,
@@ -317,14 +433,25 @@ impl UblPipeline {
                 "pipeline completed"
             );
 
-            self.persist_final_result(Some(&idem_key), world, &result)
+            if !simulate {
+                self.persist_final_result(
+                    Some(&idem_key),
+                    client_idem.as_ref(),
+                    idem_ttl_secs,
+                    world,
+                    &result,
+                )
                 .await?;
-            return Ok(result);
+            }
+            return Ok(ChipRun::finished(result));
         }
 
         // Stage 3: TR (Transition - RB-VM execution)
         let tr_start = std::time::Instant::now();
-        let tr_receipt = self.stage_transition(&parsed_request, &check).await?;
+        let tr_receipt = self
+            .stage_transition(&parsed_request, &check)
+            .instrument(stage_span(PipelineStage::Transition))
+            .await?;
         let tr_ms = tr_start.elapsed().as_millis() as i64;
         debug!(chip_type = %parsed_request.chip_type, duration_ms = tr_ms, "stage tr completed");
 
@@ -358,25 +485,32 @@ impl UblPipeline {
             .map_err(|e| PipelineError::Internal(format!("Receipt TR: {}", e)))?;
 
         // Publish TR event
-        if let Err(e) = self
-            .event_bus
-            .publish_stage_event(crate::event_bus::ReceiptEvent::from_stage_receipt(
-                tr_receipt.body_cid.as_str(),
-                &tr_receipt.receipt_type,
-                tr_receipt.body.clone(),
-                "tr",
-                StageEventContext {
-                    decision: None,
-                    duration_ms: Some(tr_ms),
-                    world: Some(world.to_string()),
-                    input_cid: Some(wa_receipt.body_cid.as_str().to_string()),
-                    binary_hash: Some(self.runtime_info.binary_hash.clone()),
-                    build_meta: serde_json::to_value(&self.runtime_info.build).ok(),
-                    actor: Some(self.did.clone()),
-                    subject_did: Some(subject_did.clone()),
-                    knock_cid: Some(knock_cid.clone()),
-                },
-            ))
+        if simulate {
+            debug!(chip_type = %parsed_request.chip_type, "simulation: TR event skipped");
+        } else if quarantined {
+            debug!(chip_type = %parsed_request.chip_type, "quarantined: TR event held");
+        } else if let Err(e) = self
+            .publish_or_hold(
+                crate::event_bus::ReceiptEvent::from_stage_receipt(
+                    tr_receipt.body_cid.as_str(),
+                    &tr_receipt.receipt_type,
+                    tr_receipt.body.clone(),
+                    "tr",
+                    StageEventContext {
+                        decision: None,
+                        duration_ms: Some(tr_ms),
+                        world: Some(world.to_string()),
+                        input_cid: Some(wa_receipt.body_cid.as_str().to_string()),
+                        binary_hash: Some(self.runtime_info.binary_hash.clone()),
+                        build_meta: serde_json::to_value(&self.runtime_info.build).ok(),
+                        actor: Some(self.did.clone()),
+                        subject_did: Some(subject_did.clone()),
+                        knock_cid: Some(knock_cid.clone()),
+                        correlation_id: correlation_id.clone(),
+                    },
+                ),
+                &mut held_events,
+            )
             .await
         {
             warn!(error = %e, "Failed to publish receipt event");
@@ -393,6 +527,7 @@ impl UblPipeline {
                 &check,
                 total_ms_before_wf,
             )
+            .instrument(stage_span(PipelineStage::WriteFinished))
             .await?;
         let wf_ms = wf_start.elapsed().as_millis() as i64;
         debug!(chip_type = %parsed_request.chip_type, duration_ms = wf_ms, "stage wf completed");
@@ -421,46 +556,97 @@ impl UblPipeline {
         let total_ms = pipeline_start.elapsed().as_millis() as i64;
 
         // Publish successful WF event
-        if let Err(e) = self
-            .event_bus
-            .publish_stage_event(crate::event_bus::ReceiptEvent::from_stage_receipt(
-                wf_receipt.body_cid.as_str(),
-                &wf_receipt.receipt_type,
-                wf_receipt.body.clone(),
-                "wf",
-                StageEventContext {
-                    decision: Some("allow".to_string()),
-                    duration_ms: Some(total_ms),
-                    world: Some(world.to_string()),
-                    input_cid: Some(tr_receipt.body_cid.as_str().to_string()),
-                    binary_hash: Some(self.runtime_info.binary_hash.clone()),
-                    build_meta: serde_json::to_value(&self.runtime_info.build).ok(),
-                    actor: Some(self.did.clone()),
-                    subject_did: Some(subject_did.clone()),
-                    knock_cid: Some(knock_cid.clone()),
-                },
-            ))
+        if simulate {
+            debug!(chip_type = %parsed_request.chip_type, "simulation: WF event skipped");
+        } else if quarantined {
+            info!(
+                chip_type = %parsed_request.chip_type,
+                world = %parsed_request.world,
+                reason = %check.reason,
+                "chip quarantined; WF event held until release"
+            );
+        } else if let Err(e) = self
+            .publish_or_hold(
+                crate::event_bus::ReceiptEvent::from_stage_receipt(
+                    wf_receipt.body_cid.as_str(),
+                    &wf_receipt.receipt_type,
+                    wf_receipt.body.clone(),
+                    "wf",
+                    StageEventContext {
+                        decision: Some(decision_to_wire(&check.decision).to_string()),
+                        duration_ms: Some(total_ms),
+                        world: Some(world.to_string()),
+                        input_cid: Some(tr_receipt.body_cid.as_str().to_string()),
+                        binary_hash: Some(self.runtime_info.binary_hash.clone()),
+                        build_meta: serde_json::to_value(&self.runtime_info.build).ok(),
+                        actor: Some(self.did.clone()),
+                        subject_did: Some(subject_did.clone()),
+                        knock_cid: Some(knock_cid.clone()),
+                        correlation_id: correlation_id.clone(),
+                    },
+                ),
+                &mut held_events,
+            )
             .await
         {
             warn!(error = %e, "Failed to publish receipt event");
         }
 
+        let result = PipelineResult {
+            final_receipt: wf_receipt.clone(),
+            chain: Self::result_chain(
+                &wa_receipt,
+                tr_receipt.body_cid.as_str(),
+                wf_receipt.body_cid.as_str(),
+            ),
+            decision: check.decision.clone(),
+            receipt,
+            replayed: false,
+        };
+
+        if simulate {
+            info!(
+                chip_type = %parsed_request.chip_type,
+                world = %parsed_request.world,
+                decision = decision_to_wire(&result.decision),
+                duration_ms = total_ms,
+                "pipeline simulated"
+            );
+            return Ok(ChipRun::finished(result));
+        }
+        if staged {
+            return Ok(ChipRun {
+                result,
+                pending: Some(Box::new(StagedWrite {
+                    body: parsed_request.body().clone(),
+                    chip_type: parsed_request.chip_type.to_string(),
+                    parents: parsed_request.parents().to_vec(),
+                    author: authorship_ctx.author.clone(),
+                    world: world.to_string(),
+                    idem_key,
+                    client_idem,
+                    idem_ttl_secs,
+                    metadata: self.wf_execution_metadata(total_ms, fuel_used, &check.trace),
+                    total_ms,
+                    counters: check.counters,
+                    events: held_events.unwrap_or_default(),
+                    advisories: held_advisories,
+                })),
+            });
+        }
+
         // Persist chip to ChipStore.
         // For `ubl/key.rotate`, mapping persistence is fail-closed.
         if let Some(ref store) = self.chip_store {
-            let metadata = ExecutionMetadata {
-                runtime_version: "rb_vm/0.1".to_string(),
-                execution_time_ms: total_ms,
-                fuel_consumed: self.fuel_limit,
-                policies_applied: check.trace.iter().map(|t| t.policy_id.clone()).collect(),
-                executor_did: ubl_types::Did::new_unchecked(&self.did),
-                reproducible: true,
-            };
+            let metadata = self.wf_execution_metadata(total_ms, fuel_used, &check.trace);
             let stored_chip_res = store
-                .store_executed_chip(
+                .store_chip_with_parents(
                     parsed_request.body().clone(),
                     unified_receipt_cid.clone(),
                     metadata,
+                    parsed_request.parents(),
+                    authorship_ctx.author.clone(),
+                    quarantined,
                 )
                 .await;
 
@@ -485,7 +671,7 @@ impl UblPipeline {
                 let mapping_meta = ExecutionMetadata {
                     runtime_version: "key_rotation/0.1".to_string(),
                     execution_time_ms: total_ms,
-                    fuel_consumed: self.fuel_limit,
+                    fuel_consumed: fuel_used.unwrap_or(0),
                     policies_applied: check.trace.iter().map(|t| t.policy_id.clone()).collect(),
                     executor_did: ubl_types::Did::new_unchecked(&self.did),
                     reproducible: true,
@@ -523,95 +709,136 @@ impl UblPipeline {
             } else if let Err(e) = stored_chip_res {
                 warn!(error = %e, "ChipStore persist failed (non-fatal)");
             }
+            self.invalidate_schema_index(parsed_request.chip_type).await;
         } else if parsed_request.chip_type == "ubl/key.rotate" {
             return Err(PipelineError::StorageError(
                 "ubl/key.rotate requires ChipStore persistence".to_string(),
             ));
         }
 
-        // Append to audit ledger (best-effort — never blocks pipeline)
-        {
-            let (app, tenant) = ubl_ai_nrf1::UblEnvelope::parse_world(world)
-                .map(|(a, t)| (a.to_string(), t.to_string()))
-                .unwrap_or_else(|| ("unknown".to_string(), "unknown".to_string()));
-            let entry = crate::ledger::LedgerEntry {
-                ts: chrono::Utc::now().to_rfc3339(),
-                event: crate::ledger::LedgerEvent::ReceiptCreated,
-                app,
-                tenant,
-                chip_cid: wf_receipt.body_cid.as_str().to_string(),
-                receipt_cid: unified_receipt_cid.clone(),
-                decision: "Allow".to_string(),
-                did: Some(self.did.clone()),
-                kid: Some(self.kid.clone()),
-            };
-            if let Err(e) = self.ledger.append(&entry).await {
-                warn!(error = %e, "Ledger append failed (non-fatal)");
-            }
-        }
-
-        // Post-WF advisory hook (non-blocking) — classify and summarize
-        if let (Some(ref engine), Some(ref store)) = (&self.advisory_engine, &self.chip_store) {
-            let adv = engine.post_wf_advisory(
-                wf_receipt.body_cid.as_str(),
-                parsed_request.chip_type,
-                "allow",
-                total_ms,
-            );
-            let body = engine.advisory_to_chip_body(&adv);
-            let store = store.clone();
-            tokio::spawn(async move {
-                let metadata = ExecutionMetadata {
-                    runtime_version: "advisory/post-wf".to_string(),
-                    execution_time_ms: 0,
-                    fuel_consumed: 0,
-                    policies_applied: vec![],
-                    executor_did: ubl_types::Did::new_unchecked("did:key:advisory"),
-                    reproducible: false,
-                };
-                if let Err(e) = store
-                    .store_executed_chip(body, "self".to_string(), metadata)
-                    .await
-                {
-                    warn!(error = %e, "advisory post-WF store failed (non-fatal)");
-                }
-            });
-        }
+        self.append_receipt_ledger(
+            world,
+            wf_receipt.body_cid.as_str(),
+            &unified_receipt_cid,
+            &check.decision,
+        )
+        .await;
+        self.post_wf_advisory(
+            wf_receipt.body_cid.as_str(),
+            parsed_request.chip_type,
+            &check.decision,
+            total_ms,
+        );
 
-        let result = PipelineResult {
-            final_receipt: wf_receipt.clone(),
-            chain: vec![
-                wa_receipt.body_cid.as_str().to_string(),
-                tr_receipt.body_cid.as_str().to_string(),
-                wf_receipt.body_cid.as_str().to_string(),
-            ],
-            decision: check.decision,
-            receipt,
-            replayed: false,
-        };
+        self.persist_final_result(
+            Some(&idem_key),
+            client_idem.as_ref(),
+            idem_ttl_secs,
+            world,
+            &result,
+        )
+        .await?;
 
-        self.persist_final_result(Some(&idem_key), world, &result)
-            .await?;
+        if !quarantined {
+            self.spawn_post_wf_hook(result.receipt.clone(), parsed_request.body().clone());
+        }
 
         info!(
             chip_type = %parsed_request.chip_type,
             world = %parsed_request.world,
-            decision = "allow",
+            decision = decision_to_wire(&result.decision),
             duration_ms = total_ms,
             receipt_cid = %unified_receipt_cid,
             "pipeline completed"
         );
 
-        Ok(result)
+        Ok(ChipRun::finished(result))
+    }
+
+    /// Publish a stage event now, or hold it when the run is staged.
+    async fn publish_or_hold(
+        &self,
+        event: crate::event_bus::ReceiptEvent,
+        held: &mut Option<Vec<crate::event_bus::ReceiptEvent>>,
+    ) -> Result<(), crate::event_bus::EventBusError> {
+        match held {
+            Some(held) => {
+                held.push(event);
+                Ok(())
+            }
+            None => self.event_bus.publish_stage_event(event).await,
+        }
+    }
+
+    pub(super) fn wf_execution_metadata(
+        &self,
+        total_ms: i64,
+        fuel_used: Option<u64>,
+        trace: &[PolicyTraceEntry],
+    ) -> ExecutionMetadata {
+        ExecutionMetadata {
+            runtime_version: "rb_vm/0.1".to_string(),
+            execution_time_ms: total_ms,
+            fuel_consumed: fuel_used.unwrap_or(0),
+            policies_applied: trace.iter().map(|t| t.policy_id.clone()).collect(),
+            executor_did: ubl_types::Did::new_unchecked(&self.did),
+            reproducible: true,
+        }
+    }
+
+    /// Append to audit ledger (best-effort — never blocks pipeline).
+    pub(super) async fn append_receipt_ledger(
+        &self,
+        world: &str,
+        chip_cid: &str,
+        receipt_cid: &str,
+        decision: &Decision,
+    ) {
+        let (app, tenant) = ubl_ai_nrf1::UblEnvelope::parse_world(world)
+            .map(|(a, t)| (a.to_string(), t.to_string()))
+            .unwrap_or_else(|| ("unknown".to_string(), "unknown".to_string()));
+        let entry = crate::ledger::LedgerEntry {
+            ts: chrono::Utc::now().to_rfc3339(),
+            event: crate::ledger::LedgerEvent::ReceiptCreated,
+            app,
+            tenant,
+            chip_cid: chip_cid.to_string(),
+            receipt_cid: receipt_cid.to_string(),
+            decision: format!("{:?}", decision),
+            did: Some(self.did.clone()),
+            kid: Some(self.kid.clone()),
+        };
+        if let Err(e) = self.ledger.append(&entry).await {
+            warn!(error = %e, "Ledger append failed (non-fatal)");
+        }
+    }
+
+    /// Post-WF advisory hook (non-blocking) — classify and summarize.
+    pub(super) fn post_wf_advisory(
+        &self,
+        wf_cid: &str,
+        chip_type: &str,
+        decision: &Decision,
+        total_ms: i64,
+    ) {
+        if let (Some(ref engine), Some(ref store)) = (&self.advisory_engine, &self.chip_store) {
+            let adv =
+                engine.post_wf_advisory(wf_cid, chip_type, decision_to_wire(decision), total_ms);
+            let body = engine.advisory_to_chip_body(&adv);
+            spawn_advisory_store(store.clone(), body, POST_WF_ADVISORY);
+        }
     }
 
     /// Produce a signed, persisted DENY receipt for envelopes rejected at KNOCK.
+    /// With `simulate` the receipt is signed and marked `simulated` but is
+    /// neither published nor persisted.
     pub async fn process_knock_rejection(
         &self,
         knock_cid: &str,
         reason_code: &str,
         reason: &str,
         subject_did_hint: Option<String>,
+        simulate: bool,
     ) -> Result<PipelineResult, PipelineError> {
         let world = "ubl/system";
         let nonce = Self::generate_nonce();
@@ -649,6 +876,9 @@ impl UblPipeline {
                 serde_json::Value::String(knock_cid.to_string()),
             );
         }
+        if simulate {
+            receipt.set_effect("simulated", serde_json::json!(true));
+        }
         receipt.deny(reason);
 
         receipt
@@ -670,12 +900,14 @@ impl UblPipeline {
             .map_err(|e| PipelineError::SignError(format!("WF(KNOCK_DENY) sign failed: {}", e)))?;
 
         let receipt_json = receipt.to_json().unwrap_or_default();
-        if let Err(e) = self
-            .event_bus
-            .publish_stage_event(crate::event_bus::ReceiptEvent::from(&receipt))
-            .await
-        {
-            warn!(error = %e, "Failed to publish knock deny receipt event");
+        if !simulate {
+            if let Err(e) = self
+                .event_bus
+                .publish_stage_event(crate::event_bus::ReceiptEvent::from(&receipt))
+                .await
+            {
+                warn!(error = %e, "Failed to publish knock deny receipt event");
+            }
         }
 
         let result = PipelineResult {
@@ -694,74 +926,186 @@ impl UblPipeline {
             replayed: false,
         };
 
-        self.persist_final_result(None, world, &result).await?;
+        if !simulate {
+            self.persist_final_result(None, None, None, world, &result)
+                .await?;
+        }
         Ok(result)
     }
 
-    async fn persist_final_result(
+    /// Live cached result for `key`; closed replay windows read as a miss.
+    async fn lookup_idempotent(
+        &self,
+        key: &IdempotencyKey,
+    ) -> Result<Option<CachedResult>, PipelineError> {
+        let cached = if let Some(durable) = &self.durable_store {
+            durable
+                .get_idempotent(&key.to_durable_key())
+                .map_err(|e| PipelineError::StorageError(format!("Idempotency lookup: {}", e)))?
+        } else {
+            self.idempotency_store.get(key).await
+        };
+        Ok(cached.filter(|cached| {
+            let expired = cached.is_expired();
+            if expired {
+                debug!(
+                    receipt_cid = %cached.receipt_cid,
+                    "idempotency replay window closed; re-executing"
+                );
+            }
+            !expired
+        }))
+    }
+
+    /// `client_idem` is a client key and the canonical CID of its request.
+    pub(super) async fn persist_final_result(
         &self,
         idem_key: Option<&IdempotencyKey>,
+        client_idem: Option<&(IdempotencyKey, String)>,
+        idem_ttl_secs: Option<u64>,
         world: &str,
         result: &PipelineResult,
     ) -> Result<(), PipelineError> {
         if let Some(ref durable) = self.durable_store {
-            let receipt_json = result
-                .receipt
-                .to_json()
-                .map_err(|e| PipelineError::DurableCommitFailed(e.to_string()))?;
-            let rt_hash = result
-                .receipt
-                .rt
-                .as_ref()
-                .map(|rt| rt.binary_hash.clone())
-                .unwrap_or_else(|| self.runtime_info.binary_hash.clone());
-            let created_at = chrono::Utc::now().timestamp();
-            let event = NewOutboxEvent {
-                event_type: "emit_receipt".to_string(),
-                payload_json: serde_json::json!({
-                    "receipt_cid": result.receipt.receipt_cid.as_str(),
-                    "decision": decision_to_wire(&result.decision),
-                    "world": world,
-                }),
-            };
-
-            let input = CommitInput {
+            let input = self.commit_input(idem_key, client_idem, idem_ttl_secs, world, result)?;
+            durable
+                .commit_wf_atomically(&input)
+                .map(|_| ())
+                .map_err(durable_commit_error)
+        } else {
+            let cached = CachedResult {
                 receipt_cid: result.receipt.receipt_cid.as_str().to_string(),
-                receipt_json,
-                did: self.did.clone(),
-                kid: self.kid.clone(),
-                rt_hash,
+                response_json: result.receipt.to_json().unwrap_or_default(),
                 decision: decision_to_wire(&result.decision).to_string(),
-                idem_key: idem_key.map(|k| k.to_durable_key()),
                 chain: result.chain.clone(),
-                outbox_events: vec![event],
-                created_at,
-                fail_after_receipt_write: false,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                expires_at: idem_ttl_secs
+                    .map(|ttl| expiry_after(chrono::Utc::now().timestamp(), ttl)),
+                fingerprint: None,
             };
-
-            match durable.commit_wf_atomically(&input) {
-                Ok(_) => Ok(()),
-                Err(DurableError::IdempotencyConflict(e)) => {
-                    Err(PipelineError::IdempotencyConflict(e))
-                }
-                Err(e) => Err(PipelineError::DurableCommitFailed(e.to_string())),
+            if let Some((key, fingerprint)) = client_idem {
+                let entry = CachedResult {
+                    fingerprint: Some(fingerprint.clone()),
+                    ..cached.clone()
+                };
+                self.idempotency_store.put(key.clone(), entry).await;
             }
-        } else {
             if let Some(key) = idem_key.cloned() {
-                self.idempotency_store
-                    .put(
-                        key,
-                        CachedResult {
-                            receipt_cid: result.receipt.receipt_cid.as_str().to_string(),
-                            response_json: result.receipt.to_json().unwrap_or_default(),
-                            decision: decision_to_wire(&result.decision).to_string(),
-                            chain: result.chain.clone(),
-                            created_at: chrono::Utc::now().to_rfc3339(),
-                        },
-                    )
-                    .await;
+                self.idempotency_store.put(key, cached).await;
             }
             Ok(())
         }
     }
+
+    /// Durable commit row for a WF result.
+    pub(super) fn commit_input(
+        &self,
+        idem_key: Option<&IdempotencyKey>,
+        client_idem: Option<&(IdempotencyKey, String)>,
+        idem_ttl_secs: Option<u64>,
+        world: &str,
+        result: &PipelineResult,
+    ) -> Result<CommitInput, PipelineError> {
+        let receipt_json = result
+            .receipt
+            .to_json()
+            .map_err(|e| PipelineError::DurableCommitFailed(e.to_string()))?;
+        let rt_hash = result
+            .receipt
+            .rt
+            .as_ref()
+            .map(|rt| rt.binary_hash.clone())
+            .unwrap_or_else(|| self.runtime_info.binary_hash.clone());
+        let created_at = chrono::Utc::now().timestamp();
+        let idem_expires_at = idem_ttl_secs.map(|ttl| expiry_after(created_at, ttl));
+        // Quarantine defers the emit to `release_quarantine`.
+        let outbox_events = if matches!(result.decision, Decision::Quarantine) {
+            vec![]
+        } else {
+            vec![NewOutboxEvent {
+                event_type: "emit_receipt".to_string(),
+                payload_json: serde_json::json!({
+                    "receipt_cid": result.receipt.receipt_cid.as_str(),
+                    "decision": decision_to_wire(&result.decision),
+                    "world": world,
+                }),
+            }]
+        };
+
+        Ok(CommitInput {
+            receipt_cid: result.receipt.receipt_cid.as_str().to_string(),
+            receipt_json,
+            did: self.did.clone(),
+            kid: self.kid.clone(),
+            rt_hash,
+            decision: decision_to_wire(&result.decision).to_string(),
+            idem_key: idem_key.map(|k| k.to_durable_key()),
+            idem_expires_at,
+            client_idem: client_idem.map(|(key, fingerprint)| ClientIdempotency {
+                key: key.to_durable_key(),
+                fingerprint: fingerprint.clone(),
+            }),
+            chain: result.chain.clone(),
+            outbox_events,
+            created_at,
+            fail_after_receipt_write: false,
+        })
+    }
+}
+
+pub(super) fn durable_commit_error(e: DurableError) -> PipelineError {
+    match e {
+        DurableError::IdempotencyConflict(e) => PipelineError::IdempotencyConflict(e),
+        e => PipelineError::DurableCommitFailed(e.to_string()),
+    }
+}
+
+const POST_CHECK_ADVISORY: &str = "advisory/post-check";
+const POST_WF_ADVISORY: &str = "advisory/post-wf";
+
+impl UblPipeline {
+    /// Run the post-WF hook in the background so a slow hook never holds up
+    /// the response.
+    pub(super) fn spawn_post_wf_hook(&self, receipt: UnifiedReceipt, chip: serde_json::Value) {
+        let hook = self.post_wf_hook.clone();
+        tokio::spawn(async move {
+            if let Err(e) = hook.after_wf(&receipt, &chip).await {
+                warn!(error = %e, "post-WF hook failed (non-fatal)");
+            }
+        });
+    }
+}
+
+/// Store an advisory chip in the background; failures are logged only.
+pub(super) fn spawn_advisory_store(
+    store: Arc<ChipStore>,
+    body: serde_json::Value,
+    runtime_version: &'static str,
+) {
+    tokio::spawn(async move {
+        let metadata = ExecutionMetadata {
+            runtime_version: runtime_version.to_string(),
+            execution_time_ms: 0,
+            fuel_consumed: 0,
+            policies_applied: vec![],
+            executor_did: ubl_types::Did::new_unchecked("did:key:advisory"),
+            reproducible: false,
+        };
+        if let Err(e) = store
+            .store_executed_chip(body, "self".to_string(), metadata)
+            .await
+        {
+            warn!(error = %e, runtime_version, "advisory store failed (non-fatal)");
+        }
+    });
+}
+
+/// Chip types whose TR writes to the store or ledger, so they cannot run
+/// without side effects.
+fn writes_during_transition(chip_type: &str) -> bool {
+    crate::audit_chip::is_audit_request_type(chip_type) || chip_type == "ubl/key.rotate"
+}
+
+fn expiry_after(created_at: i64, ttl_secs: u64) -> i64 {
+    created_at.saturating_add(i64::try_from(ttl_secs).unwrap_or(i64::MAX))
 }
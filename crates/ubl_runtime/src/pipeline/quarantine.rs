@@ -0,0 +1,214 @@
+//! `Quarantine` — a third CHECK outcome for chips that pass validation but
+//! trip a heuristic (oversized body, watched world).
+//!
+//! Quarantined chips run TR/WF and are stored with `quarantined: true`, but
+//! their TR/WF events and outbox emit are held back until an operator calls
+//! [`UblPipeline::release_quarantine`]. The receipt carries
+//! `effects.quarantine_reason`.
+
+use super::*;
+
+/// Heuristics that turn an `Allow` into `Quarantine`. Empty = disabled.
+#[derive(Debug, Clone, Default)]
+pub struct QuarantinePolicy {
+    /// Quarantine bodies whose JSON encoding exceeds this many bytes.
+    pub max_body_bytes: Option<usize>,
+    /// Quarantine chips written to these worlds (path-prefix match).
+    pub worlds: Vec<String>,
+}
+
+impl QuarantinePolicy {
+    /// `UBL_QUARANTINE_MAX_BODY_BYTES` and `UBL_QUARANTINE_WORLDS` (comma-separated).
+    pub fn from_env() -> Self {
+        let max_body_bytes = std::env::var("UBL_QUARANTINE_MAX_BODY_BYTES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|v| *v > 0);
+        let worlds = std::env::var("UBL_QUARANTINE_WORLDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty())
+            .collect();
+        Self {
+            max_body_bytes,
+            worlds,
+        }
+    }
+
+    /// Reason to quarantine, or `None` when no heuristic trips.
+    pub fn evaluate(&self, world: &str, body_size: usize) -> Option<String> {
+        if let Some(max) = self.max_body_bytes {
+            if body_size > max {
+                return Some(format!(
+                    "body size {} exceeds quarantine threshold {}",
+                    body_size, max
+                ));
+            }
+        }
+        self.worlds
+            .iter()
+            .find(|w| {
+                world == w.as_str()
+                    || world
+                        .strip_prefix(w.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .map(|w| format!("world '{}' is under quarantine watch '{}'", world, w))
+    }
+}
+
+/// Outcome of [`UblPipeline::release_quarantine`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuarantineRelease {
+    Released {
+        chip_cid: String,
+        receipt_cid: String,
+    },
+    /// Released by an earlier call; nothing was emitted again.
+    AlreadyReleased {
+        chip_cid: String,
+        receipt_cid: String,
+    },
+    NotFound,
+    NotQuarantined,
+}
+
+impl UblPipeline {
+    /// Replace the quarantine heuristics (defaults to [`QuarantinePolicy::from_env`]).
+    pub fn set_quarantine_policy(&mut self, policy: QuarantinePolicy) {
+        self.quarantine_policy = Arc::new(policy);
+    }
+
+    /// Clear the `quarantined` flag on `chip_cid` and emit what WF held back:
+    /// the `emit_receipt` outbox row, the receipt event on the bus and the
+    /// post-WF hook.
+    ///
+    /// The chip store and the durable store share no transaction, so the
+    /// outbox row is the commit point: it is enqueued at most once, before
+    /// the flag flips, and a release cut short in between completes on
+    /// retry. Releasing an already released chip emits nothing.
+    pub async fn release_quarantine(
+        &self,
+        chip_cid: &str,
+    ) -> Result<QuarantineRelease, PipelineError> {
+        let store = self.chip_store.as_ref().ok_or_else(|| {
+            PipelineError::StorageError("quarantine release requires ChipStore".to_string())
+        })?;
+        let chip = store
+            .get_chip(chip_cid)
+            .await
+            .map_err(|e| PipelineError::StorageError(format!("quarantine lookup: {}", e)))?;
+        let Some(chip) = chip else {
+            return Ok(QuarantineRelease::NotFound);
+        };
+        let receipt_cid = chip.receipt_cid.as_str().to_string();
+        let receipt = self.held_receipt(&chip).await?;
+        if !chip.quarantined {
+            let was_held = receipt
+                .as_ref()
+                .is_some_and(|r| matches!(r.decision, Decision::Quarantine));
+            return Ok(if was_held {
+                QuarantineRelease::AlreadyReleased {
+                    chip_cid: chip_cid.to_string(),
+                    receipt_cid,
+                }
+            } else {
+                QuarantineRelease::NotQuarantined
+            });
+        }
+
+        let world = chip
+            .chip_data
+            .get("@world")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        // A released chip is an approved one: consumers see an `allow`.
+        let first_release = match &self.durable_store {
+            Some(ds) => {
+                let event = NewOutboxEvent {
+                    event_type: "emit_receipt".to_string(),
+                    payload_json: serde_json::json!({
+                        "receipt_cid": receipt_cid,
+                        "decision": decision_to_wire(&Decision::Allow),
+                        "world": world,
+                        "released": true,
+                    }),
+                };
+                ds.enqueue_outbox_once(&receipt_cid, &event, chrono::Utc::now().timestamp())
+                    .map_err(|e| PipelineError::StorageError(format!("outbox enqueue: {}", e)))?
+            }
+            None => true,
+        };
+        store
+            .set_quarantined(chip_cid, false)
+            .await
+            .map_err(|e| PipelineError::StorageError(format!("quarantine release: {}", e)))?;
+        self.invalidate_schema_index(&chip.chip_type).await;
+
+        if first_release {
+            match receipt {
+                Some(receipt) => {
+                    if let Err(e) = self
+                        .event_bus
+                        .publish_stage_event(crate::event_bus::ReceiptEvent::from(&receipt))
+                        .await
+                    {
+                        warn!(error = %e, "Failed to publish released receipt event");
+                    }
+                    self.spawn_post_wf_hook(receipt, chip.chip_data.clone());
+                }
+                None => {
+                    warn!(%receipt_cid, "released chip has no retrievable receipt; event skipped")
+                }
+            }
+        }
+
+        info!(%chip_cid, %receipt_cid, "quarantined chip released");
+        Ok(QuarantineRelease::Released {
+            chip_cid: chip_cid.to_string(),
+            receipt_cid,
+        })
+    }
+
+    /// The receipt WF wrote for a stored chip, if it can still be read.
+    async fn held_receipt(
+        &self,
+        chip: &ubl_chipstore::StoredChip,
+    ) -> Result<Option<UnifiedReceipt>, PipelineError> {
+        let receipt_json = match &self.durable_store {
+            Some(ds) => ds
+                .get_receipt(chip.receipt_cid.as_str())
+                .map_err(|e| PipelineError::StorageError(format!("receipt lookup: {}", e)))?,
+            None => match IdempotencyKey::from_chip_body(&chip.chip_data) {
+                Some(key) => self
+                    .idempotency_store
+                    .get(&key)
+                    .await
+                    .map(|cached| cached.response_json),
+                None => None,
+            },
+        };
+        Ok(receipt_json.and_then(|json| UnifiedReceipt::from_json(&json).ok()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_trips_on_size_and_world_prefix() {
+        let policy = QuarantinePolicy {
+            max_body_bytes: Some(100),
+            worlds: vec!["a/watch".to_string()],
+        };
+        assert!(policy.evaluate("a/acme/t/prod", 10).is_none());
+        assert!(policy.evaluate("a/acme/t/prod", 101).is_some());
+        assert!(policy.evaluate("a/watch/t/dev", 10).is_some());
+        assert!(policy.evaluate("a/watchers/t/dev", 10).is_none());
+        assert!(QuarantinePolicy::default()
+            .evaluate("a/watch", 1 << 20)
+            .is_none());
+    }
+}
@@ -0,0 +1,139 @@
+//! Opt-in RB-VM step trace on the TR receipt.
+//!
+//! A chip asks for it with `"trace": true` in its body; `UBL_RECEIPT_TRACE=1`
+//! turns it on for every chip. The TR body then carries `vm_state.trace`,
+//! one `{step, op, fuel_before, fuel_after}` record per executed opcode, cut
+//! at `UBL_RECEIPT_TRACE_MAX_STEPS` (default 256) with
+//! `vm_state.trace_truncated: true` when steps were dropped.
+
+use super::*;
+
+pub const RECEIPT_TRACE_DEFAULT_MAX_STEPS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptTracePolicy {
+    /// Trace every chip, not only those that ask.
+    pub always: bool,
+    pub max_steps: usize,
+}
+
+impl Default for ReceiptTracePolicy {
+    fn default() -> Self {
+        Self {
+            always: false,
+            max_steps: RECEIPT_TRACE_DEFAULT_MAX_STEPS,
+        }
+    }
+}
+
+impl ReceiptTracePolicy {
+    /// `UBL_RECEIPT_TRACE` (`1`/`true`/`on`) and `UBL_RECEIPT_TRACE_MAX_STEPS`.
+    pub fn from_env() -> Self {
+        Self::parse(
+            std::env::var("UBL_RECEIPT_TRACE").ok().as_deref(),
+            std::env::var("UBL_RECEIPT_TRACE_MAX_STEPS").ok().as_deref(),
+        )
+    }
+
+    /// A missing, unparsable or zero step cap falls back to the default.
+    pub fn parse(always: Option<&str>, max_steps: Option<&str>) -> Self {
+        let always = matches!(
+            always
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+                .as_str(),
+            "true" | "1" | "on"
+        );
+        let max_steps = max_steps
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(RECEIPT_TRACE_DEFAULT_MAX_STEPS);
+        Self { always, max_steps }
+    }
+
+    pub fn wants_trace(&self, body: &serde_json::Value) -> bool {
+        self.always || body.get("trace").and_then(|v| v.as_bool()) == Some(true)
+    }
+
+    /// Step records for the receipt and whether the cap dropped any.
+    pub fn render(&self, trace: &[rb_vm::TraceStep]) -> (serde_json::Value, bool) {
+        let mut fuel_before = 0;
+        let steps = trace
+            .iter()
+            .take(self.max_steps)
+            .map(|step| {
+                let record = serde_json::json!({
+                    "step": step.step,
+                    "op": step.op,
+                    "fuel_before": fuel_before,
+                    "fuel_after": step.fuel_after,
+                });
+                fuel_before = step.fuel_after;
+                record
+            })
+            .collect::<Vec<_>>();
+        (
+            serde_json::Value::Array(steps),
+            trace.len() > self.max_steps,
+        )
+    }
+}
+
+impl UblPipeline {
+    /// Replace the receipt trace policy (defaults to [`ReceiptTracePolicy::from_env`]).
+    pub fn set_receipt_trace_policy(&mut self, policy: ReceiptTracePolicy) {
+        self.receipt_trace_policy = Arc::new(policy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn step(step: u64, op: &str, fuel_after: u64) -> rb_vm::TraceStep {
+        rb_vm::TraceStep {
+            step,
+            op: op.to_string(),
+            fuel_after,
+            stack_depth: 0,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn parse_reads_flag_and_falls_back_on_bad_caps() {
+        let p = ReceiptTracePolicy::parse(Some(" ON "), Some("8"));
+        assert!(p.always);
+        assert_eq!(p.max_steps, 8);
+        let p = ReceiptTracePolicy::parse(None, Some("0"));
+        assert_eq!(p, ReceiptTracePolicy::default());
+        assert!(!p.wants_trace(&json!({"trace": "yes"})));
+        assert!(p.wants_trace(&json!({"trace": true})));
+    }
+
+    #[test]
+    fn render_chains_fuel_and_reports_truncation() {
+        let trace = [
+            step(1, "PushInput", 1),
+            step(2, "CasGet", 3),
+            step(3, "EmitRc", 4),
+        ];
+        let policy = ReceiptTracePolicy {
+            always: true,
+            max_steps: 2,
+        };
+        let (steps, truncated) = policy.render(&trace);
+        assert!(truncated);
+        assert_eq!(
+            steps,
+            json!([
+                {"step": 1, "op": "PushInput", "fuel_before": 0, "fuel_after": 1},
+                {"step": 2, "op": "CasGet", "fuel_before": 1, "fuel_after": 3},
+            ])
+        );
+        let (_, truncated) = ReceiptTracePolicy::default().render(&trace);
+        assert!(!truncated);
+    }
+}
@@ -0,0 +1,135 @@
+//! Required governance tags — a CHECK guard that denies chips missing tag
+//! keys an operator requires for a `(world, type)`.
+//!
+//! Tags follow the `ChipQuery` convention: `key:value` strings. A chip
+//! declares them in its body under `tags` (the chip store indexes them as
+//! `tag:key:value`); a required key is satisfied by any tag
+//! `key:<non-empty value>`. Denials carry `REQUIRED_TAG_MISSING`.
+
+use super::*;
+
+pub const REQUIRED_TAG_MISSING: &str = "REQUIRED_TAG_MISSING";
+
+/// One `(world, type) → keys` requirement. `*` matches any world or type;
+/// worlds otherwise match exactly or as a path prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredTagRule {
+    pub world: String,
+    pub chip_type: String,
+    pub keys: Vec<String>,
+}
+
+/// Required tag keys per `(world, type)`. Empty = disabled.
+#[derive(Debug, Clone, Default)]
+pub struct RequiredTagsPolicy {
+    pub rules: Vec<RequiredTagRule>,
+}
+
+impl RequiredTagsPolicy {
+    /// `UBL_REQUIRED_TAGS`, e.g. `a/acme|ubl/document=classification,owner;*|acme/invoice=cost_center`.
+    pub fn from_env() -> Self {
+        Self::parse(&std::env::var("UBL_REQUIRED_TAGS").unwrap_or_default())
+    }
+
+    /// Parse `;`-separated `world|type=key1,key2` rules; malformed entries are skipped.
+    pub fn parse(raw: &str) -> Self {
+        let mut rules = Vec::new();
+        for entry in raw.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let parsed = entry.split_once('=').and_then(|(scope, keys)| {
+                let (world, chip_type) = scope.split_once('|')?;
+                let keys: Vec<String> = keys
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|k| !k.is_empty() && !k.contains(':'))
+                    .map(str::to_string)
+                    .collect();
+                let (world, chip_type) = (world.trim(), chip_type.trim());
+                (!world.is_empty() && !chip_type.is_empty() && !keys.is_empty()).then(|| {
+                    RequiredTagRule {
+                        world: world.to_string(),
+                        chip_type: chip_type.to_string(),
+                        keys,
+                    }
+                })
+            });
+            match parsed {
+                Some(rule) => rules.push(rule),
+                None => warn!(entry = %entry, "ignoring malformed UBL_REQUIRED_TAGS entry"),
+            }
+        }
+        Self { rules }
+    }
+
+    /// Required keys the chip does not carry, in rule order.
+    pub fn missing(&self, world: &str, chip_type: &str, body: &serde_json::Value) -> Vec<String> {
+        let tags: Vec<&str> = body
+            .get("tags")
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|t| t.as_str()).collect())
+            .unwrap_or_default();
+        let mut missing: Vec<String> = Vec::new();
+        for rule in self.rules.iter().filter(|r| r.applies(world, chip_type)) {
+            for key in &rule.keys {
+                let present = tags.iter().any(|t| {
+                    t.split_once(':')
+                        .is_some_and(|(k, v)| k == key && !v.is_empty())
+                });
+                if !present && !missing.contains(key) {
+                    missing.push(key.clone());
+                }
+            }
+        }
+        missing
+    }
+}
+
+impl RequiredTagRule {
+    fn applies(&self, world: &str, chip_type: &str) -> bool {
+        let world_ok = self.world == "*"
+            || world == self.world
+            || world
+                .strip_prefix(self.world.as_str())
+                .is_some_and(|rest| rest.starts_with('/'));
+        world_ok && (self.chip_type == "*" || self.chip_type == chip_type)
+    }
+}
+
+impl UblPipeline {
+    /// Replace the required-tag rules (defaults to [`RequiredTagsPolicy::from_env`]).
+    pub fn set_required_tags_policy(&mut self, policy: RequiredTagsPolicy) {
+        self.required_tags_policy = Arc::new(policy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_and_match_world_prefix_and_type() {
+        let policy = RequiredTagsPolicy::parse(
+            "a/acme|ubl/document=classification, owner; *|acme/invoice=cost_center; broken",
+        );
+        assert_eq!(policy.rules.len(), 2);
+        let tagged = json!({"tags": ["classification:public", "owner:ops"]});
+        assert!(policy
+            .missing("a/acme/t/prod", "ubl/document", &tagged)
+            .is_empty());
+        assert_eq!(
+            policy.missing(
+                "a/acme/t/prod",
+                "ubl/document",
+                &json!({"tags": ["classification:"]})
+            ),
+            vec!["classification", "owner"]
+        );
+        assert!(policy
+            .missing("a/acmex/t/prod", "ubl/document", &json!({}))
+            .is_empty());
+        assert_eq!(
+            policy.missing("a/other", "acme/invoice", &json!({})),
+            vec!["cost_center"]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Registered schema enforcement — a CHECK guard for types registered with
+//! `ubl/meta.register`.
+//!
+//! When the chip's `@type` has a register chip in the same `@world`, the
+//! latest one's `schema.required_fields` must all be present with their
+//! declared `field_type`; otherwise the chip is denied with
+//! `SCHEMA_VALIDATION_FAILED` and the list of offending fields. Unregistered
+//! types, and reserved `ubl/` types, pass through unchanged.
+//!
+//! Registers are matched on their stored `@world`, not the tag index, and
+//! held in a per-world map that is rebuilt after the pipeline writes or
+//! releases a register. Quarantined registers do not count.
+
+use super::*;
+use crate::meta_chip::TypeSchema;
+
+pub const SCHEMA_VALIDATION_FAILED: &str = "SCHEMA_VALIDATION_FAILED";
+const TYPE_META_REGISTER: &str = "ubl/meta.register";
+
+/// Registered schemas by `@world`, then by target type.
+pub(super) type SchemaIndex = HashMap<String, HashMap<String, TypeSchema>>;
+
+impl UblPipeline {
+    /// Drop the cached schemas when a chip of `chip_type` may change them.
+    pub(super) async fn invalidate_schema_index(&self, chip_type: &str) {
+        if chip_type == TYPE_META_REGISTER {
+            *self.schema_index.write().await = None;
+        }
+    }
+
+    /// The cached schema map, built from the chip store on first use.
+    async fn schema_index(&self, store: &ChipStore) -> Result<Arc<SchemaIndex>, PipelineError> {
+        if let Some(index) = self.schema_index.read().await.as_ref() {
+            return Ok(index.clone());
+        }
+        let mut slot = self.schema_index.write().await;
+        if let Some(index) = slot.as_ref() {
+            return Ok(index.clone());
+        }
+        // Newest first; the first register for a type in a world wins.
+        let registers = store
+            .query(&ubl_chipstore::ChipQuery {
+                chip_type: Some(TYPE_META_REGISTER.to_string()),
+                tags: vec![],
+                created_after: None,
+                created_before: None,
+                executor_did: None,
+                limit: Some(usize::MAX),
+                offset: None,
+            })
+            .await
+            .map_err(|e| PipelineError::Internal(format!("ChipStore: {}", e)))?;
+        let mut index = SchemaIndex::new();
+        for chip in registers.chips.iter().filter(|chip| !chip.quarantined) {
+            let Some(world) = chip.chip_data.get("@world").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Ok(register) = crate::meta_chip::parse_register(&chip.chip_data) else {
+                continue;
+            };
+            index
+                .entry(world.to_string())
+                .or_default()
+                .entry(register.target_type)
+                .or_insert(register.schema);
+        }
+        let index = Arc::new(index);
+        *slot = Some(index.clone());
+        Ok(index)
+    }
+
+    /// Deny reason when the body breaks its registered schema.
+    pub(super) async fn registered_schema_violation(
+        &self,
+        world: &str,
+        chip_type: &str,
+        body: &serde_json::Value,
+    ) -> Result<Option<String>, PipelineError> {
+        let Some(store) = self.chip_store.as_ref() else {
+            return Ok(None);
+        };
+        if chip_type.starts_with("ubl/") {
+            return Ok(None);
+        }
+        let index = self.schema_index(store).await?;
+        let Some(schema) = index.get(world).and_then(|types| types.get(chip_type)) else {
+            return Ok(None);
+        };
+        let violations = schema.violations(body);
+        Ok((!violations.is_empty()).then(|| {
+            format!(
+                "{}: {} does not match its registered schema: {}",
+                SCHEMA_VALIDATION_FAILED,
+                chip_type,
+                violations.join("; ")
+            )
+        }))
+    }
+}
@@ -5,9 +5,28 @@ impl UblPipeline {
     pub(in crate::pipeline) async fn stage_check(
         &self,
         request: &ParsedChipRequest<'_>,
+    ) -> Result<CheckResult, PipelineError> {
+        let check = self.evaluate_check(request).await?;
+        // Rejections are logged and counted by the registry; they never
+        // change the decision.
+        for emit in &check.counters {
+            let _ = self.policy_counters.record(emit);
+        }
+        Ok(check)
+    }
+
+    /// CHECK without side effects: policy counters are returned, not recorded.
+    pub(in crate::pipeline) async fn evaluate_check(
+        &self,
+        request: &ParsedChipRequest<'_>,
     ) -> Result<CheckResult, PipelineError> {
         let _check_start = std::time::Instant::now();
 
+        // ── Runtime floor: chip may pin a minimum runtime version ──
+        if let Some(min_runtime) = request.body().get("min_runtime") {
+            Self::check_min_runtime(min_runtime, &self.runtime_info.version)?;
+        }
+
         // ── Onboarding pre-check: validate body + dependency chain ──
         if crate::auth::is_onboarding_type(request.chip_type) {
             // 1. Parse chip body into typed onboarding payload
@@ -110,6 +129,108 @@ impl UblPipeline {
             })?;
         }
 
+        // ── Merge chips: every parent exists + shares world and type ─────────────
+        let mut merge_effect = None;
+        if request.chip_type == crate::merge_chip::TYPE_MERGE {
+            let merge = crate::merge_chip::MergeChip::parse(request.body())
+                .map_err(|e| PipelineError::InvalidChip(e.to_string()))?;
+            if let Some(ref store) = self.chip_store {
+                let mut resolved = Vec::with_capacity(merge.parents.len());
+                for parent_cid in &merge.parents {
+                    let parent = store
+                        .get_chip(parent_cid)
+                        .await
+                        .map_err(|e| PipelineError::Internal(format!("ChipStore: {}", e)))?
+                        .ok_or_else(|| {
+                            PipelineError::DependencyMissing(format!(
+                                "ubl/merge parent '{}' not found",
+                                parent_cid
+                            ))
+                        })?;
+                    resolved.push(crate::merge_chip::MergeParent {
+                        cid: parent_cid.clone(),
+                        world: parent
+                            .chip_data
+                            .get("@world")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        chip_type: parent.chip_type,
+                    });
+                }
+                let parent_type = merge
+                    .check_compatible(request.world, &resolved)
+                    .map_err(|e| PipelineError::InvalidChip(e.to_string()))?;
+                merge_effect = Some(merge.effect(&parent_type));
+            }
+        }
+
+        // ── Annotation chips: target exists + shares world ──────────────────────
+        if request.chip_type == crate::annotation_chip::TYPE_ANNOTATION {
+            let annotation = crate::annotation_chip::AnnotationChip::parse(request.body())
+                .map_err(|e| PipelineError::InvalidChip(e.to_string()))?;
+            if let Some(ref store) = self.chip_store {
+                let target = store
+                    .get_chip(&annotation.target_cid)
+                    .await
+                    .map_err(|e| PipelineError::Internal(format!("ChipStore: {}", e)))?
+                    .ok_or_else(|| {
+                        PipelineError::DependencyMissing(format!(
+                            "ubl/annotation target '{}' not found",
+                            annotation.target_cid
+                        ))
+                    })?;
+                let target_world = target
+                    .chip_data
+                    .get("@world")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                annotation
+                    .check_target_world(request.world, target_world)
+                    .map_err(|e| PipelineError::InvalidChip(e.to_string()))?;
+            }
+        }
+
+        // ── Token revokes: token exists, world covers it, author owns it ─────────
+        if request.chip_type == crate::token_revoke::TYPE_TOKEN_REVOKE {
+            let token_id = crate::token_revoke::token_id(request.body())
+                .map_err(|e| PipelineError::InvalidChip(e.to_string()))?;
+            if let Some(ref store) = self.chip_store {
+                let target = crate::token_revoke::resolve_target(store, token_id)
+                    .await
+                    .map_err(|e| PipelineError::Internal(format!("ChipStore: {}", e)))?
+                    .ok_or_else(|| {
+                        PipelineError::DependencyMissing(format!(
+                            "ubl/token.revoke token '{}' not found",
+                            token_id
+                        ))
+                    })?;
+                crate::token_revoke::authorize(request.world, request.author, &target)
+                    .map_err(|e| PipelineError::PolicyDenied(e.to_string()))?;
+            }
+        }
+
+        // ── Policy chips: body must parse, or reload would have to skip it ──────
+        if crate::policy_loader::is_policy_chip_type(request.chip_type) {
+            let chip = crate::policy_loader::ChipData {
+                cid: String::new(),
+                chip_type: request.chip_type.to_string(),
+                body: request.body().clone(),
+                parents: Vec::new(),
+            };
+            PolicyLoader::parse_policy_chip(&chip)
+                .map_err(|e| PipelineError::InvalidChip(format!("{}: {}", request.chip_type, e)))?;
+        }
+
+        // ── WASM allow-list chips: capability + lists that never read as empty ──
+        if request.chip_type == TYPE_WASM_ALLOWLIST {
+            crate::capability::require_cap(request.body(), "wasm:allowlist", request.world)
+                .map_err(|e| {
+                    PipelineError::InvalidChip(format!("{} capability: {}", TYPE_WASM_ALLOWLIST, e))
+                })?;
+            WasmAllowlist::from_chip(request.body())?;
+        }
+
         // ── @silicon_gate: live silicon enforcement ───────────────────────────────
         // Any chip body may declare "@silicon_gate": "<ubl/silicon.chip CID>".
         // The gate's compiled bytecode runs (ghost mode) against the incoming
@@ -159,11 +280,65 @@ impl UblPipeline {
                             rb_results: vec![],
                             duration_ms: gate_ms,
                         }],
+                        policy_set_hash: None,
+                        merge: None,
+                        counters: vec![],
                     });
                 }
             }
         }
 
+        // ── Required governance tags per (world, type) ────────────────────────────
+        let missing_tags =
+            self.required_tags_policy
+                .missing(request.world, request.chip_type, request.body());
+        if !missing_tags.is_empty() {
+            let reason = format!(
+                "{}: missing required tag key(s) {}",
+                REQUIRED_TAG_MISSING,
+                missing_tags.join(", ")
+            );
+            return Ok(CheckResult {
+                decision: Decision::Deny,
+                reason: reason.clone(),
+                short_circuited: true,
+                trace: vec![PolicyTraceEntry {
+                    level: "required_tags".to_string(),
+                    policy_id: "required_tags".to_string(),
+                    result: Decision::Deny,
+                    reason,
+                    rb_results: vec![],
+                    duration_ms: 0,
+                }],
+                policy_set_hash: None,
+                merge: None,
+                counters: vec![],
+            });
+        }
+
+        // ── Registered meta schema for the chip's type in its world ───────────────
+        if let Some(reason) = self
+            .registered_schema_violation(request.world, request.chip_type, request.body())
+            .await?
+        {
+            return Ok(CheckResult {
+                decision: Decision::Deny,
+                reason: reason.clone(),
+                short_circuited: true,
+                trace: vec![PolicyTraceEntry {
+                    level: "schema_validation".to_string(),
+                    policy_id: "schema_validation".to_string(),
+                    result: Decision::Deny,
+                    reason,
+                    rb_results: vec![],
+                    duration_ms: 0,
+                }],
+                policy_set_hash: None,
+                merge: None,
+                counters: vec![],
+            });
+        }
+
         // Convert to policy request
         let policy_request = PolicyChipRequest {
             chip_type: request.chip_type.to_string(),
@@ -179,50 +354,153 @@ impl UblPipeline {
             .await
             .map_err(|e| PipelineError::Internal(format!("Policy loading: {}", e)))?;
 
-        // Create evaluation context
-        let body_bytes = serde_json::to_vec(request.body())
+        let policy_set_hash = self.policy_snapshots.retain(&policies);
+        let context = Self::build_eval_context(request.chip_type, request.chip_id, request.body())?;
+        let mut check = Self::evaluate_policy_chain(&policies, &context);
+        check.policy_set_hash = Some(policy_set_hash);
+        check.merge = merge_effect;
+
+        // ── Quarantine heuristics: only an Allow can be held for review.
+        // Key rotation is exempt: its WF side effects cannot wait for release.
+        if matches!(check.decision, Decision::Allow) && request.chip_type != "ubl/key.rotate" {
+            if let Some(reason) = self
+                .quarantine_policy
+                .evaluate(request.world, context.body_size)
+            {
+                check.trace.push(PolicyTraceEntry {
+                    level: "quarantine".to_string(),
+                    policy_id: "quarantine".to_string(),
+                    result: Decision::Quarantine,
+                    reason: reason.clone(),
+                    rb_results: vec![],
+                    duration_ms: 0,
+                });
+                check.decision = Decision::Quarantine;
+                check.reason = reason;
+            }
+        }
+        Ok(check)
+    }
+
+    /// Dry-run the policy step of CHECK against a retained policy snapshot.
+    ///
+    /// Returns `Ok(None)` when `policy_set_hash` is not (or no longer)
+    /// retained. Type-specific pre-checks (onboarding dependencies, silicon
+    /// gates) are not replayed — only the historical policy chain.
+    pub fn evaluate_against_policy_snapshot(
+        &self,
+        chip: &serde_json::Value,
+        policy_set_hash: &str,
+    ) -> Result<Option<PolicyDryRun>, PipelineError> {
+        let Some(policies) = self.policy_snapshots.get(policy_set_hash) else {
+            return Ok(None);
+        };
+        let chip_type = chip
+            .get("@type")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| PipelineError::InvalidChip("missing @type".to_string()))?;
+        let chip_id = chip
+            .get("@id")
+            .and_then(|v| v.as_str())
+            .or_else(|| chip.get("id").and_then(|v| v.as_str()));
+        let context = Self::build_eval_context(chip_type, chip_id, chip)?;
+        let check = Self::evaluate_policy_chain(&policies, &context);
+        Ok(Some(PolicyDryRun {
+            policy_set_hash: policy_set_hash.to_string(),
+            policy_count: policies.len(),
+            decision: check.decision,
+            reason: check.reason,
+            trace: check.trace,
+        }))
+    }
+
+    fn build_eval_context(
+        chip_type: &str,
+        chip_id: Option<&str>,
+        body: &serde_json::Value,
+    ) -> Result<EvalContext, PipelineError> {
+        let body_bytes = serde_json::to_vec(body)
             .map_err(|e| PipelineError::Internal(format!("Body serialization: {}", e)))?;
 
         let mut variables = HashMap::new();
-        variables.insert(
-            "chip.@type".to_string(),
-            serde_json::json!(request.chip_type),
-        );
-        if let Some(chip_id) = request.chip_id {
+        variables.insert("chip.@type".to_string(), serde_json::json!(chip_type));
+        if let Some(chip_id) = chip_id {
             variables.insert("chip.id".to_string(), serde_json::json!(chip_id));
         }
 
-        let context = EvalContext {
-            chip: request.body().clone(),
+        Ok(EvalContext {
+            chip: body.clone(),
             body_size: body_bytes.len(),
             variables,
-        };
+        })
+    }
 
-        // Evaluate each policy, collecting trace entries
+    /// Evaluate each policy in order, collecting trace entries; stop on first DENY.
+    fn evaluate_policy_chain(policies: &[PolicyBit], context: &EvalContext) -> CheckResult {
         let mut trace = Vec::new();
-        for policy in &policies {
+        let mut counters = Vec::new();
+        for policy in policies {
             let policy_start = std::time::Instant::now();
-            let result = policy.evaluate(&context);
+            let result = policy.evaluate(context);
             let policy_ms = policy_start.elapsed().as_millis() as i64;
 
             trace.push(Self::policy_result_to_trace(&result, policy_ms));
+            counters.extend(
+                result
+                    .circuit_results
+                    .iter()
+                    .flat_map(|cr| cr.rb_results.iter())
+                    .flat_map(|rb| rb.counters.iter().cloned()),
+            );
 
-            // Stop on first DENY
             if matches!(result.decision, Decision::Deny) {
-                return Ok(CheckResult {
+                return CheckResult {
                     decision: Decision::Deny,
                     reason: result.reason,
                     short_circuited: true,
                     trace,
-                });
+                    policy_set_hash: None,
+                    merge: None,
+                    counters,
+                };
             }
         }
 
-        Ok(CheckResult {
+        CheckResult {
             decision: Decision::Allow,
             reason: "All policies allowed".to_string(),
             short_circuited: false,
             trace,
-        })
+            policy_set_hash: None,
+            merge: None,
+            counters,
+        }
+    }
+
+    /// Deny with `RUNTIME_TOO_OLD` when the chip's `min_runtime` (semver)
+    /// is newer than the runtime that would execute it.
+    pub(in crate::pipeline) fn check_min_runtime(
+        min_runtime: &serde_json::Value,
+        runtime_version: &str,
+    ) -> Result<(), PipelineError> {
+        let raw = min_runtime.as_str().ok_or_else(|| {
+            PipelineError::InvalidChip("min_runtime must be a semver string".to_string())
+        })?;
+        let required = semver::Version::parse(raw.trim()).map_err(|e| {
+            PipelineError::InvalidChip(format!("min_runtime '{}' is not valid semver: {}", raw, e))
+        })?;
+        let current = semver::Version::parse(runtime_version).map_err(|e| {
+            PipelineError::Internal(format!(
+                "runtime version '{}' is not valid semver: {}",
+                runtime_version, e
+            ))
+        })?;
+        if current < required {
+            return Err(PipelineError::RuntimeTooOld(format!(
+                "chip requires runtime >= {}, this runtime is {}",
+                required, current
+            )));
+        }
+        Ok(())
     }
 }
@@ -77,6 +77,65 @@ async fn stage_check_allow_and_deny_paths() {
     assert!(!deny.trace.is_empty());
 }
 
+#[tokio::test]
+async fn stage_check_min_runtime_satisfied_allows() {
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    let mut req = allow_request();
+    req.body["min_runtime"] = json!("0.0.1");
+    let check = pipeline.stage_check(&parsed_request(&req)).await.unwrap();
+    assert!(matches!(check.decision, Decision::Allow));
+
+    req.body["min_runtime"] = json!(env!("CARGO_PKG_VERSION"));
+    let check = pipeline.stage_check(&parsed_request(&req)).await.unwrap();
+    assert!(matches!(check.decision, Decision::Allow));
+}
+
+#[tokio::test]
+async fn stage_check_min_runtime_unsatisfied_is_runtime_too_old() {
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    let mut req = allow_request();
+    req.body["min_runtime"] = json!("999.0.0");
+    let err = match pipeline.stage_check(&parsed_request(&req)).await {
+        Err(e) => e,
+        Ok(_) => panic!("expected RUNTIME_TOO_OLD"),
+    };
+    assert!(matches!(err, PipelineError::RuntimeTooOld(_)));
+    let ubl_err = UblError::from_pipeline_error(&err);
+    assert_eq!(ubl_err.to_json()["code"], "RUNTIME_TOO_OLD");
+
+    req.body["min_runtime"] = json!("not-a-version");
+    let err = match pipeline.stage_check(&parsed_request(&req)).await {
+        Err(e) => e,
+        Ok(_) => panic!("expected invalid min_runtime"),
+    };
+    assert!(matches!(err, PipelineError::InvalidChip(_)));
+}
+
+#[tokio::test]
+async fn stage_check_retains_policy_snapshot_for_dry_run() {
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    let allow_req = allow_request();
+    let check = pipeline
+        .stage_check(&parsed_request(&allow_req))
+        .await
+        .unwrap();
+    let hash = check.policy_set_hash.expect("policy set hash recorded");
+    assert!(hash.starts_with("b3:"));
+
+    let replay = pipeline
+        .evaluate_against_policy_snapshot(&deny_request().body, &hash)
+        .unwrap()
+        .expect("snapshot retained");
+    assert_eq!(replay.policy_set_hash, hash);
+    assert!(matches!(replay.decision, Decision::Deny));
+    assert!(!replay.trace.is_empty());
+
+    let missing = pipeline
+        .evaluate_against_policy_snapshot(&allow_req.body, "b3:not-retained")
+        .unwrap();
+    assert!(missing.is_none());
+}
+
 #[tokio::test]
 async fn stage_transition_emits_vm_signature_and_payload_cid() {
     let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
@@ -95,6 +154,43 @@ async fn stage_transition_emits_vm_signature_and_payload_cid() {
     assert!(tr.body["vm_state"]["fuel_used"].as_u64().is_some());
 }
 
+#[tokio::test]
+async fn stage_transition_includes_bounded_trace_on_request() {
+    let mut pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    pipeline.set_receipt_trace_policy(ReceiptTracePolicy::default());
+    let req = allow_request();
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    let tr = pipeline.stage_transition(&parsed, &check).await.unwrap();
+    assert!(tr.body["vm_state"].get("trace").is_none());
+
+    let mut req = allow_request();
+    req.body["trace"] = json!(true);
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    let tr = pipeline.stage_transition(&parsed, &check).await.unwrap();
+    let vm_state = &tr.body["vm_state"];
+    let trace = vm_state["trace"].as_array().unwrap();
+    assert_eq!(trace.len() as u64, vm_state["trace_len"].as_u64().unwrap());
+    assert_eq!(trace[0]["fuel_before"], 0);
+    assert_eq!(
+        trace.last().unwrap()["fuel_after"].as_u64(),
+        vm_state["fuel_used"].as_u64()
+    );
+    assert!(vm_state.get("trace_truncated").is_none());
+
+    pipeline.set_receipt_trace_policy(ReceiptTracePolicy {
+        always: true,
+        max_steps: 1,
+    });
+    let req = allow_request();
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    let tr = pipeline.stage_transition(&parsed, &check).await.unwrap();
+    assert_eq!(tr.body["vm_state"]["trace"].as_array().unwrap().len(), 1);
+    assert_eq!(tr.body["vm_state"]["trace_truncated"], json!(true));
+}
+
 #[tokio::test]
 async fn stage_transition_executes_inline_wasm_adapter() {
     let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
@@ -333,6 +429,119 @@ async fn stage_transition_accepts_wasm_valid_attestation_signature() {
     assert_eq!(tr.body["vm_state"]["adapter_executed"], json!(true));
 }
 
+#[tokio::test]
+async fn stage_transition_enforces_wasm_allowlist_over_attestation() {
+    let mut pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    let module = wasm_identity_module();
+    let wasm_sha256 = hex::encode(digest::digest(&digest::SHA256, &module).as_ref());
+    let attest_payload = json!({"wasm_sha256": wasm_sha256, "abi_version": "1.0"});
+    let sk = ubl_kms::signing_key_from_hex(
+        "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff",
+    )
+    .unwrap();
+    let sig = ubl_kms::sign_canonical(&sk, &attest_payload, ubl_kms::domain::CAPSULE).unwrap();
+    let mut req = allow_request();
+    req.body["adapter"] = json!({
+        "wasm_sha256": wasm_sha256,
+        "abi_version": "1.0",
+        "wasm_b64": base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&module),
+        "attestation_signature_b64": sig,
+        "attestation_trust_anchor": wasm_test_trust_anchor_did()
+    });
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+
+    pipeline.set_wasm_allowlist(WasmAllowlist::parse(&wasm_sha256.to_uppercase()));
+    let tr = pipeline.stage_transition(&parsed, &check).await.unwrap();
+    assert_eq!(tr.body["vm_state"]["adapter_executed"], json!(true));
+
+    pipeline.set_wasm_allowlist(WasmAllowlist::parse(&"b".repeat(64)));
+    match pipeline
+        .stage_transition(&parsed, &check)
+        .await
+        .unwrap_err()
+    {
+        PipelineError::PolicyDenied(msg) => assert!(msg.contains(WASM_MODULE_NOT_ALLOWLISTED)),
+        other => panic!("expected PolicyDenied, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn stage_transition_reads_world_allowlist_chip_when_env_unset() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let mut pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), chip_store.clone());
+    pipeline.set_wasm_allowlist(WasmAllowlist::default());
+    let metadata: ubl_chipstore::ExecutionMetadata = serde_json::from_value(json!({
+        "runtime_version": "test-runtime",
+        "execution_time_ms": 1,
+        "fuel_consumed": 0,
+        "policies_applied": [],
+        "executor_did": "did:key:ztest",
+        "reproducible": true
+    }))
+    .unwrap();
+    chip_store
+        .store_executed_chip(
+            json!({
+                "@type": TYPE_WASM_ALLOWLIST,
+                "@id": "allowlist-1",
+                "@ver": "1.0",
+                "@world": "a/demo/t/main",
+                "wasm_sha256": ["b".repeat(64)]
+            }),
+            "b3:r-allowlist".to_string(),
+            metadata,
+        )
+        .await
+        .unwrap();
+
+    let module = wasm_identity_module();
+    let adapter = json!({
+        "wasm_sha256": hex::encode(digest::digest(&digest::SHA256, &module).as_ref()),
+        "abi_version": "1.0",
+        "wasm_b64": base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&module)
+    });
+    let mut req = allow_request();
+    req.body["adapter"] = adapter.clone();
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    let err = pipeline
+        .stage_transition(&parsed, &check)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(WASM_MODULE_NOT_ALLOWLISTED));
+
+    // Another world has no list: attestation-based behavior applies.
+    let mut other = allow_request();
+    other.body["@world"] = json!("a/other/t/main");
+    other.body["adapter"] = adapter;
+    let parsed = parsed_request(&other);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    let tr = pipeline.stage_transition(&parsed, &check).await.unwrap();
+    assert_eq!(tr.body["vm_state"]["adapter_executed"], json!(true));
+
+    // Writing a list needs the wasm:allowlist capability.
+    let err = pipeline
+        .process_chip(ChipRequest {
+            chip_type: TYPE_WASM_ALLOWLIST.to_string(),
+            body: json!({
+                "@type": TYPE_WASM_ALLOWLIST,
+                "@id": "allowlist-2",
+                "@ver": "1.0",
+                "@world": "a/demo/t/main",
+                "wasm_sha256": ["c".repeat(64)]
+            }),
+            parents: vec![],
+            operation: Some("create".to_string()),
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("wasm.allowlist capability"));
+}
+
 #[tokio::test]
 async fn stage_write_finished_links_wa_and_tr_receipts() {
     let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
@@ -351,6 +560,56 @@ async fn stage_write_finished_links_wa_and_tr_receipts() {
     assert_eq!(wf.body["tr_cid"], json!(tr.body_cid.as_str()));
     assert_eq!(wf.body["decision"], json!("Allow"));
     assert_eq!(wf.body["duration_ms"], json!(123));
+    assert_eq!(wf.body["fuel_limit"], tr.body["vm_state"]["fuel_limit"]);
+    assert_eq!(wf.body["fuel_used"], tr.body["vm_state"]["fuel_used"]);
+}
+
+#[tokio::test]
+async fn stage_transition_honors_clamped_fuel_limit_override() {
+    let mut pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    pipeline.set_fuel_limit(5_000);
+    pipeline.set_max_fuel_limit(20_000);
+    let with_fuel = |fuel_limit: Option<serde_json::Value>| {
+        let mut req = allow_request();
+        if let Some(limit) = fuel_limit {
+            req.body["fuel_limit"] = limit;
+        }
+        req
+    };
+
+    let req = with_fuel(None);
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    let tr = pipeline.stage_transition(&parsed, &check).await.unwrap();
+    assert_eq!(tr.body["vm_state"]["fuel_limit"], json!(5_000));
+
+    let req = with_fuel(Some(json!(1_000_000)));
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    let tr = pipeline.stage_transition(&parsed, &check).await.unwrap();
+    assert_eq!(tr.body["vm_state"]["fuel_limit"], json!(20_000));
+
+    let req = with_fuel(Some(json!(1)));
+    let parsed = parsed_request(&req);
+    let check = pipeline.stage_check(&parsed).await.unwrap();
+    match pipeline
+        .stage_transition(&parsed, &check)
+        .await
+        .unwrap_err()
+    {
+        PipelineError::FuelExhausted(msg) => assert!(msg.contains("limit: 1)")),
+        other => panic!("expected FuelExhausted, got {:?}", other),
+    }
+
+    for bad in [json!(0), json!(-5), json!("lots")] {
+        let req = with_fuel(Some(bad));
+        let parsed = parsed_request(&req);
+        let check = pipeline.stage_check(&parsed).await.unwrap();
+        assert!(matches!(
+            pipeline.stage_transition(&parsed, &check).await,
+            Err(PipelineError::InvalidChip(_))
+        ));
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -103,14 +103,17 @@ impl UblPipeline {
             kid: self.kid.clone(),
         };
         let canon = PipelineCanon;
+        let fuel_limit = self.effective_fuel_limit(request.body())?;
         let cfg = VmConfig {
-            fuel_limit: self.fuel_limit,
+            fuel_limit,
             ghost: false,
             trace: true,
         };
 
         let adapter_info = AdapterRuntimeInfo::parse_optional(request.body())?;
         let adapter_outcome = if let Some(info) = adapter_info.as_ref() {
+            self.enforce_wasm_allowlist(request.world, &info.wasm_sha256)
+                .await?;
             Some(
                 self.execute_wasm_adapter(info, &chip_nrf, &input_cid_str)
                     .await?,
@@ -135,10 +138,9 @@ impl UblPipeline {
         let mut vm = Vm::new(cfg, cas, &signer, canon, vec![input_cid.clone()])
             .with_body_size(chip_nrf.len());
         let outcome = vm.run(&instructions).map_err(|e| match e {
-            ExecError::FuelExhausted => PipelineError::FuelExhausted(format!(
-                "VM fuel exhausted (limit: {})",
-                self.fuel_limit
-            )),
+            ExecError::FuelExhausted => {
+                PipelineError::FuelExhausted(format!("VM fuel exhausted (limit: {})", fuel_limit))
+            }
             ExecError::StackUnderflow(op) => {
                 PipelineError::StackUnderflow(format!("stack underflow at {:?}", op))
             }
@@ -148,6 +150,17 @@ impl UblPipeline {
             ExecError::InvalidPayload(op) => {
                 PipelineError::TypeMismatch(format!("invalid payload for {:?}", op))
             }
+            ExecError::MemoryLimit(max) => PipelineError::FuelExhausted(format!(
+                "VM memory limit exceeded (max stack depth: {})",
+                max
+            )),
+            ExecError::NonFinite(op) => {
+                PipelineError::TypeMismatch(format!("non-finite number at {:?}", op))
+            }
+            ExecError::CasMiss(cid) => PipelineError::CasNotFound(format!("cas miss: {}", cid)),
+            e @ (ExecError::InvalidOpcode(_) | ExecError::Truncated) => {
+                PipelineError::Internal(format!("TR bytecode decode: {}", e))
+            }
             ExecError::Deny(reason) => PipelineError::PolicyDenied(reason),
         })?;
 
@@ -236,6 +249,7 @@ impl UblPipeline {
             "fuel_used".to_string(),
             serde_json::json!(outcome.fuel_used),
         );
+        vm_state.insert("fuel_limit".to_string(), serde_json::json!(fuel_limit));
         vm_state.insert("steps".to_string(), serde_json::json!(outcome.steps));
         vm_state.insert(
             "result".to_string(),
@@ -249,6 +263,13 @@ impl UblPipeline {
             "trace_len".to_string(),
             serde_json::json!(outcome.trace.len()),
         );
+        if self.receipt_trace_policy.wants_trace(request.body()) {
+            let (trace, truncated) = self.receipt_trace_policy.render(&outcome.trace);
+            vm_state.insert("trace".to_string(), trace);
+            if truncated {
+                vm_state.insert("trace_truncated".to_string(), serde_json::json!(true));
+            }
+        }
         vm_state.insert(
             "bytecode_source".to_string(),
             serde_json::json!(resolution.source),
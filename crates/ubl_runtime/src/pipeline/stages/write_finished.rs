@@ -26,14 +26,26 @@ impl UblPipeline {
             }
         }
 
+        let tr_vm_state = tr_receipt.body.get("vm_state");
         let wf_body = WfReceiptBody {
             decision: check.decision.clone(),
-            wa_cid: wa_receipt.body_cid.as_str().to_string(),
+            wa_cid: if wa_receipt.is_skipped_wa() {
+                String::new()
+            } else {
+                wa_receipt.body_cid.as_str().to_string()
+            },
+            wa_skipped: wa_receipt.is_skipped_wa(),
             tr_cid: Some(tr_receipt.body_cid.as_str().to_string()),
             artifacts,
             duration_ms: pipeline_duration_ms,
             policy_trace: check.trace.clone(),
             short_circuited: check.short_circuited,
+            fuel_limit: tr_vm_state
+                .and_then(|v| v.get("fuel_limit"))
+                .and_then(|v| v.as_u64()),
+            fuel_used: tr_vm_state
+                .and_then(|v| v.get("fuel_used"))
+                .and_then(|v| v.as_u64()),
         };
 
         let body_json = serde_json::to_value(&wf_body)
@@ -60,12 +72,19 @@ impl UblPipeline {
     ) -> Result<PipelineReceipt, PipelineError> {
         let wf_body = WfReceiptBody {
             decision: Decision::Deny,
-            wa_cid: wa_receipt.body_cid.as_str().to_string(),
+            wa_cid: if wa_receipt.is_skipped_wa() {
+                String::new()
+            } else {
+                wa_receipt.body_cid.as_str().to_string()
+            },
+            wa_skipped: wa_receipt.is_skipped_wa(),
             tr_cid: None, // No transition executed
             artifacts: HashMap::new(),
             duration_ms: pipeline_duration_ms,
             policy_trace: check.trace.clone(),
             short_circuited: true,
+            fuel_limit: None,
+            fuel_used: None,
         };
 
         let body_json = serde_json::to_value(&wf_body)
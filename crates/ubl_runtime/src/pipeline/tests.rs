@@ -70,6 +70,42 @@ async fn pipeline_allow_flow_with_real_vm() {
         .unwrap_or(false));
 }
 
+#[tokio::test]
+async fn pipeline_wa_ghost_off_omits_wa_from_chain() {
+    let mut pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    pipeline.set_wa_ghost_policy(WaGhostPolicy {
+        enabled: true,
+        skip_types: vec!["ubl/document".to_string()],
+    });
+    let mut rx = pipeline.event_bus.subscribe();
+
+    let request = ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: json!({
+            "@type": "ubl/document",
+            "@id": "no-ghost-001",
+            "@ver": "1.0",
+            "@world": "a/demo/t/main",
+            "title": "Trusted lane"
+        }),
+        parents: vec![],
+        operation: Some("create".to_string()),
+    };
+    let result = pipeline.process_chip(request).await.unwrap();
+
+    assert!(matches!(result.decision, Decision::Allow));
+    assert_eq!(result.chain.len(), 2, "chain: TR + WF");
+    let wf_body = &result.final_receipt.body;
+    assert_eq!(wf_body["wa_skipped"], json!(true));
+    assert!(wf_body.get("wa_cid").is_none());
+    assert!(!result.receipt.has_stage(PipelineStage::WriteAhead));
+    assert_eq!(result.receipt.effects["wa_skipped"], json!(true));
+    assert!(result.receipt.verify_auth_chain());
+
+    while let Ok(event) = rx.try_recv() {
+        assert_ne!(event.pipeline_stage, "wa");
+    }
+}
 #[tokio::test]
 async fn process_chip_with_context_sets_subject_and_knock_cid() {
     let storage = InMemoryPolicyStorage::new();
@@ -90,6 +126,10 @@ async fn process_chip_with_context_sets_subject_and_knock_cid() {
     let ctx = AuthorshipContext {
         subject_did_hint: Some("did:key:zCaller".to_string()),
         knock_cid: Some("b3:knock-ctx".to_string()),
+        correlation_id: None,
+        idempotency_key: None,
+        simulate: false,
+        author: None,
     };
 
     let result = pipeline
@@ -142,6 +182,7 @@ async fn knock_rejection_produces_signed_deny_receipt() {
             "KNOCK-007",
             "KNOCK-007: body is not a JSON object",
             Some("did:ubl:anon:b3:test".to_string()),
+            false,
         )
         .await
         .unwrap();
@@ -260,6 +301,46 @@ async fn key_rotate_requires_capability() {
     assert!(err.to_string().contains("key.rotate capability"));
 }
 
+#[tokio::test]
+async fn request_parents_are_recorded_on_the_stored_chip() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let policy_storage = InMemoryPolicyStorage::new();
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let pipeline = UblPipeline::with_chip_store(Box::new(policy_storage), chip_store.clone());
+
+    let request = ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: json!({
+            "@type": "ubl/document",
+            "@id": "doc-with-parent",
+            "@ver": "1.0",
+            "@world": "a/demo/t/main",
+            "parents": ["b3:body-parent"]
+        }),
+        parents: vec!["b3:request-parent".to_string()],
+        operation: Some("create".to_string()),
+    };
+    let result = pipeline.process_chip(request).await.unwrap();
+    assert!(matches!(result.decision, Decision::Allow));
+
+    let stored = chip_store
+        .get_chip_by_receipt_cid(result.receipt.receipt_cid.as_str())
+        .await
+        .unwrap()
+        .expect("chip stored");
+    assert_eq!(
+        stored.lineage_parents(),
+        vec![
+            "b3:body-parent".to_string(),
+            "b3:request-parent".to_string()
+        ]
+    );
+    assert!(stored
+        .tags
+        .contains(&"parent:b3:request-parent".to_string()));
+}
+
 #[tokio::test]
 async fn key_rotate_persists_mapping_and_replay_is_stable() {
     use ubl_chipstore::{ChipQuery, ChipStore, InMemoryBackend};
@@ -1010,6 +1091,440 @@ async fn chipstore_persists_after_allow() {
     );
 }
 
+#[tokio::test]
+async fn correlation_id_is_echoed_but_not_hashed() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), chip_store.clone());
+    let mut rx = pipeline.event_bus.subscribe();
+
+    let chip_body = json!({
+        "@type": "ubl/document",
+        "@id": "corr-001",
+        "@ver": "1.0",
+        "@world": "a/app/t/ten",
+        "title": "Correlated"
+    });
+    let mut submitted = chip_body.clone();
+    submitted["_correlation_id"] = json!("client-req-42");
+    let request = ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: submitted,
+        parents: vec![],
+        operation: Some("create".to_string()),
+    };
+    let result = pipeline.process_chip(request).await.unwrap();
+    assert!(matches!(result.decision, Decision::Allow));
+    assert_eq!(
+        result.receipt.effects["correlation_id"],
+        json!("client-req-42")
+    );
+    assert!(result.receipt.verify_auth_chain());
+
+    let nrf = ubl_ai_nrf1::to_nrf1_bytes(&chip_body).unwrap();
+    let expected_cid = ubl_ai_nrf1::compute_cid(&nrf).unwrap();
+    let stored = chip_store.get_chip(&expected_cid).await.unwrap();
+    let stored = stored.expect("chip CID must ignore the correlation id");
+    assert!(stored.chip_data.get("_correlation_id").is_none());
+
+    let mut saw_wf = false;
+    while let Ok(event) = rx.try_recv() {
+        assert_eq!(event.correlation_id.as_deref(), Some("client-req-42"));
+        saw_wf |= event.pipeline_stage == "wf";
+    }
+    assert!(saw_wf);
+
+    let oversized = ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: json!({
+            "@type": "ubl/document",
+            "@id": "corr-002",
+            "@ver": "1.0",
+            "@world": "a/app/t/ten"
+        }),
+        parents: vec![],
+        operation: Some("create".to_string()),
+    };
+    let ctx = AuthorshipContext {
+        correlation_id: Some("x".repeat(MAX_CORRELATION_ID_LEN + 1)),
+        ..AuthorshipContext::default()
+    };
+    assert!(matches!(
+        pipeline.process_chip_with_context(oversized, ctx).await,
+        Err(PipelineError::InvalidChip(_))
+    ));
+}
+
+#[tokio::test]
+async fn registered_schema_denies_missing_and_mistyped_required_fields() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let metadata: ExecutionMetadata = serde_json::from_value(json!({
+        "runtime_version": "test-runtime",
+        "execution_time_ms": 1,
+        "fuel_consumed": 0,
+        "policies_applied": [],
+        "executor_did": "did:key:ztest",
+        "reproducible": true
+    }))
+    .unwrap();
+    chip_store
+        .store_executed_chip(
+            json!({
+                "@type": "ubl/meta.register",
+                "@id": "reg-invoice",
+                "@ver": "1.0",
+                "@world": "a/acme/t/prod",
+                "target_type": "acme/invoice",
+                "description": "Invoice",
+                "schema": {
+                    "required_fields": [
+                        {"name": "amount", "field_type": "string"},
+                        {"name": "lines", "field_type": "integer"}
+                    ]
+                },
+                "kats": [{
+                    "label": "ok",
+                    "input": {"@type": "acme/invoice"},
+                    "expected_decision": "allow"
+                }]
+            }),
+            "b3:reg-invoice".to_string(),
+            metadata.clone(),
+        )
+        .await
+        .unwrap();
+    let pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), chip_store.clone());
+    let submit = |id: &str, world: &str, fields: serde_json::Value| {
+        let mut body = json!({"@type": "acme/invoice", "@id": id, "@ver": "1.0", "@world": world});
+        body.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        pipeline.process_chip(ChipRequest {
+            chip_type: "acme/invoice".to_string(),
+            body,
+            parents: vec![],
+            operation: Some("create".to_string()),
+        })
+    };
+    let schema_reason = |result: &PipelineResult| {
+        let trace = &result.final_receipt.body["policy_trace"][0];
+        (trace["policy_id"] == "schema_validation")
+            .then(|| trace["reason"].as_str().unwrap().to_string())
+    };
+
+    let missing = submit("inv-1", "a/acme/t/prod", json!({"amount": "1.00"}))
+        .await
+        .unwrap();
+    assert!(matches!(missing.decision, Decision::Deny));
+    let reason = schema_reason(&missing).unwrap();
+    assert!(reason.starts_with(SCHEMA_VALIDATION_FAILED), "{reason}");
+    assert!(reason.contains("missing 'lines'"), "{reason}");
+
+    let mistyped = submit("inv-2", "a/acme/t/prod", json!({"amount": 1, "lines": 2}))
+        .await
+        .unwrap();
+    let reason = schema_reason(&mistyped).unwrap();
+    assert!(
+        reason.contains("'amount' expected string, got number"),
+        "{reason}"
+    );
+    assert!(!reason.contains("lines"), "{reason}");
+
+    // A conforming body and an unregistered world both reach the policy chain.
+    let valid = submit(
+        "inv-3",
+        "a/acme/t/prod",
+        json!({"amount": "1.00", "lines": 2}),
+    )
+    .await
+    .unwrap();
+    assert_eq!(schema_reason(&valid), None);
+    let elsewhere = submit("inv-4", "a/acme/t/dev", json!({})).await.unwrap();
+    assert_eq!(schema_reason(&elsewhere), None);
+
+    // Schemas are cached per world until a register write invalidates them.
+    chip_store
+        .store_executed_chip(
+            json!({
+                "@type": "ubl/meta.register",
+                "@id": "reg-invoice-dev",
+                "@ver": "1.0",
+                "@world": "a/acme/t/dev",
+                "target_type": "acme/invoice",
+                "description": "Invoice",
+                "schema": {"required_fields": [{"name": "amount", "field_type": "string"}]},
+                "kats": [{
+                    "label": "ok",
+                    "input": {"@type": "acme/invoice"},
+                    "expected_decision": "allow"
+                }]
+            }),
+            "b3:reg-invoice-dev".to_string(),
+            metadata,
+        )
+        .await
+        .unwrap();
+    let cached = submit("inv-5", "a/acme/t/dev", json!({})).await.unwrap();
+    assert_eq!(schema_reason(&cached), None);
+    pipeline.invalidate_schema_index("ubl/meta.register").await;
+    let refreshed = submit("inv-6", "a/acme/t/dev", json!({})).await.unwrap();
+    let reason = schema_reason(&refreshed).unwrap();
+    assert!(reason.contains("missing 'amount'"), "{reason}");
+}
+
+#[tokio::test]
+async fn required_tags_deny_untagged_chip_and_allow_tagged() {
+    let mut pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    pipeline.set_required_tags_policy(RequiredTagsPolicy::parse(
+        "a/app|ubl/document=classification",
+    ));
+    let request = |id: &str, tags: serde_json::Value| ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: json!({
+            "@type": "ubl/document",
+            "@id": id,
+            "@ver": "1.0",
+            "@world": "a/app/t/ten",
+            "tags": tags
+        }),
+        parents: vec![],
+        operation: Some("create".to_string()),
+    };
+
+    let denied = pipeline
+        .process_chip(request("tags-001", json!(["owner:ops"])))
+        .await
+        .unwrap();
+    assert!(matches!(denied.decision, Decision::Deny));
+    let trace = &denied.final_receipt.body["policy_trace"];
+    assert_eq!(trace[0]["policy_id"], "required_tags");
+    assert!(trace[0]["reason"]
+        .as_str()
+        .unwrap()
+        .starts_with(REQUIRED_TAG_MISSING));
+
+    let allowed = pipeline
+        .process_chip(request("tags-002", json!(["classification:public"])))
+        .await
+        .unwrap();
+    assert!(matches!(allowed.decision, Decision::Allow));
+}
+
+fn routed_document(id: &str, candidates: serde_json::Value) -> ChipRequest {
+    ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: json!({
+            "@type": "ubl/document",
+            "@id": id,
+            "@ver": "1.0",
+            "@world_candidates": candidates,
+            "title": "routed"
+        }),
+        parents: vec![],
+        operation: Some("create".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn world_candidates_commit_to_first_allowed_world() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+    let store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let mut pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), store.clone());
+    pipeline.set_required_tags_policy(RequiredTagsPolicy::parse("a/one|ubl/document=owner"));
+
+    let result = pipeline
+        .process_chip(routed_document(
+            "route-001",
+            json!(["a/one/t/main", "a/two/t/main", "a/three/t/main"]),
+        ))
+        .await
+        .unwrap();
+    assert!(matches!(result.decision, Decision::Allow));
+    assert_eq!(result.receipt.world.as_str(), "a/two/t/main");
+    let selection = &result.receipt.effects["world_selection"];
+    assert_eq!(selection["chosen"], "a/two/t/main");
+    assert_eq!(selection["candidates"].as_array().unwrap().len(), 3);
+    let evaluated = selection["evaluated"].as_array().unwrap();
+    assert_eq!(evaluated.len(), 2);
+    assert_eq!(evaluated[0]["world"], "a/one/t/main");
+    assert_eq!(evaluated[0]["decision"], "deny");
+
+    let stored = store
+        .get_chips_by_type("ubl/document")
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|c| c.chip_data["@id"] == "route-001")
+        .unwrap();
+    assert_eq!(stored.chip_data["@world"], "a/two/t/main");
+    assert!(stored.chip_data.get("@world_candidates").is_none());
+}
+
+#[tokio::test]
+async fn world_candidates_deny_when_every_world_denies() {
+    let mut pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    pipeline.set_required_tags_policy(RequiredTagsPolicy::parse("*|ubl/document=owner"));
+
+    let result = pipeline
+        .process_chip(routed_document(
+            "route-002",
+            json!(["a/one/t/main", "a/two/t/main"]),
+        ))
+        .await
+        .unwrap();
+    assert!(matches!(result.decision, Decision::Deny));
+    assert_eq!(result.receipt.world.as_str(), "a/one/t/main");
+    let selection = &result.receipt.effects["world_selection"];
+    assert!(selection["chosen"].is_null());
+    assert_eq!(selection["evaluated"].as_array().unwrap().len(), 2);
+
+    let mut both = routed_document("route-003", json!(["a/one/t/main"]));
+    both.body["@world"] = json!("a/one/t/main");
+    assert!(matches!(
+        pipeline.process_chip(both).await,
+        Err(PipelineError::InvalidChip(_))
+    ));
+}
+
+#[derive(Default)]
+struct RecordingPostWfHook {
+    calls: std::sync::Mutex<Vec<(String, String)>>,
+}
+
+#[async_trait::async_trait]
+impl crate::post_wf_hook::PostWfHook for RecordingPostWfHook {
+    async fn after_wf(
+        &self,
+        receipt: &UnifiedReceipt,
+        chip: &serde_json::Value,
+    ) -> Result<(), crate::post_wf_hook::PostWfHookError> {
+        self.calls.lock().unwrap().push((
+            receipt.receipt_cid.as_str().to_string(),
+            chip["@id"].as_str().unwrap_or_default().to_string(),
+        ));
+        Ok(())
+    }
+}
+
+impl RecordingPostWfHook {
+    /// Calls so far, once `n` have landed: the hook runs in spawned tasks.
+    async fn wait_for(&self, n: usize) -> Vec<(String, String)> {
+        for _ in 0..200 {
+            if self.calls.lock().unwrap().len() >= n {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+#[tokio::test]
+async fn post_wf_hook_runs_once_per_allow() {
+    let mut pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    pipeline.set_required_tags_policy(RequiredTagsPolicy::parse("a/app|ubl/document=owner"));
+    let hook = Arc::new(RecordingPostWfHook::default());
+    pipeline.set_post_wf_hook(hook.clone());
+    let request = |id: &str, tags: serde_json::Value| ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: json!({
+            "@type": "ubl/document",
+            "@id": id,
+            "@ver": "1.0",
+            "@world": "a/app/t/ten",
+            "tags": tags
+        }),
+        parents: vec![],
+        operation: Some("create".to_string()),
+    };
+
+    let allowed = pipeline
+        .process_chip(request("hook-001", json!(["owner:ops"])))
+        .await
+        .unwrap();
+    assert!(matches!(allowed.decision, Decision::Allow));
+    let replayed = pipeline
+        .process_chip(request("hook-001", json!(["owner:ops"])))
+        .await
+        .unwrap();
+    assert!(replayed.replayed);
+    let denied = pipeline
+        .process_chip(request("hook-002", json!([])))
+        .await
+        .unwrap();
+    assert!(matches!(denied.decision, Decision::Deny));
+
+    let calls = hook.wait_for(1).await;
+    assert_eq!(
+        calls,
+        vec![(
+            allowed.receipt.receipt_cid.as_str().to_string(),
+            "hook-001".to_string()
+        )]
+    );
+}
+
+#[tokio::test]
+async fn post_wf_hook_waits_for_quarantine_release() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let mut pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), chip_store.clone());
+    pipeline.set_quarantine_policy(QuarantinePolicy {
+        max_body_bytes: None,
+        worlds: vec!["a/watch".to_string()],
+    });
+    let hook = Arc::new(RecordingPostWfHook::default());
+    pipeline.set_post_wf_hook(hook.clone());
+
+    let held = pipeline
+        .process_chip(ChipRequest {
+            chip_type: "ubl/document".to_string(),
+            body: json!({
+                "@type": "ubl/document",
+                "@id": "hook-held",
+                "@ver": "1.0",
+                "@world": "a/watch/t/dev"
+            }),
+            parents: vec![],
+            operation: Some("create".to_string()),
+        })
+        .await
+        .unwrap();
+    assert!(matches!(held.decision, Decision::Quarantine));
+    assert!(hook.wait_for(1).await.is_empty());
+
+    let receipt_cid = held.receipt.receipt_cid.as_str().to_string();
+    let chip_cid = chip_store
+        .get_chip_by_receipt_cid(&receipt_cid)
+        .await
+        .unwrap()
+        .unwrap()
+        .cid
+        .as_str()
+        .to_string();
+    assert!(matches!(
+        pipeline.release_quarantine(&chip_cid).await.unwrap(),
+        QuarantineRelease::Released { .. }
+    ));
+    assert_eq!(
+        hook.wait_for(1).await,
+        vec![(receipt_cid, "hook-held".to_string())]
+    );
+    assert!(matches!(
+        pipeline.release_quarantine(&chip_cid).await.unwrap(),
+        QuarantineRelease::AlreadyReleased { .. }
+    ));
+    assert_eq!(hook.wait_for(2).await.len(), 1);
+}
+
 #[tokio::test]
 async fn chipstore_not_called_on_deny() {
     use ubl_chipstore::{ChipStore, InMemoryBackend};
@@ -1942,6 +2457,137 @@ async fn idempotent_replay_returns_cached_result() {
     assert_eq!(r2.chain, r1.chain, "replayed chain must match original");
 }
 
+async fn assert_idem_ttl_window(pipeline: &UblPipeline, id: &str) {
+    let mut body = json!({
+        "@type": "ubl/document",
+        "@id": id,
+        "@ver": "1.0",
+        "@world": "a/test/t/dev",
+        "title": "TTL test"
+    });
+    body["_idem_ttl_secs"] = json!(1);
+
+    let r1 = submit_allow(pipeline, "ubl/document", body.clone()).await;
+    assert!(!r1.replayed);
+    let r2 = submit_allow(pipeline, "ubl/document", body.clone()).await;
+    assert!(r2.replayed, "resubmission inside the TTL must replay");
+    assert_eq!(r2.receipt.receipt_cid, r1.receipt.receipt_cid);
+
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    let r3 = submit_allow(pipeline, "ubl/document", body.clone()).await;
+    assert!(
+        !r3.replayed,
+        "resubmission after the TTL must execute afresh"
+    );
+    assert_ne!(r3.receipt.receipt_cid, r1.receipt.receipt_cid);
+    let r4 = submit_allow(pipeline, "ubl/document", body).await;
+    assert!(r4.replayed);
+    assert_eq!(r4.receipt.receipt_cid, r3.receipt.receipt_cid);
+}
+
+#[tokio::test]
+async fn idem_ttl_replays_within_window_and_reexecutes_after() {
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    assert_idem_ttl_window(&pipeline, "idem-ttl-mem").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let dsn = format!(
+        "file:{}?mode=rwc&_journal_mode=WAL",
+        dir.path().join("idem_ttl.db").display()
+    );
+    let mut durable = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    durable.set_durable_store(Some(Arc::new(DurableStore::new(dsn).unwrap())));
+    assert_idem_ttl_window(&durable, "idem-ttl-durable").await;
+}
+
+#[tokio::test]
+async fn idem_ttl_is_stripped_and_validated() {
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    let body = json!({
+        "@type": "ubl/document", "@id": "idem-ttl-strip", "@ver": "1.0",
+        "@world": "a/test/t/dev", "title": "TTL strip"
+    });
+    let mut with_ttl = body.clone();
+    with_ttl["_idem_ttl_secs"] = json!(60);
+    let r1 = submit_allow(&pipeline, "ubl/document", with_ttl).await;
+    // Same anchors without the directive: the TTL never reached the key.
+    let r2 = submit_allow(&pipeline, "ubl/document", body.clone()).await;
+    assert!(r2.replayed);
+    assert_eq!(r2.receipt.receipt_cid, r1.receipt.receipt_cid);
+
+    for bad in [json!(0), json!(-5), json!("60"), json!(1.5)] {
+        let mut invalid = body.clone();
+        invalid["@id"] = json!("idem-ttl-bad");
+        invalid["_idem_ttl_secs"] = bad;
+        let err = pipeline
+            .process_chip(ChipRequest {
+                chip_type: "ubl/document".to_string(),
+                body: invalid,
+                parents: vec![],
+                operation: Some("create".to_string()),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PipelineError::InvalidChip(_)), "{err:?}");
+    }
+}
+
+async fn assert_client_idempotency_key(pipeline: &UblPipeline, key: &str) {
+    let submit = |id: &str, world: &str, key: &str| {
+        let request = ChipRequest {
+            chip_type: "ubl/document".to_string(),
+            body: json!({
+                "@type": "ubl/document", "@id": id, "@ver": "1.0",
+                "@world": world, "title": "client key"
+            }),
+            parents: vec![],
+            operation: Some("create".to_string()),
+        };
+        let ctx = AuthorshipContext {
+            idempotency_key: Some(key.to_string()),
+            ..AuthorshipContext::default()
+        };
+        pipeline.process_chip_with_context(request, ctx)
+    };
+
+    let first = submit("client-key-a", "a/test/t/dev", key).await.unwrap();
+    assert!(!first.replayed);
+    let again = submit("client-key-a", "a/test/t/dev", key).await.unwrap();
+    assert!(again.replayed);
+    assert_eq!(again.receipt.receipt_cid, first.receipt.receipt_cid);
+
+    let err = submit("client-key-b", "a/test/t/dev", key)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, PipelineError::IdempotencyConflict(_)),
+        "{err:?}"
+    );
+    // Keys are scoped to `@world`.
+    let other_world = submit("client-key-b", "a/test/t/other", key).await.unwrap();
+    assert!(!other_world.replayed);
+
+    let err = submit("client-key-c", "a/test/t/dev", "has space")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, PipelineError::InvalidChip(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn client_idempotency_key_replays_and_conflicts_on_other_payloads() {
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    assert_client_idempotency_key(&pipeline, "req-mem-1").await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let dsn = format!(
+        "file:{}?mode=rwc&_journal_mode=WAL",
+        dir.path().join("client_idem.db").display()
+    );
+    let mut durable = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    durable.set_durable_store(Some(Arc::new(DurableStore::new(dsn).unwrap())));
+    assert_client_idempotency_key(&durable, "req-durable-1").await;
+}
+
 #[tokio::test]
 async fn idempotent_replay_different_id_is_fresh() {
     let storage = InMemoryPolicyStorage::new();
@@ -1988,3 +2634,361 @@ async fn strict_idempotency_requires_type_ver_world_id() {
     assert!(matches!(err, PipelineError::InvalidChip(_)));
     assert!(err.to_string().contains("strict idempotency anchors"));
 }
+
+/// `(span id, name, fields)`.
+type CapturedSpan = (u64, String, HashMap<String, String>);
+
+/// Captures span names and their (possibly late-recorded) string fields.
+#[derive(Clone, Default)]
+struct SpanCapture(Arc<std::sync::Mutex<Vec<CapturedSpan>>>);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl tracing::field::Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanCapture {
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut fields = HashMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        let name = attrs.metadata().name().to_string();
+        self.0.lock().unwrap().push((id.into_u64(), name, fields));
+    }
+
+    fn on_record(
+        &self,
+        id: &tracing::span::Id,
+        values: &tracing::span::Record<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut spans = self.0.lock().unwrap();
+        if let Some((_, _, fields)) = spans.iter_mut().find(|(sid, _, _)| *sid == id.into_u64()) {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+}
+
+#[tokio::test]
+async fn pipeline_span_records_receipt_cid_decision_and_stage_children() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let capture = SpanCapture::default();
+    let _guard =
+        tracing::subscriber::set_default(tracing_subscriber::registry().with(capture.clone()));
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    let result = pipeline
+        .process_chip(ChipRequest {
+            chip_type: "ubl/document".to_string(),
+            body: json!({
+                "@type": "ubl/document",
+                "@id": "span-001",
+                "@ver": "1.0",
+                "@world": "a/app/t/ten",
+                "title": "Traced"
+            }),
+            parents: vec![],
+            operation: Some("create".to_string()),
+        })
+        .await
+        .unwrap();
+
+    let spans = capture.0.lock().unwrap();
+    let (_, _, fields) = spans
+        .iter()
+        .find(|(_, name, _)| name == "ubl.pipeline")
+        .unwrap();
+    assert_eq!(fields["receipt_cid"], result.receipt.receipt_cid.as_str());
+    assert_eq!(fields["decision"], "allow");
+    assert_eq!(fields["chip_type"], "ubl/document");
+    let stages: Vec<&str> = spans
+        .iter()
+        .filter(|(_, name, _)| name == "ubl.stage")
+        .map(|(_, _, f)| f["stage"].as_str())
+        .collect();
+    assert_eq!(stages, vec!["WA", "CHECK", "TR", "WF"]);
+}
+
+#[tokio::test]
+async fn reload_policies_swaps_in_policy_chips_from_chip_store() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), chip_store.clone());
+    let metadata = || ExecutionMetadata {
+        runtime_version: "test".to_string(),
+        execution_time_ms: 0,
+        fuel_consumed: 0,
+        policies_applied: vec![],
+        executor_did: ubl_types::Did::new_unchecked("did:key:test"),
+        reproducible: true,
+    };
+    let app_cid = chip_store
+        .store_executed_chip(
+            json!({"@type": "ubl/app", "@id": "reload-app", "@ver": "1.0", "@world": "a/reload", "id": "reload-app"}),
+            "b3:reload-app-receipt".to_string(),
+            metadata(),
+        )
+        .await
+        .unwrap();
+    let document = |id: &str| ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: json!({
+            "@type": "ubl/document",
+            "@id": id,
+            "@ver": "1.0",
+            "@world": "a/reload/t/prod",
+            "id": id,
+            "title": "Draft"
+        }),
+        parents: vec![app_cid.clone()],
+        operation: Some("create".to_string()),
+    };
+
+    let before = pipeline
+        .process_chip(document("reload-doc-1"))
+        .await
+        .unwrap();
+    assert_eq!(before.decision, Decision::Allow);
+
+    chip_store
+        .store_executed_chip(
+            json!({
+                "@type": "ubl/policy",
+                "@id": "reload-app.freeze.v1",
+                "@ver": "1.0",
+                "@world": "a/reload",
+                "id": "reload-app.freeze.v1",
+                "parents": [app_cid],
+                "circuits": [{
+                    "id": "freeze",
+                    "name": "Freeze documents",
+                    "reasoning_bits": [{
+                        "id": "no_documents",
+                        "name": "No documents",
+                        "condition": {"TypeEquals": "ubl/none"},
+                        "on_true": "Allow",
+                        "on_false": "Deny",
+                        "requires_context": []
+                    }],
+                    "composition": "Sequential",
+                    "aggregator": "All"
+                }],
+                "scope": {"chip_types": ["ubl/document"], "operations": ["create"], "level": "app"}
+            }),
+            "b3:reload-policy-receipt".to_string(),
+            metadata(),
+        )
+        .await
+        .unwrap();
+    let stale = pipeline
+        .process_chip(document("reload-doc-2"))
+        .await
+        .unwrap();
+    assert_eq!(stale.decision, Decision::Allow);
+
+    let reload = pipeline.reload_policies().await.unwrap();
+    assert_eq!(reload.policy_count, 1);
+    assert_eq!(reload.chip_count, 2);
+    assert!(reload.policy_set_hash.starts_with("b3:"));
+    let after = pipeline
+        .process_chip(document("reload-doc-3"))
+        .await
+        .unwrap();
+    assert_eq!(after.decision, Decision::Deny);
+
+    let again = pipeline.reload_policies().await.unwrap();
+    assert_eq!(again.policy_set_hash, reload.policy_set_hash);
+}
+
+#[tokio::test]
+async fn check_rejects_policy_chips_that_would_not_load() {
+    let pipeline = UblPipeline::new(Box::new(InMemoryPolicyStorage::new()));
+    let request = ChipRequest {
+        chip_type: "ubl/policy".to_string(),
+        body: json!({
+            "@type": "ubl/policy",
+            "@id": "broken.v1",
+            "@ver": "1.0",
+            "@world": "a/reload",
+            "scope": {"chip_types": ["ubl/document"], "operations": ["create"], "level": "app"}
+        }),
+        parents: vec![],
+        operation: Some("create".to_string()),
+    };
+    let err = pipeline.process_chip(request).await.unwrap_err();
+    assert!(matches!(err, PipelineError::InvalidChip(_)));
+    assert!(err.to_string().contains("Missing circuits"));
+}
+
+#[tokio::test]
+async fn simulate_runs_every_stage_without_persisting_or_replaying() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), chip_store.clone());
+    let mut events = pipeline.event_bus.subscribe();
+    let body = json!({
+        "@type": "ubl/document",
+        "@id": "sim-001",
+        "@ver": "1.0",
+        "@world": "a/sim/t/prod",
+        "id": "sim-001",
+        "title": "Preview"
+    });
+    let request = || ChipRequest {
+        chip_type: "ubl/document".to_string(),
+        body: body.clone(),
+        parents: vec![],
+        operation: Some("create".to_string()),
+    };
+    let simulate = || AuthorshipContext {
+        simulate: true,
+        ..AuthorshipContext::default()
+    };
+
+    for _ in 0..2 {
+        let preview = pipeline
+            .process_chip_with_context(request(), simulate())
+            .await
+            .unwrap();
+        assert_eq!(preview.decision, Decision::Allow);
+        assert!(!preview.replayed);
+        assert_eq!(preview.receipt.effects["simulated"], json!(true));
+        assert_eq!(preview.receipt.stages.len(), 4);
+    }
+    assert!(events.try_recv().is_err());
+    let chip_cid = ubl_ai_nrf1::compute_cid(&ubl_ai_nrf1::to_nrf1_bytes(&body).unwrap()).unwrap();
+    assert!(!chip_store.exists(&chip_cid).await.unwrap());
+
+    let real = pipeline.process_chip(request()).await.unwrap();
+    assert!(!real.replayed);
+    assert!(real.receipt.effects.get("simulated").is_none());
+    assert!(chip_store.exists(&chip_cid).await.unwrap());
+
+    let err = pipeline
+        .process_chip_with_context(
+            ChipRequest {
+                chip_type: "ubl/key.rotate".to_string(),
+                body: json!({"@type": "ubl/key.rotate", "@id": "sim-rot", "@ver": "1.0", "@world": "a/sim/t/prod"}),
+                parents: vec![],
+                operation: Some("create".to_string()),
+            },
+            simulate(),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("simulation is not available"));
+}
+
+#[tokio::test]
+async fn batch_commits_every_entry_or_none() {
+    use ubl_chipstore::{ChipStore, InMemoryBackend};
+
+    let dir = tempfile::tempdir().unwrap();
+    let dsn = format!(
+        "file:{}?mode=rwc&_journal_mode=WAL",
+        dir.path().join("batch.db").display()
+    );
+    let durable = Arc::new(DurableStore::new(dsn).unwrap());
+    let chip_store = Arc::new(ChipStore::new(Arc::new(InMemoryBackend::new())));
+    let mut pipeline =
+        UblPipeline::with_chip_store(Box::new(InMemoryPolicyStorage::new()), chip_store.clone());
+    pipeline.set_durable_store(Some(durable.clone()));
+    let mut events = pipeline.event_bus.subscribe();
+    let entry = |chip_type: &str, id: &str| {
+        (
+            ChipRequest {
+                chip_type: chip_type.to_string(),
+                body: json!({"@type": chip_type, "@id": id, "@ver": "1.0", "@world": "a/batch/t/prod"}),
+                parents: vec![],
+                operation: Some("create".to_string()),
+            },
+            AuthorshipContext::default(),
+        )
+    };
+    let stored = |id: &str| {
+        let body =
+            json!({"@type": "ubl/document", "@id": id, "@ver": "1.0", "@world": "a/batch/t/prod"});
+        let cid = ubl_ai_nrf1::compute_cid(&ubl_ai_nrf1::to_nrf1_bytes(&body).unwrap()).unwrap();
+        let chip_store = chip_store.clone();
+        async move { chip_store.exists(&cid).await.unwrap() }
+    };
+
+    let rejected = pipeline
+        .process_batch(vec![
+            entry("ubl/document", "batch-a"),
+            entry("evil/hack", "batch-evil"),
+        ])
+        .await
+        .unwrap_err();
+    assert_eq!(rejected.index, Some(1));
+    assert!(matches!(rejected.error, PipelineError::PolicyDenied(_)));
+    assert!(!stored("batch-a").await);
+    assert!(events.try_recv().is_err());
+
+    let committed = pipeline
+        .process_batch(vec![
+            entry("ubl/document", "batch-a"),
+            entry("ubl/document", "batch-b"),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(committed.len(), 2);
+    for result in &committed {
+        assert_eq!(result.decision, Decision::Allow);
+        assert!(!result.replayed);
+        assert!(durable
+            .get_receipt(result.receipt.receipt_cid.as_str())
+            .unwrap()
+            .is_some());
+    }
+    assert!(stored("batch-a").await && stored("batch-b").await);
+    assert!(events.try_recv().is_ok());
+
+    let again = pipeline
+        .process_batch(vec![
+            entry("ubl/document", "batch-a"),
+            entry("ubl/document", "batch-c"),
+        ])
+        .await
+        .unwrap();
+    assert!(again[0].replayed);
+    assert_eq!(
+        again[0].receipt.receipt_cid,
+        committed[0].receipt.receipt_cid
+    );
+    assert!(!again[1].replayed);
+
+    let repeated = pipeline
+        .process_batch(vec![
+            entry("ubl/document", "batch-d"),
+            entry("ubl/document", "batch-d"),
+        ])
+        .await
+        .unwrap_err();
+    assert_eq!(repeated.index, Some(1));
+    assert!(!stored("batch-d").await);
+
+    let refused = pipeline
+        .process_batch(vec![entry("ubl/key.rotate", "batch-rot")])
+        .await
+        .unwrap_err();
+    assert_eq!(refused.index, Some(0));
+    let empty = pipeline.process_batch(vec![]).await.unwrap_err();
+    assert!(empty.index.is_none());
+}
@@ -112,6 +112,8 @@ pub(super) struct ParsedChipRequest<'a> {
     pub(super) chip_type: &'a str,
     pub(super) chip_id: Option<&'a str>,
     pub(super) world: &'a str,
+    /// Author the transport verified, from [`AuthorshipContext::author`].
+    pub(super) author: Option<&'a ubl_chipstore::ChipAuthor>,
 }
 
 impl<'a> ParsedChipRequest<'a> {
@@ -149,9 +151,15 @@ impl<'a> ParsedChipRequest<'a> {
             chip_type,
             chip_id,
             world,
+            author: None,
         })
     }
 
+    pub(super) fn with_author(mut self, author: Option<&'a ubl_chipstore::ChipAuthor>) -> Self {
+        self.author = author;
+        self
+    }
+
     pub(super) fn body(&self) -> &'a serde_json::Value {
         &self.request.body
     }
@@ -171,6 +179,13 @@ pub(super) struct CheckResult {
     pub(super) reason: String,
     pub(super) short_circuited: bool,
     pub(super) trace: Vec<PolicyTraceEntry>,
+    /// Hash of the evaluated policy chain (None when CHECK short-circuits
+    /// before policies are loaded).
+    pub(super) policy_set_hash: Option<String>,
+    /// `ubl/merge` record (parents + shared type) for the receipt.
+    pub(super) merge: Option<serde_json::Value>,
+    /// `EmitCounter` increments reached by the evaluated policies.
+    pub(super) counters: Vec<CounterEmit>,
 }
 
 pub(super) fn decision_to_wire(decision: &Decision) -> &'static str {
@@ -178,5 +193,16 @@ pub(super) fn decision_to_wire(decision: &Decision) -> &'static str {
         Decision::Allow => "allow",
         Decision::Deny => "deny",
         Decision::Require => "require",
+        Decision::Quarantine => "quarantine",
+    }
+}
+
+/// Inverse of [`decision_to_wire`]; also accepts the `Debug` form.
+pub(super) fn decision_from_wire(raw: &str) -> Decision {
+    match raw.to_ascii_lowercase().as_str() {
+        "allow" => Decision::Allow,
+        "require" => Decision::Require,
+        "quarantine" => Decision::Quarantine,
+        _ => Decision::Deny,
     }
 }
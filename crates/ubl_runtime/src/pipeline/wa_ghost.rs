@@ -0,0 +1,117 @@
+//! Optional WA ghost receipt suppression for trusted, high-throughput lanes.
+//!
+//! With the ghost off for a chip type, WA is not run: no ghost receipt, no
+//! WA stage on the unified receipt and no WA event. CHECK/TR take the KNOCK
+//! CID as input, the WF body records `wa_skipped: true` with an empty
+//! `wa_cid`, and the result chain is `[TR, WF]`.
+
+use super::*;
+
+/// Receipt type of the stand-in used when WA is skipped.
+pub(in crate::pipeline) const WA_SKIPPED_TYPE: &str = "ubl/wa.skipped";
+
+/// Which chip types get a WA ghost receipt. Default: all of them.
+#[derive(Debug, Clone)]
+pub struct WaGhostPolicy {
+    /// Global switch; `false` skips WA for every type.
+    pub enabled: bool,
+    /// Types that skip WA even when the global switch is on.
+    pub skip_types: Vec<String>,
+}
+
+impl Default for WaGhostPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            skip_types: vec![],
+        }
+    }
+}
+
+impl WaGhostPolicy {
+    /// `UBL_WA_GHOST=off` (global) and `UBL_WA_GHOST_SKIP_TYPES` (comma-separated).
+    pub fn from_env() -> Self {
+        let enabled = !matches!(
+            std::env::var("UBL_WA_GHOST")
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase()
+                .as_str(),
+            "off" | "false" | "0"
+        );
+        let skip_types = std::env::var("UBL_WA_GHOST_SKIP_TYPES")
+            .unwrap_or_default()
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        Self {
+            enabled,
+            skip_types,
+        }
+    }
+
+    pub fn emits_for(&self, chip_type: &str) -> bool {
+        self.enabled && !self.skip_types.iter().any(|t| t == chip_type)
+    }
+}
+
+impl PipelineReceipt {
+    /// Stand-in WA receipt keyed by the KNOCK CID.
+    pub(in crate::pipeline) fn skipped_wa(knock_cid: &str) -> Self {
+        Self {
+            body_cid: ubl_types::Cid::new_unchecked(knock_cid),
+            receipt_type: WA_SKIPPED_TYPE.to_string(),
+            body: serde_json::json!({ "wa_skipped": true }),
+        }
+    }
+
+    pub(in crate::pipeline) fn is_skipped_wa(&self) -> bool {
+        self.receipt_type == WA_SKIPPED_TYPE
+    }
+}
+
+impl UblPipeline {
+    pub fn set_wa_ghost_policy(&mut self, policy: WaGhostPolicy) {
+        self.wa_ghost_policy = Arc::new(policy);
+    }
+
+    /// Result chain `[WA, TR, WF]`, without WA when it was skipped.
+    pub(in crate::pipeline) fn result_chain(
+        wa_receipt: &PipelineReceipt,
+        tr_cid: &str,
+        wf_cid: &str,
+    ) -> Vec<String> {
+        let mut chain = Vec::with_capacity(3);
+        if !wa_receipt.is_skipped_wa() {
+            chain.push(wa_receipt.body_cid.as_str().to_string());
+        }
+        chain.push(tr_cid.to_string());
+        chain.push(wf_cid.to_string());
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ghost_policy_skips_globally_or_per_type() {
+        let default = WaGhostPolicy::default();
+        assert!(default.emits_for("ubl/document"));
+
+        let per_type = WaGhostPolicy {
+            enabled: true,
+            skip_types: vec!["acme/metric".to_string()],
+        };
+        assert!(!per_type.emits_for("acme/metric"));
+        assert!(per_type.emits_for("ubl/document"));
+
+        let off = WaGhostPolicy {
+            enabled: false,
+            skip_types: vec![],
+        };
+        assert!(!off.emits_for("ubl/document"));
+    }
+}
@@ -0,0 +1,179 @@
+//! WASM module allow-list — a TR guard that only runs pre-approved adapters.
+//!
+//! `UBL_WASM_ALLOWLIST` (comma/whitespace-separated `wasm_sha256` hex) is the
+//! operator list. When it is unset or empty, the latest `ubl/wasm.allowlist`
+//! chip in the request's `@world` supplies the list instead; with neither, the
+//! guard is off and attestation alone decides. A variable that is set but has
+//! no well-formed entry denies every module rather than switching the guard
+//! off. Unlisted modules are denied
+//! with `WASM_MODULE_NOT_ALLOWLISTED` before their bytes are resolved, even
+//! when their attestation is valid.
+
+use super::*;
+use std::collections::BTreeSet;
+
+pub const WASM_MODULE_NOT_ALLOWLISTED: &str = "WASM_MODULE_NOT_ALLOWLISTED";
+pub const TYPE_WASM_ALLOWLIST: &str = "ubl/wasm.allowlist";
+
+/// Approved `wasm_sha256` values, lowercase hex. Unset = disabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WasmAllowlist {
+    pub hashes: BTreeSet<String>,
+    /// The operator named at least one entry, well-formed or not. A set
+    /// list is enforced even when `hashes` is empty.
+    pub set: bool,
+}
+
+fn is_sha256_hex(s: &str) -> bool {
+    s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl WasmAllowlist {
+    pub fn from_env() -> Self {
+        Self::parse(&std::env::var("UBL_WASM_ALLOWLIST").unwrap_or_default())
+    }
+
+    /// Parse a comma/whitespace-separated hash list; malformed entries are
+    /// skipped, and a list with none left denies every module.
+    pub fn parse(raw: &str) -> Self {
+        let mut hashes = BTreeSet::new();
+        let mut set = false;
+        for entry in raw
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|e| !e.is_empty())
+        {
+            set = true;
+            if is_sha256_hex(entry) {
+                hashes.insert(entry.to_ascii_lowercase());
+            } else {
+                warn!(entry = %entry, "ignoring malformed UBL_WASM_ALLOWLIST entry");
+            }
+        }
+        if set && hashes.is_empty() {
+            error!("UBL_WASM_ALLOWLIST has no well-formed entry; denying every WASM module");
+        }
+        Self { hashes, set }
+    }
+
+    /// Strict parse of a `ubl/wasm.allowlist` body: `wasm_sha256` must be a
+    /// non-empty array of 64-hex strings, so a bad chip never disables the guard.
+    pub fn from_chip(body: &serde_json::Value) -> Result<Self, PipelineError> {
+        let invalid = || {
+            PipelineError::InvalidChip(format!(
+                "{} wasm_sha256 must be a non-empty array of 64-hex strings",
+                TYPE_WASM_ALLOWLIST
+            ))
+        };
+        let items = body
+            .get("wasm_sha256")
+            .and_then(|v| v.as_array())
+            .filter(|a| !a.is_empty())
+            .ok_or_else(invalid)?;
+        let mut hashes = BTreeSet::new();
+        for item in items {
+            let hash = item
+                .as_str()
+                .filter(|s| is_sha256_hex(s))
+                .ok_or_else(invalid)?;
+            hashes.insert(hash.to_ascii_lowercase());
+        }
+        Ok(Self { hashes, set: true })
+    }
+
+    /// Whether the list is enforced at all.
+    pub fn is_set(&self) -> bool {
+        self.set
+    }
+
+    pub fn allows(&self, wasm_sha256: &str) -> bool {
+        self.hashes.contains(&wasm_sha256.to_ascii_lowercase())
+    }
+}
+
+impl UblPipeline {
+    /// Replace the operator allow-list (defaults to [`WasmAllowlist::from_env`]).
+    pub fn set_wasm_allowlist(&mut self, allowlist: WasmAllowlist) {
+        self.wasm_allowlist = Arc::new(allowlist);
+    }
+
+    /// The list that governs `world`: the operator list when set, else the
+    /// world's latest `ubl/wasm.allowlist` chip. `None` = guard off.
+    async fn effective_wasm_allowlist(
+        &self,
+        world: &str,
+    ) -> Result<Option<WasmAllowlist>, PipelineError> {
+        if self.wasm_allowlist.is_set() {
+            return Ok(Some(self.wasm_allowlist.as_ref().clone()));
+        }
+        let Some(store) = self.chip_store.as_ref() else {
+            return Ok(None);
+        };
+        let latest = store
+            .query(&ubl_chipstore::ChipQuery {
+                chip_type: Some(TYPE_WASM_ALLOWLIST.to_string()),
+                tags: vec![format!("world:{}", world)],
+                created_after: None,
+                created_before: None,
+                executor_did: None,
+                limit: Some(1),
+                offset: None,
+            })
+            .await
+            .map_err(|e| PipelineError::Internal(format!("ChipStore: {}", e)))?;
+        latest
+            .chips
+            .first()
+            .map(|chip| WasmAllowlist::from_chip(&chip.chip_data))
+            .transpose()
+    }
+
+    pub(super) async fn enforce_wasm_allowlist(
+        &self,
+        world: &str,
+        wasm_sha256: &str,
+    ) -> Result<(), PipelineError> {
+        match self.effective_wasm_allowlist(world).await? {
+            Some(list) if !list.allows(wasm_sha256) => Err(PipelineError::PolicyDenied(format!(
+                "{}: adapter.wasm_sha256 {} is not on the allow-list",
+                WASM_MODULE_NOT_ALLOWLISTED, wasm_sha256
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const HASH_A: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    #[test]
+    fn parse_skips_malformed_entries_and_ignores_case() {
+        let list = WasmAllowlist::parse(&format!("{}, not-hex\n{}", HASH_A.to_uppercase(), "ab"));
+        assert_eq!(list.hashes.len(), 1);
+        assert!(list.allows(HASH_A));
+        assert!(!WasmAllowlist::parse("  ").is_set());
+
+        let garbled = WasmAllowlist::parse("not-hex,ab");
+        assert!(garbled.is_set());
+        assert!(!garbled.allows(HASH_A));
+    }
+
+    #[test]
+    fn chip_list_must_be_well_formed() {
+        let ok = WasmAllowlist::from_chip(&json!({"wasm_sha256": [HASH_A]})).unwrap();
+        assert!(ok.allows(HASH_A));
+        for bad in [
+            json!({}),
+            json!({"wasm_sha256": []}),
+            json!({"wasm_sha256": [HASH_A, "nope"]}),
+        ] {
+            assert!(matches!(
+                WasmAllowlist::from_chip(&bad),
+                Err(PipelineError::InvalidChip(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! World routing — a chip may carry `@world_candidates` instead of `@world`.
+//!
+//! CHECK is evaluated against each candidate in order; the first one that is
+//! not denied becomes the chip's canonical `@world`. When every candidate is
+//! denied the chip is committed as a deny under the first candidate. The
+//! candidate list and the per-world decisions are recorded in the receipt's
+//! `world_selection` effect.
+
+use super::*;
+
+pub const WORLD_CANDIDATES_FIELD: &str = "@world_candidates";
+/// Longest `@world_candidates` list accepted.
+pub const MAX_WORLD_CANDIDATES: usize = 8;
+
+/// The candidate list of a chip body, if it routes by `@world_candidates`.
+/// Candidates must be 1..=[`MAX_WORLD_CANDIDATES`] distinct, non-empty
+/// strings, and the body must not also pin `@world`.
+pub fn world_candidates(body: &serde_json::Value) -> Result<Option<Vec<String>>, PipelineError> {
+    let Some(raw) = body.get(WORLD_CANDIDATES_FIELD) else {
+        return Ok(None);
+    };
+    if body.get("@world").is_some() {
+        return Err(PipelineError::InvalidChip(format!(
+            "use either @world or {}, not both",
+            WORLD_CANDIDATES_FIELD
+        )));
+    }
+    let invalid = || {
+        PipelineError::InvalidChip(format!(
+            "{} must be an array of 1..={} distinct world strings",
+            WORLD_CANDIDATES_FIELD, MAX_WORLD_CANDIDATES
+        ))
+    };
+    let items = raw.as_array().ok_or_else(invalid)?;
+    if items.is_empty() || items.len() > MAX_WORLD_CANDIDATES {
+        return Err(invalid());
+    }
+    let mut worlds: Vec<String> = Vec::with_capacity(items.len());
+    for item in items {
+        let world = item.as_str().map(str::trim).filter(|w| !w.is_empty());
+        match world {
+            Some(w) if !worlds.iter().any(|seen| seen == w) => worlds.push(w.to_string()),
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(Some(worlds))
+}
+
+impl UblPipeline {
+    /// Resolve `@world_candidates` into `@world` on `request`, returning the
+    /// `world_selection` record for the receipt (`None` when not routing).
+    pub(super) async fn select_candidate_world(
+        &self,
+        request: &mut ChipRequest,
+    ) -> Result<Option<serde_json::Value>, PipelineError> {
+        let Some(candidates) = world_candidates(&request.body)? else {
+            return Ok(None);
+        };
+        if let Some(obj) = request.body.as_object_mut() {
+            obj.remove(WORLD_CANDIDATES_FIELD);
+        }
+
+        let mut evaluated = Vec::with_capacity(candidates.len());
+        let mut chosen = None;
+        for world in &candidates {
+            request.body["@world"] = serde_json::json!(world);
+            let parsed = ParsedChipRequest::parse(request)?;
+            let check = self.evaluate_check(&parsed).await?;
+            evaluated.push(serde_json::json!({
+                "world": world,
+                "decision": decision_to_wire(&check.decision),
+                "reason": check.reason,
+            }));
+            if !matches!(check.decision, Decision::Deny) {
+                chosen = Some(world.clone());
+                break;
+            }
+        }
+
+        let committed = chosen.clone().unwrap_or_else(|| candidates[0].clone());
+        request.body["@world"] = serde_json::json!(committed);
+        debug!(
+            chip_type = %request.chip_type,
+            world = %committed,
+            matched = chosen.is_some(),
+            "world candidates resolved"
+        );
+        Ok(Some(serde_json::json!({
+            "candidates": candidates,
+            "chosen": chosen,
+            "evaluated": evaluated,
+        })))
+    }
+}
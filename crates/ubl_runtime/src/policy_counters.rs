@@ -0,0 +1,255 @@
+//! Policy-emitted counters (`ubl_policy_counter_total`).
+//!
+//! A reasoning bit can increment a counter with `Expression::EmitCounter`.
+//! Only counter names and label keys on the operator allow-list are
+//! accepted, label values are length- and charset-bounded, and the number
+//! of distinct series is capped, so a policy cannot blow up metric
+//! cardinality. Rejected emissions are dropped and counted in
+//! `ubl_policy_counter_rejected_total`.
+
+use crate::reasoning_bit::CounterEmit;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+/// Default cap on distinct `{name, labels}` series.
+pub const DEFAULT_POLICY_COUNTER_MAX_SERIES: usize = 1000;
+/// Longest label value accepted.
+pub const MAX_LABEL_VALUE_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CounterRejection {
+    #[error("counter '{0}' is not on the allow-list")]
+    UnknownName(String),
+    #[error("label '{label}' is not allowed for counter '{name}'")]
+    LabelNotAllowed { name: String, label: String },
+    #[error("label '{0}' has an invalid value")]
+    InvalidLabelValue(String),
+    #[error("policy counter series limit ({0}) reached")]
+    SeriesLimit(usize),
+}
+
+type SeriesKey = (String, BTreeMap<String, String>);
+
+#[derive(Default)]
+struct CounterInner {
+    series: BTreeMap<SeriesKey, u64>,
+    rejected: u64,
+}
+
+/// Allow-listed, bounded counter store rendered into `/metrics`.
+pub struct PolicyCounterRegistry {
+    allow: BTreeMap<String, BTreeSet<String>>,
+    max_series: usize,
+    inner: Mutex<CounterInner>,
+}
+
+impl PolicyCounterRegistry {
+    pub fn new(allow: BTreeMap<String, BTreeSet<String>>, max_series: usize) -> Self {
+        Self {
+            allow,
+            max_series,
+            inner: Mutex::new(CounterInner::default()),
+        }
+    }
+
+    /// Allow-list from `UBL_POLICY_COUNTERS` and series cap from
+    /// `UBL_POLICY_COUNTERS_MAX_SERIES` (default 1000).
+    pub fn from_env() -> Self {
+        let allow = std::env::var("UBL_POLICY_COUNTERS")
+            .map(|v| Self::parse_allow_list(&v))
+            .unwrap_or_default();
+        let max_series = std::env::var("UBL_POLICY_COUNTERS_MAX_SERIES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_POLICY_COUNTER_MAX_SERIES);
+        Self::new(allow, max_series)
+    }
+
+    /// Parse `name:label1|label2,other_name`. Invalid names or label keys
+    /// (and the reserved `name` label) are skipped.
+    pub fn parse_allow_list(raw: &str) -> BTreeMap<String, BTreeSet<String>> {
+        let mut allow = BTreeMap::new();
+        for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, labels) = entry.split_once(':').unwrap_or((entry, ""));
+            let name = name.trim();
+            if !is_metric_ident(name) {
+                tracing::warn!(counter = %name, "ignoring invalid policy counter name");
+                continue;
+            }
+            let labels: BTreeSet<String> = labels
+                .split('|')
+                .map(str::trim)
+                .filter(|l| is_metric_ident(l) && *l != "name")
+                .map(str::to_string)
+                .collect();
+            allow.insert(name.to_string(), labels);
+        }
+        allow
+    }
+
+    /// Validate and apply one emission.
+    pub fn record(&self, emit: &CounterEmit) -> Result<(), CounterRejection> {
+        let result = self.try_record(emit);
+        if let Err(e) = &result {
+            let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            inner.rejected += 1;
+            tracing::warn!(counter = %emit.name, error = %e, "policy counter rejected");
+        }
+        result
+    }
+
+    fn try_record(&self, emit: &CounterEmit) -> Result<(), CounterRejection> {
+        let allowed = self
+            .allow
+            .get(&emit.name)
+            .ok_or_else(|| CounterRejection::UnknownName(emit.name.clone()))?;
+        for (label, value) in &emit.labels {
+            if !allowed.contains(label) {
+                return Err(CounterRejection::LabelNotAllowed {
+                    name: emit.name.clone(),
+                    label: label.clone(),
+                });
+            }
+            if !is_label_value(value) {
+                return Err(CounterRejection::InvalidLabelValue(label.clone()));
+            }
+        }
+        let key = (emit.name.clone(), emit.labels.clone());
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if !inner.series.contains_key(&key) && inner.series.len() >= self.max_series {
+            return Err(CounterRejection::SeriesLimit(self.max_series));
+        }
+        *inner.series.entry(key).or_insert(0) += 1;
+        Ok(())
+    }
+
+    /// Current value of one series (0 when never incremented).
+    pub fn value(&self, name: &str, labels: &BTreeMap<String, String>) -> u64 {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner
+            .series
+            .get(&(name.to_string(), labels.clone()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn rejected(&self) -> u64 {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.rejected
+    }
+
+    /// Prometheus text exposition of every series plus the rejection count.
+    pub fn render_prometheus(&self) -> String {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+        out.push_str(
+            "# HELP ubl_policy_counter_total Counters emitted by policy EmitCounter bits\n",
+        );
+        out.push_str("# TYPE ubl_policy_counter_total counter\n");
+        for ((name, labels), value) in &inner.series {
+            out.push_str("ubl_policy_counter_total{name=\"");
+            out.push_str(name);
+            out.push('"');
+            for (k, v) in labels {
+                out.push_str(&format!(",{}=\"{}\"", k, v));
+            }
+            out.push_str(&format!("}} {}\n", value));
+        }
+        out.push_str(
+            "# HELP ubl_policy_counter_rejected_total Policy counter emissions dropped by validation\n",
+        );
+        out.push_str("# TYPE ubl_policy_counter_rejected_total counter\n");
+        out.push_str(&format!(
+            "ubl_policy_counter_rejected_total {}\n",
+            inner.rejected
+        ));
+        out
+    }
+}
+
+impl Default for PolicyCounterRegistry {
+    fn default() -> Self {
+        Self::new(BTreeMap::new(), DEFAULT_POLICY_COUNTER_MAX_SERIES)
+    }
+}
+
+/// `[a-z_][a-z0-9_]*`, at most 64 bytes.
+fn is_metric_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+        && s.len() <= 64
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Non-empty, at most [`MAX_LABEL_VALUE_LEN`] bytes of `[A-Za-z0-9_.:/-]`;
+/// nothing that needs escaping in the exposition format.
+fn is_label_value(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= MAX_LABEL_VALUE_LEN
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '/' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emit(name: &str, labels: &[(&str, &str)]) -> CounterEmit {
+        CounterEmit {
+            name: name.to_string(),
+            labels: labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn allow_list_gates_names_labels_and_values() {
+        let registry = PolicyCounterRegistry::new(
+            PolicyCounterRegistry::parse_allow_list("invoices:tier|region, Bad-Name, seen"),
+            10,
+        );
+        registry
+            .record(&emit("invoices", &[("tier", "gold")]))
+            .unwrap();
+        registry
+            .record(&emit("invoices", &[("tier", "gold")]))
+            .unwrap();
+        registry.record(&emit("seen", &[])).unwrap();
+        assert!(matches!(
+            registry.record(&emit("unknown", &[])),
+            Err(CounterRejection::UnknownName(_))
+        ));
+        assert!(matches!(
+            registry.record(&emit("invoices", &[("user", "alice")])),
+            Err(CounterRejection::LabelNotAllowed { .. })
+        ));
+        assert!(matches!(
+            registry.record(&emit("invoices", &[("tier", "has \"quote\"")])),
+            Err(CounterRejection::InvalidLabelValue(_))
+        ));
+
+        let labels: BTreeMap<String, String> = [("tier".to_string(), "gold".to_string())].into();
+        assert_eq!(registry.value("invoices", &labels), 2);
+        assert_eq!(registry.rejected(), 3);
+        let text = registry.render_prometheus();
+        assert!(text.contains("ubl_policy_counter_total{name=\"invoices\",tier=\"gold\"} 2"));
+        assert!(text.contains("ubl_policy_counter_total{name=\"seen\"} 1"));
+        assert!(text.contains("ubl_policy_counter_rejected_total 3"));
+    }
+
+    #[test]
+    fn series_cap_rejects_new_series_but_keeps_counting_existing() {
+        let registry =
+            PolicyCounterRegistry::new(PolicyCounterRegistry::parse_allow_list("hits:tier"), 1);
+        registry.record(&emit("hits", &[("tier", "a")])).unwrap();
+        assert_eq!(
+            registry.record(&emit("hits", &[("tier", "b")])),
+            Err(CounterRejection::SeriesLimit(1))
+        );
+        registry.record(&emit("hits", &[("tier", "a")])).unwrap();
+        let labels: BTreeMap<String, String> = [("tier".to_string(), "a".to_string())].into();
+        assert_eq!(registry.value("hits", &labels), 2);
+    }
+}
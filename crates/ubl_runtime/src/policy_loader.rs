@@ -7,6 +7,7 @@ use crate::genesis::{create_genesis_policy, is_genesis_chip};
 use crate::policy_bit::PolicyBit;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -43,14 +44,37 @@ pub struct ChipData {
     pub parents: Vec<String>,
 }
 
-/// Policy loader with ancestry resolution
+/// Whether chips of `chip_type` carry a policy for the loader.
+pub fn is_policy_chip_type(chip_type: &str) -> bool {
+    chip_type.starts_with("ubl/policy")
+}
+
+/// Policy loader with ancestry resolution.
+///
+/// The storage can be swapped whole at runtime with [`Self::replace_storage`];
+/// each chain load works off one snapshot, so a concurrent swap is seen
+/// either entirely or not at all.
 pub struct PolicyLoader {
-    storage: Box<dyn PolicyStorage>,
+    storage: RwLock<Arc<dyn PolicyStorage>>,
 }
 
 impl PolicyLoader {
     pub fn new(storage: Box<dyn PolicyStorage>) -> Self {
-        Self { storage }
+        Self {
+            storage: RwLock::new(Arc::from(storage)),
+        }
+    }
+
+    /// Serve every later chain load from `storage`.
+    pub fn replace_storage(&self, storage: Box<dyn PolicyStorage>) {
+        *self.storage.write().unwrap_or_else(|e| e.into_inner()) = Arc::from(storage);
+    }
+
+    fn storage(&self) -> Arc<dyn PolicyStorage> {
+        self.storage
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
     }
 
     /// Load complete policy chain for a chip request
@@ -58,16 +82,18 @@ impl PolicyLoader {
         &self,
         chip_request: &ChipRequest,
     ) -> Result<Vec<PolicyBit>, PolicyError> {
+        let storage = self.storage();
         let mut policies = Vec::new();
 
         // 1. Always start with genesis policy
         policies.push(create_genesis_policy());
 
         // 2. Walk ancestry chain and collect policies
-        let ancestry = self.resolve_ancestry(chip_request).await?;
+        let ancestry = Self::resolve_ancestry(storage.as_ref(), chip_request).await?;
 
         for ancestor_cid in ancestry {
-            let attached_policies = self.find_attached_policies(&ancestor_cid).await?;
+            let attached_policies =
+                Self::find_attached_policies(storage.as_ref(), &ancestor_cid).await?;
             policies.extend(attached_policies);
         }
 
@@ -76,7 +102,7 @@ impl PolicyLoader {
 
     /// Resolve ancestry chain: chip → tenant → app → genesis
     async fn resolve_ancestry(
-        &self,
+        storage: &dyn PolicyStorage,
         chip_request: &ChipRequest,
     ) -> Result<Vec<String>, PolicyError> {
         let mut ancestry = Vec::new();
@@ -104,7 +130,7 @@ impl PolicyLoader {
                 ancestry.push(parent_cid.clone());
 
                 // Get the parent chip to find its parents
-                if let Some(parent_chip) = self.storage.get_chip(&parent_cid).await? {
+                if let Some(parent_chip) = storage.get_chip(&parent_cid).await? {
                     next_parents.extend(parent_chip.parents);
                 }
             }
@@ -118,14 +144,17 @@ impl PolicyLoader {
     }
 
     /// Find all policy chips attached to a given chip
-    async fn find_attached_policies(&self, chip_cid: &str) -> Result<Vec<PolicyBit>, PolicyError> {
-        let policy_chips = self.storage.find_children(chip_cid).await?;
+    async fn find_attached_policies(
+        storage: &dyn PolicyStorage,
+        chip_cid: &str,
+    ) -> Result<Vec<PolicyBit>, PolicyError> {
+        let policy_chips = storage.find_children(chip_cid).await?;
 
         let mut policies = Vec::new();
         for chip in policy_chips {
             // Only process policy chips
-            if chip.chip_type.starts_with("ubl/policy") {
-                let policy_bit = self.parse_policy_chip(&chip)?;
+            if is_policy_chip_type(&chip.chip_type) {
+                let policy_bit = Self::parse_policy_chip(&chip)?;
                 policies.push(policy_bit);
             }
         }
@@ -134,7 +163,7 @@ impl PolicyLoader {
     }
 
     /// Parse a policy chip into a PolicyBit
-    fn parse_policy_chip(&self, chip: &ChipData) -> Result<PolicyBit, PolicyError> {
+    pub fn parse_policy_chip(chip: &ChipData) -> Result<PolicyBit, PolicyError> {
         // Extract PolicyBit from chip body
         let circuits = chip
             .body
@@ -0,0 +1,193 @@
+//! Retained policy-set snapshots keyed by content hash.
+//!
+//! Every CHECK hashes the policy chain it evaluated (`policy_set_hash`) and
+//! retains the chain here, so an operator can later re-evaluate a chip
+//! against exactly the policies that applied at the time. The in-process
+//! map is bounded (FIFO eviction). With a durable store attached, every
+//! chain is also written there, and chains evicted or lost to a restart are
+//! read back on demand.
+
+use crate::durable_store::DurableStore;
+use crate::policy_bit::PolicyBit;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// Default number of distinct policy sets kept in memory.
+pub const DEFAULT_POLICY_SNAPSHOT_RETAIN: usize = 256;
+
+/// Content hash of an ordered policy chain: `b3:<hex>` over its JSON encoding.
+pub fn policy_set_hash(policies: &[PolicyBit]) -> String {
+    let bytes = serde_json::to_vec(policies).unwrap_or_default();
+    format!("b3:{}", hex::encode(blake3::hash(&bytes).as_bytes()))
+}
+
+#[derive(Default)]
+struct SnapshotInner {
+    by_hash: HashMap<String, Arc<Vec<PolicyBit>>>,
+    order: VecDeque<String>,
+}
+
+/// Bounded in-memory map `policy_set_hash -> policy chain`, optionally
+/// backed by the durable store.
+pub struct PolicySnapshotStore {
+    capacity: usize,
+    inner: Mutex<SnapshotInner>,
+    durable: Option<Arc<DurableStore>>,
+}
+
+impl PolicySnapshotStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::new(SnapshotInner::default()),
+            durable: None,
+        }
+    }
+
+    /// Persist retained chains in `durable` and fall back to it on a miss.
+    pub fn with_durable(mut self, durable: Option<Arc<DurableStore>>) -> Self {
+        self.durable = durable;
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Capacity from `UBL_POLICY_SNAPSHOT_RETAIN` (default 256).
+    pub fn from_env() -> Self {
+        let capacity = std::env::var("UBL_POLICY_SNAPSHOT_RETAIN")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_POLICY_SNAPSHOT_RETAIN);
+        Self::new(capacity)
+    }
+
+    /// Retain `policies` (no-op when already present) and return their hash.
+    /// A chain new to this process is also written to the durable store;
+    /// a failed write is logged, not fatal.
+    pub fn retain(&self, policies: &[PolicyBit]) -> String {
+        let hash = policy_set_hash(policies);
+        if !self.remember(&hash, Arc::new(policies.to_vec())) {
+            return hash;
+        }
+        if let Some(durable) = &self.durable {
+            let stored = serde_json::to_string(policies)
+                .map_err(|e| e.to_string())
+                .and_then(|json| {
+                    durable
+                        .put_policy_snapshot(&hash, &json)
+                        .map_err(|e| e.to_string())
+                });
+            if let Err(e) = stored {
+                warn!(policy_set_hash = %hash, error = %e, "policy snapshot persist failed");
+            }
+        }
+        hash
+    }
+
+    pub fn get(&self, hash: &str) -> Option<Arc<Vec<PolicyBit>>> {
+        let cached = {
+            let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+            inner.by_hash.get(hash).cloned()
+        };
+        if cached.is_some() {
+            return cached;
+        }
+        let json = match self.durable.as_ref()?.get_policy_snapshot(hash) {
+            Ok(json) => json?,
+            Err(e) => {
+                warn!(policy_set_hash = %hash, error = %e, "policy snapshot lookup failed");
+                return None;
+            }
+        };
+        let policies: Vec<PolicyBit> = serde_json::from_str(&json).ok()?;
+        let policies = Arc::new(policies);
+        self.remember(hash, policies.clone());
+        Some(policies)
+    }
+
+    /// Add `policies` to the in-memory map; false when already present.
+    fn remember(&self, hash: &str, policies: Arc<Vec<PolicyBit>>) -> bool {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.by_hash.contains_key(hash) {
+            return false;
+        }
+        while inner.order.len() >= self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.by_hash.remove(&oldest);
+            }
+        }
+        inner.by_hash.insert(hash.to_string(), policies);
+        inner.order.push_back(hash.to_string());
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for PolicySnapshotStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_POLICY_SNAPSHOT_RETAIN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genesis::create_genesis_policy;
+
+    #[test]
+    fn retain_is_idempotent_and_hash_is_stable() {
+        let store = PolicySnapshotStore::new(4);
+        let chain = vec![create_genesis_policy()];
+        let h1 = store.retain(&chain);
+        let h2 = store.retain(&chain);
+        assert_eq!(h1, h2);
+        assert_eq!(h1, policy_set_hash(&chain));
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get(&h1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn oldest_snapshot_is_evicted_at_capacity() {
+        let store = PolicySnapshotStore::new(1);
+        let genesis = create_genesis_policy();
+        let mut other = genesis.clone();
+        other.id = "policy.test.other".to_string();
+        let h1 = store.retain(std::slice::from_ref(&genesis));
+        let h2 = store.retain(&[other]);
+        assert_ne!(h1, h2);
+        assert!(store.get(&h1).is_none());
+        assert!(store.get(&h2).is_some());
+    }
+
+    #[test]
+    fn durable_snapshots_outlive_eviction_and_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let dsn = format!(
+            "file:{}?mode=rwc&_journal_mode=WAL",
+            dir.path().join("snapshots.db").display()
+        );
+        let durable = Arc::new(DurableStore::new(dsn).unwrap());
+        let store = PolicySnapshotStore::new(1).with_durable(Some(durable.clone()));
+        let genesis = create_genesis_policy();
+        let mut other = genesis.clone();
+        other.id = "policy.test.other".to_string();
+        let h1 = store.retain(std::slice::from_ref(&genesis));
+        store.retain(&[other]);
+        assert_eq!(store.get(&h1).unwrap()[0].id, genesis.id);
+
+        let restarted = PolicySnapshotStore::new(1).with_durable(Some(durable));
+        assert_eq!(restarted.get(&h1).unwrap()[0].id, genesis.id);
+        assert!(restarted.get("b3:unknown").is_none());
+    }
+}
@@ -0,0 +1,140 @@
+//! Post-WF hook — operator side effects after a successful WF commit.
+//!
+//! The hook runs once per allowed chip, after the receipt is durably
+//! committed, with the signed receipt and the chip body. It runs in a spawned
+//! task, off the request path. Unlike the outbox it has no retry queue:
+//! failures are warn-logged and never fail the submission. Denials and
+//! idempotent replays do not trigger it; a quarantined chip triggers it when
+//! it is released.
+
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use ubl_receipt::UnifiedReceipt;
+
+/// Extension point invoked after WF commit. Registered at startup with
+/// [`crate::UblPipeline::set_post_wf_hook`].
+#[async_trait::async_trait]
+pub trait PostWfHook: Send + Sync {
+    async fn after_wf(&self, receipt: &UnifiedReceipt, chip: &Value)
+        -> Result<(), PostWfHookError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PostWfHookError {
+    #[error("IO error: {0}")]
+    Io(String),
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+    #[error("Delivery error: {0}")]
+    Delivery(String),
+}
+
+/// One hook record, as written by [`NdjsonPostWfHook`] and posted by HTTP hooks.
+pub fn hook_record(receipt: &UnifiedReceipt, chip: &Value) -> Result<Value, PostWfHookError> {
+    let receipt_json = receipt
+        .to_json()
+        .map_err(|e| PostWfHookError::Serialization(e.to_string()))?;
+    Ok(serde_json::json!({
+        "@type": "ubl/post_wf",
+        "ts": chrono::Utc::now().to_rfc3339(),
+        "world": receipt.world.as_str(),
+        "receipt_cid": receipt.receipt_cid.as_str(),
+        "receipt": receipt_json,
+        "chip": chip,
+    }))
+}
+
+// ── NullPostWfHook (default) ─────────────────────────────────────
+
+/// No-op hook — the pipeline default.
+pub struct NullPostWfHook;
+
+#[async_trait::async_trait]
+impl PostWfHook for NullPostWfHook {
+    async fn after_wf(
+        &self,
+        _receipt: &UnifiedReceipt,
+        _chip: &Value,
+    ) -> Result<(), PostWfHookError> {
+        Ok(())
+    }
+}
+
+// ── NdjsonPostWfHook (filesystem) ────────────────────────────────
+
+/// Appends one [`hook_record`] line per committed chip to a single file.
+pub struct NdjsonPostWfHook {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl NdjsonPostWfHook {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PostWfHook for NdjsonPostWfHook {
+    async fn after_wf(
+        &self,
+        receipt: &UnifiedReceipt,
+        chip: &Value,
+    ) -> Result<(), PostWfHookError> {
+        let mut line = serde_json::to_string(&hook_record(receipt, chip)?)
+            .map_err(|e| PostWfHookError::Serialization(e.to_string()))?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().await;
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| PostWfHookError::Io(e.to_string()))?;
+        }
+        tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| PostWfHookError::Io(e.to_string()))?
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| PostWfHookError::Io(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_receipt() -> UnifiedReceipt {
+        UnifiedReceipt::new("a/acme/t/prod", "did:key:z123", "did:key:z123#v0", "00aa")
+    }
+
+    #[tokio::test]
+    async fn ndjson_hook_appends_one_line_per_call() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hooks").join("post_wf.ndjson");
+        let hook = NdjsonPostWfHook::new(&path);
+        let receipt = sample_receipt();
+        let chip = serde_json::json!({"@type": "ubl/document", "@id": "d1"});
+
+        hook.after_wf(&receipt, &chip).await.unwrap();
+        hook.after_wf(&receipt, &chip).await.unwrap();
+
+        let contents = tokio::fs::read_to_string(&path).await.unwrap();
+        let lines: Vec<&str> = contents.trim().split('\n').collect();
+        assert_eq!(lines.len(), 2);
+        let record: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(record["@type"], "ubl/post_wf");
+        assert_eq!(record["world"], "a/acme/t/prod");
+        assert_eq!(record["receipt_cid"], receipt.receipt_cid.as_str());
+        assert_eq!(record["chip"]["@id"], "d1");
+    }
+}
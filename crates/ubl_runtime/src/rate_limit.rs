@@ -21,6 +21,9 @@ pub struct RateLimitConfig {
     pub max_requests: u32,
     /// Time window duration.
     pub window: Duration,
+    /// Token-bucket capacity. When set, `max_requests` per `window` is the
+    /// refill rate instead of a sliding-window cap.
+    pub burst: Option<u32>,
 }
 
 impl RateLimitConfig {
@@ -28,12 +31,27 @@ impl RateLimitConfig {
         Self {
             max_requests,
             window,
+            burst: None,
         }
     }
 
     pub fn per_minute(max_requests: u32) -> Self {
         Self::new(max_requests, Duration::from_secs(60))
     }
+
+    /// Token bucket holding `burst` requests, refilled at `rate / 60` per second.
+    pub fn per_minute_with_burst(rate: u32, burst: u32) -> Self {
+        Self {
+            burst: Some(burst.max(1)),
+            ..Self::per_minute(rate)
+        }
+    }
+
+    /// `(capacity, refill per second)` when this is a token bucket.
+    fn token_bucket(&self) -> Option<(u32, f64)> {
+        let burst = self.burst?;
+        Some((burst, self.max_requests as f64 / self.window.as_secs_f64()))
+    }
 }
 
 /// Default rate limits per ARCHITECTURE.md §14.2.
@@ -143,6 +161,8 @@ impl RateLimitResult {
 pub struct RateLimiter {
     config: RateLimitConfig,
     entries: RwLock<HashMap<String, WindowEntry>>,
+    /// Used instead of `entries` when the config sets a burst.
+    buckets: RwLock<HashMap<String, TokenBucket>>,
 }
 
 impl RateLimiter {
@@ -150,12 +170,20 @@ impl RateLimiter {
         Self {
             config,
             entries: RwLock::new(HashMap::new()),
+            buckets: RwLock::new(HashMap::new()),
         }
     }
 
     /// Check and record a request for the given key.
     pub async fn check(&self, key: &str) -> RateLimitResult {
         let now = Instant::now();
+        if let Some((capacity, refill_per_sec)) = self.config.token_bucket() {
+            let mut buckets = self.buckets.write().await;
+            let bucket = buckets
+                .entry(key.to_string())
+                .or_insert_with(|| TokenBucket::full(capacity, now));
+            return bucket.take(now, capacity, refill_per_sec);
+        }
         let mut entries = self.entries.write().await;
         let entry = entries
             .entry(key.to_string())
@@ -166,6 +194,13 @@ impl RateLimiter {
     /// Peek at remaining quota without consuming.
     pub async fn remaining(&self, key: &str) -> u32 {
         let now = Instant::now();
+        if let Some((capacity, refill_per_sec)) = self.config.token_bucket() {
+            let buckets = self.buckets.read().await;
+            return buckets.get(key).map_or(capacity, |b| {
+                let elapsed = now.saturating_duration_since(b.updated).as_secs_f64();
+                (b.tokens + elapsed * refill_per_sec).min(capacity as f64) as u32
+            });
+        }
         let entries = self.entries.read().await;
         entries
             .get(key)
@@ -176,6 +211,15 @@ impl RateLimiter {
     /// Prune all expired entries (call periodically to prevent memory growth).
     pub async fn prune(&self) {
         let now = Instant::now();
+        if let Some((capacity, refill_per_sec)) = self.config.token_bucket() {
+            // A refilled bucket is identical to a fresh one.
+            let mut buckets = self.buckets.write().await;
+            buckets.retain(|_, b| {
+                b.refill(now, capacity, refill_per_sec);
+                b.tokens < capacity as f64
+            });
+            return;
+        }
         let cutoff = now - self.config.window;
         let mut entries = self.entries.write().await;
         entries.retain(|_, e| {
@@ -293,23 +337,68 @@ pub struct GateRateLimitResult {
 // Canon-aware rate limiting (P0.2)
 // ---------------------------------------------------------------------------
 
+/// What a [`CanonRateLimiter`] bucket is keyed on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CanonRateKey {
+    /// The canonical payload fingerprint.
+    #[default]
+    Payload,
+    /// The subject DID, so rotating tiny fields doesn't mint a new bucket.
+    Subject,
+    /// Both buckets; whichever fills first limits.
+    Both,
+}
+
+impl CanonRateKey {
+    /// `payload`, `subject` or `both`, case-insensitive.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "payload" => Some(Self::Payload),
+            "subject" => Some(Self::Subject),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Payload => "payload",
+            Self::Subject => "subject",
+            Self::Both => "both",
+        }
+    }
+}
+
 /// Canon-aware rate limiter.
 ///
 /// Limits by `canon_fingerprint` of the payload so that cosmetic JSON
-/// variations (whitespace, key reordering) don't bypass the limit.
+/// variations (whitespace, key reordering) don't bypass the limit, and
+/// optionally by subject DID (see [`CanonRateKey`]).
 /// Default: 5 identical canonical payloads per minute.
 #[derive(Clone)]
 pub struct CanonRateLimiter {
     limiter: Arc<RateLimiter>,
+    key: CanonRateKey,
 }
 
 impl CanonRateLimiter {
     pub fn new(config: RateLimitConfig) -> Self {
         Self {
             limiter: Arc::new(RateLimiter::new(config)),
+            key: CanonRateKey::default(),
         }
     }
 
+    /// Key buckets on `key` instead of the payload alone.
+    pub fn with_key(mut self, key: CanonRateKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    pub fn key(&self) -> CanonRateKey {
+        self.key
+    }
+
     /// Default: 5 identical canonical payloads per minute.
     pub fn default_config() -> RateLimitConfig {
         RateLimitConfig::per_minute(5)
@@ -326,6 +415,26 @@ impl CanonRateLimiter {
         Some((fp, result))
     }
 
+    /// Check a chip body sent by `subject` under the configured key. The
+    /// caller picks a subject the sender cannot rotate (a verified DID, or a
+    /// client address). Returns the key whose bucket decided the result,
+    /// never `Both`; `None` if the body can't be canonicalized.
+    pub async fn check_body_for_subject(
+        &self,
+        body: &serde_json::Value,
+        subject: &str,
+    ) -> Option<(CanonFingerprint, CanonRateKey, RateLimitResult)> {
+        let fp = CanonFingerprint::from_chip_body(body)?;
+        if self.key != CanonRateKey::Subject {
+            let result = self.limiter.check(&fp.rate_key()).await;
+            if self.key == CanonRateKey::Payload || result.is_limited() {
+                return Some((fp, CanonRateKey::Payload, result));
+            }
+        }
+        let result = self.limiter.check(&format!("subject|{}", subject)).await;
+        Some((fp, CanonRateKey::Subject, result))
+    }
+
     /// Prune expired entries.
     pub async fn prune(&self) {
         self.limiter.prune().await;
@@ -338,6 +447,178 @@ impl Default for CanonRateLimiter {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Per-world write volume
+// ---------------------------------------------------------------------------
+
+/// Token bucket: up to `burst` writes at once, refilled at `per_minute`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenBucketConfig {
+    pub per_minute: u32,
+    pub burst: u32,
+}
+
+impl TokenBucketConfig {
+    /// A bucket whose burst equals one minute of refill.
+    pub fn per_minute(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            burst: per_minute,
+        }
+    }
+
+    /// Parse `<per_minute>[:<burst>]`. `None` for zero, unparsable or
+    /// missing values; a zero burst falls back to `per_minute`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (rate, burst) = raw.trim().split_once(':').unwrap_or((raw.trim(), ""));
+        let per_minute = rate.trim().parse::<u32>().ok().filter(|n| *n > 0)?;
+        let burst = burst
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|n| *n > 0)
+            .unwrap_or(per_minute);
+        Some(Self { per_minute, burst })
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.per_minute as f64 / 60.0
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: u32, now: Instant) -> Self {
+        Self {
+            tokens: capacity as f64,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant, capacity: u32, refill_per_sec: f64) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_sec).min(capacity as f64);
+        self.updated = now;
+    }
+
+    fn take(&mut self, now: Instant, capacity: u32, refill_per_sec: f64) -> RateLimitResult {
+        self.refill(now, capacity, refill_per_sec);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            RateLimitResult::Allowed {
+                limit: capacity,
+                remaining: self.tokens as u32,
+            }
+        } else {
+            let wait = (1.0 - self.tokens) / refill_per_sec;
+            RateLimitResult::Limited {
+                limit: capacity,
+                remaining: 0,
+                retry_after: Duration::from_secs_f64(wait),
+            }
+        }
+    }
+}
+
+/// Write-volume limiter keyed by `@world`, so one noisy tenant can't starve
+/// the rest. Each world draws from its own bucket, sized by its override or
+/// the default; worlds with neither are not limited.
+#[derive(Debug, Default)]
+pub struct WorldRateLimiter {
+    default: Option<TokenBucketConfig>,
+    /// `None` exempts the world from the default.
+    overrides: HashMap<String, Option<TokenBucketConfig>>,
+    buckets: RwLock<HashMap<String, TokenBucket>>,
+}
+
+impl WorldRateLimiter {
+    pub fn new(default: Option<TokenBucketConfig>) -> Self {
+        Self {
+            default,
+            ..Self::default()
+        }
+    }
+
+    /// Size `world`'s bucket with `config` instead of the default; `None`
+    /// leaves the world unlimited.
+    pub fn with_override(mut self, world: &str, config: Option<TokenBucketConfig>) -> Self {
+        self.overrides.insert(world.to_string(), config);
+        self
+    }
+
+    /// `UBL_WORLD_RATE_LIMIT_DEFAULT` (`<per_minute>[:<burst>]`) and
+    /// `UBL_WORLD_RATE_LIMIT_OVERRIDES` (`<world>=<per_minute>[:<burst>],...`,
+    /// `0` exempts a world). `None` when neither sets a limit.
+    pub fn from_env() -> Option<Self> {
+        Self::parse(
+            std::env::var("UBL_WORLD_RATE_LIMIT_DEFAULT")
+                .ok()
+                .as_deref(),
+            std::env::var("UBL_WORLD_RATE_LIMIT_OVERRIDES")
+                .ok()
+                .as_deref(),
+        )
+    }
+
+    /// Entries without a world or `=` are skipped.
+    pub fn parse(default: Option<&str>, overrides: Option<&str>) -> Option<Self> {
+        let mut limiter = Self::new(default.and_then(TokenBucketConfig::parse));
+        for entry in overrides
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            match entry.split_once('=') {
+                Some((world, spec)) if !world.trim().is_empty() => {
+                    limiter = limiter.with_override(world.trim(), TokenBucketConfig::parse(spec));
+                }
+                _ => tracing::warn!(entry = %entry, "ignoring invalid world rate limit override"),
+            }
+        }
+        let limits_something =
+            limiter.default.is_some() || limiter.overrides.values().any(Option::is_some);
+        limits_something.then_some(limiter)
+    }
+
+    /// Bucket size for `world`, if it is limited.
+    pub fn config_for(&self, world: &str) -> Option<&TokenBucketConfig> {
+        match self.overrides.get(world) {
+            Some(config) => config.as_ref(),
+            None => self.default.as_ref(),
+        }
+    }
+
+    /// Take one write from `world`'s bucket; `None` when the world is unlimited.
+    pub async fn check(&self, world: &str) -> Option<RateLimitResult> {
+        let config = self.config_for(world)?;
+        let now = Instant::now();
+        let mut buckets = self.buckets.write().await;
+        let bucket = buckets
+            .entry(world.to_string())
+            .or_insert_with(|| TokenBucket::full(config.burst, now));
+        Some(bucket.take(now, config.burst, config.refill_per_sec()))
+    }
+
+    /// Drop buckets that have refilled completely; a fresh one is identical.
+    pub async fn prune(&self) {
+        let now = Instant::now();
+        let mut buckets = self.buckets.write().await;
+        buckets.retain(|world, bucket| {
+            let Some(config) = self.config_for(world) else {
+                return false;
+            };
+            bucket.refill(now, config.burst, config.refill_per_sec());
+            bucket.tokens < config.burst as f64
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -607,4 +888,159 @@ mod tests {
         );
         assert!(r2.is_limited(), "cosmetic variation must hit same bucket");
     }
+
+    #[test]
+    fn world_limits_parse_default_overrides_and_exemptions() {
+        assert_eq!(
+            TokenBucketConfig::parse("120:10"),
+            Some(TokenBucketConfig {
+                per_minute: 120,
+                burst: 10
+            })
+        );
+        assert_eq!(
+            TokenBucketConfig::parse(" 60 "),
+            Some(TokenBucketConfig::per_minute(60))
+        );
+        assert_eq!(TokenBucketConfig::parse("0"), None);
+        assert!(WorldRateLimiter::parse(None, None).is_none());
+        assert!(WorldRateLimiter::parse(Some("0"), Some("a/x/t/y=0")).is_none());
+
+        let limiter = WorldRateLimiter::parse(
+            Some("600"),
+            Some("a/noisy/t/prod=60:5, a/ops/t/main=0, junk"),
+        )
+        .unwrap();
+        assert_eq!(
+            limiter.config_for("a/quiet/t/prod"),
+            Some(&TokenBucketConfig::per_minute(600))
+        );
+        assert_eq!(limiter.config_for("a/noisy/t/prod").unwrap().burst, 5);
+        assert_eq!(limiter.config_for("a/ops/t/main"), None);
+    }
+
+    #[tokio::test]
+    async fn world_limiter_sheds_one_world_and_refills() {
+        let limiter = WorldRateLimiter::new(None).with_override(
+            "a/noisy/t/prod",
+            Some(TokenBucketConfig::parse("6000:2").unwrap()),
+        );
+        assert!(limiter.check("a/quiet/t/prod").await.is_none());
+        assert!(limiter.check("a/noisy/t/prod").await.unwrap().is_allowed());
+        assert!(limiter.check("a/noisy/t/prod").await.unwrap().is_allowed());
+        let limited = limiter.check("a/noisy/t/prod").await.unwrap();
+        assert!(limited.is_limited());
+        assert_eq!(limited.retry_after_secs(), Some(1));
+
+        // 6000/min refills one token every 10ms.
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(limiter.check("a/noisy/t/prod").await.unwrap().is_allowed());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        limiter.prune().await;
+        assert!(limiter.buckets.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn canon_rate_limiter_subject_key_catches_rotated_fields() {
+        let chip = |n: u32| {
+            serde_json::json!({
+                "@type": "ubl/user", "@ver": "1.0", "@world": "a/app/t/ten", "n": n
+            })
+        };
+        let payload = CanonRateLimiter::new(RateLimitConfig::per_minute(2));
+        for n in 0..4 {
+            let (_, key, result) = payload
+                .check_body_for_subject(&chip(n), "did:key:zAbuser")
+                .await
+                .unwrap();
+            assert_eq!(key, CanonRateKey::Payload);
+            assert!(result.is_allowed());
+        }
+
+        let subject = CanonRateLimiter::new(RateLimitConfig::per_minute(2))
+            .with_key(CanonRateKey::parse(" Subject ").unwrap());
+        for n in 0..2 {
+            let (_, _, result) = subject
+                .check_body_for_subject(&chip(n), "did:key:zAbuser")
+                .await
+                .unwrap();
+            assert!(result.is_allowed());
+        }
+        let (_, key, result) = subject
+            .check_body_for_subject(&chip(9), "did:key:zAbuser")
+            .await
+            .unwrap();
+        assert_eq!(key, CanonRateKey::Subject);
+        assert!(result.is_limited());
+        let (_, _, result) = subject
+            .check_body_for_subject(&chip(9), "did:key:zOther")
+            .await
+            .unwrap();
+        assert!(result.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn canon_rate_limiter_both_reports_the_bucket_that_filled() {
+        let both =
+            CanonRateLimiter::new(RateLimitConfig::per_minute(2)).with_key(CanonRateKey::Both);
+        let chip = |n: u32| {
+            serde_json::json!({
+                "@type": "ubl/user", "@ver": "1.0", "@world": "a/app/t/ten", "n": n
+            })
+        };
+        for did in ["did:key:zA", "did:key:zB"] {
+            let (_, _, result) = both.check_body_for_subject(&chip(0), did).await.unwrap();
+            assert!(result.is_allowed());
+        }
+        let (_, key, result) = both
+            .check_body_for_subject(&chip(0), "did:key:zC")
+            .await
+            .unwrap();
+        assert_eq!(key, CanonRateKey::Payload);
+        assert!(result.is_limited());
+
+        // zA already spent one subject token on chip(0).
+        let (_, _, result) = both
+            .check_body_for_subject(&chip(1), "did:key:zA")
+            .await
+            .unwrap();
+        assert!(result.is_allowed());
+        let (_, key, result) = both
+            .check_body_for_subject(&chip(2), "did:key:zA")
+            .await
+            .unwrap();
+        assert_eq!(key, CanonRateKey::Subject);
+        assert!(result.is_limited());
+        assert_eq!(CanonRateKey::parse("fingerprint"), None);
+    }
+
+    #[tokio::test]
+    async fn canon_rate_limiter_burst_passes_then_limits_until_refill() {
+        // 6000/min refills one token every 10ms.
+        let limiter = CanonRateLimiter::new(RateLimitConfig::per_minute_with_burst(6000, 3));
+        let body = serde_json::json!({"@type": "ubl/user", "@ver": "1.0", "@world": "a/app/t/ten"});
+        for _ in 0..3 {
+            let (_, result) = limiter.check_body(&body).await.unwrap();
+            assert!(result.is_allowed());
+        }
+        let (_, result) = limiter.check_body(&body).await.unwrap();
+        assert!(result.is_limited());
+        assert_eq!(result.limit(), 3);
+
+        tokio::time::sleep(Duration::from_millis(15)).await;
+        let (_, result) = limiter.check_body(&body).await.unwrap();
+        assert!(result.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn token_bucket_limiter_reports_remaining_and_prunes_full_buckets() {
+        let limiter = RateLimiter::new(RateLimitConfig::per_minute_with_burst(6000, 2));
+        assert_eq!(limiter.remaining("k").await, 2);
+        assert!(limiter.check("k").await.is_allowed());
+        assert_eq!(limiter.remaining("k").await, 1);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(limiter.remaining("k").await, 2);
+        limiter.prune().await;
+        assert!(limiter.buckets.read().await.is_empty());
+    }
 }
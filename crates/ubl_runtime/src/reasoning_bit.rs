@@ -1,6 +1,7 @@
 //! Reasoning Bit implementation - the atomic unit of decision making
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // Single Decision enum — lives in ubl_receipt, re-exported here.
 pub use ubl_receipt::Decision;
@@ -16,6 +17,9 @@ pub enum Expression {
     And(Vec<Expression>),
     Or(Vec<Expression>),
     Not(Box<Expression>),
+    /// Always true; increments `ubl_policy_counter_total{name, labels...}`
+    /// when reached during CHECK (see `policy_counters`).
+    EmitCounter(String, BTreeMap<String, String>),
 }
 
 /// The atomic unit of decision making
@@ -45,6 +49,15 @@ pub struct RbResult {
     pub reason: String,
     pub inputs_used: Vec<String>,
     pub duration_nanos: u64,
+    /// Counters reached while evaluating the condition.
+    pub counters: Vec<CounterEmit>,
+}
+
+/// One `EmitCounter` increment requested by a reasoning bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CounterEmit {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
 }
 
 impl ReasoningBit {
@@ -52,7 +65,8 @@ impl ReasoningBit {
     pub fn evaluate(&self, context: &EvalContext) -> RbResult {
         let start = std::time::Instant::now();
 
-        let condition_result = self.condition.evaluate(context);
+        let mut counters = Vec::new();
+        let condition_result = self.condition.evaluate_with(context, &mut counters);
         let decision = if condition_result {
             self.on_true.clone()
         } else {
@@ -73,6 +87,7 @@ impl ReasoningBit {
             reason,
             inputs_used,
             duration_nanos,
+            counters,
         }
     }
 }
@@ -80,6 +95,12 @@ impl ReasoningBit {
 impl Expression {
     /// Evaluate this expression against the context
     pub fn evaluate(&self, context: &EvalContext) -> bool {
+        self.evaluate_with(context, &mut Vec::new())
+    }
+
+    /// Evaluate, recording every `EmitCounter` actually reached. `And`/`Or`
+    /// short-circuit, so a counter behind a failed guard is not emitted.
+    pub fn evaluate_with(&self, context: &EvalContext, counters: &mut Vec<CounterEmit>) -> bool {
         match self {
             Expression::Always(value) => *value,
             Expression::ContextHas(key) => context.variables.contains_key(key),
@@ -95,9 +116,20 @@ impl Expression {
                 .and_then(|v| v.as_str())
                 .map(|t| t == expected_type)
                 .unwrap_or(false),
-            Expression::And(expressions) => expressions.iter().all(|e| e.evaluate(context)),
-            Expression::Or(expressions) => expressions.iter().any(|e| e.evaluate(context)),
-            Expression::Not(expr) => !expr.evaluate(context),
+            Expression::And(expressions) => expressions
+                .iter()
+                .all(|e| e.evaluate_with(context, counters)),
+            Expression::Or(expressions) => expressions
+                .iter()
+                .any(|e| e.evaluate_with(context, counters)),
+            Expression::Not(expr) => !expr.evaluate_with(context, counters),
+            Expression::EmitCounter(name, labels) => {
+                counters.push(CounterEmit {
+                    name: name.clone(),
+                    labels: labels.clone(),
+                });
+                true
+            }
         }
     }
 
@@ -126,7 +158,7 @@ impl Expression {
             Expression::Not(expr) => {
                 expr.collect_inputs(inputs);
             }
-            Expression::Always(_) | Expression::BodySizeLte(_) => {}
+            Expression::Always(_) | Expression::BodySizeLte(_) | Expression::EmitCounter(_, _) => {}
         }
     }
 }
@@ -195,4 +227,36 @@ mod tests {
         let expr = Expression::BodySizeLte(512);
         assert!(!expr.evaluate(&test_context()));
     }
+
+    #[test]
+    fn emit_counter_only_when_reached() {
+        let labels: BTreeMap<String, String> = [("tier".to_string(), "gold".to_string())].into();
+        let rb = ReasoningBit {
+            id: "count_users".to_string(),
+            name: "Count users".to_string(),
+            condition: Expression::And(vec![
+                Expression::TypeEquals("ubl/user".to_string()),
+                Expression::EmitCounter("users_seen".to_string(), labels.clone()),
+            ]),
+            on_true: Decision::Allow,
+            on_false: Decision::Allow,
+            requires_context: vec![],
+        };
+        let result = rb.evaluate(&test_context());
+        assert_eq!(
+            result.counters,
+            vec![CounterEmit {
+                name: "users_seen".to_string(),
+                labels,
+            }]
+        );
+
+        let guarded = Expression::And(vec![
+            Expression::TypeEquals("ubl/other".to_string()),
+            Expression::EmitCounter("users_seen".to_string(), BTreeMap::new()),
+        ]);
+        let mut counters = Vec::new();
+        assert!(!guarded.evaluate_with(&test_context(), &mut counters));
+        assert!(counters.is_empty());
+    }
 }
@@ -0,0 +1,227 @@
+//! Signed receipt bundles for offline audit.
+//!
+//! A bundle packs a persisted receipt, the chip it produced and the receipt's
+//! stage chain into one artifact signed by the gate key. Auditors can verify
+//! it without talking to the gate again.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use ubl_kms::Ed25519SigningKey as SigningKey;
+use ubl_receipt::UnifiedReceipt;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReceiptBundleError {
+    #[error("signature error: {0}")]
+    Signature(String),
+    #[error("did key parse error: {0}")]
+    DidKey(String),
+    #[error("invalid bundle: {0}")]
+    Invalid(String),
+    #[error("stage chain too long: {len} entries (max {max})")]
+    ChainTooLong { len: usize, max: usize },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptBundle {
+    #[serde(rename = "@type")]
+    pub bundle_type: String,
+    #[serde(rename = "@ver")]
+    pub ver: String,
+    pub issued_at: String,
+    pub did: String,
+    pub kid: String,
+    pub receipt_cid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chip_cid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chip: Option<Value>,
+    pub receipt: Value,
+    pub chain: Vec<Value>,
+    pub bundle_sig: String,
+}
+
+/// Outcome of the individual bundle checks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BundleVerification {
+    pub signature_valid: bool,
+    pub auth_chain_valid: bool,
+    pub chain_matches_receipt: bool,
+    pub chip_cid_valid: bool,
+}
+
+impl BundleVerification {
+    pub fn verified(&self) -> bool {
+        self.signature_valid
+            && self.auth_chain_valid
+            && self.chain_matches_receipt
+            && self.chip_cid_valid
+    }
+}
+
+impl ReceiptBundle {
+    /// Assemble + sign a bundle. `chain` is taken from the receipt's stages.
+    pub fn issue(
+        receipt: Value,
+        chip: Option<Value>,
+        did: &str,
+        kid: &str,
+        sk: &SigningKey,
+    ) -> Result<Self, ReceiptBundleError> {
+        let receipt_cid = receipt
+            .get("receipt_cid")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| ReceiptBundleError::Invalid("receipt missing receipt_cid".into()))?
+            .to_string();
+        let chain = stage_chain(&receipt);
+        let chip_cid = match chip.as_ref() {
+            Some(body) => Some(chip_cid_of(body)?),
+            None => None,
+        };
+        let mut bundle = Self {
+            bundle_type: "ubl/receipt.bundle".to_string(),
+            ver: "1.0".to_string(),
+            issued_at: Utc::now().to_rfc3339(),
+            did: did.to_string(),
+            kid: kid.to_string(),
+            receipt_cid,
+            chip_cid,
+            chip,
+            receipt,
+            chain,
+            bundle_sig: String::new(),
+        };
+        bundle.bundle_sig = ubl_canon::sign_domain_v1(
+            &bundle.payload_value(),
+            ubl_canon::domains::RECEIPT_BUNDLE,
+            sk,
+        )
+        .map_err(|e| ReceiptBundleError::Signature(e.to_string()))?;
+        Ok(bundle)
+    }
+
+    /// Verify the bundle signature against `did`.
+    pub fn verify_signature(&self) -> Result<bool, ReceiptBundleError> {
+        let vk = ubl_kms::verifying_key_from_did(&self.did)
+            .map_err(|e| ReceiptBundleError::DidKey(e.to_string()))?;
+        ubl_canon::verify_domain_v1(
+            &self.payload_value(),
+            ubl_canon::domains::RECEIPT_BUNDLE,
+            &vk,
+            &self.bundle_sig,
+        )
+        .map_err(|e| ReceiptBundleError::Signature(e.to_string()))
+    }
+
+    /// Reject bundles whose `chain` or receipt `stages` exceed `max`
+    /// entries, before any replay work is done.
+    pub fn check_chain_len(&self, max: usize) -> Result<(), ReceiptBundleError> {
+        let len = self.chain.len().max(
+            self.receipt
+                .get("stages")
+                .and_then(|v| v.as_array())
+                .map_or(0, Vec::len),
+        );
+        if len > max {
+            return Err(ReceiptBundleError::ChainTooLong { len, max });
+        }
+        Ok(())
+    }
+
+    /// Run every check: signature, receipt auth chain, chain/receipt
+    /// consistency and chip CID.
+    pub fn verify(&self) -> BundleVerification {
+        let signature_valid = self.verify_signature().unwrap_or(false);
+        let auth_chain_valid = UnifiedReceipt::from_json(&self.receipt)
+            .map(|r| r.receipt_cid.as_str() == self.receipt_cid && r.verify_auth_chain())
+            .unwrap_or(false);
+        let chain_matches_receipt = self.chain == stage_chain(&self.receipt);
+        let chip_cid_valid = match (&self.chip, &self.chip_cid) {
+            (Some(body), Some(cid)) => chip_cid_of(body).is_ok_and(|c| &c == cid),
+            (None, None) => true,
+            _ => false,
+        };
+        BundleVerification {
+            signature_valid,
+            auth_chain_valid,
+            chain_matches_receipt,
+            chip_cid_valid,
+        }
+    }
+
+    fn payload_value(&self) -> Value {
+        json!({
+            "@type": self.bundle_type,
+            "@ver": self.ver,
+            "issued_at": self.issued_at,
+            "did": self.did,
+            "kid": self.kid,
+            "receipt_cid": self.receipt_cid,
+            "chip_cid": self.chip_cid,
+            "chip": self.chip,
+            "receipt": self.receipt,
+            "chain": self.chain,
+        })
+    }
+}
+
+fn stage_chain(receipt: &Value) -> Vec<Value> {
+    receipt
+        .get("stages")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn chip_cid_of(body: &Value) -> Result<String, ReceiptBundleError> {
+    let bytes = ubl_ai_nrf1::to_nrf1_bytes(body)
+        .map_err(|e| ReceiptBundleError::Invalid(format!("chip encoding failed: {}", e)))?;
+    ubl_ai_nrf1::compute_cid(&bytes)
+        .map_err(|e| ReceiptBundleError::Invalid(format!("chip cid failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receipt_fixture() -> Value {
+        json!({
+            "@type": "ubl/receipt",
+            "receipt_cid": "b3:receipt-fixture",
+            "stages": [{"stage": "WA", "input_cid": "b3:in", "auth_token": "t"}],
+        })
+    }
+
+    #[test]
+    fn bundle_sign_and_verify_signature_ok() {
+        let sk = ubl_kms::generate_signing_key();
+        let vk = ubl_kms::verifying_key(&sk);
+        let did = ubl_kms::did_from_verifying_key(&vk);
+        let kid = ubl_kms::kid_from_verifying_key(&vk);
+        let chip =
+            json!({"@type": "ubl/document", "@id": "d1", "@ver": "1.0", "@world": "a/x/t/y"});
+        let bundle = ReceiptBundle::issue(receipt_fixture(), Some(chip), &did, &kid, &sk).unwrap();
+        assert!(bundle.verify_signature().unwrap());
+        let checks = bundle.verify();
+        assert!(checks.signature_valid);
+        assert!(checks.chain_matches_receipt);
+        assert!(checks.chip_cid_valid);
+    }
+
+    #[test]
+    fn bundle_tampered_chip_fails_checks() {
+        let sk = ubl_kms::generate_signing_key();
+        let vk = ubl_kms::verifying_key(&sk);
+        let did = ubl_kms::did_from_verifying_key(&vk);
+        let kid = ubl_kms::kid_from_verifying_key(&vk);
+        let chip =
+            json!({"@type": "ubl/document", "@id": "d1", "@ver": "1.0", "@world": "a/x/t/y"});
+        let mut bundle =
+            ReceiptBundle::issue(receipt_fixture(), Some(chip), &did, &kid, &sk).unwrap();
+        bundle.chip.as_mut().unwrap()["@id"] = json!("d2");
+        let checks = bundle.verify();
+        assert!(!checks.signature_valid);
+        assert!(!checks.chip_cid_valid);
+        assert!(!checks.verified());
+    }
+}
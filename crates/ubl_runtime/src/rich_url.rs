@@ -438,6 +438,31 @@ pub fn build_public_receipt_link_v1(
     })
 }
 
+/// Decode a public receipt token. Accepts the full URL
+/// (`https://<origin>/<path>#ubl:v1:<token>`), the `ubl:v1:<token>` fragment
+/// or the bare token.
+pub fn parse_public_receipt_token_v1(input: &str) -> Result<PublicReceiptTokenV1, UrlError> {
+    let input = input.trim();
+    let fragment = input.rsplit_once('#').map_or(input, |(_, f)| f);
+    let token = fragment
+        .strip_prefix(PUBLIC_RECEIPT_MODEL_V1)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .unwrap_or(fragment);
+    if token.is_empty() {
+        return Err(UrlError::MissingParam("token".into()));
+    }
+    let bytes = base64url_decode(token)?;
+    let payload: PublicReceiptTokenV1 = serde_json::from_slice(&bytes)
+        .map_err(|e| UrlError::InvalidFormat(format!("token payload: {}", e)))?;
+    if payload.v != 1 {
+        return Err(UrlError::InvalidFormat(format!(
+            "unsupported token version {}",
+            payload.v
+        )));
+    }
+    Ok(payload)
+}
+
 /// Offline verification result.
 #[derive(Debug, Clone)]
 pub struct VerificationResult {
@@ -1108,6 +1133,35 @@ mod tests {
         assert!(link.url.starts_with("https://logline.world/r#ubl:v1:"));
     }
 
+    #[test]
+    fn public_receipt_token_v1_parses_from_url_fragment_or_bare_token() {
+        let payload = PublicReceiptTokenV1 {
+            v: 1,
+            r: "b3:r".into(),
+            c: "b3:c".into(),
+            g: "genesis".into(),
+            k: "did:key:z#ed25519".into(),
+            alg: "ed25519".into(),
+            sig: "ed25519:abc".into(),
+            did: Some("did:key:z".into()),
+            rc: None,
+            bh: None,
+        };
+        let link = build_public_receipt_link_v1("https://logline.world", "/r", &payload).unwrap();
+        for input in [
+            link.url.clone(),
+            format!("{}:{}", PUBLIC_RECEIPT_MODEL_V1, link.token),
+            link.token.clone(),
+        ] {
+            assert_eq!(parse_public_receipt_token_v1(&input).unwrap(), payload);
+        }
+        assert!(parse_public_receipt_token_v1("https://logline.world/r#").is_err());
+        assert!(matches!(
+            parse_public_receipt_token_v1("not*base64"),
+            Err(UrlError::Encoding(_))
+        ));
+    }
+
     #[test]
     fn public_receipt_link_v1_rejects_bad_origin() {
         let payload = PublicReceiptTokenV1 {
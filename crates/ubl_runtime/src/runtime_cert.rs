@@ -3,7 +3,7 @@
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use ubl_kms::Ed25519SigningKey as SigningKey;
+use ubl_kms::{Ed25519SigningKey as SigningKey, Ed25519VerifyingKey as VerifyingKey};
 use ubl_receipt::RuntimeInfo;
 
 const SELF_ATTEST_DOMAIN_ENV: &str = "UBL_SIGN_DOMAIN_RUNTIME_ATTESTATION";
@@ -62,9 +62,17 @@ impl SelfAttestation {
         }
         let vk = ubl_kms::verifying_key_from_did(&self.did)
             .map_err(|e| RuntimeCertError::DidKey(e.to_string()))?;
+        self.verify_with_key(&vk)
+    }
+
+    /// [`Self::verify`] against an explicit key resolved from `kid`.
+    pub fn verify_with_key(&self, vk: &VerifyingKey) -> Result<bool, RuntimeCertError> {
+        if self.runtime_hash != self.runtime.runtime_hash() {
+            return Ok(false);
+        }
         let payload = self.payload_value();
         let domain = domain_from_env();
-        ubl_canon::verify_domain_v1(&payload, &domain, &vk, &self.sig)
+        ubl_canon::verify_domain_v1(&payload, &domain, vk, &self.sig)
             .map_err(|e| RuntimeCertError::Signature(e.to_string()))
     }
 
@@ -0,0 +1,191 @@
+//! `ubl/token.revoke` — who may kill a bearer token.
+//!
+//! A revoke names a `token_id`. It takes effect only when its `@world`
+//! covers the token's world and its verified author is an operator (admin
+//! key) or the token's owner: the token itself, or the owning user's DID
+//! proven by a chip signature or another of the user's tokens. CHECK denies
+//! revokes that fail this, and the gate's revocation list ignores stored
+//! ones that would.
+
+use serde_json::Value;
+use ubl_chipstore::{ChipAuthor, ChipQuery, ChipStore, ChipStoreError};
+
+pub const TYPE_TOKEN_REVOKE: &str = "ubl/token.revoke";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TokenRevokeError {
+    #[error("ubl/token.revoke token_id must be a non-empty string")]
+    MissingTokenId,
+    #[error("ubl/token.revoke world '{world}' does not cover token world '{token_world}'")]
+    WorldMismatch { world: String, token_world: String },
+    #[error("ubl/token.revoke requires an admin key or the token's owner")]
+    NotOwner,
+}
+
+/// The token a revoke names, as resolved from the chip store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevokeTarget {
+    pub token_cid: String,
+    pub world: String,
+    /// `did` of the token's `user_cid` chip, when it has one.
+    pub owner_did: Option<String>,
+}
+
+/// `token_id` of a revoke body.
+pub fn token_id(body: &Value) -> Result<&str, TokenRevokeError> {
+    body.get("token_id")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .ok_or(TokenRevokeError::MissingTokenId)
+}
+
+/// Look up the `ubl/token` chip whose `@id` is `token_id`.
+pub async fn resolve_target(
+    store: &ChipStore,
+    token_id: &str,
+) -> Result<Option<RevokeTarget>, ChipStoreError> {
+    let tokens = store
+        .query(&ChipQuery {
+            chip_type: Some("ubl/token".to_string()),
+            tags: vec![format!("id:{}", token_id)],
+            created_after: None,
+            created_before: None,
+            executor_did: None,
+            limit: Some(10),
+            offset: None,
+        })
+        .await?;
+    let Some(token) = tokens
+        .chips
+        .into_iter()
+        .find(|chip| chip.chip_data.get("@id").and_then(|v| v.as_str()) == Some(token_id))
+    else {
+        return Ok(None);
+    };
+    let owner_did = match token.chip_data.get("user_cid").and_then(|v| v.as_str()) {
+        Some(user_cid) => store.get_chip(user_cid).await?.and_then(|user| {
+            user.chip_data
+                .get("did")
+                .and_then(|v| v.as_str())
+                .filter(|d| d.starts_with("did:"))
+                .map(str::to_string)
+        }),
+        None => None,
+    };
+    Ok(Some(RevokeTarget {
+        token_cid: token.cid.as_str().to_string(),
+        world: token
+            .chip_data
+            .get("@world")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        owner_did,
+    }))
+}
+
+/// Whether a revoke in `world`, written by `author`, may revoke `target`.
+pub fn authorize(
+    world: &str,
+    author: Option<&ChipAuthor>,
+    target: &RevokeTarget,
+) -> Result<(), TokenRevokeError> {
+    let covers = target.world == world
+        || target
+            .world
+            .strip_prefix(world)
+            .is_some_and(|rest| rest.starts_with('/'));
+    if !covers {
+        return Err(TokenRevokeError::WorldMismatch {
+            world: world.to_string(),
+            token_world: target.world.clone(),
+        });
+    }
+    let is_owner = |did: Option<&String>| did.is_some() && did == target.owner_did.as_ref();
+    match author {
+        Some(ChipAuthor::Admin) => Ok(()),
+        Some(ChipAuthor::Token { token_cid, .. }) if *token_cid == target.token_cid => Ok(()),
+        Some(ChipAuthor::Token { did, .. }) if is_owner(did.as_ref()) => Ok(()),
+        Some(ChipAuthor::Signer { did }) if is_owner(Some(did)) => Ok(()),
+        _ => Err(TokenRevokeError::NotOwner),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn target() -> RevokeTarget {
+        RevokeTarget {
+            token_cid: "b3:tok".to_string(),
+            world: "a/acme/t/prod".to_string(),
+            owner_did: Some("did:key:zowner".to_string()),
+        }
+    }
+
+    #[test]
+    fn token_id_is_required() {
+        assert_eq!(token_id(&json!({"token_id": " tok-1 "})), Ok("tok-1"));
+        assert_eq!(
+            token_id(&json!({"token_id": ""})),
+            Err(TokenRevokeError::MissingTokenId)
+        );
+    }
+
+    #[test]
+    fn admin_self_and_owner_may_revoke_from_a_covering_world() {
+        let t = target();
+        assert!(authorize("a/acme", Some(&ChipAuthor::Admin), &t).is_ok());
+        let own_token = ChipAuthor::Token {
+            token_cid: "b3:tok".to_string(),
+            did: None,
+            world: "a/acme/t/prod".to_string(),
+        };
+        assert!(authorize("a/acme/t/prod", Some(&own_token), &t).is_ok());
+        let owner = ChipAuthor::Signer {
+            did: "did:key:zowner".to_string(),
+        };
+        assert!(authorize("a/acme/t/prod", Some(&owner), &t).is_ok());
+    }
+
+    #[test]
+    fn strangers_and_other_worlds_are_refused() {
+        let t = target();
+        assert!(matches!(
+            authorize("a/other", Some(&ChipAuthor::Admin), &t),
+            Err(TokenRevokeError::WorldMismatch { .. })
+        ));
+        assert!(matches!(
+            authorize("a/acmex", Some(&ChipAuthor::Admin), &t),
+            Err(TokenRevokeError::WorldMismatch { .. })
+        ));
+        let stranger = ChipAuthor::Token {
+            token_cid: "b3:other".to_string(),
+            did: Some("did:key:zstranger".to_string()),
+            world: "a/acme/t/prod".to_string(),
+        };
+        assert_eq!(
+            authorize("a/acme/t/prod", Some(&stranger), &t),
+            Err(TokenRevokeError::NotOwner)
+        );
+        assert_eq!(
+            authorize("a/acme/t/prod", None, &t),
+            Err(TokenRevokeError::NotOwner)
+        );
+        let ownerless = RevokeTarget {
+            owner_did: None,
+            ..target()
+        };
+        let no_did = ChipAuthor::Token {
+            token_cid: "b3:other".to_string(),
+            did: None,
+            world: "a/acme/t/prod".to_string(),
+        };
+        assert_eq!(
+            authorize("a/acme/t/prod", Some(&no_did), &ownerless),
+            Err(TokenRevokeError::NotOwner)
+        );
+    }
+}
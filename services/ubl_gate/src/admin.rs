@@ -0,0 +1,421 @@
+//! Operator-only endpoints guarded by `UBL_ADMIN_API_KEYS`.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::state::{AppState, WriteAccessPolicy};
+use crate::utils::write_access_error;
+use ubl_runtime::pipeline::QuarantineRelease;
+
+pub(crate) fn require_admin(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    state
+        .admin_access
+        .authorize(headers)
+        .map_err(|(code, msg)| {
+            let err = write_access_error(code, msg, json!({"scope": "admin"}));
+            (
+                StatusCode::from_u16(code.http_status()).unwrap_or(StatusCode::UNAUTHORIZED),
+                Json(err.to_json()),
+            )
+        })
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AdminEvaluateRequest {
+    pub(crate) chip: Value,
+    pub(crate) policy_set_hash: String,
+}
+
+/// POST /v1/admin/evaluate — dry-run CHECK for `chip` against the retained
+/// policy snapshot `policy_set_hash` (time-travel debugging). Nothing is stored.
+pub(crate) async fn admin_evaluate(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<AdminEvaluateRequest>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+
+    match state
+        .pipeline
+        .evaluate_against_policy_snapshot(&req.chip, &req.policy_set_hash)
+    {
+        Ok(Some(dry_run)) => (
+            StatusCode::OK,
+            Json(json!({
+                "@type": "ubl/admin.evaluation",
+                "dry_run": true,
+                "policy_set_hash": dry_run.policy_set_hash,
+                "policy_count": dry_run.policy_count,
+                "decision": dry_run.decision,
+                "reason": dry_run.reason,
+                "trace": dry_run.trace,
+            })),
+        ),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "NOT_FOUND",
+                "message": format!("policy snapshot {} is not retained", req.policy_set_hash),
+            })),
+        ),
+        Err(e) => {
+            let ubl_err = ubl_runtime::error_response::UblError::from_pipeline_error(&e);
+            (
+                StatusCode::from_u16(ubl_err.code.http_status()).unwrap_or(StatusCode::BAD_REQUEST),
+                Json(ubl_err.to_json()),
+            )
+        }
+    }
+}
+
+/// Audit chip type recorded on every public write-lane change.
+pub(crate) const WRITE_LANES_AUDIT_CHIP_TYPE: &str = "ubl/audit.write_lanes.update";
+
+fn write_lanes_view(policy: &WriteAccessPolicy) -> Value {
+    json!({
+        "auth_required": policy.auth_required,
+        "api_keys_configured": !policy.api_keys.is_empty(),
+        "public_worlds": policy.public_worlds,
+        "public_types": policy.public_types,
+    })
+}
+
+/// GET /v1/admin/write-lanes — current public onboarding lanes.
+pub(crate) async fn admin_get_write_lanes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    let policy = state.write_access_policy.load();
+    let mut view = write_lanes_view(&policy);
+    view["@type"] = json!("ubl/admin.write_lanes");
+    (StatusCode::OK, Json(view))
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct WriteLanesUpdate {
+    pub(crate) public_worlds: Option<Vec<String>>,
+    pub(crate) public_types: Option<Vec<String>>,
+}
+
+/// PUT /v1/admin/write-lanes — replace `public_worlds` and/or `public_types`
+/// atomically. Omitted fields keep their current value. The change is
+/// persisted as a `ubl/audit.write_lanes.update` chip.
+pub(crate) async fn admin_update_write_lanes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(update): Json<WriteLanesUpdate>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    if update.public_worlds.is_none() && update.public_types.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "INVALID_CHIP",
+                "message": "provide public_worlds and/or public_types",
+            })),
+        );
+    }
+    let clean = |values: Vec<String>| -> Vec<String> {
+        values
+            .into_iter()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect()
+    };
+
+    let _update = state.write_access_policy.begin_update().await;
+    let previous = state.write_access_policy.load();
+    let mut next = (*previous).clone();
+    if let Some(worlds) = update.public_worlds {
+        next.public_worlds = clean(worlds);
+    }
+    if let Some(types) = update.public_types {
+        next.public_types = clean(types);
+    }
+
+    let updated_at = chrono::Utc::now().to_rfc3339();
+    let audit_body = json!({
+        "@type": WRITE_LANES_AUDIT_CHIP_TYPE,
+        "@id": format!("write-lanes:{}", chrono::Utc::now().timestamp_micros()),
+        "@ver": "1.0.0",
+        "@world": "a/system/t/gate",
+        "updated_at": updated_at,
+        "previous": {
+            "public_worlds": previous.public_worlds,
+            "public_types": previous.public_types,
+        },
+        "current": {
+            "public_worlds": next.public_worlds,
+            "public_types": next.public_types,
+        },
+    });
+    let metadata = ubl_chipstore::ExecutionMetadata {
+        runtime_version: "admin/write-lanes".to_string(),
+        execution_time_ms: 0,
+        fuel_consumed: 0,
+        policies_applied: vec![],
+        executor_did: ubl_types::Did::new_unchecked(state.pipeline.did.as_str()),
+        reproducible: true,
+    };
+    let audit_cid = match state
+        .chip_store
+        .store_executed_chip(audit_body, "self".to_string(), metadata)
+        .await
+    {
+        Ok(cid) => cid,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("write-lanes audit chip store failed: {}", e),
+                })),
+            )
+        }
+    };
+
+    state.write_access_policy.store(next.clone());
+
+    let mut view = write_lanes_view(&next);
+    view["@type"] = json!("ubl/admin.write_lanes");
+    view["audit_cid"] = json!(audit_cid);
+    view["updated_at"] = json!(updated_at);
+    (StatusCode::OK, Json(view))
+}
+
+/// Audit chip type recorded for every index rebuild.
+pub(crate) const REINDEX_AUDIT_CHIP_TYPE: &str = "ubl/audit.reindex";
+
+/// POST /v1/admin/reindex — rebuild the chip store's indexes from primary
+/// storage while it keeps serving (fresh indexes are swapped in), and record
+/// the run as a `ubl/audit.reindex` chip.
+pub(crate) async fn admin_reindex(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    let started = std::time::Instant::now();
+    let report = match state.chip_store.reindex().await {
+        Ok(report) => report,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("reindex failed: {}", e),
+                })),
+            )
+        }
+    };
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let finished_at = chrono::Utc::now().to_rfc3339();
+    let audit_body = json!({
+        "@type": REINDEX_AUDIT_CHIP_TYPE,
+        "@id": format!("reindex:{}", chrono::Utc::now().timestamp_micros()),
+        "@ver": "1.0.0",
+        "@world": "a/system/t/gate",
+        "finished_at": finished_at,
+        "duration_ms": duration_ms,
+        "report": report,
+    });
+    let metadata = ubl_chipstore::ExecutionMetadata {
+        runtime_version: "admin/reindex".to_string(),
+        execution_time_ms: duration_ms as i64,
+        fuel_consumed: 0,
+        policies_applied: vec![],
+        executor_did: ubl_types::Did::new_unchecked(state.pipeline.did.as_str()),
+        reproducible: false,
+    };
+    let audit_cid = match state
+        .chip_store
+        .store_executed_chip(audit_body, "self".to_string(), metadata)
+        .await
+    {
+        Ok(cid) => cid,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("reindex audit chip store failed: {}", e),
+                })),
+            )
+        }
+    };
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/admin.reindex",
+            "chips": report.chips,
+            "indexes": {
+                "type": report.type_index,
+                "tag": report.tag_index,
+                "executor": report.executor_index,
+                "children": report.children_index,
+            },
+            "duration_ms": duration_ms,
+            "finished_at": finished_at,
+            "audit_cid": audit_cid,
+        })),
+    )
+}
+
+/// POST /v1/admin/policies/reload — re-read policy chips from the chip store
+/// and swap them in as the CHECK policy set. In-flight chips finish on the set
+/// they started with; policy chips that fail to parse are left out and listed
+/// under `skipped`.
+pub(crate) async fn admin_reload_policies(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    match state.pipeline.reload_policies().await {
+        Ok(reload) => (
+            StatusCode::OK,
+            Json(json!({
+                "@type": "ubl/admin.policies.reload",
+                "policy_count": reload.policy_count,
+                "policy_set_hash": reload.policy_set_hash,
+                "chip_count": reload.chip_count,
+                "skipped": reload.skipped,
+            })),
+        ),
+        Err(e) => {
+            let ubl_err = ubl_runtime::error_response::UblError::from_pipeline_error(&e);
+            (
+                StatusCode::from_u16(ubl_err.code.http_status())
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ubl_err.to_json()),
+            )
+        }
+    }
+}
+
+/// GET /v1/selftest — push a canned probe chip through KNOCK→WA→CHECK→TR in
+/// dry-run mode and confirm the signed receipt's CID recomputes. Nothing is
+/// stored or published; 503 when any stage fails.
+pub(crate) async fn admin_selftest(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    let report = state.pipeline.self_test().await;
+    let status = if report.passed {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(json!({
+            "@type": "ubl/selftest",
+            "dry_run": true,
+            "passed": report.passed,
+            "receipt_cid": report.receipt_cid,
+            "cid_verified": report.cid_verified,
+            "stages": report.stages,
+        })),
+    )
+}
+
+/// POST /v1/admin/quarantine/:cid/release — approve a quarantined chip: clear
+/// its flag and emit the receipt event/outbox row WF held back. Repeating a
+/// release answers `replayed: true` and emits nothing.
+pub(crate) async fn admin_release_quarantine(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(cid): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    if !cid.starts_with("b3:") {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "INVALID_CID",
+                "message": "CID must start with b3:",
+            })),
+        );
+    }
+    match state.pipeline.release_quarantine(&cid).await {
+        Ok(QuarantineRelease::Released {
+            chip_cid,
+            receipt_cid,
+        }) => (
+            StatusCode::OK,
+            Json(json!({
+                "@type": "ubl/admin.quarantine.release",
+                "chip_cid": chip_cid,
+                "receipt_cid": receipt_cid,
+                "released": true,
+            })),
+        ),
+        Ok(QuarantineRelease::AlreadyReleased {
+            chip_cid,
+            receipt_cid,
+        }) => (
+            StatusCode::OK,
+            Json(json!({
+                "@type": "ubl/admin.quarantine.release",
+                "chip_cid": chip_cid,
+                "receipt_cid": receipt_cid,
+                "released": true,
+                "replayed": true,
+            })),
+        ),
+        Ok(QuarantineRelease::NotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "NOT_FOUND",
+                "message": format!("Chip {} not found", cid),
+            })),
+        ),
+        Ok(QuarantineRelease::NotQuarantined) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "NOT_QUARANTINED",
+                "message": format!("Chip {} is not quarantined", cid),
+            })),
+        ),
+        Err(e) => {
+            let ubl_err = ubl_runtime::error_response::UblError::from_pipeline_error(&e);
+            (
+                StatusCode::from_u16(ubl_err.code.http_status())
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ubl_err.to_json()),
+            )
+        }
+    }
+}
@@ -2,24 +2,34 @@
 
 use async_stream::stream;
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::{sse::{Event as SseEvent, KeepAlive, Sse}, IntoResponse, Response},
     Json,
 };
 use serde_json::{json, Value};
 use std::convert::Infallible;
 use std::time::Duration;
+use tracing::warn;
+use ubl_chipstore::ChipStore;
 use ubl_eventstore::{EventQuery, EventStore};
+use ubl_runtime::advisory::AdvisoryEngine;
+use ubl_runtime::event_bus::ReceiptEvent;
 
-use crate::events::AdvisorQuery;
+use crate::events::{normalize_stage, AdvisorQuery};
 use crate::metrics;
 use crate::state::AppState;
-use crate::utils::parse_window_duration;
+use crate::utils::{authorize_write_headers, parse_window_duration};
 
+pub(crate) const ADVISOR_SNAPSHOT_CHIP_TYPE: &str = "ubl/advisor.snapshot";
+
+/// GET /v1/advisor/snapshots — aggregates over a window of hub events. With
+/// `?persist=true` the snapshot is also stored as a signed
+/// `ubl/advisor.snapshot` chip and its CID returned as `snapshot_cid`.
 pub(crate) async fn advisor_snapshots(
     State(state): State<AppState>,
     Query(query): Query<AdvisorQuery>,
+    headers: HeaderMap,
 ) -> Response {
     let Some(store) = state.event_store.as_ref() else {
         return (
@@ -35,25 +45,176 @@ pub(crate) async fn advisor_snapshots(
 
     let window = parse_window_duration(query.window.as_deref()).unwrap_or(Duration::from_secs(300));
     let limit = query.limit.unwrap_or(10_000).clamp(100, 50_000);
-    match build_advisor_snapshot(&state, store, query.world.as_deref(), window, limit) {
-        Ok(frame) => (
-            StatusCode::OK,
-            Json(json!({
-                "@type": "ubl/advisor.snapshot",
-                "window_ms": window.as_millis() as u64,
-                "snapshot": frame,
-            })),
+    let frame = match build_advisor_snapshot(&state, store, query.world.as_deref(), window, limit) {
+        Ok(frame) => frame,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("advisor snapshot failed: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let mut snapshot_cid = None;
+    if query.persist.unwrap_or(false) {
+        match persist_advisor_snapshot(&state, &headers, query.world.as_deref(), limit, &frame)
+            .await
+        {
+            Ok(cid) => snapshot_cid = Some(cid),
+            Err(resp) => return resp,
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/advisor.snapshot",
+            "window_ms": window.as_millis() as u64,
+            "snapshot": frame,
+            "snapshot_cid": snapshot_cid,
+        })),
+    )
+        .into_response()
+}
+
+/// Store `frame` as a signed `ubl/advisor.snapshot` chip. Unscoped snapshots
+/// are filed under `a/system`. The body keeps the window, world filter,
+/// event limit and counts, so the CID pins exactly what was observed. NRF-1
+/// has no floats, so p95 latencies are stored as whole microseconds.
+async fn persist_advisor_snapshot(
+    state: &AppState,
+    headers: &HeaderMap,
+    world: Option<&str>,
+    limit: usize,
+    frame: &Value,
+) -> Result<String, Response> {
+    let chip_world = world.unwrap_or("a/system");
+    if let Err(ubl_err) =
+        authorize_write_headers(state, headers, ADVISOR_SNAPSHOT_CHIP_TYPE, chip_world).await
+    {
+        return Err((
+            StatusCode::from_u16(ubl_err.code.http_status()).unwrap_or(StatusCode::FORBIDDEN),
+            Json(ubl_err.to_json()),
         )
-            .into_response(),
-        Err(e) => (
+            .into_response());
+    }
+    let internal = |message: String| {
+        (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({
                 "@type": "ubl/error",
                 "code": "INTERNAL_ERROR",
-                "message": format!("advisor snapshot failed: {}", e),
+                "message": message,
             })),
         )
-            .into_response(),
+            .into_response()
+    };
+
+    let generated_at = frame.get("generated_at").cloned().unwrap_or(Value::Null);
+    let p95_us: serde_json::Map<String, Value> = frame
+        .get("latency_ms_p95_by_stage")
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flatten()
+        .filter_map(|(stage, ms)| {
+            let us = (ms.as_f64()? * 1000.0).round() as i64;
+            Some((stage.clone(), json!(us)))
+        })
+        .collect();
+    let mut body = json!({
+        "@type": ADVISOR_SNAPSHOT_CHIP_TYPE,
+        "@id": format!(
+            "advisor-snapshot:{}:{}",
+            world.unwrap_or("*"),
+            generated_at.as_str().unwrap_or_default()
+        ),
+        "@ver": "1.0.0",
+        "@world": chip_world,
+        "world_filter": world.unwrap_or("*"),
+        "generated_at": generated_at,
+        "window_ms": frame.get("window_ms").cloned().unwrap_or(Value::Null),
+        "event_limit": limit,
+        "counts": frame.get("counts").cloned().unwrap_or(Value::Null),
+        "latency_us_p95_by_stage": p95_us,
+        "outbox": frame.get("outbox").cloned().unwrap_or(Value::Null),
+    });
+    state
+        .pipeline
+        .sign_artifact(&mut body)
+        .map_err(|e| internal(format!("advisor snapshot signing failed: {}", e)))?;
+    let metadata = ubl_chipstore::ExecutionMetadata {
+        runtime_version: "advisor/snapshot".to_string(),
+        execution_time_ms: 0,
+        fuel_consumed: 0,
+        policies_applied: vec![],
+        executor_did: ubl_types::Did::new_unchecked(state.pipeline.did.clone()),
+        reproducible: false,
+    };
+    state
+        .chip_store
+        .store_executed_chip(body, "self".to_string(), metadata)
+        .await
+        .map_err(|e| internal(format!("advisor snapshot persist failed: {}", e)))
+}
+
+/// Why a WF deny was denied: the receipt's `code` when it carries one,
+/// else the first policy in its trace that voted `Deny`.
+fn wf_deny_code(event: &ReceiptEvent) -> Option<String> {
+    if let Some(code) = event.metadata.get("code").and_then(|v| v.as_str()) {
+        return Some(code.to_string());
+    }
+    event
+        .metadata
+        .get("policy_trace")
+        .and_then(|v| v.as_array())?
+        .iter()
+        .find(|entry| entry.get("result").and_then(|v| v.as_str()) == Some("Deny"))
+        .and_then(|entry| entry.get("policy_id").and_then(|v| v.as_str()))
+        .map(ToString::to_string)
+}
+
+/// Feed a bus event to the engine's deny-spike window; on a spike, store
+/// the `ubl/advisory` chip and return its CID. Only WF events with a
+/// decision count as receipts.
+pub(crate) async fn observe_deny_spike(
+    engine: &AdvisoryEngine,
+    chip_store: &ChipStore,
+    event: &ReceiptEvent,
+) -> Option<String> {
+    if normalize_stage(&event.pipeline_stage) != "WF" {
+        return None;
+    }
+    let decision = event.decision.as_deref()?;
+    let code = wf_deny_code(event);
+    let advisory = engine.observe_receipt(&event.receipt_cid, decision, code.as_deref())?;
+    warn!(
+        observed_rate_bps = %advisory.output["observed_rate_bps"],
+        baseline_rate_bps = %advisory.output["baseline_rate_bps"],
+        "deny spike detected"
+    );
+    let body = engine.advisory_to_chip_body(&advisory);
+    let metadata = ubl_chipstore::ExecutionMetadata {
+        runtime_version: "advisory/deny-spike".to_string(),
+        execution_time_ms: 0,
+        fuel_consumed: 0,
+        policies_applied: vec![],
+        executor_did: ubl_types::Did::new_unchecked("did:key:advisory"),
+        reproducible: false,
+    };
+    match chip_store
+        .store_executed_chip(body, "self".to_string(), metadata)
+        .await
+    {
+        Ok(cid) => Some(cid),
+        Err(e) => {
+            warn!(error = %e, "deny spike advisory store failed (non-fatal)");
+            None
+        }
     }
 }
 
@@ -118,18 +279,7 @@ pub(crate) fn build_advisor_snapshot(
     window: Duration,
     limit: usize,
 ) -> Result<Value, String> {
-    let now = chrono::Utc::now();
-    let since = now
-        .checked_sub_signed(chrono::Duration::from_std(window).map_err(|e| e.to_string())?)
-        .ok_or_else(|| "window underflow".to_string())?;
-
-    let query = EventQuery {
-        world: world.map(ToString::to_string),
-        since: Some(since.timestamp_millis().to_string()),
-        limit: Some(limit),
-        ..Default::default()
-    };
-    let events = store.query(&query).map_err(|e| e.to_string())?;
+    let (now, events) = window_events(store, world, window, limit)?;
 
     let mut by_stage = std::collections::BTreeMap::<String, u64>::new();
     let mut by_decision = std::collections::BTreeMap::<String, u64>::new();
@@ -179,10 +329,10 @@ pub(crate) fn build_advisor_snapshot(
     }
 
     let mut p95_by_stage = serde_json::Map::new();
-    for (stage, mut vals) in lat_stage {
-        vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let idx = ((vals.len() - 1) as f64 * 0.95).round() as usize;
-        p95_by_stage.insert(stage, json!(vals[idx]));
+    for (stage, vals) in lat_stage {
+        if let Some(p) = p95(vals) {
+            p95_by_stage.insert(stage, json!(p));
+        }
     }
 
     outliers.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
@@ -231,3 +381,148 @@ pub(crate) fn build_advisor_snapshot(
         },
     }))
 }
+
+/// Events in `[now - window, now]`, optionally scoped to one world.
+fn window_events(
+    store: &EventStore,
+    world: Option<&str>,
+    window: Duration,
+    limit: usize,
+) -> Result<(chrono::DateTime<chrono::Utc>, Vec<Value>), String> {
+    let now = chrono::Utc::now();
+    let since = now
+        .checked_sub_signed(chrono::Duration::from_std(window).map_err(|e| e.to_string())?)
+        .ok_or_else(|| "window underflow".to_string())?;
+
+    let query = EventQuery {
+        world: world.map(ToString::to_string),
+        since: Some(since.timestamp_millis().to_string()),
+        limit: Some(limit),
+        ..Default::default()
+    };
+    let events = store.query(&query).map_err(|e| e.to_string())?;
+    Ok((now, events))
+}
+
+fn p95(mut vals: Vec<f64>) -> Option<f64> {
+    if vals.is_empty() {
+        return None;
+    }
+    vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let idx = ((vals.len() - 1) as f64 * 0.95).round() as usize;
+    Some(vals[idx])
+}
+
+/// `GET /v1/worlds/:world/metrics` — per-world rollup over the advisor
+/// window. `:world` is percent-encoded (`a%2Facme%2Ft%2Fprod`).
+pub(crate) async fn world_metrics(
+    State(state): State<AppState>,
+    Path(world): Path<String>,
+    Query(query): Query<AdvisorQuery>,
+) -> Response {
+    if ubl_ai_nrf1::UblEnvelope::validate_world(&world).is_err() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "INVALID_WORLD",
+                "message": format!("'{}' is not a valid @world (a/<app>[/t/<tenant>])", world),
+            })),
+        )
+            .into_response();
+    }
+    let Some(store) = state.event_store.as_ref() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "UNAVAILABLE",
+                "message": "World metrics unavailable: enable EventStore",
+            })),
+        )
+            .into_response();
+    };
+
+    let window = parse_window_duration(query.window.as_deref()).unwrap_or(Duration::from_secs(300));
+    let limit = query.limit.unwrap_or(10_000).clamp(100, 50_000);
+    match build_world_metrics(&state, store, &world, window, limit) {
+        Ok(Some(frame)) => (StatusCode::OK, Json(frame)).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "NOT_FOUND",
+                "message": format!("no activity for world '{}' in the last {}ms", world, window.as_millis()),
+            })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "INTERNAL_ERROR",
+                "message": format!("world metrics failed: {}", e),
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// Decision counts, end-to-end p95, pending outbox and last activity for
+/// one world. `None` when the world has no events in the window.
+fn build_world_metrics(
+    state: &AppState,
+    store: &EventStore,
+    world: &str,
+    window: Duration,
+    limit: usize,
+) -> Result<Option<Value>, String> {
+    let (now, events) = window_events(store, Some(world), window, limit)?;
+    if events.is_empty() {
+        return Ok(None);
+    }
+
+    let mut decisions = std::collections::BTreeMap::<String, u64>::new();
+    let mut latencies = Vec::new();
+    let mut last_activity: Option<&str> = None;
+    for event in &events {
+        if let Some(decision) = event
+            .get("receipt")
+            .and_then(|v| v.get("decision"))
+            .and_then(|v| v.as_str())
+        {
+            *decisions.entry(decision.to_ascii_lowercase()).or_default() += 1;
+        }
+        if let Some(lat) = event
+            .get("perf")
+            .and_then(|v| v.get("latency_ms"))
+            .and_then(|v| v.as_f64())
+        {
+            latencies.push(lat);
+        }
+        if let Some(when) = event.get("when").and_then(|v| v.as_str()) {
+            if last_activity.is_none_or(|prev| when > prev) {
+                last_activity = Some(when);
+            }
+        }
+    }
+
+    let outbox_pending = state
+        .durable_store
+        .as_ref()
+        .and_then(|store| store.outbox_pending_for_world(world).ok());
+
+    Ok(Some(json!({
+        "@type": "ubl/world.metrics",
+        "@world": world,
+        "generated_at": now.to_rfc3339(),
+        "window_ms": window.as_millis() as u64,
+        "events": events.len(),
+        "allow": decisions.get("allow").copied().unwrap_or(0),
+        "deny": decisions.get("deny").copied().unwrap_or(0),
+        "decisions": decisions,
+        "latency_ms_p95": p95(latencies),
+        "outbox_pending": outbox_pending,
+        "last_activity_at": last_activity,
+    })))
+}
@@ -64,6 +64,26 @@ pub(crate) async fn audit_table_partial(
 pub(crate) struct AuditListQuery {
     pub(crate) world: Option<String>,
     pub(crate) limit: Option<usize>,
+    pub(crate) offset: Option<usize>,
+    /// Only rows created strictly after this RFC-3339 timestamp.
+    pub(crate) after: Option<String>,
+    /// Only rows created strictly before this RFC-3339 timestamp.
+    pub(crate) before: Option<String>,
+}
+
+/// Page window over audit rows (newest first).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AuditWindow {
+    pub(crate) limit: usize,
+    pub(crate) offset: usize,
+    pub(crate) after: Option<String>,
+    pub(crate) before: Option<String>,
+}
+
+pub(crate) struct AuditRowsPage {
+    pub(crate) rows: Vec<AuditRow>,
+    pub(crate) total: usize,
+    pub(crate) has_more: bool,
 }
 
 pub(crate) async fn list_audit_reports(
@@ -87,6 +107,15 @@ pub(crate) async fn list_audit_compactions(
     list_audit_kind_json(state, "compactions", query).await
 }
 
+fn parse_window_bound(name: &str, raw: Option<&str>) -> Result<Option<String>, String> {
+    match raw.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(v) => chrono::DateTime::parse_from_rfc3339(v)
+            .map(|ts| Some(ts.with_timezone(&chrono::Utc).to_rfc3339()))
+            .map_err(|_| format!("{} must be an RFC-3339 timestamp", name)),
+    }
+}
+
 async fn list_audit_kind_json(
     state: AppState,
     kind: &str,
@@ -96,15 +125,36 @@ async fn list_audit_kind_json(
         .world
         .as_deref()
         .filter(|w| !w.trim().is_empty() && *w != "*");
-    let limit = query.limit.unwrap_or(100).clamp(1, 500);
-    match query_audit_rows(&state, kind, world, limit).await {
-        Ok(rows) => (
+    let bounds = parse_window_bound("after", query.after.as_deref()).and_then(|after| {
+        parse_window_bound("before", query.before.as_deref()).map(|before| (after, before))
+    });
+    let (after, before) = match bounds {
+        Ok(bounds) => bounds,
+        Err(message) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"@type": "ubl/error", "code": "INVALID_QUERY", "message": message})),
+            );
+        }
+    };
+    let window = AuditWindow {
+        limit: query.limit.unwrap_or(100).clamp(1, 500),
+        offset: query.offset.unwrap_or(0),
+        after,
+        before,
+    };
+    match query_audit_page(&state, kind, world, &window).await {
+        Ok(page) => (
             StatusCode::OK,
             Json(json!({
                 "@type": "ubl/audit.list",
                 "kind": normalize_audit_kind(kind),
-                "count": rows.len(),
-                "rows": rows.iter().map(|r| json!({
+                "count": page.rows.len(),
+                "total": page.total,
+                "limit": window.limit,
+                "offset": window.offset,
+                "has_more": page.has_more,
+                "rows": page.rows.iter().map(|r| json!({
                     "cid": r.cid,
                     "chip_type": r.chip_type,
                     "world": r.world,
@@ -148,6 +198,21 @@ pub(crate) async fn query_audit_rows(
     world: Option<&str>,
     limit: usize,
 ) -> Result<Vec<AuditRow>, String> {
+    let window = AuditWindow {
+        limit,
+        ..AuditWindow::default()
+    };
+    query_audit_page(state, kind, world, &window)
+        .await
+        .map(|page| page.rows)
+}
+
+pub(crate) async fn query_audit_page(
+    state: &AppState,
+    kind: &str,
+    world: Option<&str>,
+    window: &AuditWindow,
+) -> Result<AuditRowsPage, String> {
     let chip_type = audit_chip_type_for_kind(kind);
     let mut tags = Vec::new();
     if let Some(world) = world {
@@ -158,11 +223,11 @@ pub(crate) async fn query_audit_rows(
         .query(&ubl_chipstore::ChipQuery {
             chip_type: Some(chip_type.to_string()),
             tags,
-            created_after: None,
-            created_before: None,
+            created_after: window.after.clone(),
+            created_before: window.before.clone(),
             executor_did: None,
-            limit: Some(limit),
-            offset: None,
+            limit: Some(window.limit),
+            offset: Some(window.offset),
         })
         .await
         .map_err(|e| e.to_string())?;
@@ -183,7 +248,11 @@ pub(crate) async fn query_audit_rows(
             summary: audit_summary(&chip.chip_data, kind),
         })
         .collect();
-    Ok(rows)
+    Ok(AuditRowsPage {
+        rows,
+        total: result.total_count,
+        has_more: result.has_more,
+    })
 }
 
 pub(crate) fn audit_summary(chip_data: &Value, kind: &str) -> String {
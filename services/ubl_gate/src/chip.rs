@@ -2,22 +2,36 @@
 
 use axum::{
     body::Bytes,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     Json,
 };
+use serde::Deserialize;
 use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashSet, VecDeque};
 
+use crate::client_ip::client_ip_from_headers;
 use crate::metrics;
-use crate::state::AppState;
+use crate::outbox::{
+    await_emit_receipt_delivery, DEFAULT_AWAIT_DELIVERY_MS, MAX_AWAIT_DELIVERY_MS,
+};
+use crate::registry::type_deprecation;
+use crate::registry_cache::is_registry_chip_type;
+use crate::revocation_cache::TOKEN_REVOKE_CHIP_TYPE;
+use crate::state::{AppState, DenialRedaction};
 use crate::utils::{
-    actor_hint_from_headers, build_public_receipt_link, deny_write_with_receipt,
-    knock_reason_code, parse_bearer_token, resolve_session_bearer, scope_allows_any, too_many_requests_error, verify_receipt_auth_chain,
-    world_scope_allows,
+    actor_hint_from_headers, build_public_receipt_link, canonical_binary_response,
+    deny_write_with_receipt, knock_reason_code, not_acceptable_response, parse_bearer_token,
+    read_encoding, resolve_session_bearer, scope_allows_any, scope_allows_write_type,
+    too_many_requests_error, unavailable_error, verify_receipt_auth_chain, world_scope_allows,
+    write_scope_denial_message, ReadEncoding,
 };
+use ubl_chipstore::ChipAuthor;
 use ubl_runtime::error_response::{ErrorCode, UblError};
-use ubl_runtime::rate_limit::RateLimitResult;
+use ubl_runtime::pipeline::MAX_BATCH_CHIPS;
+use ubl_runtime::rate_limit::{CanonRateKey, RateLimitResult};
+use ubl_runtime::reasoning_bit::Decision;
 
 pub(crate) async fn submit_chip_bytes(
     state: &AppState,
@@ -25,13 +39,71 @@ pub(crate) async fn submit_chip_bytes(
     trusted_write: bool,
     body: &[u8],
 ) -> (StatusCode, HeaderMap, Value) {
-    metrics::inc_chips_total();
-    let t0 = std::time::Instant::now();
+    submit_chip_bytes_with(state, headers, trusted_write, body, false, false).await
+}
+
+/// A chip that passed KNOCK, write authorization and rate limits.
+struct AdmittedChip {
+    request: ubl_runtime::pipeline::ChipRequest,
+    ctx: ubl_runtime::pipeline::AuthorshipContext,
+    /// Denials for this caller get the generic message only.
+    redact_denial: bool,
+}
+
+/// What a chip is admitted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    /// Rate limited; rejections are receipted and stored.
+    Submit,
+    /// Only the per-IP limit applies; nothing is stored or published.
+    Simulate,
+    /// Rate limited, but a rejection stores nothing: the batch rolls back.
+    Batch,
+}
+
+/// Everything [`submit_chip_bytes_with`] checks before the pipeline runs;
+/// `Err` is the response for a rejected chip.
+async fn admit_chip(
+    state: &AppState,
+    headers: Option<&HeaderMap>,
+    trusted_write: bool,
+    body: &[u8],
+    admission: Admission,
+    t0: std::time::Instant,
+) -> Result<AdmittedChip, (StatusCode, HeaderMap, Value)> {
+    let simulate = admission == Admission::Simulate;
+    // Rejections outside a real submission are receipted but never stored.
+    let dry_rejection = admission != Admission::Submit;
+    // The per-IP limit covers simulations too: each one still runs the pipeline.
+    if let (Some(limiter), Some(ip)) = (
+        state.ip_rate_limiter.as_ref(),
+        headers.and_then(client_ip_from_headers),
+    ) {
+        if let RateLimitResult::Limited { retry_after, .. } = limiter.check(&ip.to_string()).await {
+            metrics::observe_pipeline_seconds(t0.elapsed().as_secs_f64());
+            metrics::inc_error("TooManyRequests");
+            let mut headers = HeaderMap::new();
+            let retry_secs = retry_after.as_secs().saturating_add(1);
+            if let Ok(v) = retry_secs.to_string().parse() {
+                headers.insert(header::RETRY_AFTER, v);
+            }
+            let err = too_many_requests_error(
+                format!("Rate limit exceeded for client IP {}", ip),
+                json!({
+                    "limited_by": "ip",
+                    "retry_after_seconds": retry_secs,
+                }),
+            );
+            return Err((StatusCode::TOO_MANY_REQUESTS, headers, err.to_json()));
+        }
+    }
     let knock_cid = ubl_runtime::authorship::knock_cid_from_bytes(body);
     let actor_hint = actor_hint_from_headers(headers);
 
-    let value = match ubl_runtime::knock::knock(body) {
-        Ok(v) => v,
+    let knocked = ubl_runtime::pipeline::stage_span(ubl_receipt::PipelineStage::Knock)
+        .in_scope(|| ubl_runtime::knock::knock_with_signer(body));
+    let (value, signer_did) = match knocked {
+        Ok(knocked) => knocked,
         Err(e) => {
             metrics::observe_pipeline_seconds(t0.elapsed().as_secs_f64());
             let reason_code = knock_reason_code(&e);
@@ -42,15 +114,25 @@ pub(crate) async fn submit_chip_bytes(
 
             match state
                 .pipeline
-                .process_knock_rejection(&knock_cid, &reason_code, &reason_msg, Some(subject_did))
+                .process_knock_rejection(
+                    &knock_cid,
+                    &reason_code,
+                    &reason_msg,
+                    Some(subject_did),
+                    dry_rejection,
+                )
                 .await
             {
                 Ok(result) => {
                     let receipt_json = result.receipt.to_json().unwrap_or(json!({}));
-                    let public_receipt = build_public_receipt_link(state, &receipt_json);
+                    let public_receipt = if dry_rejection {
+                        None
+                    } else {
+                        build_public_receipt_link(state, &receipt_json)
+                    };
                     let receipt_url = public_receipt.as_ref().map(|p| p.url.clone());
                     let status = StatusCode::UNPROCESSABLE_ENTITY;
-                    return (
+                    return Err((
                         status,
                         HeaderMap::new(),
                         json!({
@@ -67,30 +149,44 @@ pub(crate) async fn submit_chip_bytes(
                             "decision": "Deny",
                             "status": "denied",
                         }),
-                    );
+                    ));
                 }
                 Err(process_err) => {
                     let ubl_err = UblError::from_pipeline_error(&process_err);
                     let status = StatusCode::from_u16(ubl_err.code.http_status())
                         .unwrap_or(StatusCode::BAD_REQUEST);
-                    return (status, HeaderMap::new(), ubl_err.to_json());
+                    return Err((status, HeaderMap::new(), ubl_err.to_json()));
                 }
             }
         }
     };
 
     let mut subject_did_from_token_hint: Option<String> = None;
+    let mut token_author: Option<ChipAuthor> = None;
+    // Write-authorized callers always see full denial detail.
+    let mut privileged_caller = trusted_write;
 
     if !trusted_write {
         let chip_type = value.get("@type").and_then(|v| v.as_str()).unwrap_or("");
-        let world = value.get("@world").and_then(|v| v.as_str()).unwrap_or("");
+        // Routing submissions must be authorized for every candidate world.
+        let target_worlds = match ubl_runtime::pipeline::world_candidates(&value) {
+            Ok(Some(candidates)) => candidates,
+            _ => vec![value
+                .get("@world")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string()],
+        };
         let mut authorized_via_token = false;
         if let Some(h) = headers {
             if parse_bearer_token(h).is_some() {
                 match resolve_session_bearer(state, h).await {
                     Ok(Some(auth)) => {
-                        if scope_allows_any(&auth.scope, &["write", "chip:write", "mcp:write"]) {
-                            if !world_scope_allows(&auth.world, world) {
+                        if scope_allows_write_type(&auth.scope, chip_type) {
+                            if let Some(world) = target_worlds
+                                .iter()
+                                .find(|w| !world_scope_allows(&auth.world, w))
+                            {
                                 let err_code = ErrorCode::PolicyDenied;
                                 let reason_msg = format!(
                                     "token world '{}' does not authorize target world '{}'",
@@ -106,7 +202,7 @@ pub(crate) async fn submit_chip_bytes(
                                     .ok()
                                     .and_then(|v| v.as_str().map(|s| s.to_string()))
                                     .unwrap_or_else(|| "POLICY_DENIED".to_string());
-                                return deny_write_with_receipt(
+                                return Err(deny_write_with_receipt(
                                     state,
                                     &knock_cid,
                                     &reason_code,
@@ -114,14 +210,21 @@ pub(crate) async fn submit_chip_bytes(
                                     err_code,
                                     &value,
                                     subject_did,
+                                    dry_rejection,
                                 )
-                                .await;
+                                .await);
                             }
                             authorized_via_token = true;
+                            privileged_caller = true;
                             subject_did_from_token_hint = auth.subject_did.clone();
+                            token_author = Some(ChipAuthor::Token {
+                                token_cid: auth.token_cid.clone(),
+                                did: auth.subject_did.clone(),
+                                world: auth.world.clone(),
+                            });
                         } else {
                             let err_code = ErrorCode::PolicyDenied;
-                            let reason_msg = "token scope does not allow write".to_string();
+                            let reason_msg = write_scope_denial_message(&auth.scope, chip_type);
                             let subject_did = auth.subject_did.clone().unwrap_or_else(|| {
                                 ubl_runtime::authorship::resolve_subject_did(
                                     Some(&value),
@@ -132,7 +235,7 @@ pub(crate) async fn submit_chip_bytes(
                                 .ok()
                                 .and_then(|v| v.as_str().map(|s| s.to_string()))
                                 .unwrap_or_else(|| "POLICY_DENIED".to_string());
-                            return deny_write_with_receipt(
+                            return Err(deny_write_with_receipt(
                                 state,
                                 &knock_cid,
                                 &reason_code,
@@ -140,13 +243,14 @@ pub(crate) async fn submit_chip_bytes(
                                 err_code,
                                 &value,
                                 subject_did,
+                                dry_rejection,
                             )
-                            .await;
+                            .await);
                         }
                     }
                     Ok(None) => {}
-                    Err(msg) => {
-                        let err_code = ErrorCode::Unauthorized;
+                    Err(rejection) => {
+                        let err_code = rejection.code;
                         let subject_did = ubl_runtime::authorship::resolve_subject_did(
                             Some(&value),
                             Some(&actor_hint),
@@ -155,25 +259,31 @@ pub(crate) async fn submit_chip_bytes(
                             .ok()
                             .and_then(|v| v.as_str().map(|s| s.to_string()))
                             .unwrap_or_else(|| "UNAUTHORIZED".to_string());
-                        return deny_write_with_receipt(
+                        let (status, headers, mut body) = deny_write_with_receipt(
                             state,
                             &knock_cid,
                             &reason_code,
-                            &msg,
+                            &rejection.message,
                             err_code,
                             &value,
                             subject_did,
+                            dry_rejection,
                         )
                         .await;
+                        if let Some(details) = body.get_mut("details") {
+                            *details = rejection.details_with(details.take());
+                        }
+                        return Err((status, headers, body));
                     }
                 }
             }
         }
 
         if !authorized_via_token {
-            if let Err((err_code, reason_msg)) = state
-                .write_access_policy
-                .authorize_write(headers, chip_type, world)
+            let policy = state.write_access_policy.load();
+            if let Some((err_code, reason_msg)) = target_worlds
+                .iter()
+                .find_map(|world| policy.authorize_write(headers, chip_type, world).err())
             {
                 let subject_did = subject_did_from_token_hint.clone().unwrap_or_else(|| {
                     ubl_runtime::authorship::resolve_subject_did(Some(&value), Some(&actor_hint))
@@ -182,7 +292,7 @@ pub(crate) async fn submit_chip_bytes(
                     .ok()
                     .and_then(|v| v.as_str().map(|s| s.to_string()))
                     .unwrap_or_else(|| "UNAUTHORIZED".to_string());
-                return deny_write_with_receipt(
+                return Err(deny_write_with_receipt(
                     state,
                     &knock_cid,
                     &reason_code,
@@ -190,16 +300,18 @@ pub(crate) async fn submit_chip_bytes(
                     err_code,
                     &value,
                     subject_did,
+                    dry_rejection,
                 )
-                .await;
+                .await);
             }
         }
     }
 
-    if let Some(ref limiter) = state.canon_rate_limiter {
-        if let Some((fp, RateLimitResult::Limited { retry_after, .. })) =
-            limiter.check_body(&value).await
-        {
+    if let (Some(limiter), Some(world)) = (
+        state.world_rate_limiter.as_ref().filter(|_| !simulate),
+        value["@world"].as_str(),
+    ) {
+        if let Some(RateLimitResult::Limited { retry_after, .. }) = limiter.check(world).await {
             metrics::observe_pipeline_seconds(t0.elapsed().as_secs_f64());
             metrics::inc_error("TooManyRequests");
             let mut headers = HeaderMap::new();
@@ -208,12 +320,76 @@ pub(crate) async fn submit_chip_bytes(
                 headers.insert(header::RETRY_AFTER, v);
             }
             let err = too_many_requests_error(
-                format!(
+                format!("Rate limit exceeded for world {}", world),
+                json!({
+                    "limited_by": "world",
+                    "world": world,
+                    "retry_after_seconds": retry_secs,
+                }),
+            );
+            return Err((StatusCode::TOO_MANY_REQUESTS, headers, err.to_json()));
+        }
+    }
+
+    // An admin key outranks a chip signature, which outranks a token.
+    let author = if headers.is_some_and(|h| state.admin_access.authorize(h).is_ok()) {
+        Some(ChipAuthor::Admin)
+    } else if let Some(did) = &signer_did {
+        Some(ChipAuthor::Signer { did: did.clone() })
+    } else {
+        token_author
+    };
+
+    // Subject rate buckets key on a subject the gate verified: the chip
+    // signer, else the bearer token's subject. Anything else is derived from
+    // the body or headers and can be rotated, so anonymous writers share a
+    // bucket per client IP.
+    let rate_subject = match signer_did.as_ref().or(subject_did_from_token_hint.as_ref()) {
+        Some(did) => did.clone(),
+        None => format!(
+            "ip:{}",
+            headers
+                .and_then(client_ip_from_headers)
+                .map(|ip| ip.to_string())
+                .unwrap_or_default()
+        ),
+    };
+
+    // A verified chip signature outranks token and header hints.
+    let subject_did = match signer_did {
+        Some(did) => did,
+        None if !trusted_write => subject_did_from_token_hint.unwrap_or_else(|| {
+            ubl_runtime::authorship::resolve_subject_did(Some(&value), Some(&actor_hint))
+        }),
+        None => ubl_runtime::authorship::resolve_subject_did(Some(&value), Some(&actor_hint)),
+    };
+
+    if let Some(limiter) = state.canon_rate_limiter.as_ref().filter(|_| !simulate) {
+        if let Some((fp, key, RateLimitResult::Limited { retry_after, .. })) =
+            limiter.check_body_for_subject(&value, &rate_subject).await
+        {
+            metrics::observe_pipeline_seconds(t0.elapsed().as_secs_f64());
+            metrics::inc_error("TooManyRequests");
+            let mut headers = HeaderMap::new();
+            let retry_secs = retry_after.as_secs().saturating_add(1);
+            if let Ok(v) = retry_secs.to_string().parse() {
+                headers.insert(header::RETRY_AFTER, v);
+            }
+            let message = match key {
+                CanonRateKey::Subject => {
+                    format!("Rate limit exceeded for subject {}", rate_subject)
+                }
+                _ => format!(
                     "Rate limit exceeded for canonical payload {}",
                     fp.rate_key()
                 ),
+            };
+            let err = too_many_requests_error(
+                message,
                 json!({
                     "limited_by": "canon_fingerprint",
+                    "key": key.as_str(),
+                    "subject": rate_subject,
                     "fingerprint": fp.hash,
                     "at_type": fp.at_type,
                     "at_ver": fp.at_ver,
@@ -221,95 +397,216 @@ pub(crate) async fn submit_chip_bytes(
                     "retry_after_seconds": retry_secs,
                 }),
             );
-            return (StatusCode::TOO_MANY_REQUESTS, headers, err.to_json());
+            return Err((StatusCode::TOO_MANY_REQUESTS, headers, err.to_json()));
         }
     }
 
-    let chip_type = value["@type"].as_str().unwrap_or("").to_string();
+    let redact_denial = !privileged_caller
+        && !state.write_access_policy.load().matches_api_key(headers)
+        && match ubl_runtime::pipeline::world_candidates(&value) {
+            Ok(Some(candidates)) => candidates.iter().any(|w| state.denial_redaction.applies(w)),
+            _ => state
+                .denial_redaction
+                .applies(value["@world"].as_str().unwrap_or("")),
+        };
+
     let request = ubl_runtime::pipeline::ChipRequest {
-        chip_type,
+        chip_type: value["@type"].as_str().unwrap_or("").to_string(),
         body: value,
         parents: vec![],
         operation: Some("create".to_string()),
     };
-    let subject_did = if !trusted_write {
-        subject_did_from_token_hint.unwrap_or_else(|| {
-            ubl_runtime::authorship::resolve_subject_did(Some(&request.body), Some(&actor_hint))
-        })
-    } else {
-        ubl_runtime::authorship::resolve_subject_did(Some(&request.body), Some(&actor_hint))
-    };
     let ctx = ubl_runtime::pipeline::AuthorshipContext {
         subject_did_hint: Some(subject_did),
         knock_cid: Some(knock_cid.clone()),
+        correlation_id: headers
+            .and_then(|h| h.get("x-ubl-correlation-id"))
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+        idempotency_key: headers
+            .and_then(|h| h.get("idempotency-key"))
+            .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned()),
+        simulate,
+        author,
+    };
+    Ok(AdmittedChip {
+        request,
+        ctx,
+        redact_denial,
+    })
+}
+
+/// [`submit_chip_bytes`]; with `decision_only` a successful run answers with
+/// just `{decision, receipt_cid}`, skipping receipt and public-link
+/// serialization. With `simulate` the admitted chip runs through the pipeline
+/// without being persisted, and only the per-IP rate limit applies; KNOCK and
+/// write authorization rejections are receipted but nothing is stored or
+/// published.
+pub(crate) async fn submit_chip_bytes_with(
+    state: &AppState,
+    headers: Option<&HeaderMap>,
+    trusted_write: bool,
+    body: &[u8],
+    decision_only: bool,
+    simulate: bool,
+) -> (StatusCode, HeaderMap, Value) {
+    if !simulate {
+        metrics::inc_chips_total();
+    }
+    let t0 = std::time::Instant::now();
+    let admission = if simulate {
+        Admission::Simulate
+    } else {
+        Admission::Submit
     };
+    let AdmittedChip {
+        request,
+        ctx,
+        redact_denial,
+    } = match admit_chip(state, headers, trusted_write, body, admission, t0).await {
+        Ok(admitted) => admitted,
+        Err(rejection) => return rejection,
+    };
+    let chip_type = request.chip_type.clone();
 
     match state.pipeline.process_chip_with_context(request, ctx).await {
         Ok(result) => {
             metrics::observe_pipeline_seconds(t0.elapsed().as_secs_f64());
-            let decision_str = format!("{:?}", result.decision);
-            if decision_str.contains("Allow") {
-                metrics::inc_allow();
-            } else {
-                metrics::inc_deny();
+            // Previews change nothing and are not counted as decisions.
+            if !simulate {
+                record_committed(state, &chip_type, &result.decision);
             }
-            let receipt_json = result.receipt.to_json().unwrap_or(json!({}));
-            let public_receipt = build_public_receipt_link(state, &receipt_json);
+            let decision_str = format!("{:?}", result.decision);
+            let quarantined = matches!(result.decision, Decision::Quarantine);
             let mut headers = HeaderMap::new();
             if result.replayed {
                 metrics::inc_idempotency_hit();
                 metrics::inc_idempotency_replay_block();
                 headers.insert("X-UBL-Replay", "true".parse().unwrap());
             }
+            let status = if quarantined {
+                StatusCode::ACCEPTED
+            } else {
+                StatusCode::OK
+            };
+            // A redacted denial names no receipt: receipt reads are public
+            // and the receipt carries the full policy trace.
+            if redact_denial && matches!(result.decision, Decision::Deny) {
+                return (
+                    status,
+                    headers,
+                    json!({
+                        "@type": "ubl/response",
+                        "status": "success",
+                        "decision": decision_str,
+                        "code": ErrorCode::PolicyDenied,
+                        "message": DenialRedaction::MESSAGE,
+                        "redacted": true,
+                        "replayed": result.replayed,
+                    }),
+                );
+            }
+            if decision_only {
+                return (
+                    status,
+                    headers,
+                    json!({
+                        "decision": decision_str,
+                        "receipt_cid": result.receipt.receipt_cid,
+                    }),
+                );
+            }
+            let receipt_json = result.receipt.to_json().unwrap_or(json!({}));
+            // A simulated receipt is never stored, so it gets no public link.
+            let public_receipt = if simulate {
+                None
+            } else {
+                build_public_receipt_link(state, &receipt_json)
+            };
             let receipt_url = public_receipt.as_ref().map(|p| p.url.clone());
-            (
-                StatusCode::OK,
-                headers,
-                json!({
-                    "@type": "ubl/response",
-                    "status": "success",
-                    "decision": decision_str,
-                    "receipt_cid": result.receipt.receipt_cid,
-                    "receipt_url": receipt_url,
-                    "receipt_public": public_receipt,
-                    "chain": result.chain,
-                    "subject_did": result.receipt.subject_did,
-                    "knock_cid": result.receipt.knock_cid,
-                    "receipt": receipt_json,
-                    "replayed": result.replayed,
-                }),
-            )
+            let mut response = json!({
+                "@type": "ubl/response",
+                "status": "success",
+                "decision": decision_str,
+                "receipt_cid": result.receipt.receipt_cid,
+                "receipt_url": receipt_url,
+                "receipt_public": public_receipt,
+                "chain": result.chain,
+                "subject_did": result.receipt.subject_did,
+                "knock_cid": result.receipt.knock_cid,
+                "receipt": receipt_json,
+                "replayed": result.replayed,
+            });
+            if simulate {
+                response["simulated"] = json!(true);
+            }
+            // Deprecation is advisory: the write still stands.
+            if let Some(dep) = type_deprecation(state, &chip_type).await {
+                response["warnings"] = json!([{
+                    "code": "TYPE_DEPRECATED",
+                    "message": dep.reason,
+                    "replacement_type": dep.replacement_type,
+                    "sunset_at": dep.sunset_at,
+                }]);
+            }
+            (status, headers, response)
         }
         Err(e) => {
             metrics::observe_pipeline_seconds(t0.elapsed().as_secs_f64());
-            let ubl_err = UblError::from_pipeline_error(&e);
-            match ubl_err.code {
-                ErrorCode::SignError | ErrorCode::InvalidSignature => {
-                    let mode = std::env::var("UBL_CRYPTO_MODE")
-                        .unwrap_or_else(|_| "compat_v1".to_string());
-                    metrics::inc_crypto_verify_fail("pipeline", &mode);
-                }
-                ErrorCode::CanonError => metrics::inc_canon_divergence("pipeline"),
-                _ => {}
-            }
-            let code_str = format!("{:?}", ubl_err.code);
-            if code_str.contains("Knock") {
-                metrics::inc_knock_reject();
-            }
-            metrics::inc_error(&code_str);
-            let status = StatusCode::from_u16(ubl_err.code.http_status())
-                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-            (status, HeaderMap::new(), ubl_err.to_json())
+            pipeline_error_response(&e, redact_denial)
         }
     }
 }
 
+/// Cache invalidation and decision metrics for a result the pipeline
+/// committed (or replayed).
+fn record_committed(state: &AppState, chip_type: &str, decision: &Decision) {
+    if is_registry_chip_type(chip_type) {
+        state.registry_cache.invalidate();
+    }
+    if chip_type == TOKEN_REVOKE_CHIP_TYPE {
+        state.revocation_cache.invalidate();
+    }
+    match decision {
+        Decision::Allow => metrics::inc_allow(),
+        Decision::Quarantine => {}
+        _ => metrics::inc_deny(),
+    }
+}
+
+fn pipeline_error_response(
+    e: &ubl_runtime::pipeline::PipelineError,
+    redact_denial: bool,
+) -> (StatusCode, HeaderMap, Value) {
+    let mut ubl_err = UblError::from_pipeline_error(e);
+    if redact_denial && ubl_err.code == ErrorCode::PolicyDenied {
+        ubl_err.message = DenialRedaction::MESSAGE.to_string();
+        ubl_err.details = None;
+    }
+    match ubl_err.code {
+        ErrorCode::SignError | ErrorCode::InvalidSignature => {
+            let mode = std::env::var("UBL_CRYPTO_MODE").unwrap_or_else(|_| "compat_v1".to_string());
+            metrics::inc_crypto_verify_fail("pipeline", &mode);
+        }
+        ErrorCode::CanonError => metrics::inc_canon_divergence("pipeline"),
+        _ => {}
+    }
+    let code_str = format!("{:?}", ubl_err.code);
+    if code_str.contains("Knock") {
+        metrics::inc_knock_reject();
+    }
+    metrics::inc_error(&code_str);
+    let status = StatusCode::from_u16(ubl_err.code.http_status())
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, HeaderMap::new(), ubl_err.to_json())
+}
+
 pub(crate) async fn get_runtime_attestation(
     State(state): State<AppState>,
 ) -> (StatusCode, Json<Value>) {
     match state.pipeline.runtime_self_attestation() {
         Ok(attestation) => {
-            let verified = attestation.verify().unwrap_or(false);
+            let verified = state.pipeline.verify_runtime_attestation(&attestation);
             (
                 StatusCode::OK,
                 Json(json!({
@@ -330,17 +627,280 @@ pub(crate) async fn get_runtime_attestation(
     }
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct CreateChipQuery {
+    /// Answer with only `{decision, receipt_cid}`.
+    pub decision_only: Option<bool>,
+    /// Wait for the outbox `emit_receipt` delivery before answering.
+    pub await_delivery: Option<bool>,
+    /// Wait budget for `await_delivery`, capped at [`MAX_AWAIT_DELIVERY_MS`].
+    pub await_timeout_ms: Option<u64>,
+}
+
+/// 503 with `Retry-After` while genesis bootstrap is still running.
+pub(crate) fn starting_up(state: &AppState) -> Option<(StatusCode, HeaderMap, Json<Value>)> {
+    if state.readiness.is_ready() {
+        return None;
+    }
+    let retry_secs = state.readiness.retry_after_secs;
+    let mut headers = HeaderMap::new();
+    if let Ok(v) = retry_secs.to_string().parse() {
+        headers.insert(header::RETRY_AFTER, v);
+    }
+    metrics::inc_error("Unavailable");
+    let err = unavailable_error(
+        "Gate is starting up; retry after initialization completes".to_string(),
+        json!({"reason": "startup", "retry_after_seconds": retry_secs}),
+    );
+    Some((
+        StatusCode::SERVICE_UNAVAILABLE,
+        headers,
+        Json(err.to_json()),
+    ))
+}
+
 pub(crate) async fn create_chip(
     State(state): State<AppState>,
+    Query(query): Query<CreateChipQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    if let Some(unavailable) = starting_up(&state) {
+        return unavailable;
+    }
+    let decision_only = query.decision_only.unwrap_or(false);
+    let (status, headers, mut payload) =
+        submit_chip_bytes_with(&state, Some(&headers), false, &body, decision_only, false).await;
+    if query.await_delivery.unwrap_or(false) && status.is_success() {
+        if let Some(receipt_cid) = payload["receipt_cid"].as_str().map(str::to_string) {
+            let timeout = query
+                .await_timeout_ms
+                .unwrap_or(DEFAULT_AWAIT_DELIVERY_MS)
+                .min(MAX_AWAIT_DELIVERY_MS);
+            payload["delivery"] = await_emit_receipt_delivery(
+                state.durable_store.clone(),
+                &receipt_cid,
+                std::time::Duration::from_millis(timeout),
+            )
+            .await;
+        }
+    }
+    (status, headers, Json(payload))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct SimulateChipQuery {
+    /// Answer with only `{decision, receipt_cid}`.
+    pub decision_only: Option<bool>,
+}
+
+/// POST /v1/chips/simulate — run the chip through KNOCK→WA→CHECK→TR→WF like
+/// `POST /v1/chips` and return the decision and signed receipt, without
+/// storing the chip, committing the receipt, enqueueing outbox events or
+/// publishing. Idempotency and the world and canon rate limits are bypassed;
+/// the per-IP limit still applies.
+pub(crate) async fn simulate_chip(
+    State(state): State<AppState>,
+    Query(query): Query<SimulateChipQuery>,
     headers: HeaderMap,
     body: Bytes,
 ) -> impl IntoResponse {
-    let (status, headers, payload) = submit_chip_bytes(&state, Some(&headers), false, &body).await;
+    if let Some(unavailable) = starting_up(&state) {
+        return unavailable;
+    }
+    let decision_only = query.decision_only.unwrap_or(false);
+    let (status, headers, payload) =
+        submit_chip_bytes_with(&state, Some(&headers), false, &body, decision_only, true).await;
     (status, headers, Json(payload))
 }
 
-pub(crate) async fn metrics_handler() -> String {
-    metrics::encode_metrics()
+/// POST /v1/chips/batch — a JSON array of chips, each admitted and run like
+/// `POST /v1/chips`, whose WF receipts commit in one transaction. A deny or
+/// error on any entry rolls the whole batch back; the response names the
+/// failing index and carries its error, and nothing is stored for it. At
+/// most [`MAX_BATCH_CHIPS`] entries are accepted, and `Idempotency-Key` is
+/// refused: entries replay by their own `@type`/`@ver`/`@world`/`@id`.
+pub(crate) async fn create_chip_batch(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    if let Some(unavailable) = starting_up(&state) {
+        return unavailable;
+    }
+    let t0 = std::time::Instant::now();
+    // Entries replay by their own idempotency anchors; a key for the whole
+    // batch would have nothing to replay.
+    if headers.contains_key("idempotency-key") {
+        let e = ubl_runtime::pipeline::PipelineError::InvalidChip(
+            "Idempotency-Key is not supported on batch submissions".to_string(),
+        );
+        return batch_rolled_back(None, pipeline_error_response(&e, false));
+    }
+    // Entries stay raw so each knock CID covers exactly the bytes sent.
+    let chips = match serde_json::from_slice::<Vec<&serde_json::value::RawValue>>(&body) {
+        Ok(chips) => chips,
+        Err(_) => {
+            let e = ubl_runtime::pipeline::PipelineError::InvalidChip(
+                "batch body must be a JSON array of chips".to_string(),
+            );
+            return batch_rolled_back(None, pipeline_error_response(&e, false));
+        }
+    };
+    if chips.len() > MAX_BATCH_CHIPS {
+        let e = ubl_runtime::pipeline::PipelineError::InvalidChip(format!(
+            "batch has {} entries; at most {} are accepted",
+            chips.len(),
+            MAX_BATCH_CHIPS
+        ));
+        return batch_rolled_back(None, pipeline_error_response(&e, false));
+    }
+
+    let mut entries = Vec::with_capacity(chips.len());
+    let mut redact = Vec::with_capacity(chips.len());
+    for (index, chip) in chips.iter().enumerate() {
+        metrics::inc_chips_total();
+        let bytes = chip.get().as_bytes();
+        match admit_chip(&state, Some(&headers), false, bytes, Admission::Batch, t0).await {
+            Ok(admitted) => {
+                redact.push(admitted.redact_denial);
+                entries.push((admitted.request, admitted.ctx));
+            }
+            Err(rejection) => return batch_rolled_back(Some(index), rejection),
+        }
+    }
+    let chip_types: Vec<String> = entries.iter().map(|(r, _)| r.chip_type.clone()).collect();
+
+    let outcome = state.pipeline.process_batch(entries).await;
+    metrics::observe_pipeline_seconds(t0.elapsed().as_secs_f64());
+    match outcome {
+        Ok(results) => {
+            let results: Vec<Value> = results
+                .iter()
+                .zip(&chip_types)
+                .enumerate()
+                .map(|(index, (result, chip_type))| {
+                    record_committed(&state, chip_type, &result.decision);
+                    json!({
+                        "index": index,
+                        "decision": format!("{:?}", result.decision),
+                        "receipt_cid": result.receipt.receipt_cid,
+                        "chain": result.chain,
+                        "replayed": result.replayed,
+                    })
+                })
+                .collect();
+            (
+                StatusCode::OK,
+                HeaderMap::new(),
+                Json(json!({
+                    "@type": "ubl/batch.response",
+                    "status": "committed",
+                    "count": results.len(),
+                    "results": results,
+                })),
+            )
+        }
+        Err(rejection) => {
+            let redact_denial = rejection.index.is_some_and(|i| redact[i]);
+            batch_rolled_back(
+                rejection.index,
+                pipeline_error_response(&rejection.error, redact_denial),
+            )
+        }
+    }
+}
+
+fn batch_rolled_back(
+    failed_index: Option<usize>,
+    (status, headers, error): (StatusCode, HeaderMap, Value),
+) -> (StatusCode, HeaderMap, Json<Value>) {
+    (
+        status,
+        headers,
+        Json(json!({
+            "@type": "ubl/batch.response",
+            "status": "rolled_back",
+            "failed_index": failed_index,
+            "error": error,
+        })),
+    )
+}
+
+pub(crate) async fn metrics_handler(State(state): State<AppState>) -> String {
+    let mut text = metrics::encode_metrics();
+    text.push_str(&state.pipeline.policy_counters().render_prometheus());
+    text
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct StatusQuery {
+    format: Option<String>,
+}
+
+/// GET /v1/status — one-line health summary for terminals and chat ops
+/// (`format=line`, the default) or its structured form (`format=json`).
+pub(crate) async fn get_status(
+    State(state): State<AppState>,
+    Query(query): Query<StatusQuery>,
+) -> axum::response::Response {
+    let format = query.format.as_deref().unwrap_or("line");
+    if format != "line" && format != "json" {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "INVALID_FORMAT",
+                "message": "format must be 'line' or 'json'",
+            })),
+        )
+            .into_response();
+    }
+
+    let snapshot = metrics::status_snapshot();
+    let ready = state.readiness.is_ready();
+    let outbox = state
+        .durable_store
+        .as_ref()
+        .map(|store| store.outbox_pending());
+    let status = if !ready {
+        "starting"
+    } else if matches!(outbox, Some(Err(_))) {
+        "degraded"
+    } else {
+        "ok"
+    };
+    let outbox_pending = outbox.and_then(Result::ok);
+    let decided = snapshot.allow + snapshot.deny;
+    let allow_pct = (decided > 0).then(|| snapshot.allow as f64 * 100.0 / decided as f64);
+    let p95_ms = snapshot.p95_seconds.map(|s| (s * 1000.0).round() as u64);
+
+    if format == "json" {
+        return Json(json!({
+            "@type": "ubl/status",
+            "status": status,
+            "ready": ready,
+            "chips": snapshot.chips,
+            "allow": snapshot.allow,
+            "deny": snapshot.deny,
+            "allow_pct": allow_pct.map(|p| (p * 10.0).round() / 10.0),
+            "p95_ms": p95_ms,
+            "outbox_pending": outbox_pending,
+        }))
+        .into_response();
+    }
+
+    let or_dash = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+    let line = format!(
+        "{} chips={} allow={} p95={} outbox={} ready={}\n",
+        status,
+        snapshot.chips,
+        or_dash(allow_pct.map(|p| format!("{:.1}%", p))),
+        or_dash(p95_ms.map(|ms| format!("{}ms", ms))),
+        or_dash(outbox_pending.map(|n| n.to_string())),
+        ready,
+    );
+    ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], line).into_response()
 }
 
 pub(crate) async fn verify_chip(
@@ -388,9 +948,11 @@ pub(crate) async fn verify_chip(
         match state.durable_store.as_ref() {
             Some(store) => match store.get_receipt(receipt_cid.as_str()) {
                 Ok(Some(receipt_json)) => {
-                    if let Err(ubl_err) =
-                        verify_receipt_auth_chain(receipt_cid.as_str(), &receipt_json)
-                    {
+                    if let Err(ubl_err) = verify_receipt_auth_chain(
+                        &state.pipeline,
+                        receipt_cid.as_str(),
+                        &receipt_json,
+                    ) {
                         return (
                             StatusCode::from_u16(ubl_err.code.http_status())
                                 .unwrap_or(StatusCode::UNPROCESSABLE_ENTITY),
@@ -439,26 +1001,177 @@ pub(crate) async fn verify_chip(
     )
 }
 
+/// `GET /v1/chips/:cid/policies` — policies applied during the chip's CHECK.
+///
+/// Per-policy decisions come from the CHECK stage trace of the durable
+/// receipt; without one only the ids in `execution_metadata` are listed.
+pub(crate) async fn get_chip_policies(
+    State(state): State<AppState>,
+    Path(cid): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    if !cid.starts_with("b3:") {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(
+                json!({"@type": "ubl/error", "code": "INVALID_CID", "message": "CID must start with b3:"}),
+            ),
+        );
+    }
+
+    let chip = match state.chip_store.get_chip(&cid).await {
+        Ok(Some(c)) => c,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(
+                    json!({"@type": "ubl/error", "code": "NOT_FOUND", "message": format!("Chip {} not found", cid)}),
+                ),
+            )
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()}),
+                ),
+            )
+        }
+    };
+
+    let receipt_cid = chip.receipt_cid.as_str();
+    let receipt_json = match state.durable_store.as_ref() {
+        Some(store) if !receipt_cid.is_empty() => match store.get_receipt(receipt_cid) {
+            Ok(found) => found,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "@type": "ubl/error",
+                        "code": "INTERNAL_ERROR",
+                        "message": format!("Receipt fetch failed: {}", e)
+                    })),
+                );
+            }
+        },
+        _ => None,
+    };
+    let check_trace = receipt_json
+        .as_ref()
+        .and_then(|r| ubl_receipt::UnifiedReceipt::from_json(r).ok())
+        .and_then(|r| {
+            r.stages
+                .into_iter()
+                .find(|s| s.stage == ubl_receipt::PipelineStage::Check)
+                .map(|s| s.policy_trace)
+        });
+
+    let (source, policies): (&str, Vec<Value>) = match check_trace {
+        Some(trace) => (
+            "receipt",
+            trace
+                .iter()
+                .map(|t| {
+                    json!({
+                        "policy_id": t.policy_id,
+                        "level": t.level,
+                        "decision": format!("{:?}", t.result).to_ascii_lowercase(),
+                        "reason": t.reason,
+                        "duration_ms": t.duration_ms,
+                    })
+                })
+                .collect(),
+        ),
+        None => (
+            "execution_metadata",
+            chip.execution_metadata
+                .policies_applied
+                .iter()
+                .map(|id| json!({"policy_id": id}))
+                .collect(),
+        ),
+    };
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/chip.policies",
+            "cid": cid,
+            "chip_type": chip.chip_type,
+            "receipt_cid": chip.receipt_cid,
+            "source": source,
+            "count": policies.len(),
+            "policies": policies,
+        })),
+    )
+}
+
+const ANNOTATIONS_MAX_LISTED: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct GetChipQuery {
+    include: Option<String>,
+}
+
+/// GET /v1/chips/:cid — the stored chip. `?include=annotations` adds the
+/// `ubl/annotation` chips targeting it, oldest first, each with the author
+/// the gate verified when it was written; that view changes as
+/// notes are appended, so it is served without the immutable ETag.
+/// `Accept: application/x-nrf1` or `application/cbor` returns just the chip
+/// body as NRF-1.1 bytes, the exact input of its CID, under the requested
+/// content type; the ETag is the CID either way.
 pub(crate) async fn get_chip(
     State(state): State<AppState>,
     Path(cid): Path<String>,
+    Query(query): Query<GetChipQuery>,
     headers: HeaderMap,
-) -> impl IntoResponse {
+) -> Response {
     if !cid.starts_with("b3:") {
         return (
             StatusCode::BAD_REQUEST,
             HeaderMap::new(),
             Json(json!({"@type": "ubl/error", "code": "INVALID_CID", "message": "CID must start with b3:"})),
-        );
+        )
+            .into_response();
+    }
+
+    let mut include_annotations = false;
+    for item in query.include.as_deref().unwrap_or_default().split(',') {
+        match item.trim() {
+            "" => {}
+            "annotations" => include_annotations = true,
+            other => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    HeaderMap::new(),
+                    Json(json!({
+                        "@type": "ubl/error",
+                        "code": "INVALID_INCLUDE",
+                        "message": format!("unknown include '{}'; supported: annotations", other),
+                    })),
+                )
+                    .into_response()
+            }
+        }
+    }
+
+    if include_annotations {
+        return get_chip_with_annotations(&state, &cid)
+            .await
+            .into_response();
     }
 
+    let Some(encoding) = read_encoding(&headers) else {
+        return not_acceptable_response();
+    };
+
     if let Some(inm) = headers.get(header::IF_NONE_MATCH) {
         if let Ok(inm_str) = inm.to_str() {
             let etag = format!("\"{}\"", cid);
             if inm_str == etag || inm_str.trim_matches('"') == cid {
                 let mut h = HeaderMap::new();
                 h.insert(header::ETAG, etag.parse().unwrap());
-                return (StatusCode::NOT_MODIFIED, h, Json(json!(null)));
+                h.insert(header::VARY, "accept".parse().unwrap());
+                return (StatusCode::NOT_MODIFIED, h, Json(json!(null))).into_response();
             }
         }
     }
@@ -468,10 +1181,17 @@ pub(crate) async fn get_chip(
             let mut h = HeaderMap::new();
             let etag = format!("\"{}\"", chip.cid);
             h.insert(header::ETAG, etag.parse().unwrap());
-            h.insert(
-                header::CACHE_CONTROL,
-                "public, max-age=31536000, immutable".parse().unwrap(),
-            );
+            // The quarantine flag flips on release, so don't let caches pin it.
+            let cache_control = if chip.quarantined {
+                "no-store"
+            } else {
+                "public, max-age=31536000, immutable"
+            };
+            h.insert(header::CACHE_CONTROL, cache_control.parse().unwrap());
+            h.insert(header::VARY, "accept".parse().unwrap());
+            if let ReadEncoding::Canonical(content_type) = encoding {
+                return canonical_binary_response(&chip.chip_data, content_type, h);
+            }
             (
                 StatusCode::OK,
                 h,
@@ -483,18 +1203,590 @@ pub(crate) async fn get_chip(
                     "receipt_cid": chip.receipt_cid,
                     "created_at": chip.created_at,
                     "tags": chip.tags,
+                    "quarantined": chip.quarantined,
                 })),
             )
+                .into_response()
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
             HeaderMap::new(),
             Json(json!({"@type": "ubl/error", "code": "NOT_FOUND", "message": format!("Chip {} not found", cid)})),
-        ),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             HeaderMap::new(),
             Json(json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+async fn get_chip_with_annotations(
+    state: &AppState,
+    cid: &str,
+) -> (StatusCode, HeaderMap, Json<Value>) {
+    let internal = |message: String| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            HeaderMap::new(),
+            Json(json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": message})),
+        )
+    };
+    let chip = match state.chip_store.get_chip(cid).await {
+        Ok(Some(chip)) => chip,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                HeaderMap::new(),
+                Json(
+                    json!({"@type": "ubl/error", "code": "NOT_FOUND", "message": format!("Chip {} not found", cid)}),
+                ),
+            )
+        }
+        Err(e) => return internal(e.to_string()),
+    };
+    let result = match state
+        .chip_store
+        .query(&ubl_chipstore::ChipQuery {
+            chip_type: Some(ubl_runtime::annotation_chip::TYPE_ANNOTATION.to_string()),
+            tags: vec![format!("target_cid:{}", cid)],
+            created_after: None,
+            created_before: None,
+            executor_did: None,
+            limit: Some(ANNOTATIONS_MAX_LISTED),
+            offset: None,
+        })
+        .await
+    {
+        Ok(result) => result,
+        Err(e) => return internal(format!("annotation query failed: {}", e)),
+    };
+    // Queries return newest first; annotations read in append order.
+    let annotations: Vec<Value> = result
+        .chips
+        .iter()
+        .rev()
+        .filter(|a| !a.quarantined)
+        .map(|a| {
+            json!({
+                "cid": a.cid,
+                "receipt_cid": a.receipt_cid,
+                "created_at": a.created_at,
+                "executor_did": a.execution_metadata.executor_did,
+                "author": a.author,
+                "notes": a.chip_data.get("notes").cloned().unwrap_or(Value::Null),
+            })
+        })
+        .collect();
+
+    let mut h = HeaderMap::new();
+    h.insert(header::CACHE_CONTROL, "no-cache".parse().unwrap());
+    (
+        StatusCode::OK,
+        h,
+        Json(json!({
+            "@type": "ubl/chip",
+            "cid": chip.cid,
+            "chip_type": chip.chip_type,
+            "chip_data": chip.chip_data,
+            "receipt_cid": chip.receipt_cid,
+            "created_at": chip.created_at,
+            "tags": chip.tags,
+            "quarantined": chip.quarantined,
+            "annotations": annotations,
+            "annotations_truncated": result.has_more,
+        })),
+    )
+}
+
+const SEARCH_DEFAULT_LIMIT: usize = 50;
+const SEARCH_MAX_LIMIT: usize = 500;
+
+/// Chip types whose bodies carry credentials: a `ubl/token`'s `@id` is its
+/// bearer string, and a revocation names it. Read endpoints never list them.
+pub(crate) const SECRET_CHIP_TYPES: [&str; 2] = ["ubl/token", TOKEN_REVOKE_CHIP_TYPE];
+
+/// Which stored chips a reader may list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ChipReadScope {
+    /// An operator presenting an admin API key: every world.
+    Admin,
+    /// A session bearer: chips whose `@world` the token's world covers.
+    World(String),
+}
+
+impl ChipReadScope {
+    /// Quarantined and secret-bearing chips are never listed.
+    pub(crate) fn allows(&self, chip: &ubl_chipstore::StoredChip) -> bool {
+        if chip.quarantined || SECRET_CHIP_TYPES.contains(&chip.chip_type.as_str()) {
+            return false;
+        }
+        match self {
+            Self::Admin => true,
+            Self::World(scope) => chip
+                .chip_data
+                .get("@world")
+                .and_then(|v| v.as_str())
+                .is_some_and(|world| world_scope_allows(scope, world)),
+        }
+    }
+}
+
+/// Token scopes that allow listing chips in the token's world.
+const CHIP_READ_SCOPES: [&str; 5] = ["read", "write", "chip:write", "mcp", "mcp:write"];
+
+/// Resolve who is reading: an admin key, else a session bearer with a read
+/// scope. Anonymous callers are refused.
+pub(crate) async fn chip_read_scope(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<ChipReadScope, (StatusCode, Json<Value>)> {
+    let refuse = |status: StatusCode, code: ErrorCode, message: String| {
+        (
+            status,
+            Json(json!({"@type": "ubl/error", "code": code, "message": message})),
+        )
+    };
+    if state.admin_access.authorize(headers).is_ok() {
+        return Ok(ChipReadScope::Admin);
+    }
+    match resolve_session_bearer(state, headers).await {
+        Ok(Some(auth)) if scope_allows_any(&auth.scope, &CHIP_READ_SCOPES) => {
+            Ok(ChipReadScope::World(auth.world))
+        }
+        Ok(Some(_)) => Err(refuse(
+            StatusCode::FORBIDDEN,
+            ErrorCode::PolicyDenied,
+            "token scope does not allow reading chips".to_string(),
+        )),
+        Ok(None) => Err(refuse(
+            StatusCode::UNAUTHORIZED,
+            ErrorCode::Unauthorized,
+            "chip search requires an admin API key or Authorization: Bearer <token>".to_string(),
+        )),
+        Err(rejection) => Err(refuse(
+            StatusCode::from_u16(rejection.code.http_status()).unwrap_or(StatusCode::UNAUTHORIZED),
+            rejection.code,
+            rejection.message,
+        )),
+    }
+}
+
+/// GET /v1/chips/search — [`ubl_chipstore::ChipQuery`] over query params,
+/// newest first: `chip_type` (alias `type`), `tag` (repeatable; `tags` also
+/// takes a comma-separated list — a chip must carry every tag), `after`,
+/// `before`, `limit` (default 50, max 500) and `offset`.
+///
+/// Needs an admin key or a session bearer; a bearer only sees chips in its
+/// token's world. Quarantined chips and [`SECRET_CHIP_TYPES`] are never
+/// returned, and `total` counts only what the caller may see.
+pub(crate) async fn search_chips(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<Vec<(String, String)>>,
+) -> (StatusCode, Json<Value>) {
+    match chip_read_scope(&state, &headers).await {
+        Ok(scope) => search_chips_as(&state, &scope, params).await,
+        Err(refused) => refused,
+    }
+}
+
+/// [`search_chips`] for an already-resolved reader.
+pub(crate) async fn search_chips_as(
+    state: &AppState,
+    scope: &ChipReadScope,
+    params: Vec<(String, String)>,
+) -> (StatusCode, Json<Value>) {
+    let invalid = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"@type": "ubl/error", "code": "INVALID_QUERY", "message": message})),
+        )
+    };
+    let mut query = ubl_chipstore::ChipQuery {
+        chip_type: None,
+        tags: Vec::new(),
+        created_after: None,
+        created_before: None,
+        executor_did: None,
+        limit: Some(SEARCH_DEFAULT_LIMIT),
+        offset: None,
+    };
+    for (key, value) in params {
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        match key.as_str() {
+            "chip_type" | "type" => query.chip_type = Some(value.to_string()),
+            "tag" => query.tags.push(value.to_string()),
+            "tags" => query.tags.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string),
+            ),
+            "after" => query.created_after = Some(value.to_string()),
+            "before" => query.created_before = Some(value.to_string()),
+            "limit" | "offset" => {
+                let Ok(n) = value.parse::<usize>() else {
+                    return invalid(format!("{} must be a non-negative integer", key));
+                };
+                if key == "limit" {
+                    query.limit = Some(n.clamp(1, SEARCH_MAX_LIMIT));
+                } else {
+                    query.offset = Some(n);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Visibility is decided per chip, so page after filtering.
+    let unpaged = ubl_chipstore::ChipQuery {
+        limit: Some(usize::MAX),
+        offset: None,
+        ..query.clone()
+    };
+    match state.chip_store.query(&unpaged).await {
+        Ok(result) => {
+            let visible: Vec<_> = result
+                .chips
+                .into_iter()
+                .filter(|c| scope.allows(c))
+                .collect();
+            let total = visible.len();
+            let offset = query.offset.unwrap_or(0);
+            let chips: Vec<_> = visible
+                .into_iter()
+                .skip(offset)
+                .take(query.limit.unwrap_or(SEARCH_DEFAULT_LIMIT))
+                .collect();
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "@type": "ubl/chips.search.response",
+                    "query": query,
+                    "count": chips.len(),
+                    "total": total,
+                    "has_more": offset + chips.len() < total,
+                    "chips": chips,
+                })),
+            )
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": format!("chip search failed: {}", e)}),
+            ),
         ),
     }
 }
+
+const LINEAGE_DEFAULT_DEPTH: usize = 3;
+const LINEAGE_MAX_DEPTH: usize = 10;
+const LINEAGE_MAX_NODES: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LineageQuery {
+    pub(crate) depth: Option<usize>,
+}
+
+/// GET /v1/chips/:cid/lineage — bounded provenance subgraph around a chip.
+///
+/// Edges carry `from`/`to`/`type`. Parent links are reported once, as
+/// `parent` when reached walking up and `child` when reached walking down.
+/// A `ubl/merge` node (`merge: true`) has one parent edge per merged lineage.
+/// `supersedes` points from the newer chip to the older one; `tombstone`
+/// points from a `ubl/revoke` chip to the chip it revokes. Each node carries
+/// its chip type and the decision it was stored under (`null` when missing).
+/// A chip already in the graph is linked again but not revisited, so cyclic
+/// `parents` terminate.
+pub(crate) async fn get_chip_lineage(
+    State(state): State<AppState>,
+    Path(cid): Path<String>,
+    Query(query): Query<LineageQuery>,
+) -> (StatusCode, Json<Value>) {
+    if !cid.starts_with("b3:") {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(
+                json!({"@type": "ubl/error", "code": "INVALID_CID", "message": "CID must start with b3:"}),
+            ),
+        );
+    }
+    let max_depth = query
+        .depth
+        .unwrap_or(LINEAGE_DEFAULT_DEPTH)
+        .min(LINEAGE_MAX_DEPTH);
+
+    match state.chip_store.get_chip(&cid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(
+                    json!({"@type": "ubl/error", "code": "NOT_FOUND", "message": format!("Chip {} not found", cid)}),
+                ),
+            );
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()}),
+                ),
+            );
+        }
+    }
+
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut link_keys: BTreeSet<(String, String, &'static str)> = BTreeSet::new();
+    let mut nodes: Vec<Value> = Vec::new();
+    let mut edges: Vec<Value> = Vec::new();
+    let mut truncated = false;
+    let mut frontier: VecDeque<(String, usize)> = VecDeque::new();
+    visited.insert(cid.clone());
+    frontier.push_back((cid.clone(), 0));
+
+    while let Some((node_cid, depth)) = frontier.pop_front() {
+        let chip = match state.chip_store.get_chip(&node_cid).await {
+            Ok(chip) => chip,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(
+                        json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()}),
+                    ),
+                );
+            }
+        };
+        nodes.push(json!({
+            "cid": node_cid,
+            "chip_type": chip.as_ref().map(|c| c.chip_type.clone()),
+            "depth": depth,
+            "missing": chip.is_none(),
+            "decision": chip
+                .as_ref()
+                .map(|c| if c.quarantined { "Quarantine" } else { "Allow" }),
+            "merge": chip
+                .as_ref()
+                .is_some_and(|c| c.chip_type == ubl_runtime::merge_chip::TYPE_MERGE),
+        }));
+        if depth >= max_depth {
+            continue;
+        }
+
+        let neighbours = match lineage_neighbours(&state, &node_cid, chip.as_ref()).await {
+            Ok(v) => v,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e})),
+                );
+            }
+        };
+        for (from, to, kind, next) in neighbours {
+            // A parent/child link is one relation seen from either end.
+            let key = match kind {
+                "parent" => (to.clone(), from.clone(), "parent"),
+                "child" => (from.clone(), to.clone(), "parent"),
+                other => (from.clone(), to.clone(), other),
+            };
+            if !link_keys.insert(key) {
+                continue;
+            }
+            if visited.contains(&next) {
+                edges.push(json!({"from": from, "to": to, "type": kind}));
+                continue;
+            }
+            if visited.len() >= LINEAGE_MAX_NODES {
+                truncated = true;
+                continue;
+            }
+            edges.push(json!({"from": from, "to": to, "type": kind}));
+            visited.insert(next.clone());
+            frontier.push_back((next, depth + 1));
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/chip.lineage",
+            "cid": cid,
+            "depth": max_depth,
+            "node_count": nodes.len(),
+            "edge_count": edges.len(),
+            "truncated": truncated,
+            "nodes": nodes,
+            "edges": edges,
+        })),
+    )
+}
+
+const CHILDREN_MAX_NODES: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ChildrenQuery {
+    #[serde(default)]
+    pub(crate) recursive: bool,
+}
+
+/// GET /v1/chips/:cid/children — chips that declare `cid` as a parent.
+///
+/// Direct children by default; `recursive=true` walks every descendant,
+/// breadth-first, up to `CHILDREN_MAX_NODES` (`truncated` is set when the
+/// walk stops early). Each chip is listed once, under the first parent it was
+/// reached from.
+pub(crate) async fn get_chip_children(
+    State(state): State<AppState>,
+    Path(cid): Path<String>,
+    Query(query): Query<ChildrenQuery>,
+) -> (StatusCode, Json<Value>) {
+    if !cid.starts_with("b3:") {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(
+                json!({"@type": "ubl/error", "code": "INVALID_CID", "message": "CID must start with b3:"}),
+            ),
+        );
+    }
+    match state.chip_store.get_chip(&cid).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(
+                    json!({"@type": "ubl/error", "code": "NOT_FOUND", "message": format!("Chip {} not found", cid)}),
+                ),
+            );
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()}),
+                ),
+            );
+        }
+    }
+
+    let mut visited: HashSet<String> = HashSet::from([cid.clone()]);
+    let mut frontier: VecDeque<(String, usize)> = VecDeque::from([(cid.clone(), 1)]);
+    let mut children: Vec<Value> = Vec::new();
+    let mut truncated = false;
+    'walk: while let Some((parent, depth)) = frontier.pop_front() {
+        let found = match state.chip_store.get_children(&parent).await {
+            Ok(found) => found,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(
+                        json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()}),
+                    ),
+                );
+            }
+        };
+        for child in found {
+            let child_cid = child.cid.as_str().to_string();
+            if !visited.insert(child_cid.clone()) {
+                continue;
+            }
+            if children.len() >= CHILDREN_MAX_NODES {
+                truncated = true;
+                break 'walk;
+            }
+            children.push(json!({
+                "cid": child_cid,
+                "chip_type": child.chip_type,
+                "created_at": child.created_at,
+                "parent": parent,
+                "depth": depth,
+            }));
+            if query.recursive {
+                frontier.push_back((child_cid, depth + 1));
+            }
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/chip.children",
+            "cid": cid,
+            "recursive": query.recursive,
+            "count": children.len(),
+            "truncated": truncated,
+            "children": children,
+        })),
+    )
+}
+
+/// Direct lineage links of one chip as `(from, to, type, neighbour_cid)`.
+async fn lineage_neighbours(
+    state: &AppState,
+    cid: &str,
+    chip: Option<&ubl_chipstore::StoredChip>,
+) -> Result<Vec<(String, String, &'static str, String)>, String> {
+    let mut out = Vec::new();
+
+    if let Some(chip) = chip {
+        for parent in chip.lineage_parents() {
+            out.push((cid.to_string(), parent.clone(), "parent", parent));
+        }
+        if let Some(older) = chip.chip_data.get("supersedes").and_then(|v| v.as_str()) {
+            out.push((
+                cid.to_string(),
+                older.to_string(),
+                "supersedes",
+                older.to_string(),
+            ));
+        }
+        if chip.chip_type == "ubl/revoke" {
+            if let Some(target) = chip.chip_data.get("target_cid").and_then(|v| v.as_str()) {
+                out.push((
+                    cid.to_string(),
+                    target.to_string(),
+                    "tombstone",
+                    target.to_string(),
+                ));
+            }
+        }
+    }
+
+    for (tag, chip_type) in [
+        (format!("parent:{}", cid), None),
+        (format!("supersedes:{}", cid), None),
+        (format!("target_cid:{}", cid), Some("ubl/revoke")),
+    ] {
+        let result = state
+            .chip_store
+            .query(&ubl_chipstore::ChipQuery {
+                chip_type: chip_type.map(str::to_string),
+                tags: vec![tag.clone()],
+                created_after: None,
+                created_before: None,
+                executor_did: None,
+                limit: Some(LINEAGE_MAX_NODES),
+                offset: None,
+            })
+            .await
+            .map_err(|e| format!("lineage query failed: {}", e))?;
+        for other in result.chips {
+            let other_cid = other.cid.as_str().to_string();
+            if tag.starts_with("parent:") {
+                out.push((cid.to_string(), other_cid.clone(), "child", other_cid));
+            } else if tag.starts_with("supersedes:") {
+                out.push((other_cid.clone(), cid.to_string(), "supersedes", other_cid));
+            } else {
+                out.push((other_cid.clone(), cid.to_string(), "tombstone", other_cid));
+            }
+        }
+    }
+
+    Ok(out)
+}
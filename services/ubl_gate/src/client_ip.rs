@@ -0,0 +1,181 @@
+//! Client IP resolution behind trusted proxies.
+//!
+//! Forwarded headers are honored only when the TCP peer is listed in
+//! `UBL_TRUSTED_PROXIES`; from anyone else they are ignored and the peer
+//! address is the client. `X-Forwarded-For` is always walked from a trusted
+//! peer; `CF-Connecting-IP` only with `UBL_TRUST_CF_CONNECTING_IP=true`, for
+//! deployments whose trusted proxies are Cloudflare's edge. The resolved
+//! address is handed to handlers in [`CLIENT_IP_HEADER`], which the
+//! middleware always overwrites so callers cannot inject it.
+
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::{warn, Instrument};
+
+use crate::state::AppState;
+use crate::utils::{csv_env, env_bool};
+
+/// Gate-internal request header carrying the resolved client IP.
+pub(crate) const CLIENT_IP_HEADER: &str = "x-ubl-client-ip";
+
+/// The address [`resolve_client_ip`] published for this request, if any.
+pub(crate) fn client_ip_from_headers(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get(CLIENT_IP_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// An address or CIDR block, e.g. `10.0.0.0/8`, `::1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IpNet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNet {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (addr, prefix) = match raw.split_once('/') {
+            Some((addr, prefix)) => (addr.trim(), Some(prefix.trim().parse::<u8>().ok()?)),
+            None => (raw.trim(), None),
+        };
+        let addr: IpAddr = addr.parse().ok()?;
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        (prefix <= max).then_some(Self { addr, prefix })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Upstreams allowed to report the client address. Empty = trust no one.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TrustedProxies {
+    nets: Vec<IpNet>,
+    /// Take `CF-Connecting-IP` from a trusted peer before `X-Forwarded-For`.
+    cf_connecting_ip: bool,
+}
+
+impl TrustedProxies {
+    /// `UBL_TRUSTED_PROXIES`: comma-separated addresses or CIDR blocks;
+    /// `UBL_TRUST_CF_CONNECTING_IP`: also honor `CF-Connecting-IP`.
+    pub fn from_env() -> Self {
+        Self::parse(&csv_env("UBL_TRUSTED_PROXIES"))
+            .with_cf_connecting_ip(env_bool("UBL_TRUST_CF_CONNECTING_IP", false))
+    }
+
+    pub fn with_cf_connecting_ip(mut self, enabled: bool) -> Self {
+        self.cf_connecting_ip = enabled;
+        self
+    }
+
+    /// Invalid entries are skipped with a warning.
+    pub fn parse<S: AsRef<str>>(entries: &[S]) -> Self {
+        let nets = entries
+            .iter()
+            .filter_map(|e| {
+                let net = IpNet::parse(e.as_ref());
+                if net.is_none() {
+                    warn!(entry = %e.as_ref(), "ignoring invalid UBL_TRUSTED_PROXIES entry");
+                }
+                net
+            })
+            .collect();
+        Self {
+            nets,
+            cf_connecting_ip: false,
+        }
+    }
+
+    pub fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.nets.iter().any(|n| n.contains(ip))
+    }
+
+    /// Client address for a request from `peer`. From a trusted peer,
+    /// `CF-Connecting-IP` wins when opted in; otherwise `X-Forwarded-For` is
+    /// walked from the right, skipping trusted hops, and the first untrusted hop is the
+    /// client. An unparsable hop stops the walk at the last good address.
+    pub fn resolve(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let peer = peer?;
+        if !self.is_trusted(peer) {
+            return Some(peer);
+        }
+        if let Some(ip) = self
+            .cf_connecting_ip
+            .then(|| headers.get("CF-Connecting-IP"))
+            .flatten()
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<IpAddr>().ok())
+        {
+            return Some(ip);
+        }
+        let mut client = peer;
+        for hop in headers
+            .get_all("X-Forwarded-For")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+        {
+            let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+                break;
+            };
+            client = ip;
+            if !self.is_trusted(ip) {
+                break;
+            }
+        }
+        Some(client)
+    }
+}
+
+/// Resolve the client IP, publish it in [`CLIENT_IP_HEADER`] and run the
+/// request inside a span tagged with it.
+pub(crate) async fn resolve_client_ip(
+    State(state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let peer = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client = state.trusted_proxies.resolve(peer, req.headers());
+    let headers = req.headers_mut();
+    headers.remove(CLIENT_IP_HEADER);
+    if let Some(value) = client.and_then(|ip| HeaderValue::from_str(&ip.to_string()).ok()) {
+        headers.insert(CLIENT_IP_HEADER, value);
+    }
+    let client_ip = client.map(|ip| ip.to_string()).unwrap_or_default();
+    let span = tracing::info_span!(
+        "request",
+        method = %req.method(),
+        path = %req.uri().path(),
+        client_ip = %client_ip,
+    );
+    next.run(req).instrument(span).await
+}
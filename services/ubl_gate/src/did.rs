@@ -0,0 +1,130 @@
+//! DID resolution: `GET /v1/did/:did`.
+//!
+//! `did:key` DIDs are self-describing, so the document is built from the
+//! embedded Ed25519 key. Other DIDs resolve only when a stored `ubl/user`
+//! chip registers them with a `public_key_multibase` and the chip's verified
+//! author may bind that key: an operator, the DID itself, or a signer holding
+//! the bound key. Registrations are found through the `did:` tag index and
+//! the earliest bindable one wins; they are listed under `ubl:registrations`.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use ubl_chipstore::{ChipAuthor, ChipQuery, StoredChip};
+
+use crate::state::AppState;
+
+/// Chip types whose body registers a DID under `did`.
+const DID_REGISTRATION_TYPES: &[&str] = &["ubl/user"];
+
+/// Registrations read per type for one DID.
+const MAX_DID_REGISTRATIONS: usize = 100;
+
+pub(crate) async fn resolve_did(
+    State(state): State<AppState>,
+    Path(did): Path<String>,
+) -> Response {
+    let mut bindings = Vec::new();
+    for chip_type in DID_REGISTRATION_TYPES {
+        let query = ChipQuery {
+            chip_type: Some(chip_type.to_string()),
+            tags: vec![format!("did:{}", did)],
+            created_after: None,
+            created_before: None,
+            executor_did: None,
+            limit: Some(MAX_DID_REGISTRATIONS),
+            offset: None,
+        };
+        let chips = match state.chip_store.query(&query).await {
+            Ok(result) => result.chips,
+            Err(e) => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()})),
+                )
+                    .into_response();
+            }
+        };
+        bindings.extend(chips.into_iter().filter_map(|chip| {
+            if chip.quarantined
+                || chip.chip_data.get("did").and_then(|v| v.as_str()) != Some(did.as_str())
+            {
+                return None;
+            }
+            let key = chip
+                .chip_data
+                .get("public_key_multibase")
+                .and_then(|v| v.as_str())
+                .and_then(public_key_from_multibase)?;
+            may_bind(&chip, &did, &key).then_some((chip, key))
+        }));
+    }
+    bindings.sort_by(|(a, _), (b, _)| a.created_at.cmp(&b.created_at));
+
+    let key = if did.starts_with("did:key:") {
+        ubl_kms::verifying_key_from_did(&did)
+            .ok()
+            .map(|vk| vk.to_bytes())
+    } else {
+        bindings.first().map(|(_, key)| *key)
+    };
+    let Some(key) = key else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "NOT_FOUND",
+                "message": format!("DID {} could not be resolved", did),
+            })),
+        )
+            .into_response();
+    };
+
+    let mut doc = ubl_did::ed25519_did_document(&did, &key);
+    if !bindings.is_empty() {
+        doc["ubl:registrations"] = bindings
+            .iter()
+            .map(|(chip, _)| {
+                json!({
+                    "chip_cid": chip.cid.as_str(),
+                    "chip_type": chip.chip_type,
+                    "world": chip.chip_data.get("@world").cloned().unwrap_or(Value::Null),
+                })
+            })
+            .collect();
+    }
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/did+json")],
+        Json(doc),
+    )
+        .into_response()
+}
+
+/// Whether `chip`'s verified author may bind `key` to `did`: an operator,
+/// the DID itself, or a signer whose `did:key` is `key`.
+fn may_bind(chip: &StoredChip, did: &str, key: &[u8; 32]) -> bool {
+    match &chip.author {
+        Some(ChipAuthor::Admin) => true,
+        Some(ChipAuthor::Signer { did: signer }) => {
+            signer == did
+                || ubl_kms::verifying_key_from_did(signer).is_ok_and(|vk| vk.to_bytes() == *key)
+        }
+        Some(ChipAuthor::Token {
+            did: Some(holder), ..
+        }) => holder == did,
+        _ => false,
+    }
+}
+
+/// Ed25519 key from `z<base58>` (raw 32 bytes or multicodec-prefixed).
+fn public_key_from_multibase(multibase: &str) -> Option<[u8; 32]> {
+    multibase.strip_prefix('z')?;
+    ubl_kms::verifying_key_from_did(&format!("did:key:{}", multibase))
+        .ok()
+        .map(|vk| vk.to_bytes())
+}
@@ -3,15 +3,16 @@
 use async_stream::stream;
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{sse::{Event as SseEvent, KeepAlive, Sse}, IntoResponse, Response},
     Json,
 };
 use serde::Deserialize;
 use serde_json::{json, Value};
+use std::collections::HashSet;
 use std::convert::Infallible;
 use std::time::Duration;
-use ubl_eventstore::EventQuery;
+use ubl_eventstore::{event_cursor, EventQuery, EventStoreError};
 use ubl_runtime::event_bus::ReceiptEvent;
 
 use crate::metrics;
@@ -24,16 +25,29 @@ pub(crate) struct EventStreamQuery {
     pub(crate) stage: Option<String>,
     pub(crate) decision: Option<String>,
     pub(crate) code: Option<String>,
+    pub(crate) code_prefix: Option<String>,
     #[serde(rename = "type")]
     pub(crate) chip_type: Option<String>,
     pub(crate) actor: Option<String>,
     pub(crate) since: Option<String>,
+    pub(crate) until: Option<String>,
+    /// Resume cursor when the client cannot send `Last-Event-ID`.
+    pub(crate) since_id: Option<String>,
     pub(crate) limit: Option<usize>,
 }
 
+/// GET /v1/events — replays stored history, then follows the live bus.
+///
+/// Every frame's SSE id is the event's store cursor
+/// ([`ubl_eventstore::event_cursor`]). A reconnect with `Last-Event-ID` (or
+/// `?since_id=`) replays everything after it, `limit` events per store page,
+/// until caught up; live events already replayed are skipped, so the hand-off
+/// neither drops nor repeats frames. If a later page cannot be read, a
+/// `ubl.gap` frame names the last replayed cursor before the live tail starts.
 pub(crate) async fn stream_events(
     State(state): State<AppState>,
     Query(query): Query<EventStreamQuery>,
+    headers: HeaderMap,
 ) -> Response {
     let Some(store) = state.event_store.as_ref() else {
         return (
@@ -53,14 +67,41 @@ pub(crate) async fn stream_events(
         stage: query.stage.clone(),
         decision: query.decision.clone(),
         code: query.code.clone(),
+        code_prefix: query.code_prefix.clone(),
         chip_type: query.chip_type.clone(),
         actor: query.actor.clone(),
         since: query.since.clone(),
+        until: query.until.clone(),
         limit: query.limit,
     };
 
-    let historical = match store.query(&db_query) {
+    let resume_from = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string)
+        .or_else(|| query.since_id.clone());
+
+    // Subscribe before reading history so nothing lands in between.
+    let mut rx = state.pipeline.event_bus.subscribe();
+    let historical = match resume_from.as_deref() {
+        Some(cursor) => store.query_after(cursor, &db_query),
+        None => store.query(&db_query),
+    };
+    let historical = match historical {
         Ok(events) => events,
+        Err(EventStoreError::InvalidEvent(message)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INVALID_REQUEST",
+                    "message": format!("cannot resume event stream: {}", message),
+                })),
+            )
+                .into_response();
+        }
         Err(e) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -84,31 +125,58 @@ pub(crate) async fn stream_events(
     }
 
     metrics::inc_events_stream_clients(&world_label);
-    let mut rx = state.pipeline.event_bus.subscribe();
+    let pipeline = state.pipeline.clone();
+    let store = store.clone();
     let stream_world = world_label.clone();
     let live_filters = query.clone();
     let sse_stream = stream! {
         let _guard = StreamClientGuard { world: stream_world };
+        let mut replayed = HashSet::new();
 
-        for event in historical {
-            let payload = match serde_json::to_string(&event) {
-                Ok(p) => p,
-                Err(_) => {
-                    metrics::inc_events_stream_dropped("serialize_error");
-                    continue;
+        let mut page = historical;
+        loop {
+            let mut last_id = None;
+            for event in page {
+                let id = sse_event_id(&event);
+                last_id = Some(id.clone());
+                replayed.insert(id.clone());
+                let payload = match serde_json::to_string(&event) {
+                    Ok(p) => p,
+                    Err(_) => {
+                        metrics::inc_events_stream_dropped("serialize_error");
+                        continue;
+                    }
+                };
+                yield Ok::<SseEvent, Infallible>(SseEvent::default().id(id).event("ubl.event").data(payload));
+            }
+            // A fresh connection replays one page of history; a resume
+            // pages forward until a page comes back empty.
+            let Some(after) = last_id.filter(|_| resume_from.is_some()) else {
+                break;
+            };
+            page = match store.query_after(&after, &db_query) {
+                Ok(next) => next,
+                Err(e) => {
+                    metrics::inc_events_stream_dropped("replay_error");
+                    let gap = json!({"after": after, "message": e.to_string()});
+                    yield Ok::<SseEvent, Infallible>(SseEvent::default().event("ubl.gap").data(gap.to_string()));
+                    break;
                 }
             };
-            let id = event.get("@id").and_then(|v| v.as_str()).unwrap_or("evt");
-            yield Ok::<SseEvent, Infallible>(SseEvent::default().id(id).event("ubl.event").data(payload));
         }
 
         loop {
             match rx.recv().await {
                 Ok(receipt_event) => {
-                    let hub = to_hub_event(&receipt_event);
+                    let mut hub = to_hub_event(&receipt_event);
+                    pipeline.sign_hub_event(&mut hub);
                     if !hub_matches_query(&hub, &live_filters) {
                         continue;
                     }
+                    let id = sse_event_id(&hub);
+                    if replayed.remove(&id) {
+                        continue;
+                    }
                     let payload = match serde_json::to_string(&hub) {
                         Ok(p) => p,
                         Err(_) => {
@@ -116,7 +184,6 @@ pub(crate) async fn stream_events(
                             continue;
                         }
                     };
-                    let id = hub.get("@id").and_then(|v| v.as_str()).unwrap_or("evt");
                     yield Ok::<SseEvent, Infallible>(SseEvent::default().id(id).event("ubl.event").data(payload));
                 }
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
@@ -142,21 +209,32 @@ pub(crate) struct EventSearchQuery {
     pub(crate) stage: Option<String>,
     pub(crate) decision: Option<String>,
     pub(crate) code: Option<String>,
+    pub(crate) code_prefix: Option<String>,
     #[serde(rename = "type")]
     pub(crate) chip_type: Option<String>,
     pub(crate) actor: Option<String>,
+    #[serde(alias = "since")]
     pub(crate) from: Option<String>,
+    #[serde(alias = "until")]
     pub(crate) to: Option<String>,
     pub(crate) page_key: Option<String>,
     pub(crate) limit: Option<usize>,
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct WorldsQuery {
+    pub(crate) prefix: Option<String>,
+    pub(crate) limit: Option<usize>,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub(crate) struct AdvisorQuery {
     pub(crate) world: Option<String>,
     pub(crate) window: Option<String>,
     pub(crate) interval_ms: Option<u64>,
     pub(crate) limit: Option<usize>,
+    /// Snapshots only: store the snapshot as a signed `ubl/advisor.snapshot` chip.
+    pub(crate) persist: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -177,6 +255,61 @@ pub(crate) struct LlmPanelQuery {
     pub(crate) chip_type: Option<String>,
 }
 
+/// `GET /v1/worlds`: the world directory maintained by the EventStore on
+/// ingestion, filtered by `prefix` and bounded by `limit` (default 100).
+pub(crate) async fn list_worlds(
+    State(state): State<AppState>,
+    Query(query): Query<WorldsQuery>,
+) -> Response {
+    let Some(store) = state.event_store.as_ref() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "UNAVAILABLE",
+                "message": "Event hub unavailable: enable EventStore",
+            })),
+        )
+            .into_response();
+    };
+
+    let worlds = match store.worlds(query.prefix.as_deref(), query.limit.unwrap_or(100)) {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("world listing failed: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let worlds: Vec<Value> = worlds
+        .into_iter()
+        .map(|w| {
+            json!({
+                "world": w.world,
+                "last_activity": chrono::DateTime::from_timestamp_millis(w.last_activity_ms)
+                    .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+                "event_count": w.event_count,
+            })
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/worlds.response",
+            "count": worlds.len(),
+            "worlds": worlds,
+        })),
+    )
+        .into_response()
+}
+
 pub(crate) async fn search_events(
     State(state): State<AppState>,
     Query(query): Query<EventSearchQuery>,
@@ -204,13 +337,15 @@ pub(crate) async fn search_events(
         stage: query.stage.clone(),
         decision: query.decision.clone(),
         code: query.code.clone(),
+        code_prefix: query.code_prefix.clone(),
         chip_type: query.chip_type.clone(),
         actor: query.actor.clone(),
         since,
+        until: query.to.clone(),
         limit: query.limit,
     };
 
-    let mut events = match store.query(&db_query) {
+    let events = match store.query(&db_query) {
         Ok(v) => v,
         Err(e) => {
             return (
@@ -225,16 +360,6 @@ pub(crate) async fn search_events(
         }
     };
 
-    if let Some(to) = query.to.as_deref().and_then(parse_when_to_ms) {
-        events.retain(|e| {
-            let when = e
-                .get("when")
-                .and_then(|v| v.as_str())
-                .or_else(|| e.get("timestamp").and_then(|v| v.as_str()));
-            when.and_then(parse_when_to_ms).is_some_and(|ms| ms <= to)
-        });
-    }
-
     let next_page_key = events
         .last()
         .and_then(|e| {
@@ -258,6 +383,17 @@ pub(crate) async fn search_events(
 
 // ── Hub event helpers ─────────────────────────────────────────────────────────
 
+/// SSE id for a hub event: its store cursor, else its `@id`.
+fn sse_event_id(event: &Value) -> String {
+    event_cursor(event).unwrap_or_else(|| {
+        event
+            .get("@id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("evt")
+            .to_string()
+    })
+}
+
 pub(crate) fn to_hub_event(event: &ReceiptEvent) -> Value {
     let stage = normalize_stage(&event.pipeline_stage);
     let event_id = deterministic_event_id(event, &stage);
@@ -294,6 +430,7 @@ pub(crate) fn to_hub_event(event: &ReceiptEvent) -> Value {
         "decision": decision,
         "code": code,
         "knock_cid": event.knock_cid.clone(),
+        "correlation_id": event.correlation_id.clone(),
     });
     if let Some(obj) = receipt.as_object_mut() {
         if obj.get("code").is_some_and(Value::is_null) {
@@ -302,6 +439,9 @@ pub(crate) fn to_hub_event(event: &ReceiptEvent) -> Value {
         if obj.get("knock_cid").is_some_and(Value::is_null) {
             obj.remove("knock_cid");
         }
+        if obj.get("correlation_id").is_some_and(Value::is_null) {
+            obj.remove("correlation_id");
+        }
     }
 
     let mut actor = json!({
@@ -401,6 +541,30 @@ pub(crate) fn hub_matches_query(event: &Value, query: &EventStreamQuery) -> bool
             return false;
         }
     }
+    if let Some(prefix) = &query.code_prefix {
+        if !event
+            .get("receipt")
+            .and_then(|v| v.get("code"))
+            .and_then(|v| v.as_str())
+            .is_some_and(|c| c.starts_with(prefix.as_str()))
+        {
+            return false;
+        }
+    }
+    let since = query.since.as_deref().and_then(parse_when_to_ms);
+    let until = query.until.as_deref().and_then(parse_when_to_ms);
+    if since.is_some() || until.is_some() {
+        let Some(when) = event
+            .get("when")
+            .and_then(|v| v.as_str())
+            .and_then(parse_when_to_ms)
+        else {
+            return false;
+        };
+        if since.is_some_and(|s| when < s) || until.is_some_and(|u| when > u) {
+            return false;
+        }
+    }
     if let Some(chip_type) = &query.chip_type {
         let actual = event
             .get("chip")
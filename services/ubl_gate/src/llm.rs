@@ -415,6 +415,71 @@ pub(crate) fn resolve_llm_endpoint() -> Result<(String, String, Option<String>),
     }
 }
 
+pub(crate) const DEFAULT_LLM_MAX_TOKENS: u32 = 400;
+pub(crate) const DEFAULT_LLM_TEMPERATURE: f64 = 0.3;
+
+/// Sampling knobs sent with every narration request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LlmSampling {
+    pub max_tokens: u32,
+    pub temperature: f64,
+}
+
+impl LlmSampling {
+    /// `UBL_LLM_MAX_TOKENS` (>= 1) and `UBL_LLM_TEMPERATURE` (0..=2);
+    /// missing or out-of-range values keep the defaults.
+    pub(crate) fn from_env() -> Self {
+        Self::parse(
+            std::env::var("UBL_LLM_MAX_TOKENS").ok().as_deref(),
+            std::env::var("UBL_LLM_TEMPERATURE").ok().as_deref(),
+        )
+    }
+
+    pub(crate) fn parse(max_tokens: Option<&str>, temperature: Option<&str>) -> Self {
+        let max_tokens = match max_tokens.map(|v| v.trim().parse::<u32>()) {
+            Some(Ok(n)) if n >= 1 => n,
+            Some(_) => {
+                tracing::warn!("UBL_LLM_MAX_TOKENS must be an integer >= 1; using default");
+                DEFAULT_LLM_MAX_TOKENS
+            }
+            None => DEFAULT_LLM_MAX_TOKENS,
+        };
+        let temperature = match temperature.map(|v| v.trim().parse::<f64>()) {
+            Some(Ok(t)) if (0.0..=2.0).contains(&t) => t,
+            Some(_) => {
+                tracing::warn!("UBL_LLM_TEMPERATURE must be within 0..=2; using default");
+                DEFAULT_LLM_TEMPERATURE
+            }
+            None => DEFAULT_LLM_TEMPERATURE,
+        };
+        Self {
+            max_tokens,
+            temperature,
+        }
+    }
+}
+
+/// Chat-completions request body.
+pub(crate) fn llm_payload(
+    model: &str,
+    page: &str,
+    context: &Value,
+    sampling: LlmSampling,
+    stream: bool,
+) -> Value {
+    let user_msg = format!("Contexto (página '{}'): {}", page, context);
+    json!({
+        "model": model,
+        "messages": [
+            {"role": "system", "content": llm_system_prompt(page)},
+            {"role": "user",   "content": user_msg}
+        ],
+        "max_tokens": sampling.max_tokens,
+        "temperature": sampling.temperature,
+        "stream": stream
+    })
+}
+
 pub(crate) fn llm_system_prompt(page: &str) -> &'static str {
     match page {
         "receipt" => concat!(
@@ -423,6 +488,12 @@ pub(crate) fn llm_system_prompt(page: &str) -> &'static str {
             "o que aconteceu, por que importa, o que observar. ",
             "Responda em portugu\u{ea}s. 1 par\u{e1}grafo curto, m\u{e1}ximo 80 palavras. Sem bullets."
         ),
+        "receipt.deny" => concat!(
+            "Voc\u{ea} \u{e9} o UBL Advisory Engine. Este receipt foi NEGADO. ",
+            "Cite o policy_id e a regra que falhou (campo denied_by), explique o motivo ",
+            "e d\u{ea} uma sugest\u{e3}o concreta de corre\u{e7}\u{e3}o baseada em remediation_hint. ",
+            "Responda em portugu\u{ea}s. M\u{e1}ximo 80 palavras. Sem bullets."
+        ),
         _ => concat!(
             "Voc\u{ea} \u{e9} um analista t\u{e9}cnico do sistema UBL (Universal Business Leverage). ",
             "O UBL usa um pipeline determin\u{ed}stico KNOCK\u{2192}WA\u{2192}CHECK\u{2192}TR\u{2192}WF com chips, receipts e pol\u{ed}ticas. ",
@@ -443,17 +514,7 @@ pub(crate) async fn call_real_llm(
         .and_then(|v| v.parse::<u64>().ok())
         .unwrap_or(12_000);
 
-    let user_msg = format!("Contexto (página '{}'): {}", page, context);
-    let payload = json!({
-        "model": model,
-        "messages": [
-            {"role": "system", "content": llm_system_prompt(page)},
-            {"role": "user",   "content": user_msg}
-        ],
-        "max_tokens": 400,
-        "temperature": 0.3,
-        "stream": false
-    });
+    let payload = llm_payload(&model, page, context, LlmSampling::from_env(), false);
 
     let mut req = client
         .post(&endpoint)
@@ -502,17 +563,7 @@ pub(crate) async fn call_real_llm_stream_sse(
         .and_then(|v| v.parse::<u64>().ok())
         .unwrap_or(30_000);
 
-    let user_msg = format!("Contexto (página '{}'): {}", page, context);
-    let payload = json!({
-        "model": model,
-        "messages": [
-            {"role": "system", "content": llm_system_prompt(&page)},
-            {"role": "user",   "content": user_msg}
-        ],
-        "max_tokens": 400,
-        "temperature": 0.3,
-        "stream": true
-    });
+    let payload = llm_payload(&model, &page, &context, LlmSampling::from_env(), true);
 
     let mut req = client
         .post(&endpoint)
@@ -571,7 +622,7 @@ pub(crate) async fn call_real_llm_stream_sse(
                 else { continue };
 
                 yield Ok::<SseEvent, Infallible>(
-                    SseEvent::default().event("token").data(token.to_string())
+                    SseEvent::default().event("token").data(token)
                 );
             }
         }
@@ -0,0 +1,141 @@
+//! Serialized OpenAPI / MCP / WebMCP manifests, cached with ETag + gzip.
+//!
+//! Clients poll these documents often and they only change when the
+//! manifest does, so each one is serialized and compressed once. An entry is
+//! rebuilt when `state.manifest` is swapped for a different `Arc`.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use flate2::{write::GzEncoder, Compression};
+use serde_json::Value;
+use ubl_runtime::manifest::GateManifest;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ManifestDoc {
+    OpenApi,
+    Mcp,
+    WebMcp,
+}
+
+impl ManifestDoc {
+    fn render(self, manifest: &GateManifest) -> Value {
+        match self {
+            Self::OpenApi => manifest.to_openapi(),
+            Self::Mcp => manifest.to_mcp_manifest(),
+            Self::WebMcp => manifest.to_webmcp_manifest(),
+        }
+    }
+}
+
+struct CachedDoc {
+    /// Manifest the entry was rendered from; a different `Arc` means stale.
+    source: Arc<GateManifest>,
+    etag: HeaderValue,
+    json: Arc<[u8]>,
+    gzip: Option<Arc<[u8]>>,
+}
+
+#[derive(Default)]
+pub(crate) struct ManifestCache {
+    entries: RwLock<HashMap<ManifestDoc, Arc<CachedDoc>>>,
+}
+
+impl ManifestCache {
+    fn get_or_render(&self, doc: ManifestDoc, manifest: &Arc<GateManifest>) -> Arc<CachedDoc> {
+        if let Some(hit) = self
+            .entries
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&doc)
+            .filter(|c| Arc::ptr_eq(&c.source, manifest))
+        {
+            return hit.clone();
+        }
+        let json = serde_json::to_vec(&doc.render(manifest)).unwrap_or_default();
+        let etag = format!("\"b3:{}\"", &blake3::hash(&json).to_hex()[..32]);
+        let gzip = gzip(&json).map(Arc::from);
+        let entry = Arc::new(CachedDoc {
+            source: manifest.clone(),
+            etag: HeaderValue::from_str(&etag).expect("hex etag is a valid header"),
+            json: Arc::from(json),
+            gzip,
+        });
+        self.entries
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(doc, entry.clone());
+        entry
+    }
+
+    /// Serve `doc`: 304 on a matching `If-None-Match`, gzip when accepted.
+    pub(crate) fn respond(
+        &self,
+        doc: ManifestDoc,
+        manifest: &Arc<GateManifest>,
+        headers: &HeaderMap,
+    ) -> Response {
+        let cached = self.get_or_render(doc, manifest);
+        let mut out = HeaderMap::new();
+        out.insert(header::ETAG, cached.etag.clone());
+        out.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        out.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+
+        if if_none_match_hits(headers, &cached.etag) {
+            return (StatusCode::NOT_MODIFIED, out).into_response();
+        }
+
+        out.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        let body = match (&cached.gzip, accepts_gzip(headers)) {
+            (Some(gz), true) => {
+                out.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+                gz.clone()
+            }
+            _ => cached.json.clone(),
+        };
+        (StatusCode::OK, out, Body::from(body.to_vec())).into_response()
+    }
+}
+
+fn gzip(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes).ok()?;
+    encoder.finish().ok()
+}
+
+fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',').any(|enc| {
+                let mut parts = enc.trim().split(';');
+                let name = parts.next().unwrap_or("").trim();
+                let refused = parts.any(|p| p.trim().replace(' ', "") == "q=0");
+                name.eq_ignore_ascii_case("gzip") && !refused
+            })
+        })
+}
+
+fn if_none_match_hits(headers: &HeaderMap, etag: &HeaderValue) -> bool {
+    let Some(raw) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let etag = etag.to_str().unwrap_or("");
+    raw.split(',').any(|candidate| {
+        let candidate = candidate.trim();
+        candidate == "*" || candidate.trim_start_matches("W/") == etag
+    })
+}
@@ -4,7 +4,7 @@ use async_stream::stream;
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        Query, State,
     },
     http::{HeaderMap, StatusCode},
     response::{
@@ -14,27 +14,38 @@ use axum::{
     Json,
 };
 use serde_json::{json, Value};
+use futures_util::{SinkExt, StreamExt};
 use std::collections::HashMap;
 use std::convert::Infallible;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
 use tracing::{info, warn};
 use ubl_runtime::advisory::{Advisory, AdvisoryHook};
 use ubl_runtime::error_response::{ErrorCode, UblError};
 
-use crate::chip::submit_chip_bytes;
+use crate::chip::{chip_read_scope, search_chips_as, submit_chip_bytes, ChipReadScope};
+use crate::manifest_cache::ManifestDoc;
+use crate::receipt::ReceiptListQuery;
 use crate::state::{AppState, McpWsAuth};
 use crate::utils::{scope_allows_any, validate_mcp_ws_bearer, verify_receipt_auth_chain};
 
-pub(crate) async fn openapi_spec(State(state): State<AppState>) -> Json<Value> {
-    Json(state.manifest.to_openapi())
+pub(crate) async fn openapi_spec(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    state
+        .manifest_cache
+        .respond(ManifestDoc::OpenApi, &state.manifest, &headers)
 }
 
-pub(crate) async fn mcp_manifest(State(state): State<AppState>) -> Json<Value> {
-    Json(state.manifest.to_mcp_manifest())
+pub(crate) async fn mcp_manifest(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    state
+        .manifest_cache
+        .respond(ManifestDoc::Mcp, &state.manifest, &headers)
 }
 
-pub(crate) async fn webmcp_manifest(State(state): State<AppState>) -> Json<Value> {
-    Json(state.manifest.to_webmcp_manifest())
+pub(crate) async fn webmcp_manifest(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    state
+        .manifest_cache
+        .respond(ManifestDoc::WebMcp, &state.manifest, &headers)
 }
 
 pub(crate) async fn mcp_rpc_sse(
@@ -74,13 +85,60 @@ pub(crate) async fn mcp_rpc_sse(
     )
 }
 
+/// Largest JSON-RPC batch accepted on `/mcp/rpc`.
+const MCP_RPC_MAX_BATCH: usize = 50;
+
 pub(crate) async fn mcp_rpc(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(rpc): Json<Value>,
-) -> (StatusCode, Json<Value>) {
-    let (status, payload) = handle_mcp_rpc_request(&state, rpc, Some(&headers), None).await;
-    (status, Json(payload))
+) -> Response {
+    let Value::Array(batch) = rpc else {
+        let (status, payload) = handle_mcp_rpc_request(&state, rpc, Some(&headers), None).await;
+        return (status, Json(payload)).into_response();
+    };
+    match handle_mcp_rpc_batch(&state, batch, Some(&headers), None).await {
+        Err(invalid) => (StatusCode::BAD_REQUEST, Json(invalid)).into_response(),
+        Ok(responses) if responses.is_empty() => StatusCode::NO_CONTENT.into_response(),
+        Ok(responses) => (StatusCode::OK, Json(Value::Array(responses))).into_response(),
+    }
+}
+
+/// Run a JSON-RPC batch. `Err` is the single `-32600` reply for an empty or
+/// oversized batch; `Ok` holds one response per non-notification entry, in
+/// request order (empty when every entry was a notification).
+pub(crate) async fn handle_mcp_rpc_batch(
+    state: &AppState,
+    batch: Vec<Value>,
+    mcp_headers: Option<&HeaderMap>,
+    ws_auth: Option<&McpWsAuth>,
+) -> Result<Vec<Value>, Value> {
+    if batch.is_empty() || batch.len() > MCP_RPC_MAX_BATCH {
+        let message = if batch.is_empty() {
+            "Invalid Request: empty batch".to_string()
+        } else {
+            format!(
+                "Invalid Request: batch exceeds {} requests",
+                MCP_RPC_MAX_BATCH
+            )
+        };
+        return Err(mcp_error_value(json!(null), -32600, message, None));
+    }
+
+    // Entries run in order, each through the single-request path, so auth
+    // and per-tool rate limits apply per call. Notifications (no `id`) get
+    // no response entry.
+    let mut responses = Vec::with_capacity(batch.len());
+    for entry in batch {
+        let is_notification = entry.is_object()
+            && entry.get("id").is_none()
+            && entry.get("jsonrpc").and_then(|v| v.as_str()) == Some("2.0");
+        let (_status, payload) = handle_mcp_rpc_request(state, entry, mcp_headers, ws_auth).await;
+        if !is_notification {
+            responses.push(payload);
+        }
+    }
+    Ok(responses)
 }
 
 fn mcp_error_value(id: Value, code: i32, message: impl Into<String>, data: Option<Value>) -> Value {
@@ -107,6 +165,71 @@ fn canonical_tool_name(name: &str) -> &str {
     }
 }
 
+/// JSON-RPC error code for a gate HTTP status.
+fn mcp_code_for_status(status: StatusCode) -> i32 {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => -32006,
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => -32602,
+        StatusCode::UNAUTHORIZED => -32001,
+        StatusCode::FORBIDDEN => -32003,
+        StatusCode::NOT_FOUND => -32004,
+        StatusCode::CONFLICT => -32005,
+        StatusCode::SERVICE_UNAVAILABLE => -32000,
+        _ => -32603,
+    }
+}
+
+/// Wrap a read-only HTTP handler's response as an MCP tool result: success
+/// bodies become text content, `ubl/error` bodies a JSON-RPC error.
+fn http_result_to_mcp(id: Value, status: StatusCode, payload: Value) -> (StatusCode, Json<Value>) {
+    if status.is_success() {
+        return (
+            StatusCode::OK,
+            Json(json!({
+                "jsonrpc": "2.0", "id": id,
+                "result": { "content": [{ "type": "text", "text": serde_json::to_string(&payload).unwrap_or_default() }] }
+            })),
+        );
+    }
+    let message = payload
+        .get("message")
+        .and_then(|m| m.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("HTTP {}", status.as_u16()));
+    (
+        StatusCode::OK,
+        Json(mcp_error_value(
+            id,
+            mcp_code_for_status(status),
+            message,
+            Some(payload),
+        )),
+    )
+}
+
+/// `ubl.chip.search` arguments as `GET /v1/chips/search` query pairs.
+/// `tags` may be an array or a comma-separated string.
+fn chip_search_params(arguments: &Value) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let Some(args) = arguments.as_object() else {
+        return params;
+    };
+    for (key, value) in args {
+        match value {
+            Value::String(s) => params.push((key.clone(), s.clone())),
+            Value::Number(n) => params.push((key.clone(), n.to_string())),
+            Value::Array(items) if key == "tags" => params.extend(
+                items
+                    .iter()
+                    .filter_map(|t| t.as_str())
+                    .map(|t| ("tag".to_string(), t.to_string())),
+            ),
+            _ => {}
+        }
+    }
+    params
+}
+
 fn is_write_tool_call(tool_name: &str, arguments: &Value) -> bool {
     match canonical_tool_name(tool_name) {
         "ubl.deliver" => true,
@@ -228,15 +351,69 @@ pub(crate) async fn mcp_ws_upgrade(
         .into_response()
 }
 
-pub(crate) async fn mcp_ws_session(mut socket: WebSocket, state: AppState, auth: McpWsAuth) {
+/// Reply to one WebSocket text frame: a single response, a batch array, or
+/// `None` when a batch held only notifications.
+pub(crate) async fn mcp_ws_reply(state: &AppState, auth: &McpWsAuth, text: &str) -> Option<Value> {
+    let rpc: Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(e) => {
+            return Some(mcp_error_value(
+                json!(null),
+                -32700,
+                format!("Parse error: {}", e),
+                None,
+            ))
+        }
+    };
+    let Value::Array(batch) = rpc else {
+        let (_status, payload) = handle_mcp_rpc_request(state, rpc, None, Some(auth)).await;
+        return Some(payload);
+    };
+    match handle_mcp_rpc_batch(state, batch, None, Some(auth)).await {
+        Err(invalid) => Some(invalid),
+        Ok(responses) if responses.is_empty() => None,
+        Ok(responses) => Some(Value::Array(responses)),
+    }
+}
+
+/// Requests one MCP WebSocket session may run at once.
+const MCP_WS_DEFAULT_MAX_INFLIGHT: usize = 4;
+
+/// `UBL_MCP_WS_MAX_INFLIGHT`; unset, unparsable or zero means the default.
+pub(crate) fn mcp_ws_max_inflight(raw: Option<&str>) -> usize {
+    raw.and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(MCP_WS_DEFAULT_MAX_INFLIGHT)
+}
+
+/// Frames are dispatched concurrently, at most `UBL_MCP_WS_MAX_INFLIGHT` at a
+/// time; once the limit is reached the reader stops pulling frames until a
+/// request finishes. Replies go through one writer task in completion order,
+/// so clients match them by JSON-RPC `id`, not by position on the socket.
+pub(crate) async fn mcp_ws_session(socket: WebSocket, state: AppState, auth: McpWsAuth) {
+    let max_inflight =
+        mcp_ws_max_inflight(std::env::var("UBL_MCP_WS_MAX_INFLIGHT").ok().as_deref());
     info!(
         token_id = %auth.token_id,
         token_cid = %auth.token_cid,
         world = %auth.world,
         scope_count = auth.scope.len(),
+        max_inflight,
         "mcp/ws session started"
     );
-    while let Some(next) = socket.recv().await {
+    let (mut sink, mut frames) = socket.split();
+    let (tx, mut rx) = mpsc::channel::<Message>(max_inflight * 2);
+    let writer = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            if sink.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+    let inflight = Arc::new(Semaphore::new(max_inflight));
+    let auth = Arc::new(auth);
+
+    while let Some(next) = frames.next().await {
         let msg = match next {
             Ok(m) => m,
             Err(e) => {
@@ -245,77 +422,47 @@ pub(crate) async fn mcp_ws_session(mut socket: WebSocket, state: AppState, auth:
             }
         };
 
-        match msg {
-            Message::Text(text) => {
-                let rpc: Value = match serde_json::from_str(&text) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        let err = mcp_error_value(
-                            json!(null),
-                            -32700,
-                            format!("Parse error: {}", e),
-                            None,
-                        );
-                        let _ = socket.send(Message::Text(err.to_string())).await;
-                        continue;
-                    }
-                };
-                let (_status, payload) =
-                    handle_mcp_rpc_request(&state, rpc, None, Some(&auth)).await;
-                if socket
-                    .send(Message::Text(payload.to_string()))
-                    .await
-                    .is_err()
-                {
-                    break;
-                }
-            }
-            Message::Binary(bytes) => {
-                let text = match String::from_utf8(bytes.to_vec()) {
-                    Ok(t) => t,
-                    Err(_) => {
-                        let err = mcp_error_value(
-                            json!(null),
-                            -32700,
-                            "Parse error: binary payload must be UTF-8 JSON-RPC text",
-                            None,
-                        );
-                        let _ = socket.send(Message::Text(err.to_string())).await;
-                        continue;
-                    }
-                };
-                let rpc: Value = match serde_json::from_str(&text) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        let err = mcp_error_value(
-                            json!(null),
-                            -32700,
-                            format!("Parse error: {}", e),
-                            None,
-                        );
-                        let _ = socket.send(Message::Text(err.to_string())).await;
-                        continue;
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Binary(bytes) => match String::from_utf8(bytes.to_vec()) {
+                Ok(t) => t,
+                Err(_) => {
+                    let err = mcp_error_value(
+                        json!(null),
+                        -32700,
+                        "Parse error: binary payload must be UTF-8 JSON-RPC text",
+                        None,
+                    );
+                    if tx.send(Message::Text(err.to_string())).await.is_err() {
+                        break;
                     }
-                };
-                let (_status, payload) =
-                    handle_mcp_rpc_request(&state, rpc, None, Some(&auth)).await;
-                if socket
-                    .send(Message::Text(payload.to_string()))
-                    .await
-                    .is_err()
-                {
-                    break;
+                    continue;
                 }
-            }
+            },
             Message::Ping(payload) => {
-                if socket.send(Message::Pong(payload)).await.is_err() {
+                if tx.send(Message::Pong(payload)).await.is_err() {
                     break;
                 }
+                continue;
             }
-            Message::Pong(_) => {}
+            Message::Pong(_) => continue,
             Message::Close(_) => break,
-        }
+        };
+
+        let Ok(permit) = inflight.clone().acquire_owned().await else {
+            break;
+        };
+        let (state, auth, tx) = (state.clone(), auth.clone(), tx.clone());
+        tokio::spawn(async move {
+            let _permit = permit;
+            if let Some(reply) = mcp_ws_reply(&state, &auth, &text).await {
+                let _ = tx.send(Message::Text(reply.to_string())).await;
+            }
+        });
     }
+    // The writer drains once every in-flight request has replied.
+    drop(tx);
+    let _ = writer.await;
     info!(token_id = %auth.token_id, "mcp/ws session ended");
 }
 
@@ -385,26 +532,10 @@ pub(crate) async fn dispatch_tool_call(
                 let (mcp_code, message) = serde_json::from_value::<UblError>(payload.clone())
                     .map(|e| (e.code.mcp_code(), e.message))
                     .unwrap_or_else(|_| {
-                        let code = if status == StatusCode::TOO_MANY_REQUESTS {
-                            -32006
-                        } else if status == StatusCode::BAD_REQUEST
-                            || status == StatusCode::UNPROCESSABLE_ENTITY
-                        {
-                            -32602
-                        } else if status == StatusCode::UNAUTHORIZED {
-                            -32001
-                        } else if status == StatusCode::FORBIDDEN {
-                            -32003
-                        } else if status == StatusCode::NOT_FOUND {
-                            -32004
-                        } else if status == StatusCode::CONFLICT {
-                            -32005
-                        } else if status == StatusCode::SERVICE_UNAVAILABLE {
-                            -32000
-                        } else {
-                            -32603
-                        };
-                        (code, format!("HTTP {}", status.as_u16()))
+                        (
+                            mcp_code_for_status(status),
+                            format!("HTTP {}", status.as_u16()),
+                        )
                     });
                 (
                     StatusCode::OK,
@@ -491,6 +622,46 @@ pub(crate) async fn dispatch_tool_call(
             }
         }
 
+        "ubl.chip.search" => {
+            // WS sessions already hold a verified token; HTTP callers must
+            // present an admin key or bearer like `GET /v1/chips/search`.
+            let scope = match ws_auth {
+                Some(auth) => ChipReadScope::World(auth.world.clone()),
+                None => {
+                    let headers = mcp_headers.cloned().unwrap_or_default();
+                    match chip_read_scope(state, &headers).await {
+                        Ok(scope) => scope,
+                        Err((status, Json(payload))) => {
+                            return http_result_to_mcp(id, status, payload)
+                        }
+                    }
+                }
+            };
+            let (status, Json(payload)) =
+                search_chips_as(state, &scope, chip_search_params(arguments)).await;
+            http_result_to_mcp(id, status, payload)
+        }
+
+        "ubl.receipt.list" => {
+            let query = match serde_json::from_value::<ReceiptListQuery>(arguments.clone()) {
+                Ok(query) => query,
+                Err(e) => {
+                    return (
+                        StatusCode::OK,
+                        Json(mcp_error_value(
+                            id,
+                            -32602,
+                            format!("invalid arguments: {}", e),
+                            None,
+                        )),
+                    )
+                }
+            };
+            let (status, Json(payload)) =
+                crate::receipt::list_receipts(State(state.clone()), Query(query)).await;
+            http_result_to_mcp(id, status, payload)
+        }
+
         "ubl.verify" => {
             let cid = arguments.get("cid").and_then(|v| v.as_str()).unwrap_or("");
             match state.chip_store.get_chip(cid).await {
@@ -540,7 +711,9 @@ pub(crate) async fn dispatch_tool_call(
             if let Some(store) = state.durable_store.as_ref() {
                 match store.get_receipt(cid) {
                     Ok(Some(receipt_json)) => {
-                        if let Err(ubl_err) = verify_receipt_auth_chain(cid, &receipt_json) {
+                        if let Err(ubl_err) =
+                            verify_receipt_auth_chain(&state.pipeline, cid, &receipt_json)
+                        {
                             return (
                                 StatusCode::OK,
                                 Json(mcp_error_value(
@@ -661,25 +834,78 @@ pub(crate) async fn dispatch_tool_call(
             let instructions = match rb_vm::tlv::decode_stream(&bytecode) {
                 Ok(v) => v,
                 Err(e) => {
+                    let vm_error = rb_vm::VmError::from(e);
                     return (
                         StatusCode::OK,
-                        Json(mcp_error_value(id, -32602, format!("invalid bytecode stream: {}", e), None)),
+                        Json(mcp_error_value(
+                            id,
+                            -32602,
+                            format!("invalid bytecode stream: {}", vm_error),
+                            Some(json!({"vm_code": vm_error.code()})),
+                        )),
                     );
                 }
             };
 
+            let ghost = arguments
+                .get("ghost")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            // Inputs enter CAS as NRF-1 bytes, as TR seeds the chip body, so
+            // input 0 of a chip body has that chip's CID.
+            let mut cas = McpRbCas::default();
+            let mut input_cids = Vec::new();
+            let mut body_size = 0;
+            let inputs = match arguments.get("inputs") {
+                None | Some(Value::Null) => &[][..],
+                Some(Value::Array(items)) => items.as_slice(),
+                Some(_) => {
+                    return (
+                        StatusCode::OK,
+                        Json(mcp_error_value(
+                            id,
+                            -32602,
+                            "inputs must be an array of JSON values",
+                            None,
+                        )),
+                    );
+                }
+            };
+            for (index, input) in inputs.iter().enumerate() {
+                let nrf = match ubl_ai_nrf1::to_nrf1_bytes(input) {
+                    Ok(nrf) => nrf,
+                    Err(e) => {
+                        return (
+                            StatusCode::OK,
+                            Json(mcp_error_value(
+                                id,
+                                -32602,
+                                format!("inputs[{}] is not canonical: {}", index, e),
+                                None,
+                            )),
+                        );
+                    }
+                };
+                if index == 0 {
+                    body_size = nrf.len();
+                }
+                input_cids.push(rb_vm::CasProvider::put(&mut cas, &nrf));
+            }
+            let input_cid_strs: Vec<String> = input_cids.iter().map(|c| c.0.clone()).collect();
+
             let signer = McpRbSigner;
             let mut vm = rb_vm::Vm::new(
                 rb_vm::VmConfig {
                     fuel_limit,
-                    ghost: false,
+                    ghost,
                     trace: true,
                 },
-                McpRbCas::default(),
+                cas,
                 &signer,
                 McpRbCanon,
-                vec![],
-            );
+                input_cids,
+            )
+            .with_body_size(body_size);
 
             match vm.run(&instructions) {
                 Ok(outcome) => (
@@ -693,12 +919,74 @@ pub(crate) async fn dispatch_tool_call(
                             "steps": outcome.steps,
                             "fuel_used": outcome.fuel_used,
                             "trace_len": outcome.trace.len(),
+                            "input_cids": input_cid_strs,
+                            "ghost": ghost,
                         })).unwrap_or_default() }]}
                     })),
                 ),
                 Err(e) => (
                     StatusCode::OK,
-                    Json(mcp_error_value(id, -32602, format!("rb execute failed: {}", e), None)),
+                    Json(mcp_error_value(
+                        id,
+                        -32602,
+                        format!("rb execute failed: {}", e),
+                        Some(json!({"vm_code": e.code()})),
+                    )),
+                ),
+            }
+        }
+
+        "ubl.disasm" => {
+            let bytecode_hex = arguments
+                .get("bytecode_hex")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            if bytecode_hex.is_empty() {
+                return (
+                    StatusCode::OK,
+                    Json(mcp_error_value(
+                        id,
+                        -32602,
+                        "missing required argument: bytecode_hex",
+                        None,
+                    )),
+                );
+            }
+            let clean = bytecode_hex.replace([' ', '\n', '\t'], "");
+            let bytecode = match hex::decode(&clean) {
+                Ok(v) => v,
+                Err(e) => {
+                    return (
+                        StatusCode::OK,
+                        Json(mcp_error_value(
+                            id,
+                            -32602,
+                            format!("invalid bytecode_hex: {}", e),
+                            None,
+                        )),
+                    );
+                }
+            };
+            match rb_vm::disassemble(&bytecode) {
+                Ok(listing) => (
+                    StatusCode::OK,
+                    Json(json!({
+                        "jsonrpc": "2.0", "id": id,
+                        "result": { "content": [{ "type": "text", "text": serde_json::to_string(&json!({
+                            "size": bytecode.len(),
+                            "instructions": rb_vm::count_tlv_instrs(&bytecode),
+                            "listing": listing,
+                        })).unwrap_or_default() }] }
+                    })),
+                ),
+                Err(e) => (
+                    StatusCode::OK,
+                    Json(mcp_error_value(
+                        id,
+                        -32602,
+                        format!("disassembly error: {}", e),
+                        None,
+                    )),
                 ),
             }
         }
@@ -778,7 +1066,7 @@ pub(crate) async fn dispatch_tool_call(
                     state.advisory_engine.model.clone(),
                     AdvisoryHook::OnDemand,
                 );
-                let body = state.advisory_engine.advisory_to_chip_body(&adv);
+                let body = state.advisory_engine.narration_to_chip_body(&adv);
                 let metadata = ubl_chipstore::ExecutionMetadata {
                     runtime_version: "advisory/on-demand".to_string(),
                     execution_time_ms: 0,
@@ -789,7 +1077,7 @@ pub(crate) async fn dispatch_tool_call(
                 };
                 match state
                     .chip_store
-                    .store_executed_chip(body, receipt_cid.to_string(), metadata)
+                    .store_executed_chip(body, "self".to_string(), metadata)
                     .await
                 {
                     Ok(adv_cid) => persisted_advisory_cid = Some(adv_cid),
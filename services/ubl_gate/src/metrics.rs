@@ -122,6 +122,19 @@ static IDEMPOTENCY_REPLAY_BLOCK_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
     c
 });
 
+static REGISTRY_CACHE_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let c = IntCounterVec::new(
+        Opts::new(
+            "ubl_registry_cache_total",
+            "Materialized registry lookups by cache result (hit/miss)",
+        ),
+        &["result"],
+    )
+    .unwrap();
+    REGISTRY.register(Box::new(c.clone())).unwrap();
+    c
+});
+
 static EVENTS_INGESTED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
     let c = IntCounterVec::new(
         Opts::new(
@@ -211,6 +224,11 @@ pub fn inc_idempotency_replay_block() {
     IDEMPOTENCY_REPLAY_BLOCK_TOTAL.inc();
 }
 
+pub fn inc_registry_cache(hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    REGISTRY_CACHE_TOTAL.with_label_values(&[result]).inc();
+}
+
 pub fn inc_events_ingested(stage: &str, world: &str) {
     EVENTS_INGESTED_TOTAL
         .with_label_values(&[stage, world])
@@ -231,6 +249,43 @@ pub fn inc_events_stream_dropped(reason: &str) {
         .inc();
 }
 
+/// Point-in-time read of the decision counters and pipeline latency, for
+/// `GET /v1/status`.
+pub struct StatusSnapshot {
+    pub chips: u64,
+    pub allow: u64,
+    pub deny: u64,
+    /// Upper bound of the histogram bucket holding the 95th percentile;
+    /// `None` before the first observation.
+    pub p95_seconds: Option<f64>,
+}
+
+pub fn status_snapshot() -> StatusSnapshot {
+    use prometheus::core::Metric;
+
+    let histogram = PIPELINE_SECONDS.metric();
+    let histogram = histogram.get_histogram();
+    let count = histogram.get_sample_count();
+    let rank = (count as f64 * 0.95).ceil() as u64;
+    let buckets = histogram.get_bucket();
+    // Samples past the last finite bucket report that bucket's bound.
+    let p95_seconds = if count == 0 {
+        None
+    } else {
+        buckets
+            .iter()
+            .find(|b| b.cumulative_count() >= rank)
+            .or_else(|| buckets.last())
+            .map(|b| b.upper_bound())
+    };
+    StatusSnapshot {
+        chips: CHIPS_TOTAL.get(),
+        allow: ALLOW_TOTAL.get(),
+        deny: DENY_TOTAL.get(),
+        p95_seconds,
+    }
+}
+
 pub fn encode_metrics() -> String {
     // Force lazy init of all metrics so they appear even at zero
     Lazy::force(&CHIPS_TOTAL);
@@ -245,6 +300,7 @@ pub fn encode_metrics() -> String {
     Lazy::force(&OUTBOX_RETRY_TOTAL);
     Lazy::force(&IDEMPOTENCY_HIT_TOTAL);
     Lazy::force(&IDEMPOTENCY_REPLAY_BLOCK_TOTAL);
+    Lazy::force(&REGISTRY_CACHE_TOTAL);
     Lazy::force(&EVENTS_INGESTED_TOTAL);
     Lazy::force(&EVENTS_STREAM_CLIENTS);
     Lazy::force(&EVENTS_STREAM_DROPPED_TOTAL);
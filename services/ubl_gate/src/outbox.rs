@@ -1,7 +1,43 @@
-use reqwest::Client;
-use serde_json::json;
-use tracing::warn;
-use ubl_runtime::durable_store::OutboxEvent;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use futures_util::future::{BoxFuture, FutureExt};
+use reqwest::{header::CONTENT_TYPE, Client};
+use ring::hmac;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::{warn, Instrument};
+use ubl_runtime::durable_store::{outbox_delivery_id, DurableStore, OutboxEvent};
+
+use crate::admin::require_admin;
+use crate::state::AppState;
+
+/// Stable per-event token; receivers dedup redeliveries on it.
+pub(crate) const DELIVERY_ID_HEADER: &str = "X-UBL-Delivery-Id";
+
+/// `t=<unix seconds>,v1=<hex HMAC-SHA256>` over the signing string
+/// `"<t>.<raw body>"`, sent when `UBL_OUTBOX_HMAC_SECRET` is set. Receivers
+/// recompute it and reject stale `t` to stop replays.
+pub(crate) const SIGNATURE_HEADER: &str = "X-UBL-Signature";
+
+/// Default and ceiling for `POST /v1/chips?await_delivery=true` waits.
+pub(crate) const DEFAULT_AWAIT_DELIVERY_MS: u64 = 5_000;
+pub(crate) const MAX_AWAIT_DELIVERY_MS: u64 = 30_000;
+
+/// `UBL_OUTBOX_MAX_ATTEMPTS`: failed deliveries before an event goes dead.
+pub(crate) fn outbox_max_attempts_from_env() -> Option<i64> {
+    std::env::var("UBL_OUTBOX_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|n| *n > 0)
+}
 
 pub(crate) fn outbox_endpoint_from_env() -> Option<String> {
     std::env::var("UBL_OUTBOX_ENDPOINT")
@@ -10,9 +46,149 @@ pub(crate) fn outbox_endpoint_from_env() -> Option<String> {
         .filter(|v| !v.is_empty())
 }
 
+/// `UBL_OUTBOX_HMAC_SECRET`: key for [`SIGNATURE_HEADER`]; unset sends unsigned.
+pub(crate) fn outbox_hmac_secret_from_env() -> Option<String> {
+    std::env::var("UBL_OUTBOX_HMAC_SECRET")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// [`SIGNATURE_HEADER`] value for `body` sent at `timestamp`.
+pub(crate) fn outbox_signature(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    let mut ctx = hmac::Context::with_key(&key);
+    ctx.update(timestamp.to_string().as_bytes());
+    ctx.update(b".");
+    ctx.update(body);
+    format!("t={},v1={}", timestamp, hex::encode(ctx.sign().as_ref()))
+}
+
+type OutboxHandler =
+    Arc<dyn Fn(OutboxEvent) -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// Delivery handlers keyed by outbox `event_type`. Dispatcher workers route
+/// each claimed event through [`OutboxHandlers::dispatch`]; an `Err` is
+/// retried with backoff like any failed delivery.
+#[derive(Clone, Default)]
+pub(crate) struct OutboxHandlers {
+    handlers: HashMap<String, OutboxHandler>,
+}
+
+impl OutboxHandlers {
+    /// Route `event_type` to `handler`, replacing any earlier one.
+    pub(crate) fn with_handler<F, Fut>(mut self, event_type: &str, handler: F) -> Self
+    where
+        F: Fn(OutboxEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.handlers.insert(
+            event_type.to_string(),
+            Arc::new(move |event| handler(event).boxed()),
+        );
+        self
+    }
+
+    /// Run the handler for the event's type. Unknown types fail (and count
+    /// as a retry) so they stay visible in the backlog until one is registered.
+    pub(crate) async fn dispatch(&self, event: OutboxEvent) -> Result<(), String> {
+        let Some(handler) = self.handlers.get(&event.event_type) else {
+            crate::metrics::inc_outbox_retry();
+            return Err(format!("unknown outbox event type: {}", event.event_type));
+        };
+        handler(event).await
+    }
+}
+
+/// Handlers the gate registers at startup: `emit_receipt` to `UBL_OUTBOX_ENDPOINT`.
+pub(crate) fn gate_outbox_handlers(
+    client: Client,
+    endpoint: Option<String>,
+    hmac_secret: Option<Vec<u8>>,
+) -> OutboxHandlers {
+    let endpoint: Arc<Option<String>> = Arc::new(endpoint);
+    let hmac_secret: Arc<Option<Vec<u8>>> = Arc::new(hmac_secret);
+    OutboxHandlers::default().with_handler("emit_receipt", move |event| {
+        let client = client.clone();
+        let endpoint = endpoint.clone();
+        let hmac_secret = hmac_secret.clone();
+        async move {
+            deliver_emit_receipt_event(&client, endpoint.as_deref(), hmac_secret.as_deref(), event)
+                .await
+        }
+    })
+}
+
 pub(crate) async fn deliver_emit_receipt_event(
     client: &Client,
     endpoint: Option<&str>,
+    hmac_secret: Option<&[u8]>,
+    event: OutboxEvent,
+) -> Result<(), String> {
+    let span = tracing::trace_span!(
+        "ubl.outbox.deliver",
+        event_id = event.id,
+        delivery_id = %event.delivery_id,
+        receipt_cid = event.payload_json["receipt_cid"].as_str().unwrap_or_default(),
+        decision = event.payload_json["decision"].as_str().unwrap_or_default(),
+    );
+    deliver(client, endpoint, hmac_secret, event)
+        .instrument(span)
+        .await
+}
+
+/// Wait up to `timeout` for the receipt's `emit_receipt` event to be
+/// delivered by the outbox workers. Never fails the write: anything short of
+/// delivery reports `pending` (or `dead`/`not_queued`) with the attempts so far.
+/// Each SQLite poll runs on the blocking pool, off the async workers.
+pub(crate) async fn await_emit_receipt_delivery(
+    store: Option<Arc<DurableStore>>,
+    receipt_cid: &str,
+    timeout: Duration,
+) -> Value {
+    let Some(store) = store else {
+        return json!({"status": "not_queued"});
+    };
+    let delivery_id = Arc::new(outbox_delivery_id(receipt_cid, "emit_receipt", 0));
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let lookup = {
+            let store = store.clone();
+            let delivery_id = delivery_id.clone();
+            tokio::task::spawn_blocking(move || {
+                store
+                    .outbox_delivery_state(&delivery_id)
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()))
+        };
+        match lookup {
+            // Quarantined receipts defer their emit; nothing to wait for yet.
+            Ok(None) => return json!({"status": "not_queued"}),
+            Ok(Some(state)) if state.status == "done" => {
+                return json!({"status": "delivered", "attempts": state.attempts});
+            }
+            Ok(Some(state)) if state.status == "dead" => {
+                return json!({"status": "dead", "attempts": state.attempts});
+            }
+            Ok(Some(state)) if tokio::time::Instant::now() >= deadline => {
+                return json!({"status": "pending", "attempts": state.attempts});
+            }
+            Ok(Some(_)) => {}
+            Err(e) => {
+                warn!(receipt_cid, error = %e, "await_delivery: outbox lookup failed");
+                return json!({"status": "pending"});
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+async fn deliver(
+    client: &Client,
+    endpoint: Option<&str>,
+    hmac_secret: Option<&[u8]>,
     event: OutboxEvent,
 ) -> Result<(), String> {
     let Some(endpoint) = endpoint else {
@@ -25,14 +201,25 @@ pub(crate) async fn deliver_emit_receipt_event(
 
     let payload = json!({
         "event_id": event.id,
+        "delivery_id": event.delivery_id,
         "event_type": event.event_type,
         "attempt": event.attempts.saturating_add(1),
         "payload": event.payload_json,
     });
+    // Sign the exact bytes sent, so serialize once.
+    let body =
+        serde_json::to_vec(&payload).map_err(|e| format!("outbox payload encode failed: {}", e))?;
 
-    let response = client
+    let mut request = client
         .post(endpoint)
-        .json(&payload)
+        .header(DELIVERY_ID_HEADER, event.delivery_id.as_str())
+        .header(CONTENT_TYPE, "application/json");
+    if let Some(secret) = hmac_secret {
+        let signature = outbox_signature(secret, chrono::Utc::now().timestamp(), &body);
+        request = request.header(SIGNATURE_HEADER, signature);
+    }
+    let response = request
+        .body(body)
         .send()
         .await
         .map_err(|e| format!("outbox http send failed: {}", e))?;
@@ -52,3 +239,110 @@ pub(crate) async fn deliver_emit_receipt_event(
 
     Ok(())
 }
+
+fn durable_unavailable() -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({
+            "@type": "ubl/error",
+            "code": "UNAVAILABLE",
+            "message": "Outbox unavailable: enable the durable store",
+        })),
+    )
+}
+
+fn outbox_store_error(e: impl std::fmt::Display) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "@type": "ubl/error",
+            "code": "INTERNAL_ERROR",
+            "message": format!("outbox query failed: {}", e),
+        })),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct DeadOutboxQuery {
+    pub(crate) limit: Option<usize>,
+}
+
+/// GET /v1/outbox/dead — outbox events that ran out of delivery attempts,
+/// oldest first, with their attempt count and last error. Admin only.
+pub(crate) async fn list_dead_outbox(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<DeadOutboxQuery>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    let Some(store) = state.durable_store.as_ref() else {
+        return durable_unavailable();
+    };
+    let limit = query.limit.unwrap_or(100).clamp(1, 1_000);
+    let dead = match store.list_dead_outbox(limit) {
+        Ok(dead) => dead,
+        Err(e) => return outbox_store_error(e),
+    };
+    let events: Vec<Value> = dead
+        .into_iter()
+        .map(|e| {
+            json!({
+                "id": e.id,
+                "delivery_id": e.delivery_id,
+                "event_type": e.event_type,
+                "receipt_cid": e.payload_json.get("receipt_cid"),
+                "attempts": e.attempts,
+                "created_at": e.created_at,
+                "last_error": e.last_error,
+                "payload": e.payload_json,
+            })
+        })
+        .collect();
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/outbox.dead",
+            "count": events.len(),
+            "events": events,
+        })),
+    )
+}
+
+/// POST /v1/outbox/dead/:id/retry — requeue one dead event for immediate
+/// delivery with a fresh attempt budget. Admin only.
+pub(crate) async fn retry_dead_outbox(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(denied) = require_admin(&state, &headers) {
+        return denied;
+    }
+    let Some(store) = state.durable_store.as_ref() else {
+        return durable_unavailable();
+    };
+    match store.retry_dead_outbox(id) {
+        Ok(true) => {
+            crate::metrics::set_outbox_pending(store.outbox_pending().unwrap_or_default());
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "@type": "ubl/outbox.retry",
+                    "id": id,
+                    "status": "pending",
+                })),
+            )
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "NOT_FOUND",
+                "message": format!("outbox event {} is not dead", id),
+            })),
+        ),
+        Err(e) => outbox_store_error(e),
+    }
+}
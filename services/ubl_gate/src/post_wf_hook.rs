@@ -0,0 +1,72 @@
+//! Built-in post-WF hooks selected from the environment.
+//!
+//! `UBL_POST_WF_HOOK_URL` posts each committed chip's hook record to an HTTP
+//! endpoint (separate from `UBL_OUTBOX_ENDPOINT`: one attempt, no retries);
+//! `UBL_POST_WF_HOOK_NDJSON` appends it to a file. The URL wins when both are set.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde_json::Value;
+use tracing::warn;
+use ubl_receipt::UnifiedReceipt;
+use ubl_runtime::post_wf_hook::{hook_record, NdjsonPostWfHook, PostWfHook, PostWfHookError};
+
+use crate::utils::env_opt_trim;
+
+/// POSTs [`hook_record`] JSON; any non-2xx status is a failure.
+pub(crate) struct HttpPostWfHook {
+    client: Client,
+    endpoint: String,
+}
+
+impl HttpPostWfHook {
+    pub fn new(endpoint: String) -> Result<Self, reqwest::Error> {
+        let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+        Ok(Self { client, endpoint })
+    }
+}
+
+#[async_trait::async_trait]
+impl PostWfHook for HttpPostWfHook {
+    async fn after_wf(
+        &self,
+        receipt: &UnifiedReceipt,
+        chip: &Value,
+    ) -> Result<(), PostWfHookError> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .json(&hook_record(receipt, chip)?)
+            .send()
+            .await
+            .map_err(|e| PostWfHookError::Delivery(format!("http send failed: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(PostWfHookError::Delivery(format!(
+                "{} returned {}",
+                self.endpoint, status
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn post_wf_hook_from_env() -> Option<Arc<dyn PostWfHook>> {
+    let url = env_opt_trim("UBL_POST_WF_HOOK_URL");
+    let ndjson = env_opt_trim("UBL_POST_WF_HOOK_NDJSON");
+    if let Some(url) = url {
+        if ndjson.is_some() {
+            warn!("UBL_POST_WF_HOOK_URL and UBL_POST_WF_HOOK_NDJSON both set; using the URL");
+        }
+        return match HttpPostWfHook::new(url) {
+            Ok(hook) => Some(Arc::new(hook)),
+            Err(e) => {
+                warn!(error = %e, "post-WF HTTP hook disabled");
+                None
+            }
+        };
+    }
+    ndjson.map(|path| Arc::new(NdjsonPostWfHook::new(path)) as Arc<dyn PostWfHook>)
+}
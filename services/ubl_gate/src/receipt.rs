@@ -1,6 +1,7 @@
 //! Receipt retrieval, public URLs, narration, trace, passport advisory handlers.
 
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{
@@ -13,31 +14,49 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use std::convert::Infallible;
 use ubl_runtime::advisory::{Advisory, AdvisoryHook};
+use ubl_runtime::durable_store::{ReceiptCursor, ReceiptFilter};
+use ubl_runtime::receipt_bundle::ReceiptBundle;
 
+use crate::chip::{starting_up, submit_chip_bytes_with};
 use crate::llm::{call_real_llm, call_real_llm_stream_sse, llm_is_enabled};
 use crate::state::AppState;
-use crate::utils::{build_public_receipt_link, verify_receipt_auth_chain};
+use crate::utils::{
+    build_public_receipt_link, canonical_binary_response, chain_too_long_error,
+    not_acceptable_response, read_encoding, tamper_detected_error, verify_receipt_auth_chain,
+    ReadEncoding,
+};
 
+/// GET /v1/receipts/:cid — the stored receipt, after its auth chain checks
+/// out. `Accept: application/x-nrf1` or `application/cbor` returns it as
+/// NRF-1.1 bytes under the requested content type. Unlike a chip body, those
+/// bytes are not the CID's input: the receipt CID hashes the receipt with
+/// `sig`, `@id` and `receipt_cid` blanked. The ETag is the CID either way.
 pub(crate) async fn get_receipt(
     State(state): State<AppState>,
     Path(cid): Path<String>,
     headers: HeaderMap,
-) -> impl IntoResponse {
+) -> Response {
     if !cid.starts_with("b3:") {
         return (
             StatusCode::BAD_REQUEST,
             HeaderMap::new(),
             Json(json!({"@type": "ubl/error", "code": "INVALID_CID", "message": "CID must start with b3:"})),
-        );
+        )
+            .into_response();
     }
 
+    let Some(encoding) = read_encoding(&headers) else {
+        return not_acceptable_response();
+    };
+
     if let Some(inm) = headers.get(header::IF_NONE_MATCH) {
         if let Ok(inm_str) = inm.to_str() {
             let etag = format!("\"{}\"", cid);
             if inm_str == etag || inm_str.trim_matches('"') == cid {
                 let mut h = HeaderMap::new();
                 h.insert(header::ETAG, etag.parse().unwrap());
-                return (StatusCode::NOT_MODIFIED, h, Json(json!(null)));
+                h.insert(header::VARY, "accept".parse().unwrap());
+                return (StatusCode::NOT_MODIFIED, h, Json(json!(null))).into_response();
             }
         }
     }
@@ -51,18 +70,20 @@ pub(crate) async fn get_receipt(
                 "code": "UNAVAILABLE",
                 "message": "Receipt store unavailable: enable SQLite durable store",
             })),
-        );
+        )
+            .into_response();
     };
 
     match store.get_receipt(&cid) {
         Ok(Some(receipt)) => {
-            if let Err(ubl_err) = verify_receipt_auth_chain(&cid, &receipt) {
+            if let Err(ubl_err) = verify_receipt_auth_chain(&state.pipeline, &cid, &receipt) {
                 return (
                     StatusCode::from_u16(ubl_err.code.http_status())
                         .unwrap_or(StatusCode::UNPROCESSABLE_ENTITY),
                     HeaderMap::new(),
                     Json(ubl_err.to_json()),
-                );
+                )
+                    .into_response();
             }
             let mut h = HeaderMap::new();
             let etag = format!("\"{}\"", cid);
@@ -71,13 +92,18 @@ pub(crate) async fn get_receipt(
                 header::CACHE_CONTROL,
                 "public, max-age=31536000, immutable".parse().unwrap(),
             );
-            (StatusCode::OK, h, Json(receipt))
+            h.insert(header::VARY, "accept".parse().unwrap());
+            if let ReadEncoding::Canonical(content_type) = encoding {
+                return canonical_binary_response(&receipt, content_type, h);
+            }
+            (StatusCode::OK, h, Json(receipt)).into_response()
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
             HeaderMap::new(),
             Json(json!({"@type": "ubl/error", "code": "NOT_FOUND", "message": format!("Receipt {} not found", cid)})),
-        ),
+        )
+            .into_response(),
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             HeaderMap::new(),
@@ -86,8 +112,308 @@ pub(crate) async fn get_receipt(
                 "code": "INTERNAL_ERROR",
                 "message": format!("Receipt fetch failed: {}", e),
             })),
-        ),
+        )
+            .into_response(),
+    }
+}
+
+const RECEIPT_LIST_DEFAULT_LIMIT: usize = 50;
+const RECEIPT_LIST_MAX_LIMIT: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReceiptListQuery {
+    limit: Option<usize>,
+    cursor: Option<String>,
+    decision: Option<String>,
+    world: Option<String>,
+}
+
+/// GET /v1/receipts — receipt summaries, newest first. `next_cursor` is set
+/// while more rows remain; pass it back as `cursor`, with the same
+/// `decision` / `world` filters, for the next page.
+pub(crate) async fn list_receipts(
+    State(state): State<AppState>,
+    Query(query): Query<ReceiptListQuery>,
+) -> (StatusCode, Json<Value>) {
+    let Some(store) = state.durable_store.as_ref() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "UNAVAILABLE",
+                "message": "Receipt store unavailable: enable SQLite durable store",
+            })),
+        );
+    };
+
+    let cursor = match query.cursor.as_deref().filter(|c| !c.trim().is_empty()) {
+        Some(raw) => match ReceiptCursor::decode(raw) {
+            Some(cursor) => Some(cursor),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(
+                        json!({"@type": "ubl/error", "code": "INVALID_CURSOR", "message": "cursor is not a receipt list cursor"}),
+                    ),
+                )
+            }
+        },
+        None => None,
+    };
+    let decision = query
+        .decision
+        .as_deref()
+        .map(|d| d.trim().to_ascii_lowercase())
+        .filter(|d| !d.is_empty());
+    if let Some(d) = decision.as_deref() {
+        if !matches!(d, "allow" | "deny" | "quarantine") {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INVALID_QUERY",
+                    "message": format!("decision must be allow, deny or quarantine, got '{}'", d),
+                })),
+            );
+        }
+    }
+    let filter = ReceiptFilter {
+        decision,
+        world: query
+            .world
+            .as_deref()
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .map(str::to_string),
+    };
+    let limit = query
+        .limit
+        .unwrap_or(RECEIPT_LIST_DEFAULT_LIMIT)
+        .clamp(1, RECEIPT_LIST_MAX_LIMIT);
+
+    // One extra row tells whether another page exists.
+    let mut rows = match store.list_receipts(&filter, cursor.as_ref(), limit + 1) {
+        Ok(rows) => rows,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("Receipt list failed: {}", e),
+                })),
+            )
+        }
+    };
+    let exhausted = rows.len() <= limit;
+    rows.truncate(limit);
+
+    let receipts: Vec<Value> = rows
+        .iter()
+        .map(|r| {
+            json!({
+                "receipt_cid": r.receipt_cid,
+                "decision": r.decision,
+                "did": r.did,
+                "world": r.world,
+                "created_at": chrono::DateTime::from_timestamp(r.created_at, 0)
+                    .map(|dt| dt.to_rfc3339()),
+            })
+        })
+        .collect();
+    let mut body = json!({
+        "@type": "ubl/receipts.list",
+        "count": receipts.len(),
+        "receipts": receipts,
+    });
+    if let (false, Some(last)) = (exhausted, rows.last()) {
+        let next = ReceiptCursor {
+            created_at: last.created_at,
+            receipt_cid: last.receipt_cid.clone(),
+        };
+        body["next_cursor"] = json!(next.encode());
+    }
+    (StatusCode::OK, Json(body))
+}
+
+/// Rows fetched from the durable store per export page.
+const RECEIPT_EXPORT_CHUNK: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReceiptExportQuery {
+    after: Option<String>,
+    before: Option<String>,
+}
+
+fn parse_export_bound(name: &str, raw: Option<&str>) -> Result<Option<i64>, String> {
+    let Some(raw) = raw.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(None);
+    };
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .map(|dt| Some(dt.timestamp()))
+        .map_err(|e| format!("{} must be an RFC-3339 timestamp: {}", name, e))
+}
+
+/// GET /v1/receipts/export — every persisted receipt as NDJSON, oldest
+/// first. `after` (inclusive) and `before` (exclusive) bound `created_at` at
+/// second precision. Rows are paged from the durable store in chunks, so the
+/// export never holds the whole table in memory; a store error mid-stream
+/// aborts the body.
+pub(crate) async fn export_receipts(
+    State(state): State<AppState>,
+    Query(query): Query<ReceiptExportQuery>,
+) -> Response {
+    let Some(store) = state.durable_store.clone() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "UNAVAILABLE",
+                "message": "Receipt store unavailable: enable SQLite durable store",
+            })),
+        )
+            .into_response();
+    };
+    let (from, to) = match (
+        parse_export_bound("after", query.after.as_deref()),
+        parse_export_bound("before", query.before.as_deref()),
+    ) {
+        (Ok(from), Ok(to)) => (from, to),
+        (Err(message), _) | (_, Err(message)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"@type": "ubl/error", "code": "INVALID_QUERY", "message": message})),
+            )
+                .into_response()
+        }
+    };
+
+    // The first page is read up front so a broken store is a 500, not a
+    // truncated 200.
+    let first = match store.export_receipts(from, to, None, RECEIPT_EXPORT_CHUNK) {
+        Ok(rows) => rows,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("Receipt export failed: {}", e),
+                })),
+            )
+                .into_response()
+        }
+    };
+    let lines = async_stream::stream! {
+        let mut page = first;
+        loop {
+            let full = page.len() == RECEIPT_EXPORT_CHUNK;
+            let cursor = page.last().map(|r| ReceiptCursor {
+                created_at: r.created_at,
+                receipt_cid: r.receipt_cid.clone(),
+            });
+            for row in page {
+                yield Ok::<String, std::io::Error>(row.body_json + "\n");
+            }
+            let Some(cursor) = cursor.filter(|_| full) else {
+                break;
+            };
+            match store.export_receipts(from, to, Some(&cursor), RECEIPT_EXPORT_CHUNK) {
+                Ok(next) => page = next,
+                Err(e) => {
+                    tracing::warn!(error = %e, "receipt export aborted");
+                    yield Err(std::io::Error::other(e.to_string()));
+                    break;
+                }
+            }
+        }
+    };
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// Largest number of CIDs accepted by `POST /v1/receipts/batch`.
+const RECEIPT_BATCH_MAX: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReceiptBatchRequest {
+    cids: Vec<String>,
+}
+
+/// POST /v1/receipts/batch — `{cids}` → `{receipts: {cid: receipt | error}}`.
+/// Each receipt passes the same auth-chain check as `GET /v1/receipts/:cid`;
+/// tampered, missing or malformed entries are replaced by their error object.
+pub(crate) async fn get_receipts_batch(
+    State(state): State<AppState>,
+    Json(req): Json<ReceiptBatchRequest>,
+) -> (StatusCode, Json<Value>) {
+    if req.cids.is_empty() || req.cids.len() > RECEIPT_BATCH_MAX {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "INVALID_REQUEST",
+                "message": format!("cids must hold 1..={} entries", RECEIPT_BATCH_MAX),
+            })),
+        );
     }
+    let Some(store) = state.durable_store.as_ref() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "UNAVAILABLE",
+                "message": "Receipt store unavailable: enable SQLite durable store",
+            })),
+        );
+    };
+
+    let mut receipts = serde_json::Map::new();
+    let mut found = 0usize;
+    for cid in req.cids {
+        if receipts.contains_key(&cid) {
+            continue;
+        }
+        let entry = if !cid.starts_with("b3:") {
+            json!({"@type": "ubl/error", "code": "INVALID_CID", "message": "CID must start with b3:"})
+        } else {
+            match store.get_receipt(&cid) {
+                Ok(Some(receipt)) => {
+                    match verify_receipt_auth_chain(&state.pipeline, &cid, &receipt) {
+                        Ok(()) => {
+                            found += 1;
+                            receipt
+                        }
+                        Err(ubl_err) => ubl_err.to_json(),
+                    }
+                }
+                Ok(None) => json!({
+                    "@type": "ubl/error",
+                    "code": "NOT_FOUND",
+                    "message": format!("Receipt {} not found", cid),
+                }),
+                Err(e) => json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("Receipt fetch failed: {}", e),
+                }),
+            }
+        };
+        receipts.insert(cid, entry);
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/receipts.batch",
+            "requested": receipts.len(),
+            "found": found,
+            "receipts": receipts,
+        })),
+    )
 }
 
 pub(crate) async fn get_receipt_public_url(
@@ -114,7 +440,7 @@ pub(crate) async fn get_receipt_public_url(
 
     match store.get_receipt(&cid) {
         Ok(Some(receipt)) => {
-            if let Err(ubl_err) = verify_receipt_auth_chain(&cid, &receipt) {
+            if let Err(ubl_err) = verify_receipt_auth_chain(&state.pipeline, &cid, &receipt) {
                 return (
                     StatusCode::from_u16(ubl_err.code.http_status())
                         .unwrap_or(StatusCode::UNPROCESSABLE_ENTITY),
@@ -156,6 +482,338 @@ pub(crate) async fn get_receipt_public_url(
     }
 }
 
+/// POST /v1/receipts/preview-url — run a chip body like
+/// `POST /v1/chips/simulate` and return the public URL its receipt would get.
+/// Nothing is stored; the URL only resolves once a real submission produces
+/// (a different) receipt.
+pub(crate) async fn preview_receipt_url(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Response {
+    if let Some(unavailable) = starting_up(&state) {
+        return unavailable.into_response();
+    }
+    let (status, headers, payload) =
+        submit_chip_bytes_with(&state, Some(&headers), false, &body, false, true).await;
+    // Rejections and redacted denials have no receipt to link.
+    let Some(receipt_json) = payload.get("receipt").filter(|_| status.is_success()) else {
+        return (status, headers, Json(payload)).into_response();
+    };
+    match build_public_receipt_link(&state, receipt_json) {
+        Some(link) => (
+            status,
+            headers,
+            Json(json!({
+                "@type": "ubl/receipt.url",
+                "preview": true,
+                "receipt_cid": payload["receipt_cid"],
+                "decision": payload["decision"],
+                "receipt_url": link.url,
+                "receipt_public": link,
+                "note": "preview only: the URL resolves after a real submission, whose receipt CID will differ",
+            })),
+        )
+            .into_response(),
+        None => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"INTERNAL_ERROR",
+                "message":"failed to derive canonical public receipt URL",
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /v1/receipts/:cid/bundle — signed `{chip, receipt, chain, bundle_sig}`
+/// for offline audit. Auth chains and the chip CID are verified before assembly.
+pub(crate) async fn get_receipt_bundle(
+    State(state): State<AppState>,
+    Path(cid): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    if !cid.starts_with("b3:") {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(
+                json!({"@type": "ubl/error", "code": "INVALID_CID", "message": "CID must start with b3:"}),
+            ),
+        );
+    }
+
+    let Some(store) = state.durable_store.as_ref() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "UNAVAILABLE",
+                "message": "Receipt store unavailable: enable SQLite durable store",
+            })),
+        );
+    };
+
+    let receipt = match store.get_receipt(&cid) {
+        Ok(Some(receipt)) => receipt,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(
+                    json!({"@type": "ubl/error", "code": "NOT_FOUND", "message": format!("Receipt {} not found", cid)}),
+                ),
+            )
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("Receipt fetch failed: {}", e),
+                })),
+            )
+        }
+    };
+
+    if let Err(ubl_err) = verify_receipt_auth_chain(&state.pipeline, &cid, &receipt) {
+        return (
+            StatusCode::from_u16(ubl_err.code.http_status())
+                .unwrap_or(StatusCode::UNPROCESSABLE_ENTITY),
+            Json(ubl_err.to_json()),
+        );
+    }
+
+    let chip = match state.chip_store.get_chip_by_receipt_cid(&cid).await {
+        Ok(Some(chip)) => {
+            let computed = ubl_ai_nrf1::to_nrf1_bytes(&chip.chip_data)
+                .ok()
+                .and_then(|b| ubl_ai_nrf1::compute_cid(&b).ok());
+            if computed.as_deref() != Some(chip.cid.as_str()) {
+                let err = tamper_detected_error(
+                    format!("chip {} content does not match its CID", chip.cid.as_str()),
+                    json!({
+                        "receipt_cid": cid,
+                        "chip_cid": chip.cid.as_str(),
+                        "computed_cid": computed,
+                        "reason": "chip_cid_mismatch",
+                    }),
+                );
+                return (StatusCode::UNPROCESSABLE_ENTITY, Json(err.to_json()));
+            }
+            Some(chip.chip_data)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()}),
+                ),
+            )
+        }
+    };
+
+    match state.pipeline.issue_receipt_bundle(receipt, chip) {
+        Ok(bundle) => (StatusCode::OK, Json(json!(bundle))),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"@type": "ubl/error", "code": "INTERNAL_ERROR", "message": e.to_string()})),
+        ),
+    }
+}
+
+/// POST /v1/verify/bundle — check a bundle issued by this gate.
+pub(crate) async fn verify_receipt_bundle(
+    State(state): State<AppState>,
+    Json(body): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let bundle: ReceiptBundle = match serde_json::from_value(body) {
+        Ok(b) => b,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INVALID_BUNDLE",
+                    "message": format!("bundle parse failed: {}", e),
+                })),
+            )
+        }
+    };
+
+    if let Err(e) = bundle.check_chain_len(ubl_receipt::max_chain_len_from_env()) {
+        let err = chain_too_long_error(
+            format!("receipt bundle {} rejected: {}", bundle.receipt_cid, e),
+            json!({"receipt_cid": bundle.receipt_cid}),
+        );
+        return (StatusCode::UNPROCESSABLE_ENTITY, Json(err.to_json()));
+    }
+
+    let checks = bundle.verify();
+    let issuer_matches_gate = bundle.did == state.pipeline.did;
+    if !checks.verified() || !issuer_matches_gate {
+        let err = tamper_detected_error(
+            format!("receipt bundle {} failed verification", bundle.receipt_cid),
+            json!({
+                "receipt_cid": bundle.receipt_cid,
+                "checks": checks,
+                "issuer_matches_gate": issuer_matches_gate,
+            }),
+        );
+        return (StatusCode::UNPROCESSABLE_ENTITY, Json(err.to_json()));
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/receipt.bundle.verification",
+            "receipt_cid": bundle.receipt_cid,
+            "chip_cid": bundle.chip_cid,
+            "verified": true,
+            "issuer_matches_gate": issuer_matches_gate,
+            "checks": checks,
+        })),
+    )
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct VerifyLinkRequest {
+    /// Full public receipt URL, `ubl:v1:<token>` fragment or bare token.
+    #[serde(alias = "url")]
+    token: String,
+}
+
+/// POST /v1/receipts/verify-link — check a shared public receipt URL without
+/// trusting the sharer: the token must decode, name a stored receipt whose
+/// auth chain is intact and whose signature verifies against this gate's
+/// DID, and match that receipt's CIDs, signer and signature. Failed checks
+/// answer 200 with `valid: false` and a `reason`.
+pub(crate) async fn verify_receipt_link(
+    State(state): State<AppState>,
+    Json(body): Json<VerifyLinkRequest>,
+) -> (StatusCode, Json<Value>) {
+    let invalid = |receipt_cid: Option<&str>, reason: &str, message: String| {
+        (
+            StatusCode::OK,
+            Json(json!({
+                "@type": "ubl/receipt.link.verification",
+                "valid": false,
+                "receipt_cid": receipt_cid,
+                "reason": reason,
+                "message": message,
+            })),
+        )
+    };
+
+    let token = match ubl_runtime::rich_url::parse_public_receipt_token_v1(&body.token) {
+        Ok(token) => token,
+        Err(e) => return invalid(None, "token_invalid", e.to_string()),
+    };
+    let cid = token.r.as_str();
+
+    let Some(store) = state.durable_store.as_ref() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "UNAVAILABLE",
+                "message": "Receipt store unavailable: enable SQLite durable store",
+            })),
+        );
+    };
+    let receipt_json = match store.get_receipt(cid) {
+        Ok(Some(receipt)) => receipt,
+        Ok(None) => {
+            return invalid(
+                Some(cid),
+                "receipt_not_found",
+                format!("Receipt {} not found", cid),
+            )
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type": "ubl/error",
+                    "code": "INTERNAL_ERROR",
+                    "message": format!("Receipt fetch failed: {}", e),
+                })),
+            )
+        }
+    };
+
+    if let Err(ubl_err) = verify_receipt_auth_chain(&state.pipeline, cid, &receipt_json) {
+        return invalid(Some(cid), "auth_chain_invalid", ubl_err.message);
+    }
+    let receipt = match ubl_receipt::UnifiedReceipt::from_json(&receipt_json) {
+        Ok(receipt) => receipt,
+        Err(e) => return invalid(Some(cid), "receipt_invalid", e.to_string()),
+    };
+    let Some(signature_valid) = state.pipeline.verify_receipt_signature(&receipt) else {
+        return invalid(
+            Some(cid),
+            "signer_not_gate",
+            format!(
+                "receipt signed by {}, which is not in the gate keyring",
+                receipt.kid.as_str()
+            ),
+        );
+    };
+    if !signature_valid {
+        return invalid(
+            Some(cid),
+            "signature_invalid",
+            "receipt signature does not verify".into(),
+        );
+    }
+
+    let expected = match ubl_runtime::rich_url::build_public_receipt_token_v1(
+        &receipt_json,
+        state.genesis_pubkey_sha256.as_deref(),
+        state.release_commit.as_deref(),
+        state.gate_binary_sha256.as_deref(),
+    ) {
+        Ok(expected) => expected,
+        Err(e) => return invalid(Some(cid), "receipt_invalid", e.to_string()),
+    };
+    let mismatched: Vec<&str> = [
+        ("c", token.c == expected.c),
+        ("k", token.k == expected.k),
+        ("alg", token.alg == expected.alg),
+        ("sig", token.sig == expected.sig),
+        ("did", token.did == expected.did),
+        (
+            "g",
+            state.genesis_pubkey_sha256.is_none() || token.g == expected.g,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, ok)| (!ok).then_some(field))
+    .collect();
+    if !mismatched.is_empty() {
+        return invalid(
+            Some(cid),
+            "token_mismatch",
+            format!(
+                "token fields do not match the receipt: {}",
+                mismatched.join(", ")
+            ),
+        );
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/receipt.link.verification",
+            "valid": true,
+            "receipt_cid": cid,
+            "chip_cid": expected.c,
+            "world": receipt.world.as_str(),
+            "decision": format!("{:?}", receipt.decision),
+        })),
+    )
+}
+
 pub(crate) async fn get_passport_advisories(
     State(state): State<AppState>,
     Path(passport_cid): Path<String>,
@@ -303,7 +961,9 @@ pub(crate) async fn get_receipt_trace(
     if let Some(store) = state.durable_store.as_ref() {
         match store.get_receipt(&cid) {
             Ok(Some(receipt_json)) => {
-                if let Err(ubl_err) = verify_receipt_auth_chain(&cid, &receipt_json) {
+                if let Err(ubl_err) =
+                    verify_receipt_auth_chain(&state.pipeline, &cid, &receipt_json)
+                {
                     return (
                         StatusCode::from_u16(ubl_err.code.http_status())
                             .unwrap_or(StatusCode::UNPROCESSABLE_ENTITY),
@@ -366,6 +1026,9 @@ pub(crate) async fn get_receipt_trace(
 #[derive(Debug, Deserialize)]
 pub(crate) struct NarrateQuery {
     pub persist: Option<bool>,
+    /// `deny`: spend the narrator on denials (failing rule + remediation)
+    /// and answer allow receipts with the terse deterministic summary.
+    pub focus: Option<String>,
 }
 
 pub(crate) async fn narrate_receipt(
@@ -373,6 +1036,20 @@ pub(crate) async fn narrate_receipt(
     Path(cid): Path<String>,
     Query(query): Query<NarrateQuery>,
 ) -> (StatusCode, Json<Value>) {
+    let deny_focus = query.focus.as_deref() == Some("deny");
+    if deny_focus {
+        let receipt = state
+            .durable_store
+            .as_ref()
+            .and_then(|store| store.get_receipt(&cid).ok().flatten());
+        if let Some(receipt) =
+            receipt.filter(|r| r.get("decision").and_then(|v| v.as_str()) == Some("Deny"))
+        {
+            return narrate_deny_receipt(&state, &cid, &receipt, query.persist.unwrap_or(false))
+                .await;
+        }
+    }
+
     let chip = match state.chip_store.get_chip_by_receipt_cid(&cid).await {
         Ok(Some(chip)) => chip,
         Ok(None) => {
@@ -412,7 +1089,7 @@ pub(crate) async fn narrate_receipt(
         chip.chip_type, decision, latency_ms, fuel, policy_count
     );
 
-    let summary = if llm_is_enabled() {
+    let summary = if llm_is_enabled() && !deny_focus {
         let llm_ctx = serde_json::json!({
             "chip_type": chip.chip_type,
             "chip_body": chip.chip_data,
@@ -446,40 +1123,189 @@ pub(crate) async fn narrate_receipt(
 
     let mut persisted_advisory_cid: Option<String> = None;
     if query.persist.unwrap_or(false) {
-        let adv = Advisory::new(
-            state.advisory_engine.passport_cid.clone(),
-            "narrate".to_string(),
-            cid.clone(),
-            narration.clone(),
-            90,
-            state.advisory_engine.model.clone(),
-            AdvisoryHook::OnDemand,
-        );
-        let body = state.advisory_engine.advisory_to_chip_body(&adv);
-        let metadata = ubl_chipstore::ExecutionMetadata {
-            runtime_version: "advisory/on-demand".to_string(),
-            execution_time_ms: 0,
-            fuel_consumed: 0,
-            policies_applied: vec![],
-            executor_did: chip.execution_metadata.executor_did.clone(),
-            reproducible: true,
-        };
-        match state
-            .chip_store
-            .store_executed_chip(body, cid.clone(), metadata)
-            .await
+        match persist_narration(
+            &state,
+            &cid,
+            &narration,
+            chip.execution_metadata.executor_did.clone(),
+        )
+        .await
         {
             Ok(adv_cid) => persisted_advisory_cid = Some(adv_cid),
-            Err(e) => {
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({
-                        "@type":"ubl/error",
-                        "code":"INTERNAL_ERROR",
-                        "message": format!("narration persist failed: {}", e),
-                    })),
-                );
+            Err(resp) => return resp,
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type":"ubl/advisory.narration.response",
+            "receipt_cid": cid,
+            "narration": narration,
+            "persisted_advisory_cid": persisted_advisory_cid,
+        })),
+    )
+}
+
+async fn persist_narration(
+    state: &AppState,
+    cid: &str,
+    narration: &Value,
+    executor_did: ubl_types::Did,
+) -> Result<String, (StatusCode, Json<Value>)> {
+    let adv = Advisory::new(
+        state.advisory_engine.passport_cid.clone(),
+        "narrate".to_string(),
+        cid.to_string(),
+        narration.clone(),
+        90,
+        state.advisory_engine.model.clone(),
+        AdvisoryHook::OnDemand,
+    );
+    let body = state.advisory_engine.narration_to_chip_body(&adv);
+    let metadata = ubl_chipstore::ExecutionMetadata {
+        runtime_version: "advisory/on-demand".to_string(),
+        execution_time_ms: 0,
+        fuel_consumed: 0,
+        policies_applied: vec![],
+        executor_did,
+        reproducible: true,
+    };
+    // Stored as "self": filing it under `cid` would shadow the narrated chip
+    // in the receipt index, so a retried narrate would describe the advisory.
+    state
+        .chip_store
+        .store_executed_chip(body, "self".to_string(), metadata)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type":"ubl/error",
+                    "code":"INTERNAL_ERROR",
+                    "message": format!("narration persist failed: {}", e),
+                })),
+            )
+        })
+}
+
+/// First denying policy in the receipt's stage traces, plus the receipt's
+/// `deny_reason` effect. Shape: `{stage, policy_id, level, reason, rb_id, deny_reason}`.
+pub(crate) fn denied_by_from_receipt(receipt: &Value) -> Value {
+    let deny_reason = receipt
+        .get("effects")
+        .and_then(|e| e.get("deny_reason"))
+        .and_then(|v| v.as_str());
+    let stages = receipt
+        .get("stages")
+        .and_then(|v| v.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for stage in stages {
+        let traces = stage
+            .get("policy_trace")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for entry in traces {
+            if entry.get("result").and_then(|v| v.as_str()) != Some("Deny") {
+                continue;
             }
+            let rb_id = entry
+                .get("rb_results")
+                .and_then(|v| v.as_array())
+                .and_then(|rbs| {
+                    rbs.iter()
+                        .find(|rb| rb.get("decision").and_then(|v| v.as_str()) == Some("Deny"))
+                })
+                .and_then(|rb| rb.get("rb_id"))
+                .cloned();
+            return json!({
+                "stage": stage.get("stage").cloned(),
+                "policy_id": entry.get("policy_id").cloned(),
+                "level": entry.get("level").cloned(),
+                "reason": entry.get("reason").cloned(),
+                "rb_id": rb_id,
+                "deny_reason": deny_reason,
+            });
+        }
+    }
+    json!({
+        "stage": null,
+        "policy_id": null,
+        "level": null,
+        "reason": deny_reason,
+        "rb_id": null,
+        "deny_reason": deny_reason,
+    })
+}
+
+fn deny_remediation(denied_by: &Value) -> String {
+    let reason = denied_by
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .unwrap_or("the policy condition");
+    match denied_by.get("policy_id").and_then(|v| v.as_str()) {
+        Some(policy_id) => format!(
+            "Adjust the chip so policy '{}' passes ({}), or request the capability/permission it requires, then resubmit.",
+            policy_id, reason
+        ),
+        None => format!("Address '{}' and resubmit.", reason),
+    }
+}
+
+async fn narrate_deny_receipt(
+    state: &AppState,
+    cid: &str,
+    receipt: &Value,
+    persist: bool,
+) -> (StatusCode, Json<Value>) {
+    let world = receipt
+        .get("@world")
+        .and_then(|v| v.as_str())
+        .unwrap_or("a/system/t/unknown");
+    let denied_by = denied_by_from_receipt(receipt);
+    let remediation = deny_remediation(&denied_by);
+
+    let base_summary = format!(
+        "Denied by policy '{}' at {}: {}. {}",
+        denied_by["policy_id"].as_str().unwrap_or("unknown"),
+        denied_by["stage"].as_str().unwrap_or("CHECK"),
+        denied_by["reason"].as_str().unwrap_or("no reason recorded"),
+        remediation
+    );
+    let summary = if llm_is_enabled() {
+        let llm_ctx = json!({
+            "decision": "deny",
+            "world": world,
+            "denied_by": denied_by,
+            "remediation_hint": remediation,
+        });
+        match call_real_llm(&state.http_client, "receipt.deny", &llm_ctx).await {
+            Ok(text) => text,
+            Err(_) => base_summary.clone(),
+        }
+    } else {
+        base_summary.clone()
+    };
+
+    let narration = json!({
+        "@type": "ubl/advisory.narration",
+        "receipt_cid": cid,
+        "decision": "deny",
+        "world": world,
+        "denied_by": denied_by,
+        "remediation": remediation,
+        "summary": summary,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+    });
+
+    let mut persisted_advisory_cid: Option<String> = None;
+    if persist {
+        let executor_did = ubl_types::Did::new_unchecked(state.pipeline.did.clone());
+        match persist_narration(state, cid, &narration, executor_did).await {
+            Ok(adv_cid) => persisted_advisory_cid = Some(adv_cid),
+            Err(resp) => return resp,
         }
     }
 
@@ -1,4 +1,4 @@
-//! Registry page handlers and materialize_registry builder.
+//! Registry page handlers and the cached materialize_registry builder.
 
 use axum::{
     extract::{Form, Path, Query, State},
@@ -7,16 +7,40 @@ use axum::{
     Json,
 };
 use serde_json::{json, Value};
+use std::sync::Arc;
 
 use axum::http::HeaderMap;
-use crate::chip::submit_chip_bytes;
+use crate::chip::submit_chip_bytes_with;
 use crate::console::{render_html, split_rows};
+use crate::metrics;
 use crate::state::AppState;
 use crate::templates::{
-    RegistryKatRow, RegistryKatResultTemplate, RegistryKatTestForm, RegistryRow,
+    KatFieldDiff, RegistryKatResultTemplate, RegistryKatRow, RegistryKatTestForm, RegistryRow,
     RegistryTableTemplate, RegistryTemplate, RegistryTypeTemplate, RegistryTypeVersionRow,
     RegistryTypeView, RegistryVersionView, RegistryView,
 };
+use crate::utils::authorize_write_headers;
+
+pub(crate) const KAT_REPORT_CHIP_TYPE: &str = "ubl/audit.kat.report";
+
+/// Latest `ubl/meta.deprecate` record for `chip_type`, in any world — the
+/// same record the registry type page reports.
+pub(crate) async fn type_deprecation(
+    state: &AppState,
+    chip_type: &str,
+) -> Option<ubl_runtime::meta_chip::DeprecateChip> {
+    let mut deprecates = state
+        .chip_store
+        .get_chips_by_type("ubl/meta.deprecate")
+        .await
+        .ok()?;
+    deprecates.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    deprecates
+        .iter()
+        .rev()
+        .filter_map(|chip| ubl_runtime::meta_chip::parse_deprecate(&chip.chip_data).ok())
+        .find(|dep| dep.target_type == chip_type)
+}
 
 pub(crate) async fn registry_page(
     Query(query): Query<std::collections::BTreeMap<String, String>>,
@@ -130,6 +154,11 @@ pub(crate) async fn registry_type_page(
                         .and_then(|v| v.as_str())
                         .unwrap_or("-")
                         .to_string();
+                    let expected_error_contains = kat
+                        .get("expected_error_contains")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("-")
+                        .to_string();
                     let input_json_preview = kat
                         .get("input")
                         .map(Value::to_string)
@@ -146,6 +175,7 @@ pub(crate) async fn registry_type_page(
                         label,
                         expected_decision,
                         expected_error,
+                        expected_error_contains,
                         input_json_preview,
                     }
                 })
@@ -238,59 +268,201 @@ pub(crate) async fn registry_kat_test(
             .into_response();
     };
 
-    let kat_label = kat
-        .get("label")
-        .and_then(|v| v.as_str())
-        .unwrap_or("kat")
-        .to_string();
-    let expected_decision = kat
-        .get("expected_decision")
-        .and_then(|v| v.as_str())
-        .unwrap_or("-")
-        .to_string();
-    let expected_error = kat
-        .get("expected_error")
+    let run = run_kat(&state, kat).await;
+    render_html(&RegistryKatResultTemplate {
+        status_code: run.status_code,
+        kat_label: run.label,
+        mode: run.mode.as_str(),
+        expected_decision: run.expected_decision,
+        expected_error: run.expected_error,
+        expected_error_contains: run.expected_error_contains,
+        actual_decision: run.actual_decision,
+        actual_error: run.actual_error,
+        diff: run.diff,
+        receipt_cid: run.receipt_cid,
+        pass: run.pass,
+        response_json: serde_json::to_string_pretty(&run.payload)
+            .unwrap_or_else(|_| "{}".to_string()),
+        message: run.message,
+    })
+}
+
+/// What a KAT asserts about its run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KatMode {
+    /// Only the decision (and, when set, an error substring) is checked.
+    Decision,
+    /// `expected_decision: deny` with an exact `expected_error` code.
+    DenyCode,
+}
+
+impl KatMode {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Decision => "decision",
+            Self::DenyCode => "deny_code",
+        }
+    }
+}
+
+/// Outcome of submitting a single KAT input through the gate pipeline.
+pub(crate) struct KatRun {
+    pub(crate) status_code: u16,
+    pub(crate) label: String,
+    pub(crate) mode: KatMode,
+    pub(crate) expected_decision: String,
+    pub(crate) expected_error: String,
+    pub(crate) expected_error_contains: String,
+    pub(crate) actual_decision: String,
+    pub(crate) actual_error: String,
+    pub(crate) receipt_cid: String,
+    pub(crate) pass: bool,
+    /// Expected-vs-actual for every field the KAT asserts.
+    pub(crate) diff: Vec<KatFieldDiff>,
+    pub(crate) payload: Value,
+    pub(crate) message: String,
+}
+
+impl KatRun {
+    fn to_json(&self) -> Value {
+        json!({
+            "label": self.label,
+            "mode": self.mode.as_str(),
+            "status_code": self.status_code,
+            "expected_decision": self.expected_decision,
+            "expected_error": self.expected_error,
+            "expected_error_contains": self.expected_error_contains,
+            "actual_decision": self.actual_decision,
+            "actual_error": self.actual_error,
+            "receipt_cid": self.receipt_cid,
+            "pass": self.pass,
+            "diff": self.diff,
+            "message": self.message,
+        })
+    }
+}
+
+fn kat_str(kat: &Value, field: &str) -> String {
+    kat.get(field)
         .and_then(|v| v.as_str())
         .unwrap_or("-")
-        .to_string();
-    let Some(input_chip) = kat.get("input") else {
-        return render_html(&RegistryKatResultTemplate {
-            status_code: 400,
-            kat_label,
-            expected_decision,
-            expected_error,
-            actual_decision: "-".to_string(),
-            actual_error: "missing_kat_input".to_string(),
-            receipt_cid: "-".to_string(),
-            pass: false,
-            response_json: "{}".to_string(),
-            message: "KAT input missing".to_string(),
+        .to_string()
+}
+
+/// Lowercase `allow`/`deny`/`quarantine` from a submit response. A 4xx
+/// rejection of the chip carries no decision and counts as `deny`; rate
+/// limits, timeouts and 5xx responses say nothing about the chip and count
+/// as `error`.
+pub(crate) fn kat_actual_decision(status: StatusCode, payload: &Value) -> String {
+    match payload.get("decision").and_then(|v| v.as_str()) {
+        Some(decision) => decision.to_ascii_lowercase(),
+        None if kat_infra_failure(status) => "error".to_string(),
+        None if status.is_client_error() => "deny".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Statuses that reflect the gate rather than the chip.
+fn kat_infra_failure(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+/// Compare a run against the KAT's expectations, one diff entry per
+/// asserted field.
+fn kat_diff(
+    expected_decision: &str,
+    expected_error: &str,
+    expected_error_contains: &str,
+    actual_decision: &str,
+    actual_error: &str,
+    actual_message: &str,
+) -> Vec<KatFieldDiff> {
+    let mut diff = Vec::new();
+    if expected_decision != "-" {
+        diff.push(KatFieldDiff {
+            field: "decision",
+            expected: expected_decision.to_ascii_lowercase(),
+            actual: actual_decision.to_string(),
+            matched: actual_decision.eq_ignore_ascii_case(expected_decision),
+        });
+    }
+    if expected_error != "-" {
+        diff.push(KatFieldDiff {
+            field: "error",
+            expected: expected_error.to_string(),
+            actual: actual_error.to_string(),
+            matched: actual_error == expected_error,
+        });
+    }
+    if expected_error_contains != "-" {
+        let needle = expected_error_contains.to_ascii_lowercase();
+        diff.push(KatFieldDiff {
+            field: "error_contains",
+            expected: expected_error_contains.to_string(),
+            actual: format!("{}: {}", actual_error, actual_message),
+            matched: actual_error.to_ascii_lowercase().contains(&needle)
+                || actual_message.to_ascii_lowercase().contains(&needle),
         });
+    }
+    diff
+}
+
+/// Run one KAT as a trusted, simulated write and compare against its
+/// expectations. The KAT input is never stored and publishes no events.
+///
+/// A KAT expecting `deny` passes on a deny receipt or a 4xx rejection;
+/// every other KAT needs a successful response, and a rate limit, timeout
+/// or 5xx always fails the KAT. The decision is compared
+/// exactly (case-insensitive), `expected_error` exactly against the error
+/// code, and `expected_error_contains` as a substring of the code or message.
+pub(crate) async fn run_kat(state: &AppState, kat: &Value) -> KatRun {
+    let label = match kat_str(kat, "label") {
+        label if label == "-" => "kat".to_string(),
+        label => label,
+    };
+    let expected_decision = kat_str(kat, "expected_decision");
+    let expected_error = kat_str(kat, "expected_error");
+    let expected_error_contains = kat_str(kat, "expected_error_contains");
+    let expects_deny = expected_decision.eq_ignore_ascii_case("deny");
+    let mode = if expects_deny && expected_error != "-" {
+        KatMode::DenyCode
+    } else {
+        KatMode::Decision
+    };
+    let failed = |status_code: u16, actual_error: &str, message: String| KatRun {
+        status_code,
+        label: label.clone(),
+        mode,
+        expected_decision: expected_decision.clone(),
+        expected_error: expected_error.clone(),
+        expected_error_contains: expected_error_contains.clone(),
+        actual_decision: "-".to_string(),
+        actual_error: actual_error.to_string(),
+        receipt_cid: "-".to_string(),
+        pass: false,
+        diff: vec![],
+        payload: json!({}),
+        message,
+    };
+    let Some(input_chip) = kat.get("input") else {
+        return failed(400, "missing_kat_input", "KAT input missing".to_string());
     };
     let body = match serde_json::to_vec(input_chip) {
         Ok(v) => v,
         Err(e) => {
-            return render_html(&RegistryKatResultTemplate {
-                status_code: 500,
-                kat_label,
-                expected_decision,
-                expected_error,
-                actual_decision: "-".to_string(),
-                actual_error: "kat_input_serialize_error".to_string(),
-                receipt_cid: "-".to_string(),
-                pass: false,
-                response_json: "{}".to_string(),
-                message: format!("KAT input serialization failed: {}", e),
-            });
+            return failed(
+                500,
+                "kat_input_serialize_error",
+                format!("KAT input serialization failed: {}", e),
+            );
         }
     };
 
-    let (status, _headers, payload): (StatusCode, HeaderMap, Value) = submit_chip_bytes(&state, None, true, &body).await;
-    let actual_decision = payload
-        .get("decision")
-        .and_then(|v| v.as_str())
-        .unwrap_or("-")
-        .to_string();
+    let (status, _headers, payload): (StatusCode, HeaderMap, Value) =
+        submit_chip_bytes_with(state, None, true, &body, false, true).await;
+    let actual_decision = kat_actual_decision(status, &payload);
     let actual_error = payload
         .get("code")
         .and_then(|v| v.as_str())
@@ -302,6 +474,15 @@ pub(crate) async fn registry_kat_test(
         })
         .unwrap_or("-")
         .to_string();
+    let actual_message = payload
+        .get("message")
+        .and_then(|v| v.as_str())
+        .or_else(|| {
+            payload
+                .pointer("/receipt/effects/deny_reason")
+                .and_then(|v| v.as_str())
+        })
+        .unwrap_or("");
     let receipt_cid = payload
         .get("receipt_cid")
         .and_then(|v| v.as_str())
@@ -314,31 +495,272 @@ pub(crate) async fn registry_kat_test(
         .unwrap_or("-")
         .to_string();
 
-    let decision_match = expected_decision == "-"
-        || actual_decision
-            .to_ascii_lowercase()
-            .contains(&expected_decision.to_ascii_lowercase());
-    let error_match = expected_error == "-" || actual_error == expected_error;
-    let pass = status.is_success() && decision_match && error_match;
-    let response_json = serde_json::to_string_pretty(&payload).unwrap_or_else(|_| "{}".to_string());
+    let diff = kat_diff(
+        &expected_decision,
+        &expected_error,
+        &expected_error_contains,
+        &actual_decision,
+        &actual_error,
+        actual_message,
+    );
+    let pass = (status.is_success() || (expects_deny && !kat_infra_failure(status)))
+        && diff.iter().all(|d| d.matched);
     let message = if pass {
         "KAT passed".to_string()
+    } else if kat_infra_failure(status) {
+        format!("KAT error: HTTP {}", status.as_u16())
+    } else if !status.is_success() && !expects_deny {
+        format!("KAT failed: HTTP {}", status.as_u16())
     } else {
-        "KAT failed".to_string()
+        let mismatched: Vec<&str> = diff
+            .iter()
+            .filter(|d| !d.matched)
+            .map(|d| d.field)
+            .collect();
+        format!("KAT failed: {} mismatch", mismatched.join(", "))
     };
 
-    render_html(&RegistryKatResultTemplate {
+    KatRun {
         status_code: status.as_u16(),
-        kat_label,
+        label,
+        mode,
         expected_decision,
         expected_error,
+        expected_error_contains,
         actual_decision,
         actual_error,
         receipt_cid,
         pass,
-        response_json,
+        diff,
+        payload,
         message,
-    })
+    }
+}
+
+/// Run every KAT of one registered version; returns the result rows and
+/// how many passed.
+async fn run_version_kats(state: &AppState, ver: &RegistryVersionView) -> (Vec<Value>, usize) {
+    let mut results = Vec::with_capacity(ver.kats.len());
+    let mut passed = 0usize;
+    for (index, kat) in ver.kats.iter().enumerate() {
+        let run = run_kat(state, kat).await;
+        if run.pass {
+            passed += 1;
+        }
+        let mut row = run.to_json();
+        row["version"] = json!(ver.version);
+        row["index"] = json!(index);
+        results.push(row);
+    }
+    (results, passed)
+}
+
+/// POST /v1/registry/:chip_type/validate — run every KAT of every registered
+/// version and persist the outcome as a `ubl/audit.kat.report` chip.
+pub(crate) async fn registry_validate_type(
+    State(state): State<AppState>,
+    Path(chip_type): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let registry = match materialize_registry(&state, None).await {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type":"ubl/error",
+                    "code":"INTERNAL_ERROR",
+                    "message": format!("registry materialization failed: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+    let Some(view) = registry.types.get(&chip_type) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry type '{}' not found", chip_type),
+            })),
+        )
+            .into_response();
+    };
+    let Some(world) = view.world.clone() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry type '{}' has no registered world", chip_type),
+            })),
+        )
+            .into_response();
+    };
+
+    if let Err(ubl_err) =
+        authorize_write_headers(&state, &headers, KAT_REPORT_CHIP_TYPE, &world).await
+    {
+        return (
+            StatusCode::from_u16(ubl_err.code.http_status()).unwrap_or(StatusCode::FORBIDDEN),
+            Json(ubl_err.to_json()),
+        )
+            .into_response();
+    }
+
+    let mut results = Vec::new();
+    let mut passed = 0usize;
+    for ver in view.versions.values() {
+        let (rows, ver_passed) = run_version_kats(&state, ver).await;
+        results.extend(rows);
+        passed += ver_passed;
+    }
+    let total = results.len();
+    let failed = total - passed;
+    let generated_at = chrono::Utc::now().to_rfc3339();
+
+    let report = json!({
+        "@type": KAT_REPORT_CHIP_TYPE,
+        "@id": format!("kat-report:{}:{}", chip_type, generated_at),
+        "@ver": "1.0.0",
+        "@world": world,
+        "target_type": chip_type,
+        "latest_version": view.latest_version,
+        "generated_at": generated_at,
+        "total": total,
+        "passed": passed,
+        "failed": failed,
+        "results": results,
+    });
+    let metadata = ubl_chipstore::ExecutionMetadata {
+        runtime_version: "registry/kat-validate".to_string(),
+        execution_time_ms: 0,
+        fuel_consumed: 0,
+        policies_applied: vec![],
+        executor_did: ubl_types::Did::new_unchecked(state.pipeline.did.clone()),
+        reproducible: false,
+    };
+    let report_cid = match state
+        .chip_store
+        .store_executed_chip(report.clone(), "self".to_string(), metadata)
+        .await
+    {
+        Ok(cid) => cid,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type":"ubl/error",
+                    "code":"INTERNAL_ERROR",
+                    "message": format!("KAT report persist failed: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/registry.validation",
+            "type": chip_type,
+            "report_cid": report_cid,
+            "total": total,
+            "passed": passed,
+            "failed": failed,
+            "report": report,
+        })),
+    )
+        .into_response()
+}
+
+/// POST /v1/registry/types/:chip_type/versions/:ver/kats/run — run every KAT
+/// of one version and return the report. Nothing is persisted; the status is
+/// 422 when any KAT fails so deploy pipelines can gate on it.
+pub(crate) async fn registry_run_version_kats(
+    State(state): State<AppState>,
+    Path((chip_type, ver)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    let registry = match materialize_registry(&state, None).await {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type":"ubl/error",
+                    "code":"INTERNAL_ERROR",
+                    "message": format!("registry materialization failed: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+    let Some(view) = registry.types.get(&chip_type) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry type '{}' not found", chip_type),
+            })),
+        )
+            .into_response();
+    };
+    let Some(version) = view.versions.get(&ver) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry version '{}' not found for type '{}'", ver, chip_type),
+            })),
+        )
+            .into_response();
+    };
+    let Some(world) = view.world.clone() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry type '{}' has no registered world", chip_type),
+            })),
+        )
+            .into_response();
+    };
+
+    // KAT inputs are submitted as trusted writes of the type itself.
+    if let Err(ubl_err) = authorize_write_headers(&state, &headers, &chip_type, &world).await {
+        return (
+            StatusCode::from_u16(ubl_err.code.http_status()).unwrap_or(StatusCode::FORBIDDEN),
+            Json(ubl_err.to_json()),
+        )
+            .into_response();
+    }
+
+    let (results, passed) = run_version_kats(&state, version).await;
+    let total = results.len();
+    let failed = total - passed;
+    let status = if failed == 0 {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    (
+        status,
+        Json(json!({
+            "@type": "ubl/registry.kat_run",
+            "type": chip_type,
+            "version": version.version,
+            "total": total,
+            "passed": passed,
+            "failed": failed,
+            "results": results,
+        })),
+    )
+        .into_response()
 }
 
 pub(crate) async fn registry_types(
@@ -510,9 +932,313 @@ pub(crate) async fn registry_type_version(
         .into_response()
 }
 
+/// Placeholder for a schema `field_type`. Types the schema check does not
+/// know only need a non-null value, so they get a `"<field_type>"` stub.
+fn template_placeholder(field_type: &str) -> Value {
+    match field_type {
+        "string" => json!(""),
+        "number" | "integer" => json!(0),
+        "boolean" | "bool" => json!(false),
+        "array" => json!([]),
+        "object" => json!({}),
+        other => json!(format!("<{}>", other)),
+    }
+}
+
+/// GET /v1/registry/types/:chip_type/template — skeleton body for the latest
+/// (or `?version=`) registered version: envelope stubs plus every required
+/// field with a placeholder of its declared type.
+pub(crate) async fn registry_type_template(
+    State(state): State<AppState>,
+    Path(chip_type): Path<String>,
+    Query(query): Query<std::collections::BTreeMap<String, String>>,
+) -> Response {
+    let registry = match materialize_registry(&state, None).await {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type":"ubl/error",
+                    "code":"INTERNAL_ERROR",
+                    "message": format!("registry materialization failed: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+    let Some(view) = registry.types.get(&chip_type) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry type '{}' not found", chip_type),
+            })),
+        )
+            .into_response();
+    };
+    let ver = query
+        .get("version")
+        .cloned()
+        .or_else(|| view.latest_version.clone());
+    let Some(version) = ver.as_ref().and_then(|v| view.versions.get(v)) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!(
+                    "Registry version '{}' not found for type '{}'",
+                    ver.as_deref().unwrap_or("-"),
+                    chip_type
+                ),
+            })),
+        )
+            .into_response();
+    };
+    let schema: Option<ubl_runtime::meta_chip::TypeSchema> = version
+        .schema
+        .clone()
+        .and_then(|s| serde_json::from_value(s).ok());
+
+    let mut template = serde_json::Map::new();
+    template.insert("@type".into(), json!(chip_type));
+    template.insert("@ver".into(), json!(version.version));
+    template.insert(
+        "@world".into(),
+        json!(view
+            .world
+            .clone()
+            .unwrap_or_else(|| "a/<app>/t/<tenant>".to_string())),
+    );
+    template.insert("@id".into(), json!("<unique-id>"));
+    let mut fields = Vec::new();
+    if let Some(schema) = &schema {
+        for field in &schema.required_fields {
+            template.insert(field.name.clone(), template_placeholder(&field.field_type));
+        }
+        let all = schema
+            .required_fields
+            .iter()
+            .map(|f| (f, true))
+            .chain(schema.optional_fields.iter().map(|f| (f, false)));
+        for (field, required) in all {
+            fields.push(json!({
+                "name": field.name,
+                "field_type": field.field_type,
+                "required": required,
+                "description": field.description,
+            }));
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(json!({
+            "@type": "ubl/registry.template",
+            "type": chip_type,
+            "version": version.version,
+            "required_cap": version.required_cap,
+            "template": template,
+            "fields": fields,
+            "hints": {
+                "required_cap": version.required_cap.as_ref().map(|cap| format!(
+                    "submissions need a capability granting '{}'", cap
+                )),
+                "@id": "replace with an id unique within the world",
+            },
+        })),
+    )
+        .into_response()
+}
+
+fn version_schema(version: &RegistryVersionView) -> ubl_runtime::meta_chip::TypeSchema {
+    version
+        .schema
+        .clone()
+        .and_then(|s| serde_json::from_value(s).ok())
+        .unwrap_or(ubl_runtime::meta_chip::TypeSchema {
+            required_fields: vec![],
+            optional_fields: vec![],
+            required_cap: None,
+        })
+}
+
+fn kat_labels(version: &RegistryVersionView) -> std::collections::BTreeSet<String> {
+    version
+        .kats
+        .iter()
+        .filter_map(|k| k.get("label").and_then(|v| v.as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Structured difference between two registered versions of one type.
+///
+/// Fields are matched by name: a field in both versions is `changed` when its
+/// `field_type` differs or it moved between required and optional; otherwise
+/// it is added or removed from its list. KATs are matched by label.
+fn registry_version_diff(from: &RegistryVersionView, to: &RegistryVersionView) -> Value {
+    use std::collections::BTreeMap;
+    use ubl_runtime::meta_chip::{SchemaField, TypeSchema};
+
+    fn by_name(schema: &TypeSchema) -> BTreeMap<&str, (&SchemaField, bool)> {
+        schema
+            .required_fields
+            .iter()
+            .map(|f| (f.name.as_str(), (f, true)))
+            .chain(
+                schema
+                    .optional_fields
+                    .iter()
+                    .map(|f| (f.name.as_str(), (f, false))),
+            )
+            .collect()
+    }
+    fn field_json(field: &SchemaField) -> Value {
+        json!({"name": field.name, "field_type": field.field_type})
+    }
+
+    let (from_schema, to_schema) = (version_schema(from), version_schema(to));
+    let (old, new) = (by_name(&from_schema), by_name(&to_schema));
+    let mut added_required = Vec::new();
+    let mut added_optional = Vec::new();
+    let mut removed_required = Vec::new();
+    let mut removed_optional = Vec::new();
+    let mut changed = Vec::new();
+    for (name, (field, required)) in &new {
+        match old.get(name) {
+            None if *required => added_required.push(field_json(field)),
+            None => added_optional.push(field_json(field)),
+            Some((prev, prev_required))
+                if prev.field_type != field.field_type || prev_required != required =>
+            {
+                changed.push(json!({
+                    "name": name,
+                    "from": {"field_type": prev.field_type, "required": prev_required},
+                    "to": {"field_type": field.field_type, "required": required},
+                }));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, (field, required)) in &old {
+        if !new.contains_key(name) {
+            if *required {
+                removed_required.push(field_json(field));
+            } else {
+                removed_optional.push(field_json(field));
+            }
+        }
+    }
+
+    let required_cap = (from_schema.required_cap != to_schema.required_cap)
+        .then(|| json!({"from": from_schema.required_cap, "to": to_schema.required_cap}));
+    let (old_kats, new_kats) = (kat_labels(from), kat_labels(to));
+    json!({
+        "added_required": added_required,
+        "removed_required": removed_required,
+        "added_optional": added_optional,
+        "removed_optional": removed_optional,
+        "changed": changed,
+        "required_cap": required_cap,
+        "added_kats": new_kats.difference(&old_kats).collect::<Vec<_>>(),
+        "removed_kats": old_kats.difference(&new_kats).collect::<Vec<_>>(),
+    })
+}
+
+/// GET /v1/registry/types/:chip_type/diff?from=&to= — what changed between
+/// two registered versions of a type.
+pub(crate) async fn registry_type_diff(
+    State(state): State<AppState>,
+    Path(chip_type): Path<String>,
+    Query(query): Query<std::collections::BTreeMap<String, String>>,
+) -> Response {
+    let (Some(from), Some(to)) = (query.get("from"), query.get("to")) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"INVALID_REQUEST",
+                "message": "both ?from= and ?to= versions are required",
+            })),
+        )
+            .into_response();
+    };
+    let registry = match materialize_registry(&state, None).await {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "@type":"ubl/error",
+                    "code":"INTERNAL_ERROR",
+                    "message": format!("registry materialization failed: {}", e),
+                })),
+            )
+                .into_response();
+        }
+    };
+    let Some(view) = registry.types.get(&chip_type) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry type '{}' not found", chip_type),
+            })),
+        )
+            .into_response();
+    };
+    let (Some(from_version), Some(to_version)) = (view.versions.get(from), view.versions.get(to))
+    else {
+        let missing = if view.versions.contains_key(from) {
+            to
+        } else {
+            from
+        };
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "@type":"ubl/error",
+                "code":"NOT_FOUND",
+                "message": format!("Registry version '{}' not found for type '{}'", missing, chip_type),
+            })),
+        )
+            .into_response();
+    };
+
+    let mut diff = registry_version_diff(from_version, to_version);
+    diff["@type"] = json!("ubl/registry.diff");
+    diff["type"] = json!(chip_type);
+    diff["from"] = json!(from);
+    diff["to"] = json!(to);
+    (StatusCode::OK, Json(diff)).into_response()
+}
+
+/// The registry as seen from `world_filter` (all worlds when `None`), served
+/// from [`RegistryCache`] until the next meta chip write.
 pub(crate) async fn materialize_registry(
     state: &AppState,
     world_filter: Option<&str>,
+) -> Result<Arc<RegistryView>, String> {
+    if let Some(hit) = state.registry_cache.get(world_filter) {
+        metrics::inc_registry_cache(true);
+        return Ok(hit);
+    }
+    metrics::inc_registry_cache(false);
+    let generation = state.registry_cache.generation();
+    let view = Arc::new(build_registry(state, world_filter).await?);
+    state
+        .registry_cache
+        .put(world_filter, generation, view.clone());
+    Ok(view)
+}
+
+async fn build_registry(
+    state: &AppState,
+    world_filter: Option<&str>,
 ) -> Result<RegistryView, String> {
     fn world_matches(chip: &ubl_chipstore::StoredChip, world_filter: Option<&str>) -> bool {
         let Some(expected) = world_filter else {
@@ -532,6 +1258,7 @@ pub(crate) async fn materialize_registry(
         map.entry(chip_type.to_string())
             .or_insert_with(|| RegistryTypeView {
                 chip_type: chip_type.to_string(),
+                world: None,
                 latest_version: None,
                 deprecated: false,
                 has_kats: false,
@@ -561,6 +1288,11 @@ pub(crate) async fn materialize_registry(
             continue;
         };
         let entry = type_entry(&mut types, &parsed.target_type);
+        entry.world = chip
+            .chip_data
+            .get("@world")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
         entry.latest_version = Some(parsed.type_version.clone());
         entry.description = Some(parsed.description.clone());
         entry.has_kats = entry.has_kats || !parsed.kats.is_empty();
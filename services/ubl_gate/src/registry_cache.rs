@@ -0,0 +1,87 @@
+//! Materialized registry snapshots, cached per world filter.
+//!
+//! Building a [`RegistryView`] scans every `ubl/meta.*` chip, so each built
+//! view is kept until a meta chip is written. A write drops every entry and
+//! bumps a generation counter, so a view built while the write lands is never
+//! stored as fresh. At most [`MAX_CACHED_VIEWS`] filters are held; the least
+//! recently read one makes room. A cold cache simply rebuilds on first read.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::templates::RegistryView;
+
+/// World filters held at once; the query string picks them, so bound them.
+pub(crate) const MAX_CACHED_VIEWS: usize = 256;
+
+/// Chip types whose writes change the materialized registry.
+pub(crate) fn is_registry_chip_type(chip_type: &str) -> bool {
+    chip_type.starts_with("ubl/meta.")
+}
+
+struct CachedView {
+    view: Arc<RegistryView>,
+    /// Read clock tick of the last hit, for eviction.
+    last_used: AtomicU64,
+}
+
+#[derive(Default)]
+pub(crate) struct RegistryCache {
+    generation: AtomicU64,
+    clock: AtomicU64,
+    entries: RwLock<HashMap<Option<String>, CachedView>>,
+}
+
+impl RegistryCache {
+    /// Generation to stamp a view built from now on.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Cached view for `world_filter`, if any.
+    pub(crate) fn get(&self, world_filter: Option<&str>) -> Option<Arc<RegistryView>> {
+        let entries = self.entries.read().unwrap_or_else(|e| e.into_inner());
+        let cached = entries.get(&world_filter.map(str::to_string))?;
+        cached.last_used.store(self.tick(), Ordering::Relaxed);
+        Some(cached.view.clone())
+    }
+
+    /// Store a view built at `generation`, unless a write landed during the
+    /// build. Evicts the least recently read view when full.
+    pub(crate) fn put(&self, world_filter: Option<&str>, generation: u64, view: Arc<RegistryView>) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        // Checked under the lock `invalidate` clears through.
+        if generation != self.generation() {
+            return;
+        }
+        let key = world_filter.map(str::to_string);
+        if !entries.contains_key(&key) && entries.len() >= MAX_CACHED_VIEWS {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, c)| c.last_used.load(Ordering::Relaxed))
+                .map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        let last_used = AtomicU64::new(self.tick());
+        entries.insert(key, CachedView { view, last_used });
+    }
+
+    /// Drop every cached view.
+    pub(crate) fn invalidate(&self) {
+        let mut entries = self.entries.write().unwrap_or_else(|e| e.into_inner());
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.clear();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
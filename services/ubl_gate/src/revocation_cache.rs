@@ -0,0 +1,137 @@
+//! Materialized bearer-token revocation list.
+//!
+//! Every `ubl/token.revoke` chip names a `token_id`; the list maps each
+//! revoked id to when it was revoked (the body's `revoked_at`, else the
+//! chip's `created_at`, earliest revoke winning). Only revokes that pass
+//! [`ubl_runtime::token_revoke::authorize`] count, so a chip that reached
+//! the store without CHECK's authorship rule is ignored. It is rebuilt from the
+//! chip store only after a revoke lands, using the same generation scheme as
+//! the registry cache so a list built during a write is never served fresh.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use ubl_chipstore::{ChipQuery, ChipStore, StoredChip};
+use ubl_runtime::token_revoke::{self, RevokeTarget};
+
+pub(crate) const TOKEN_REVOKE_CHIP_TYPE: &str = token_revoke::TYPE_TOKEN_REVOKE;
+
+/// Revoked token id -> revocation time.
+pub(crate) type RevokedTokens = HashMap<String, DateTime<Utc>>;
+
+struct CachedList {
+    generation: u64,
+    revoked: Arc<RevokedTokens>,
+}
+
+#[derive(Default)]
+pub(crate) struct RevocationCache {
+    generation: AtomicU64,
+    entry: RwLock<Option<CachedList>>,
+}
+
+impl RevocationCache {
+    /// Current revocation list, rebuilt when a revoke landed since the last
+    /// build.
+    pub(crate) async fn revoked_tokens(
+        &self,
+        store: &ChipStore,
+    ) -> Result<Arc<RevokedTokens>, String> {
+        let current = self.generation.load(Ordering::Acquire);
+        if let Some(cached) = self
+            .entry
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .filter(|c| c.generation == current)
+        {
+            return Ok(cached.revoked.clone());
+        }
+
+        let revokes = store
+            .query(&ChipQuery {
+                chip_type: Some(TOKEN_REVOKE_CHIP_TYPE.to_string()),
+                tags: vec![],
+                created_after: None,
+                created_before: None,
+                executor_did: None,
+                limit: Some(usize::MAX),
+                offset: None,
+            })
+            .await
+            .map_err(|e| format!("token revocation query failed: {}", e))?;
+        let mut honored = Vec::with_capacity(revokes.chips.len());
+        let mut targets: HashMap<String, Option<RevokeTarget>> = HashMap::new();
+        for chip in revokes.chips {
+            let Ok(token_id) = token_revoke::token_id(&chip.chip_data) else {
+                continue;
+            };
+            if !targets.contains_key(token_id) {
+                let target = token_revoke::resolve_target(store, token_id)
+                    .await
+                    .map_err(|e| format!("token revocation lookup failed: {}", e))?;
+                targets.insert(token_id.to_string(), target);
+            }
+            let world = chip
+                .chip_data
+                .get("@world")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let authorized = targets[token_id].as_ref().is_some_and(|target| {
+                token_revoke::authorize(world, chip.author.as_ref(), target).is_ok()
+            });
+            if authorized {
+                honored.push(chip);
+            }
+        }
+        let revoked = Arc::new(materialize(&honored));
+        *self.entry.write().unwrap_or_else(|e| e.into_inner()) = Some(CachedList {
+            generation: current,
+            revoked: revoked.clone(),
+        });
+        Ok(revoked)
+    }
+
+    /// Mark the cached list stale.
+    pub(crate) fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Revoke chips without a `token_id` or a parsable timestamp are ignored.
+fn materialize(chips: &[StoredChip]) -> RevokedTokens {
+    let mut revoked = RevokedTokens::new();
+    for chip in chips {
+        let Some(token_id) = chip
+            .chip_data
+            .get("token_id")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+        else {
+            continue;
+        };
+        let Some(revoked_at) = chip
+            .chip_data
+            .get("revoked_at")
+            .and_then(|v| v.as_str())
+            .and_then(parse_rfc3339)
+            .or_else(|| parse_rfc3339(&chip.created_at))
+        else {
+            continue;
+        };
+        revoked
+            .entry(token_id.to_string())
+            .and_modify(|at| *at = (*at).min(revoked_at))
+            .or_insert(revoked_at);
+    }
+    revoked
+}
+
+fn parse_rfc3339(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
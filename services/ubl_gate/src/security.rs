@@ -0,0 +1,113 @@
+//! Security response headers for the console and API.
+//!
+//! HTML responses get `Content-Security-Policy`, `X-Frame-Options` and
+//! `Referrer-Policy`; every response gets `X-Content-Type-Options: nosniff`.
+//! Each HTML header can be overridden by env or disabled with `off`.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::warn;
+
+use crate::state::AppState;
+
+/// Default CSP: same-origin everything, plus the htmx CDN and the inline
+/// scripts/styles the console templates ship with.
+pub(crate) const DEFAULT_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; \
+script-src 'self' 'unsafe-inline' https://unpkg.com; style-src 'self' 'unsafe-inline'; \
+img-src 'self' data:; connect-src 'self'; frame-ancestors 'none'; base-uri 'self'; \
+form-action 'self'";
+pub(crate) const DEFAULT_X_FRAME_OPTIONS: &str = "DENY";
+pub(crate) const DEFAULT_REFERRER_POLICY: &str = "no-referrer";
+
+pub(crate) struct SecurityHeaders {
+    pub content_security_policy: Option<HeaderValue>,
+    pub frame_options: Option<HeaderValue>,
+    pub referrer_policy: Option<HeaderValue>,
+}
+
+impl SecurityHeaders {
+    /// Overrides: `UBL_CONTENT_SECURITY_POLICY`, `UBL_X_FRAME_OPTIONS`,
+    /// `UBL_REFERRER_POLICY`.
+    pub fn from_env() -> Self {
+        Self {
+            content_security_policy: header_from_env(
+                "UBL_CONTENT_SECURITY_POLICY",
+                DEFAULT_CONTENT_SECURITY_POLICY,
+            ),
+            frame_options: header_from_env("UBL_X_FRAME_OPTIONS", DEFAULT_X_FRAME_OPTIONS),
+            referrer_policy: header_from_env("UBL_REFERRER_POLICY", DEFAULT_REFERRER_POLICY),
+        }
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) {
+        headers
+            .entry(header::X_CONTENT_TYPE_OPTIONS)
+            .or_insert(HeaderValue::from_static("nosniff"));
+        let is_html = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/html"));
+        if !is_html {
+            return;
+        }
+        for (name, value) in [
+            (
+                header::CONTENT_SECURITY_POLICY,
+                &self.content_security_policy,
+            ),
+            (header::X_FRAME_OPTIONS, &self.frame_options),
+            (header::REFERRER_POLICY, &self.referrer_policy),
+        ] {
+            if let Some(value) = value {
+                headers.entry(name).or_insert(value.clone());
+            }
+        }
+    }
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self {
+            content_security_policy: Some(HeaderValue::from_static(
+                DEFAULT_CONTENT_SECURITY_POLICY,
+            )),
+            frame_options: Some(HeaderValue::from_static(DEFAULT_X_FRAME_OPTIONS)),
+            referrer_policy: Some(HeaderValue::from_static(DEFAULT_REFERRER_POLICY)),
+        }
+    }
+}
+
+fn header_from_env(name: &str, default: &'static str) -> Option<HeaderValue> {
+    let Ok(raw) = std::env::var(name) else {
+        return Some(HeaderValue::from_static(default));
+    };
+    let raw = raw.trim();
+    if raw.eq_ignore_ascii_case("off") {
+        return None;
+    }
+    if raw.is_empty() {
+        return Some(HeaderValue::from_static(default));
+    }
+    match HeaderValue::from_str(raw) {
+        Ok(value) => Some(value),
+        Err(_) => {
+            warn!(env = name, "invalid header value, using default");
+            Some(HeaderValue::from_static(default))
+        }
+    }
+}
+
+/// Router-wide middleware applying `state.security_headers`.
+pub(crate) async fn apply_security_headers(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let mut res = next.run(req).await;
+    state.security_headers.apply(res.headers_mut());
+    res
+}
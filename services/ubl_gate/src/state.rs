@@ -1,4 +1,5 @@
 use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -8,20 +9,30 @@ use ubl_eventstore::EventStore;
 use ubl_runtime::advisory::AdvisoryEngine;
 use ubl_runtime::durable_store::DurableStore;
 use ubl_runtime::manifest::GateManifest;
-use ubl_runtime::rate_limit::CanonRateLimiter;
+use ubl_runtime::rate_limit::{CanonRateLimiter, RateLimiter, WorldRateLimiter};
 use ubl_runtime::UblPipeline;
 use ubl_runtime::error_response::ErrorCode;
 
-use crate::utils::{env_bool, csv_env, extract_api_key};
+use crate::client_ip::TrustedProxies;
+use crate::manifest_cache::ManifestCache;
+use crate::registry_cache::RegistryCache;
+use crate::revocation_cache::RevocationCache;
+use crate::security::SecurityHeaders;
+use crate::utils::{csv_env, env_bool, extract_api_key, world_scope_allows};
 
 #[derive(Clone)]
 pub(crate) struct AppState {
     pub pipeline: Arc<UblPipeline>,
     pub chip_store: Arc<ChipStore>,
     pub manifest: Arc<GateManifest>,
+    pub manifest_cache: Arc<ManifestCache>,
+    pub registry_cache: Arc<RegistryCache>,
+    pub revocation_cache: Arc<RevocationCache>,
     pub advisory_engine: Arc<AdvisoryEngine>,
     pub http_client: reqwest::Client,
     pub canon_rate_limiter: Option<Arc<CanonRateLimiter>>,
+    pub ip_rate_limiter: Option<Arc<RateLimiter>>,
+    pub world_rate_limiter: Option<Arc<WorldRateLimiter>>,
     pub mcp_token_rate_limiter: Arc<McpTokenRateLimiter>,
     pub durable_store: Option<Arc<DurableStore>>,
     pub event_store: Option<Arc<EventStore>>,
@@ -30,7 +41,48 @@ pub(crate) struct AppState {
     pub genesis_pubkey_sha256: Option<String>,
     pub release_commit: Option<String>,
     pub gate_binary_sha256: Option<String>,
-    pub write_access_policy: Arc<WriteAccessPolicy>,
+    pub write_access_policy: Arc<SharedWriteAccessPolicy>,
+    pub readiness: Arc<GateReadiness>,
+    pub admin_access: Arc<AdminAccessPolicy>,
+    pub security_headers: Arc<SecurityHeaders>,
+    pub trusted_proxies: Arc<TrustedProxies>,
+    pub denial_redaction: Arc<DenialRedaction>,
+}
+
+/// Startup readiness flag. The gate binds before genesis bootstrap finishes;
+/// until `mark_ready` flips the flag, chip writes are refused with 503.
+pub(crate) struct GateReadiness {
+    ready: AtomicBool,
+    pub retry_after_secs: u64,
+}
+
+impl GateReadiness {
+    pub fn starting() -> Self {
+        let retry_after_secs = std::env::var("UBL_STARTUP_RETRY_AFTER_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(2)
+            .max(1);
+        Self {
+            ready: AtomicBool::new(false),
+            retry_after_secs,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn ready_for_tests() -> Self {
+        let readiness = Self::starting();
+        readiness.mark_ready();
+        readiness
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    pub fn mark_ready(&self) {
+        self.ready.store(true, Ordering::Release);
+    }
 }
 
 #[derive(Clone)]
@@ -97,7 +149,9 @@ pub(crate) struct McpWsAuth {
 pub(crate) struct WriteAccessPolicy {
     pub auth_required: bool,
     pub api_keys: Vec<String>,
+    /// Exact worlds, or `<prefix>/*` for a whole subtree.
     pub public_worlds: Vec<String>,
+    /// Exact types, or `<prefix>*` (e.g. `ubl/meta.*`).
     pub public_types: Vec<String>,
 }
 
@@ -177,8 +231,13 @@ impl WriteAccessPolicy {
         if ubl_runtime::auth::is_onboarding_type(chip_type) {
             return true;
         }
-        self.public_worlds.iter().any(|w| w == world)
-            && self.public_types.iter().any(|t| t == chip_type)
+        self.public_worlds
+            .iter()
+            .any(|w| public_world_matches(w, world))
+            && self
+                .public_types
+                .iter()
+                .any(|t| public_type_matches(t, chip_type))
     }
 
     pub fn matches_api_key(&self, headers: Option<&HeaderMap>) -> bool {
@@ -191,3 +250,124 @@ impl WriteAccessPolicy {
         self.api_keys.iter().any(|k| k == &presented)
     }
 }
+
+/// `a/chip-registry/*` admits `a/chip-registry` and every world under it;
+/// any other entry must match exactly.
+fn public_world_matches(entry: &str, world: &str) -> bool {
+    match entry.strip_suffix("/*") {
+        Some(prefix) => world_scope_allows(prefix, world),
+        None => entry == world,
+    }
+}
+
+/// `ubl/meta.*` admits every type starting with `ubl/meta.`.
+fn public_type_matches(entry: &str, chip_type: &str) -> bool {
+    match entry.strip_suffix('*') {
+        Some(prefix) => chip_type.starts_with(prefix),
+        None => entry == chip_type,
+    }
+}
+
+/// Worlds whose denials are answered without policy detail. The receipt
+/// still records `denied_by`; only the submission response is generic, and
+/// it omits `receipt_cid` because receipt reads are public. Callers holding a
+/// write API key or bearer token get the full response.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DenialRedaction {
+    pub worlds: Vec<String>,
+}
+
+impl DenialRedaction {
+    /// Generic message returned in place of the denial reason.
+    pub const MESSAGE: &'static str = "request denied by policy";
+
+    /// `UBL_REDACT_DENIAL_WORLDS`: comma-separated worlds (exact or path
+    /// prefix, `*` for all). Empty = no redaction.
+    pub fn from_env() -> Self {
+        Self {
+            worlds: csv_env("UBL_REDACT_DENIAL_WORLDS"),
+        }
+    }
+
+    pub fn applies(&self, world: &str) -> bool {
+        self.worlds.iter().any(|w| {
+            w == "*"
+                || w == world
+                || world
+                    .strip_prefix(w.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Runtime-swappable holder for the write policy. Readers take a snapshot
+/// with `load`; admin updates hold `begin_update` from their `load` to their
+/// `store`, so concurrent edits apply one after another instead of one
+/// overwriting the other.
+pub(crate) struct SharedWriteAccessPolicy {
+    inner: std::sync::RwLock<Arc<WriteAccessPolicy>>,
+    updates: tokio::sync::Mutex<()>,
+}
+
+impl SharedWriteAccessPolicy {
+    pub fn new(policy: WriteAccessPolicy) -> Self {
+        Self {
+            inner: std::sync::RwLock::new(Arc::new(policy)),
+            updates: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub async fn begin_update(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.updates.lock().await
+    }
+
+    pub fn load(&self) -> Arc<WriteAccessPolicy> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn store(&self, policy: WriteAccessPolicy) {
+        *self.inner.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(policy);
+    }
+}
+
+/// Guard for `/v1/admin/*` endpoints. Fails closed: with no
+/// `UBL_ADMIN_API_KEYS` configured every admin call is refused.
+#[derive(Clone, Debug)]
+pub(crate) struct AdminAccessPolicy {
+    pub api_keys: Vec<String>,
+}
+
+impl AdminAccessPolicy {
+    pub fn from_env() -> Self {
+        Self {
+            api_keys: csv_env("UBL_ADMIN_API_KEYS"),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_keys_for_tests(keys: &[&str]) -> Self {
+        Self {
+            api_keys: keys.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    pub fn authorize(&self, headers: &HeaderMap) -> Result<(), (ErrorCode, String)> {
+        if self.api_keys.is_empty() {
+            return Err((
+                ErrorCode::Unauthorized,
+                "admin endpoints disabled: UBL_ADMIN_API_KEYS not configured".to_string(),
+            ));
+        }
+        let Some(presented) = extract_api_key(headers) else {
+            return Err((
+                ErrorCode::Unauthorized,
+                "admin API key required (X-API-Key)".to_string(),
+            ));
+        };
+        if self.api_keys.iter().any(|k| k == &presented) {
+            Ok(())
+        } else {
+            Err((ErrorCode::Unauthorized, "invalid admin API key".to_string()))
+        }
+    }
+}
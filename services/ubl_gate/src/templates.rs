@@ -14,6 +14,7 @@ pub(crate) struct RegistryView {
 #[derive(Debug, Clone, serde::Serialize)]
 pub(crate) struct RegistryTypeView {
     pub(crate) chip_type: String,
+    pub(crate) world: Option<String>,
     pub(crate) latest_version: Option<String>,
     pub(crate) deprecated: bool,
     pub(crate) has_kats: bool,
@@ -139,18 +140,31 @@ pub(crate) struct RegistryKatRow {
     pub(crate) label: String,
     pub(crate) expected_decision: String,
     pub(crate) expected_error: String,
+    pub(crate) expected_error_contains: String,
     pub(crate) input_json_preview: String,
 }
 
+/// One KAT expectation against what the run produced.
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct KatFieldDiff {
+    pub(crate) field: &'static str,
+    pub(crate) expected: String,
+    pub(crate) actual: String,
+    pub(crate) matched: bool,
+}
+
 #[derive(Template)]
 #[template(path = "registry_kat_result.html")]
 pub(crate) struct RegistryKatResultTemplate {
     pub(crate) status_code: u16,
     pub(crate) kat_label: String,
+    pub(crate) mode: &'static str,
     pub(crate) expected_decision: String,
     pub(crate) expected_error: String,
+    pub(crate) expected_error_contains: String,
     pub(crate) actual_decision: String,
     pub(crate) actual_error: String,
+    pub(crate) diff: Vec<KatFieldDiff>,
     pub(crate) receipt_cid: String,
     pub(crate) pass: bool,
     pub(crate) response_json: String,
@@ -12,21 +12,100 @@ use tracing_subscriber::EnvFilter;
 use ubl_receipt::UnifiedReceipt;
 use ubl_runtime::{
     error_response::{ErrorCode, UblError},
-    rate_limit::{CanonRateLimiter, RateLimitConfig},
+    rate_limit::{CanonRateKey, CanonRateLimiter, RateLimitConfig, RateLimiter, WorldRateLimiter},
     rich_url::{build_public_receipt_link_v1, build_public_receipt_token_v1, PublicReceiptLink},
+    UblPipeline,
 };
 
 use crate::state::{AppState, McpWsAuth};
 
 // ── Tracing ──────────────────────────────────────────────────────────────────
 
-pub(crate) fn init_tracing() {
+/// Keeps the OTLP exporter alive; dropping it flushes buffered spans.
+pub(crate) struct TracingGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            let _ = provider.shutdown();
+        }
+    }
+}
+
+/// Log to stdout (`RUST_LOG`). With the `otel` feature and
+/// `UBL_OTLP_ENDPOINT` set (a full OTLP/HTTP traces URL, e.g.
+/// `http://collector:4318/v1/traces`), spans are also exported, including
+/// the trace-level `ubl.pipeline`, `ubl.stage` and `ubl.outbox.deliver`
+/// spans that stay disabled otherwise.
+pub(crate) fn init_tracing() -> TracingGuard {
+    use tracing_subscriber::prelude::*;
+
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info,ubl_runtime=debug,ubl_gate=debug"));
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(env_filter)
-        .with_target(false)
-        .try_init();
+    let registry = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .with_filter(env_filter),
+    );
+
+    #[cfg(feature = "otel")]
+    let (registry, provider, otlp_error) = {
+        use opentelemetry::trace::TracerProvider as _;
+        use tracing_subscriber::filter::{LevelFilter, Targets};
+
+        // Reported once the subscriber is installed.
+        let mut otlp_error = None;
+        let provider = env_opt_trim("UBL_OTLP_ENDPOINT").and_then(|endpoint| {
+            otlp_tracer_provider(&endpoint)
+                .map_err(|e| otlp_error = Some((endpoint, e.to_string())))
+                .ok()
+        });
+        let layer = provider.as_ref().map(|p| {
+            tracing_opentelemetry::layer()
+                .with_tracer(p.tracer("ubl_gate"))
+                .with_filter(
+                    Targets::new()
+                        .with_default(LevelFilter::INFO)
+                        .with_target("ubl_runtime", LevelFilter::TRACE)
+                        .with_target("ubl_gate", LevelFilter::TRACE),
+                )
+        });
+        (registry.with(layer), provider, otlp_error)
+    };
+
+    let _ = registry.try_init();
+    #[cfg(feature = "otel")]
+    if let Some((endpoint, error)) = otlp_error {
+        warn!(%endpoint, %error, "OTLP exporter disabled");
+    }
+    TracingGuard {
+        #[cfg(feature = "otel")]
+        provider,
+    }
+}
+
+#[cfg(feature = "otel")]
+fn otlp_tracer_provider(
+    endpoint: &str,
+) -> Result<opentelemetry_sdk::trace::SdkTracerProvider, opentelemetry_otlp::ExporterBuildError> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()?;
+    Ok(opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name("ubl_gate")
+                .build(),
+        )
+        .build())
 }
 
 // ── Env helpers ───────────────────────────────────────────────────────────────
@@ -150,9 +229,41 @@ pub(crate) fn load_canon_rate_limiter() -> Option<Arc<CanonRateLimiter>> {
         .and_then(|v| v.parse::<u32>().ok())
         .unwrap_or(120)
         .max(1);
-    Some(Arc::new(CanonRateLimiter::new(
-        RateLimitConfig::per_minute(per_min),
-    )))
+    // `UBL_CANON_RATE_LIMIT_KEY=payload|subject|both`; default `payload`.
+    let key = match env_opt_trim("UBL_CANON_RATE_LIMIT_KEY") {
+        None => CanonRateKey::Payload,
+        Some(raw) => CanonRateKey::parse(&raw).unwrap_or_else(|| {
+            warn!(value = %raw, "unknown UBL_CANON_RATE_LIMIT_KEY; keying on payload");
+            CanonRateKey::Payload
+        }),
+    };
+    // `UBL_CANON_RATE_LIMIT_BURST` turns the window into a token bucket.
+    let config = match std::env::var("UBL_CANON_RATE_LIMIT_BURST")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|v| *v > 0)
+    {
+        Some(burst) => RateLimitConfig::per_minute_with_burst(per_min, burst),
+        None => RateLimitConfig::per_minute(per_min),
+    };
+    Some(Arc::new(CanonRateLimiter::new(config).with_key(key)))
+}
+
+/// Per-client-IP front-door limit; opt-in via `UBL_IP_RATE_LIMIT_PER_MIN` (unset or 0 = off).
+pub(crate) fn load_ip_rate_limiter() -> Option<Arc<RateLimiter>> {
+    let per_min = std::env::var("UBL_IP_RATE_LIMIT_PER_MIN")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|v| *v > 0)?;
+    Some(Arc::new(RateLimiter::new(RateLimitConfig::per_minute(
+        per_min,
+    ))))
+}
+
+/// Per-world write volume; opt-in via `UBL_WORLD_RATE_LIMIT_DEFAULT` and
+/// `UBL_WORLD_RATE_LIMIT_OVERRIDES` (see [`WorldRateLimiter::from_env`]).
+pub(crate) fn load_world_rate_limiter() -> Option<Arc<WorldRateLimiter>> {
+    WorldRateLimiter::from_env().map(Arc::new)
 }
 
 // ── Error builders ────────────────────────────────────────────────────────────
@@ -170,6 +281,19 @@ pub(crate) fn too_many_requests_error(message: String, details: Value) -> UblErr
     }
 }
 
+pub(crate) fn unavailable_error(message: String, details: Value) -> UblError {
+    UblError {
+        error_type: "ubl/error".to_string(),
+        id: format!("err-unavailable-{}", chrono::Utc::now().timestamp_micros()),
+        ver: "1.0".to_string(),
+        world: "a/system/t/errors".to_string(),
+        code: ErrorCode::Unavailable,
+        message,
+        link: "https://docs.ubl.agency/errors#UNAVAILABLE".to_string(),
+        details: Some(details),
+    }
+}
+
 pub(crate) fn tamper_detected_error(message: String, details: Value) -> UblError {
     UblError {
         error_type: "ubl/error".to_string(),
@@ -183,6 +307,19 @@ pub(crate) fn tamper_detected_error(message: String, details: Value) -> UblError
     }
 }
 
+pub(crate) fn chain_too_long_error(message: String, details: Value) -> UblError {
+    UblError {
+        error_type: "ubl/error".to_string(),
+        id: format!("err-chain-{}", chrono::Utc::now().timestamp_micros()),
+        ver: "1.0".to_string(),
+        world: "a/system/t/errors".to_string(),
+        code: ErrorCode::ChainTooLong,
+        message,
+        link: "https://docs.ubl.agency/errors#CHAIN_TOO_LONG".to_string(),
+        details: Some(details),
+    }
+}
+
 pub(crate) fn write_access_error(code: ErrorCode, message: String, details: Value) -> UblError {
     UblError {
         error_type: "ubl/error".to_string(),
@@ -202,6 +339,7 @@ pub(crate) fn write_access_error(code: ErrorCode, message: String, details: Valu
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn deny_write_with_receipt(
     state: &AppState,
     knock_cid: &str,
@@ -210,24 +348,37 @@ pub(crate) async fn deny_write_with_receipt(
     err_code: ErrorCode,
     value: &Value,
     subject_did: String,
+    simulate: bool,
 ) -> (StatusCode, HeaderMap, Value) {
+    let write_policy = state.write_access_policy.load();
     let details = json!({
         "@type": value.get("@type").and_then(|v| v.as_str()).unwrap_or("unknown"),
         "@world": value.get("@world").and_then(|v| v.as_str()).unwrap_or("unknown"),
         "knock_cid": knock_cid,
-        "auth_required": state.write_access_policy.auth_required,
-        "api_keys_configured": !state.write_access_policy.api_keys.is_empty(),
+        "auth_required": write_policy.auth_required,
+        "api_keys_configured": !write_policy.api_keys.is_empty(),
     });
     let ubl_err = write_access_error(err_code, reason_msg.to_string(), details);
 
     match state
         .pipeline
-        .process_knock_rejection(knock_cid, reason_code, reason_msg, Some(subject_did))
+        .process_knock_rejection(
+            knock_cid,
+            reason_code,
+            reason_msg,
+            Some(subject_did),
+            simulate,
+        )
         .await
     {
         Ok(result) => {
             let receipt_json = result.receipt.to_json().unwrap_or(json!({}));
-            let public_receipt = build_public_receipt_link(state, &receipt_json);
+            // A simulated denial is never stored, so it gets no public link.
+            let public_receipt = if simulate {
+                None
+            } else {
+                build_public_receipt_link(state, &receipt_json)
+            };
             let receipt_url = public_receipt.as_ref().map(|p| p.url.clone());
             (
                 StatusCode::from_u16(err_code.http_status()).unwrap_or(StatusCode::FORBIDDEN),
@@ -262,6 +413,7 @@ pub(crate) async fn deny_write_with_receipt(
 
 #[allow(clippy::result_large_err)]
 pub(crate) fn verify_receipt_auth_chain(
+    pipeline: &UblPipeline,
     receipt_cid: &str,
     receipt_json: &Value,
 ) -> Result<(), UblError> {
@@ -275,6 +427,16 @@ pub(crate) fn verify_receipt_auth_chain(
         )
     })?;
 
+    if let Err(e) = receipt.check_chain_len(ubl_receipt::max_chain_len_from_env()) {
+        return Err(chain_too_long_error(
+            format!("receipt {} rejected: {}", receipt_cid, e),
+            json!({
+                "receipt_cid": receipt_cid,
+                "reason": "chain_too_long"
+            }),
+        ));
+    }
+
     if !receipt.verify_auth_chain() {
         return Err(tamper_detected_error(
             format!("receipt {} auth chain broken", receipt_cid),
@@ -285,6 +447,23 @@ pub(crate) fn verify_receipt_auth_chain(
         ));
     }
 
+    // A receipt naming one of our kids must carry that key's signature;
+    // receipts from signers outside the keyring are judged by the chain alone.
+    if pipeline.verify_receipt_signature(&receipt) == Some(false) {
+        return Err(tamper_detected_error(
+            format!(
+                "receipt {} signature does not verify under kid {}",
+                receipt_cid,
+                receipt.kid.as_str()
+            ),
+            json!({
+                "receipt_cid": receipt_cid,
+                "kid": receipt.kid.as_str(),
+                "reason": "signature_invalid"
+            }),
+        ));
+    }
+
     Ok(())
 }
 
@@ -326,12 +505,13 @@ pub(crate) fn actor_hint_from_headers(
         return hint;
     };
 
-    if let Some(forwarded_for) = h
-        .get("CF-Connecting-IP")
-        .or_else(|| h.get("X-Forwarded-For"))
+    // Set by `client_ip::resolve_client_ip`; forwarded headers are only
+    // trusted there.
+    if let Some(ip) = h
+        .get(crate::client_ip::CLIENT_IP_HEADER)
         .and_then(|v| v.to_str().ok())
     {
-        let ip = forwarded_for.split(',').next().unwrap_or_default().trim();
+        let ip = ip.trim();
         if !ip.is_empty() {
             let parts: Vec<&str> = ip.split('.').collect();
             if parts.len() == 4 {
@@ -357,6 +537,55 @@ pub(crate) fn knock_reason_code(err: &ubl_runtime::knock::KnockError) -> String
 
 // ── Bearer / session auth ─────────────────────────────────────────────────────
 
+/// Write authorization for gate-originated artifacts (not chip submissions).
+///
+/// Mirrors the submit path: a session bearer must carry a write scope and a
+/// world that covers `world`; otherwise the static `WriteAccessPolicy` applies.
+pub(crate) async fn authorize_write_headers(
+    state: &AppState,
+    headers: &HeaderMap,
+    chip_type: &str,
+    world: &str,
+) -> Result<(), UblError> {
+    if parse_bearer_token(headers).is_some() {
+        return match resolve_session_bearer(state, headers).await {
+            Ok(Some(auth)) => {
+                if !scope_allows_write_type(&auth.scope, chip_type) {
+                    return Err(write_access_error(
+                        ErrorCode::PolicyDenied,
+                        write_scope_denial_message(&auth.scope, chip_type),
+                        json!({"chip_type": chip_type, "world": world}),
+                    ));
+                }
+                if !world_scope_allows(&auth.world, world) {
+                    return Err(write_access_error(
+                        ErrorCode::PolicyDenied,
+                        format!(
+                            "token world '{}' does not authorize target world '{}'",
+                            auth.world, world
+                        ),
+                        json!({"chip_type": chip_type, "world": world}),
+                    ));
+                }
+                Ok(())
+            }
+            Ok(None) => Ok(()),
+            Err(rejection) => Err(write_access_error(
+                rejection.code,
+                rejection.message.clone(),
+                rejection.details_with(json!({"chip_type": chip_type, "world": world})),
+            )),
+        };
+    }
+    state
+        .write_access_policy
+        .load()
+        .authorize_write(Some(headers), chip_type, world)
+        .map_err(|(code, msg)| {
+            write_access_error(code, msg, json!({"chip_type": chip_type, "world": world}))
+        })
+}
+
 pub(crate) fn parse_bearer_token(headers: &HeaderMap) -> Option<String> {
     let auth = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
     let (scheme, token) = auth.split_once(' ')?;
@@ -366,6 +595,103 @@ pub(crate) fn parse_bearer_token(headers: &HeaderMap) -> Option<String> {
     Some(token.trim().to_string())
 }
 
+/// Media type of the canonical NRF-1.1 encoding, the bytes a CID hashes.
+pub(crate) const NRF1_CONTENT_TYPE: &str = "application/x-nrf1";
+
+/// `application/cbor`, the binary media type clients negotiate for the same
+/// canonical bytes.
+pub(crate) const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Encoding a chip or receipt read is served in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadEncoding {
+    Json,
+    /// Canonical NRF-1.1 bytes, labelled with the binary media type asked for.
+    Canonical(&'static str),
+}
+
+/// The read encoding `Accept` asks for: the canonical bytes when it ranks
+/// `application/x-nrf1` or `application/cbor` above JSON, where `*/*` and
+/// `application/*` count as JSON; JSON otherwise. `None` when it accepts
+/// none of them, which is a 406.
+pub(crate) fn read_encoding(headers: &HeaderMap) -> Option<ReadEncoding> {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.trim().is_empty())
+    else {
+        return Some(ReadEncoding::Json);
+    };
+    let (mut binary, mut json) = (None::<(&'static str, f32)>, 0.0_f32);
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let media = params.next().unwrap_or_default().trim();
+        let q = params
+            .find_map(|p| p.trim().strip_prefix("q="))
+            .and_then(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if let Some(content_type) = [NRF1_CONTENT_TYPE, CBOR_CONTENT_TYPE]
+            .into_iter()
+            .find(|m| media.eq_ignore_ascii_case(m))
+        {
+            if !binary.is_some_and(|(_, best)| best >= q) {
+                binary = Some((content_type, q));
+            }
+        } else if ["application/json", "application/*", "*/*"]
+            .iter()
+            .any(|m| media.eq_ignore_ascii_case(m))
+        {
+            json = json.max(q);
+        }
+    }
+    match binary {
+        Some((content_type, q)) if q > json => Some(ReadEncoding::Canonical(content_type)),
+        _ if json > 0.0 => Some(ReadEncoding::Json),
+        _ => None,
+    }
+}
+
+/// 406 for an `Accept` that [`read_encoding`] cannot satisfy.
+pub(crate) fn not_acceptable_response() -> Response {
+    (
+        StatusCode::NOT_ACCEPTABLE,
+        [(header::VARY, "accept")],
+        Json(json!({
+            "@type": "ubl/error",
+            "code": "NOT_ACCEPTABLE",
+            "message": format!(
+                "supported encodings: application/json, {}, {}",
+                NRF1_CONTENT_TYPE, CBOR_CONTENT_TYPE
+            ),
+        })),
+    )
+        .into_response()
+}
+
+/// `value` encoded as NRF-1.1, sent with `headers` (ETag, caching) and
+/// `content_type`, the binary media type the client negotiated.
+pub(crate) fn canonical_binary_response(
+    value: &Value,
+    content_type: &'static str,
+    mut headers: HeaderMap,
+) -> Response {
+    match ubl_ai_nrf1::to_nrf1_bytes(value) {
+        Ok(bytes) => {
+            headers.insert(header::CONTENT_TYPE, content_type.parse().unwrap());
+            (StatusCode::OK, headers, bytes).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "@type": "ubl/error",
+                "code": "INTERNAL_ERROR",
+                "message": format!("canonical encoding failed: {}", e),
+            })),
+        )
+            .into_response(),
+    }
+}
+
 pub(crate) fn scope_allows_any(scope: &[String], required: &[&str]) -> bool {
     scope.iter().any(|s| s == "*")
         || required
@@ -373,14 +699,83 @@ pub(crate) fn scope_allows_any(scope: &[String], required: &[&str]) -> bool {
             .any(|needle| scope.iter().any(|s| s == needle))
 }
 
+/// Untyped scopes that authorize writing every chip type.
+const WRITE_SCOPES: [&str; 3] = ["write", "chip:write", "mcp:write"];
+
+/// `write:<type>` scopes on a token, without the prefix.
+fn typed_write_scopes(scope: &[String]) -> impl Iterator<Item = &str> {
+    scope.iter().filter_map(|s| s.strip_prefix("write:"))
+}
+
+/// Whether `scope` may write `chip_type`. Plain `write` (or `chip:write`,
+/// `mcp:write`, `*`) allows every type; `write:<type>` allows exactly that
+/// type and `write:<prefix>*` every type under the prefix.
+pub(crate) fn scope_allows_write_type(scope: &[String], chip_type: &str) -> bool {
+    scope_allows_any(scope, &WRITE_SCOPES)
+        || typed_write_scopes(scope).any(|allowed| match allowed.strip_suffix('*') {
+            Some(prefix) => chip_type.starts_with(prefix),
+            None => allowed == chip_type,
+        })
+}
+
+/// Denial message when [`scope_allows_write_type`] fails.
+pub(crate) fn write_scope_denial_message(scope: &[String], chip_type: &str) -> String {
+    if typed_write_scopes(scope).next().is_some() {
+        format!("token scope does not allow writing @type '{}'", chip_type)
+    } else {
+        "token scope does not allow write".to_string()
+    }
+}
+
+/// Why a session bearer was refused.
+pub(crate) struct BearerRejection {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Extra denial details, e.g. `revoked_at` for a revoked token.
+    pub details: Value,
+}
+
+impl BearerRejection {
+    /// `base` details with the rejection's own merged in.
+    pub(crate) fn details_with(&self, mut base: Value) -> Value {
+        if let (Some(target), Some(extra)) = (base.as_object_mut(), self.details.as_object()) {
+            target.extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        base
+    }
+}
+
+impl From<String> for BearerRejection {
+    fn from(message: String) -> Self {
+        Self {
+            code: ErrorCode::Unauthorized,
+            message,
+            details: json!({}),
+        }
+    }
+}
+
 pub(crate) async fn resolve_session_bearer(
     state: &AppState,
     headers: &HeaderMap,
-) -> Result<Option<McpWsAuth>, String> {
+) -> Result<Option<McpWsAuth>, BearerRejection> {
     let Some(token_id) = parse_bearer_token(headers) else {
         return Ok(None);
     };
 
+    let revoked_tokens = state
+        .revocation_cache
+        .revoked_tokens(&state.chip_store)
+        .await?;
+    if let Some(revoked_at) = revoked_tokens.get(&token_id) {
+        let revoked_at = revoked_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        return Err(BearerRejection {
+            code: ErrorCode::TokenRevoked,
+            message: format!("token revoked at {}", revoked_at),
+            details: json!({"token_id": token_id, "revoked_at": revoked_at}),
+        });
+    }
+
     let token_query = ubl_chipstore::ChipQuery {
         chip_type: Some("ubl/token".to_string()),
         tags: vec![format!("id:{}", token_id)],
@@ -401,7 +796,7 @@ pub(crate) async fn resolve_session_bearer(
         .into_iter()
         .find(|chip| chip.chip_data.get("@id").and_then(|v| v.as_str()) == Some(token_id.as_str()))
     else {
-        return Err("token not found".to_string());
+        return Err("token not found".to_string().into());
     };
 
     let session = ubl_runtime::SessionToken::from_chip_body(&token_chip.chip_data)
@@ -411,7 +806,7 @@ pub(crate) async fn resolve_session_bearer(
         .map(|t| t.with_timezone(&chrono::Utc))
         .map_err(|e| format!("invalid token expiry: {}", e))?;
     if expires_at <= chrono::Utc::now() {
-        return Err("token expired".to_string());
+        return Err("token expired".to_string().into());
     }
 
     let revoke_query = ubl_chipstore::ChipQuery {
@@ -430,7 +825,7 @@ pub(crate) async fn resolve_session_bearer(
         .map(|r| r.total_count > 0)
         .unwrap_or(false);
     if revoked {
-        return Err("token revoked".to_string());
+        return Err("token revoked".to_string().into());
     }
 
     let token_world = token_chip
@@ -468,7 +863,7 @@ pub(crate) async fn resolve_session_bearer(
         .map(|r| r.total_count > 0)
         .unwrap_or(false);
     if user_revoked {
-        return Err("token user revoked".to_string());
+        return Err("token user revoked".to_string().into());
     }
 
     Ok(Some(McpWsAuth {
@@ -509,13 +904,15 @@ pub(crate) async fn validate_mcp_ws_bearer(
             )
                 .into_response())
         }
-        Err(msg) => {
+        Err(rejection) => {
             return Err((
-                StatusCode::UNAUTHORIZED,
+                StatusCode::from_u16(rejection.code.http_status())
+                    .unwrap_or(StatusCode::UNAUTHORIZED),
                 Json(json!({
                     "@type":"ubl/error",
-                    "code":"UNAUTHORIZED",
-                    "message": msg
+                    "code": rejection.code,
+                    "message": rejection.message,
+                    "details": rejection.details,
                 })),
             )
                 .into_response())